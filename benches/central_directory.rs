@@ -0,0 +1,54 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Benchmarks the parsing of central directories holding many entries, which is dominated by the reading of each
+//! record's dynamic length fields (filename, extra field, and comment).
+
+use async_zip::write::ZipFileWriter;
+use async_zip::{Compression, ZipEntryBuilder, ZipFile};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use tokio::runtime::Runtime;
+
+/// Writes an archive of empty stored entries (each with a comment) and returns only its central directory.
+async fn central_directory(entries: usize, comment_length: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    for index in 0..entries {
+        let entry = ZipEntryBuilder::new(format!("directory/subdirectory/{}.bin", index), Compression::Stored)
+            .comment("c".repeat(comment_length));
+        writer.write_entry_whole(entry, &[]).await.unwrap();
+    }
+    writer.close().await.unwrap();
+
+    // The archive has no comment, so the end of central directory record occupies its final 22 bytes.
+    let eocdr = &data[data.len() - 22..];
+    let size = u32::from_le_bytes(eocdr[12..16].try_into().unwrap()) as usize;
+    let offset = u32::from_le_bytes(eocdr[16..20].try_into().unwrap()) as usize;
+
+    data[offset..offset + size].to_vec()
+}
+
+fn parse(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let cases = [
+        ("1k-entries", 1000, runtime.block_on(central_directory(1000, 0))),
+        ("10k-entries", 10_000, runtime.block_on(central_directory(10_000, 0))),
+        ("10k-entries-commented", 10_000, runtime.block_on(central_directory(10_000, 64))),
+    ];
+
+    let mut group = c.benchmark_group("central_directory");
+
+    for (name, entries, data) in cases.iter() {
+        group.throughput(Throughput::Elements(*entries));
+        group.bench_with_input(BenchmarkId::new("parse_cd", name), data, |b, data| {
+            b.iter(|| ZipFile::parse_cd(data, *entries).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Benchmarks the opening of archives (which is dominated by locating the end of central directory record for archives
+//! with few entries) via the seekable reader, both in memory and over a buffered source.
+
+use async_zip::read::seek::ZipFileReader;
+use async_zip::write::ZipFileWriter;
+use async_zip::{Compression, ZipEntryBuilder};
+
+use std::io::Cursor;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tokio::io::BufReader;
+use tokio::runtime::Runtime;
+
+/// Writes an archive containing stored entries of the provided length, alongside a file comment.
+async fn archive(entries: usize, entry_length: usize, comment_length: usize) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    for index in 0..entries {
+        let entry = ZipEntryBuilder::new(format!("{}.bin", index), Compression::Stored);
+        writer.write_entry_whole(entry, &vec![0; entry_length]).await.unwrap();
+    }
+
+    writer.comment("a".repeat(comment_length)).unwrap();
+    writer.close().await.unwrap();
+
+    data
+}
+
+fn locator(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let cases = [
+        ("small", runtime.block_on(archive(1, 1024, 0))),
+        ("within-window", runtime.block_on(archive(1, 32 * 1024, 0))),
+        ("max-comment", runtime.block_on(archive(1, 32 * 1024, u16::MAX as usize))),
+        ("large", runtime.block_on(archive(1, 1024 * 1024, 0))),
+        ("many-entries", runtime.block_on(archive(1000, 16, 0))),
+    ];
+
+    let mut group = c.benchmark_group("locator");
+
+    for (name, data) in cases.iter() {
+        group.bench_with_input(BenchmarkId::new("memory", name), data, |b, data| {
+            b.to_async(&runtime).iter(|| async { ZipFileReader::new(Cursor::new(data)).await.unwrap() })
+        });
+        group.bench_with_input(BenchmarkId::new("buffered", name), data, |b, data| {
+            b.to_async(&runtime).iter(|| async { ZipFileReader::new(BufReader::new(Cursor::new(data))).await.unwrap() })
+        });
+    }
+
+    group.finish();
+}
+
+/// Compares the EOCDR search strategies over in-memory data, isolated from the remainder of opening an archive.
+#[cfg(feature = "memchr")]
+fn strategy(c: &mut Criterion) {
+    use async_zip::format::{locate_eocdr_with_strategy, SearchStrategy};
+
+    let runtime = Runtime::new().unwrap();
+    let cases = [
+        ("small", runtime.block_on(archive(1, 1024, 0))),
+        ("max-comment", runtime.block_on(archive(1, 32 * 1024, u16::MAX as usize))),
+    ];
+    let strategies = [("reverse-scan", SearchStrategy::ReverseScan), ("memchr", SearchStrategy::Memchr)];
+
+    let mut group = c.benchmark_group("strategy");
+
+    for (name, data) in cases.iter() {
+        for (strategy_name, strategy) in strategies {
+            group.bench_with_input(BenchmarkId::new(strategy_name, name), data, |b, data| {
+                b.iter(|| locate_eocdr_with_strategy(data, strategy).unwrap())
+            });
+        }
+    }
+
+    group.finish();
+}
+
+#[cfg(not(feature = "memchr"))]
+fn strategy(_: &mut Criterion) {}
+
+criterion_group!(benches, locator, strategy);
+criterion_main!(benches);
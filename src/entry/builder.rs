@@ -1,10 +1,12 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::entry::ZipEntry;
+use crate::entry::{ZipEntry, DOS_DIRECTORY, S_IFLNK, S_IFMT};
 use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::compression::{Compression, DeflateOption};
+use crate::spec::extra_field::{build_metadata, push_extra_field, remove_extra_field, ExtraField, METADATA_HEADER_ID};
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
 
 /// A builder for [`ZipEntry`].
 pub struct ZipEntryBuilder(pub(crate) ZipEntry);
@@ -23,6 +25,33 @@ impl ZipEntryBuilder {
         Self(ZipEntry::new(filename, compression))
     }
 
+    /// Constructs a new builder using a preset which maximises compatibility with older extractors.
+    ///
+    /// This profile is intended for small archives which need to be opened by as wide a range of tools as possible,
+    /// including the extractor built into Windows XP. Entries constructed with it:
+    /// - Are compressed with Deflate, so version 2.0 is the version needed to extract.
+    /// - Use MS-DOS attribute host compatibility, with the directory attribute set for directory entries.
+    /// - Store non-ASCII filenames as IBM Code Page 437 alongside an Info-ZIP Unicode Path extra field (see
+    ///   [`ZipEntryBuilder::unicode_path_extra_field()`]).
+    /// - Store an extended timestamp extra field alongside the MS-DOS date & time (see
+    ///   [`ZipEntryBuilder::extended_timestamp()`]).
+    ///
+    /// To also avoid data descriptors, entries should be written via [`ZipFileWriter::write_entry_whole()`] or, where
+    /// the data isn't available upfront and the writer is seekable, [`ZipFileWriter::write_entry_reader()`].
+    ///
+    /// [`ZipFileWriter::write_entry_whole()`]: crate::write::ZipFileWriter::write_entry_whole
+    /// [`ZipFileWriter::write_entry_reader()`]: crate::write::ZipFileWriter::write_entry_reader
+    #[cfg(feature = "deflate")]
+    pub fn max_compatibility(filename: String) -> Self {
+        let attribute = if filename.ends_with('/') { DOS_DIRECTORY } else { 0 };
+
+        Self::new(filename, Compression::Deflate)
+            .attribute_compatibility(AttributeCompatibility::Dos)
+            .external_file_attribute(attribute)
+            .unicode_path_extra_field(true)
+            .extended_timestamp(true)
+    }
+
     /// Set the deflate compression option.
     ///
     /// If the compression type isn't deflate, this option has no effect.
@@ -38,8 +67,12 @@ impl ZipEntryBuilder {
     }
 
     /// Sets the entry's last modification date.
+    ///
+    /// As the MS-DOS date & time stored within the entry's headers only has a resolution of two seconds, this also
+    /// enables the writing of an extended timestamp extra field (see [`ZipEntryBuilder::extended_timestamp()`]).
     pub fn last_modification_date(mut self, date: DateTime<Utc>) -> Self {
         self.0.last_modification_date = date;
+        self.0.extended_timestamp = true;
         self
     }
 
@@ -61,6 +94,48 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Adds a typed extra field to the entry, replacing any existing extra field with the same header ID.
+    ///
+    /// The field is written to both the local file header and central directory record. Adding an extended timestamp
+    /// extra field disables the one otherwise written automatically (see [`ZipEntryBuilder::extended_timestamp()`]), so
+    /// it should be added after any last modification date is set.
+    ///
+    /// # Panics
+    /// If the encoded field doesn't fit within a single extra field block (65,535 bytes).
+    pub fn add_extra_field(mut self, field: ExtraField) -> Self {
+        let data = field.to_bytes();
+        assert!(data.len() <= u16::MAX as usize, "extra field exceeds the maximum length of an extra field block");
+
+        if let ExtraField::ExtendedTimestamp { .. } = field {
+            self.0.extended_timestamp = false;
+        }
+
+        self.0.extra_field = remove_extra_field(&self.0.extra_field, field.header_id());
+        push_extra_field(&mut self.0.extra_field, field.header_id(), &data);
+        self
+    }
+
+    /// Adds each of the provided typed extra fields to the entry, as per [`ZipEntryBuilder::add_extra_field()`].
+    pub fn extra_fields(self, fields: impl IntoIterator<Item = ExtraField>) -> Self {
+        fields.into_iter().fold(self, Self::add_extra_field)
+    }
+
+    /// Sets a map of custom key-value metadata which is stored alongside the entry within a dedicated extra field.
+    ///
+    /// This provides a place for small amounts of per-entry sidecar metadata (which would otherwise have to be encoded
+    /// within the entry's comment). Any existing metadata extra field is replaced, and the map can be retrieved from
+    /// entries read back via [`ZipEntry::metadata()`].
+    ///
+    /// # Panics
+    /// If the encoded map doesn't fit within a single extra field block (65,535 bytes).
+    pub fn metadata(mut self, metadata: &BTreeMap<String, String>) -> Self {
+        let data = build_metadata(metadata).expect("metadata map exceeds the maximum length of an extra field block");
+
+        self.0.extra_field = remove_extra_field(&self.0.extra_field, METADATA_HEADER_ID);
+        push_extra_field(&mut self.0.extra_field, METADATA_HEADER_ID, &data);
+        self
+    }
+
     /// Sets the entry's file comment.
     pub fn comment(mut self, comment: String) -> Self {
         self.0.comment = comment;
@@ -77,6 +152,100 @@ impl ZipEntryBuilder {
         self
     }
 
+    /// Sets whether or not a non-ASCII filename is written using an Info-ZIP Unicode Path extra field.
+    ///
+    /// By default, non-ASCII filenames & comments are written as UTF-8 with the language encoding flag set. When this
+    /// option is enabled, they're instead written as IBM Code Page 437 (with unrepresentable characters replaced) and
+    /// the exact UTF-8 filename is stored within a Unicode Path extra field. This improves compatibility with older
+    /// extractors which don't recognise the language encoding flag.
+    pub fn unicode_path_extra_field(mut self, value: bool) -> Self {
+        self.0.unicode_path_extra_field = value;
+        self
+    }
+
+    /// Sets whether or not an extended timestamp extra field is written containing the entry's last modification date.
+    ///
+    /// Unlike the MS-DOS date & time stored within the entry's headers, this has a resolution of one second and is
+    /// stored in UTC, so it isn't affected by the timezone of the extracting system. This is enabled automatically when
+    /// a last modification date is set.
+    pub fn extended_timestamp(mut self, value: bool) -> Self {
+        self.0.extended_timestamp = value;
+        self
+    }
+
+    /// Sets a password with which the entry's data is encrypted via traditional PKWARE (ZipCrypto) encryption.
+    ///
+    /// ZipCrypto is cryptographically weak and should only be used for compatibility with extractors which don't
+    /// support WinZip AES encryption. The 12-byte encryption header precedes the entry's data, and the encrypted flag is
+    /// set within its general purpose bit flag. This has no effect for entries written using AES encryption, and
+    /// writing such an entry via [`ZipFileWriter::write_entry_reader()`] isn't supported.
+    ///
+    /// [`ZipFileWriter::write_entry_reader()`]: crate::write::ZipFileWriter::write_entry_reader
+    pub fn zipcrypto_password(mut self, password: &[u8]) -> Self {
+        self.0.zipcrypto_password = Some(password.to_vec());
+        self
+    }
+
+    /// Sets the alignment (in bytes) of the offset at which the entry's data begins when written, as `zipalign` does.
+    ///
+    /// Android tooling requires stored entries to be aligned (eg. to 4-byte or page boundaries) so that they can be
+    /// memory-mapped directly. The alignment is achieved by padding the local file header's extra field with an Android
+    /// alignment extra field, which isn't written to the central directory. Alignments of zero or one have no effect.
+    pub fn alignment(mut self, alignment: u16) -> Self {
+        self.0.alignment = alignment;
+        self
+    }
+
+    /// Sets the UID & GID of the entry's owner, which are stored within an Info-ZIP Unix extra field.
+    ///
+    /// This allows ownership to be restored when extracting (eg. when backing up system files), and is retrieved from
+    /// entries read back via [`ZipEntry::uid()`] & [`ZipEntry::gid()`]. Any existing Info-ZIP Unix extra field is
+    /// replaced.
+    pub fn unix_owner(self, uid: u32, gid: u32) -> Self {
+        self.add_extra_field(ExtraField::UnixOwner { uid, gid })
+    }
+
+    /// Marks the entry as a symbolic link whilst retaining any permission bits already set.
+    ///
+    /// The data written for this entry should be the path of the link's target. If no permission bits have been set,
+    /// they default to `0o777`. As with [`ZipEntryBuilder::unix_permissions()`], this will have no effect if the
+    /// attribute host compatibility isn't set to Unix.
+    pub fn symlink(self) -> Self {
+        let mode = (self.0.external_file_attribute >> 16) as u16 & !S_IFMT;
+        let mode = if mode == 0 { 0o777 } else { mode };
+
+        self.unix_permissions(S_IFLNK | mode)
+    }
+
+    /// Populates the entry's last modification date, Unix permissions, and directory status from a file's metadata.
+    ///
+    /// The last modification date is only set if the platform provides one. On Unix, the file's mode (including its
+    /// type) is used as-is; elsewhere, a conventional mode is derived from the file's type and read-only permission. As
+    /// with [`ZipEntryBuilder::unix_permissions()`], the mode will have no effect if the attribute host compatibility
+    /// isn't set to Unix. For directories, a trailing `/` is appended to the filename (if not already present) and the
+    /// MS-DOS directory attribute is set.
+    pub fn file_metadata(mut self, metadata: &std::fs::Metadata) -> Self {
+        if let Ok(modified) = metadata.modified() {
+            self = self.last_modification_date(DateTime::from(modified));
+        }
+        if metadata.is_dir() {
+            if !self.0.filename.ends_with('/') {
+                self.0.filename.push('/');
+            }
+            self.0.external_file_attribute |= DOS_DIRECTORY;
+        }
+
+        self.unix_permissions(file_mode(metadata))
+    }
+
+    /// Populates the entry's last modification date, Unix permissions, and directory status from an open file.
+    ///
+    /// See [`ZipEntryBuilder::file_metadata()`] for more information.
+    #[cfg(feature = "fs")]
+    pub async fn file(self, file: &tokio::fs::File) -> std::io::Result<Self> {
+        Ok(self.file_metadata(&file.metadata().await?))
+    }
+
     /// Consumes this builder and returns a final [`ZipEntry`].
     ///
     /// This is equivalent to:
@@ -90,3 +259,27 @@ impl ZipEntryBuilder {
         self.into()
     }
 }
+
+/// Returns the Unix mode (including the file type) of a file from its metadata.
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> u16 {
+    use std::os::unix::fs::PermissionsExt;
+
+    metadata.permissions().mode() as u16
+}
+
+/// Returns a conventional Unix mode (including the file type) for a file from its metadata.
+#[cfg(not(unix))]
+fn file_mode(metadata: &std::fs::Metadata) -> u16 {
+    use crate::entry::{S_IFDIR, S_IFREG};
+
+    let (file_type, mode) = match metadata.file_type() {
+        file_type if file_type.is_dir() => (S_IFDIR, 0o755),
+        file_type if file_type.is_symlink() => (S_IFLNK, 0o777),
+        _ => (S_IFREG, 0o644),
+    };
+
+    // Clear the write permission bits of read-only files.
+    let mode = if metadata.permissions().readonly() { mode & !0o222 } else { mode };
+    file_type | mode
+}
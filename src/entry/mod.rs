@@ -6,8 +6,32 @@ pub mod builder;
 use crate::entry::builder::ZipEntryBuilder;
 use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::compression::Compression;
+use crate::spec::encoding::FilenameSource;
+use crate::spec::encryption::EncryptionMethod;
+#[cfg(feature = "aes")]
+use crate::spec::extra_field::AesExtraField;
+use crate::spec::extra_field::{
+    find_extra_field, parse_metadata, parse_unix_owner, METADATA_HEADER_ID, UNIX_OWNER_HEADER_ID,
+};
 use crate::spec::header::GeneralPurposeFlag;
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+/// The bit mask of the file type within a Unix mode.
+pub(crate) const S_IFMT: u16 = 0o170000;
+
+/// The file type of a regular file within a Unix mode.
+#[cfg(not(unix))]
+pub(crate) const S_IFREG: u16 = 0o100000;
+
+/// The file type of a directory within a Unix mode.
+pub(crate) const S_IFDIR: u16 = 0o040000;
+
+/// The file type of a symbolic link within a Unix mode.
+pub(crate) const S_IFLNK: u16 = 0o120000;
+
+/// The MS-DOS directory attribute.
+pub(crate) const DOS_DIRECTORY: u32 = 0x10;
 
 /// An immutable store of data about a ZIP entry.
 ///
@@ -28,6 +52,22 @@ pub struct ZipEntry {
     pub(crate) external_file_attribute: u32,
     pub(crate) extra_field: Vec<u8>,
     pub(crate) comment: String,
+    pub(crate) filename_source: FilenameSource,
+    pub(crate) unicode_path_extra_field: bool,
+    pub(crate) extended_timestamp: bool,
+    pub(crate) ntfs_timestamps: Option<Timestamps>,
+    pub(crate) extended_timestamps: Option<Timestamps>,
+    pub(crate) zipcrypto_password: Option<Vec<u8>>,
+    pub(crate) encryption: Option<EncryptionMethod>,
+    pub(crate) alignment: u16,
+}
+
+/// The timestamps stored within an NTFS or extended timestamp extra field.
+#[derive(Clone)]
+pub(crate) struct Timestamps {
+    pub(crate) last_modification_date: Option<DateTime<Utc>>,
+    pub(crate) last_access_date: Option<DateTime<Utc>>,
+    pub(crate) creation_date: Option<DateTime<Utc>>,
 }
 
 impl From<ZipEntryBuilder> for ZipEntry {
@@ -51,6 +91,14 @@ impl ZipEntry {
             external_file_attribute: 0,
             extra_field: Vec::new(),
             comment: String::new(),
+            filename_source: FilenameSource::Utf8,
+            unicode_path_extra_field: false,
+            extended_timestamp: false,
+            ntfs_timestamps: None,
+            extended_timestamps: None,
+            zipcrypto_password: None,
+            encryption: None,
+            alignment: 0,
         }
     }
 
@@ -64,6 +112,22 @@ impl ZipEntry {
         &self.filename
     }
 
+    /// Returns the source from which the entry's filename was decoded.
+    ///
+    /// For entries which haven't been read from an existing ZIP file, this will always be [`FilenameSource::Utf8`].
+    pub fn filename_source(&self) -> FilenameSource {
+        self.filename_source
+    }
+
+    /// Returns a version of the entry's filename which is safe to display within a terminal or UI.
+    ///
+    /// Control characters (which could otherwise be used to inject terminal escape sequences) and Unicode bidirectional
+    /// formatting characters (which could be used to visually disguise a filename) are stripped. As such, this should
+    /// only be used for display purposes and never to locate or extract an entry.
+    pub fn display_name(&self) -> String {
+        self.filename.chars().filter(|c| !c.is_control() && !is_bidi_control(*c)).collect()
+    }
+
     /// Returns the entry's compression method.
     pub fn compression(&self) -> Compression {
         self.compression
@@ -84,6 +148,17 @@ impl ZipEntry {
         self.compressed_size
     }
 
+    /// Returns the entry's compression ratio (ie. its uncompressed size divided by its compressed size).
+    ///
+    /// This is computed from the sizes stored within the entry's headers alone, and so can be checked before any data
+    /// is read. Returns None if the compressed size is zero.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        match self.compressed_size {
+            0 => None,
+            compressed_size => Some(self.uncompressed_size as f64 / compressed_size as f64),
+        }
+    }
+
     /// Returns the entry's attribute's host compatibility.
     pub fn attribute_compatibility(&self) -> AttributeCompatibility {
         self.attribute_compatibility
@@ -94,6 +169,49 @@ impl ZipEntry {
         &self.last_modification_date
     }
 
+    /// Returns the entry's last modification time & date, if stored within an extended timestamp extra field.
+    ///
+    /// When present, this is also used as the entry's [`ZipEntry::last_modification_date()`]. This is only available
+    /// for entries read from an existing ZIP file.
+    pub fn extended_last_modification_date(&self) -> Option<&DateTime<Utc>> {
+        self.extended_timestamps.as_ref().and_then(|timestamps| timestamps.last_modification_date.as_ref())
+    }
+
+    /// Returns the entry's last access time & date, if stored within an extended timestamp extra field.
+    ///
+    /// The central directory variant of this extra field only stores the modification time, so this is generally
+    /// only available for entries read via their local file headers (eg. via the [`stream`] reader).
+    ///
+    /// [`stream`]: crate::read::stream
+    pub fn extended_last_access_date(&self) -> Option<&DateTime<Utc>> {
+        self.extended_timestamps.as_ref().and_then(|timestamps| timestamps.last_access_date.as_ref())
+    }
+
+    /// Returns the entry's creation time & date, if stored within an extended timestamp extra field.
+    ///
+    /// As with [`ZipEntry::extended_last_access_date()`], this is generally only available via local file headers.
+    pub fn extended_creation_date(&self) -> Option<&DateTime<Utc>> {
+        self.extended_timestamps.as_ref().and_then(|timestamps| timestamps.creation_date.as_ref())
+    }
+
+    /// Returns the entry's high-resolution last modification time & date, if stored within an NTFS extra field.
+    ///
+    /// Unlike [`ZipEntry::last_modification_date()`], which has a resolution of two seconds, this has a resolution of
+    /// 100 nanoseconds. This is only available for entries read from an existing ZIP file.
+    pub fn ntfs_last_modification_date(&self) -> Option<&DateTime<Utc>> {
+        self.ntfs_timestamps.as_ref().and_then(|timestamps| timestamps.last_modification_date.as_ref())
+    }
+
+    /// Returns the entry's high-resolution last access time & date, if stored within an NTFS extra field.
+    pub fn ntfs_last_access_date(&self) -> Option<&DateTime<Utc>> {
+        self.ntfs_timestamps.as_ref().and_then(|timestamps| timestamps.last_access_date.as_ref())
+    }
+
+    /// Returns the entry's high-resolution creation time & date, if stored within an NTFS extra field.
+    pub fn ntfs_creation_date(&self) -> Option<&DateTime<Utc>> {
+        self.ntfs_timestamps.as_ref().and_then(|timestamps| timestamps.creation_date.as_ref())
+    }
+
     /// Returns the entry's internal file attribute.
     pub fn internal_file_attribute(&self) -> u16 {
         self.internal_file_attribute
@@ -109,11 +227,35 @@ impl ZipEntry {
         &self.extra_field
     }
 
+    /// Returns the entry's custom key-value metadata map, if one is stored.
+    ///
+    /// See [`ZipEntryBuilder::metadata()`]. This will also return None if the stored metadata is malformed.
+    pub fn metadata(&self) -> Option<BTreeMap<String, String>> {
+        find_extra_field(&self.extra_field, METADATA_HEADER_ID).and_then(parse_metadata)
+    }
+
     /// Returns the entry's file comment.
     pub fn comment(&self) -> &str {
         &self.comment
     }
 
+    /// Returns whether or not the entry's data is encrypted.
+    ///
+    /// For entries which haven't been read from an existing ZIP file, this will always be false.
+    pub fn is_encrypted(&self) -> bool {
+        self.encryption.is_some()
+    }
+
+    /// Returns the method with which the entry's data is encrypted, if it is.
+    ///
+    /// Encrypted entries can't be read via the readers' `entry()` methods, which return [`ZipError::EntryEncrypted`].
+    /// ZipCrypto & AES-encrypted entries can instead be read via their `entry_with_password()` methods.
+    ///
+    /// [`ZipError::EntryEncrypted`]: crate::error::ZipError::EntryEncrypted
+    pub fn encryption(&self) -> Option<EncryptionMethod> {
+        self.encryption
+    }
+
     /// Returns the entry's integer-based UNIX permissions.
     ///
     /// # Note
@@ -126,15 +268,144 @@ impl ZipEntry {
         Some(((self.external_file_attribute) >> 16) as u16)
     }
 
+    /// Returns the UID of the entry's owner, if stored within an Info-ZIP Unix extra field.
+    ///
+    /// See [`ZipEntryBuilder::unix_owner()`].
+    pub fn uid(&self) -> Option<u32> {
+        self.unix_owner().map(|(uid, _)| uid)
+    }
+
+    /// Returns the GID of the entry's owner, if stored within an Info-ZIP Unix extra field.
+    ///
+    /// See [`ZipEntryBuilder::unix_owner()`].
+    pub fn gid(&self) -> Option<u32> {
+        self.unix_owner().map(|(_, gid)| gid)
+    }
+
+    /// Returns the UID & GID of the entry's owner, if stored within an Info-ZIP Unix extra field.
+    fn unix_owner(&self) -> Option<(u32, u32)> {
+        find_extra_field(&self.extra_field, UNIX_OWNER_HEADER_ID).and_then(parse_unix_owner)
+    }
+
+    /// Returns the entry's Unix permission bits (including the setuid, setgid, and sticky bits).
+    ///
+    /// # Note
+    /// This will return None if the attribute host compatibility is not listed as Unix, or if no permission bits have
+    /// been stored (as is the case for ZIP files created by some tools which don't populate the external attribute).
+    pub fn unix_mode(&self) -> Option<u32> {
+        self.unix_permissions().map(|mode| (mode & !S_IFMT) as u32).filter(|mode| *mode != 0)
+    }
+
     /// Returns whether or not the entry represents a directory.
     pub fn dir(&self) -> bool {
         self.filename.ends_with('/')
     }
+
+    /// Returns whether or not the entry's filename is empty once normalized.
+    ///
+    /// This is the case if every component of the filename (separated by `/` or `\`) is empty, `.`, or consists only
+    /// of whitespace, and so it doesn't name any path within the destination when extracted. Such entries are only
+    /// found within broken or hostile ZIP files, and are rejected (or optionally skipped) by extraction.
+    pub fn has_empty_filename(&self) -> bool {
+        is_empty_filename(&self.filename)
+    }
+
+    /// Returns whether or not the entry represents a symbolic link.
+    ///
+    /// The data of a symlink entry is the path of the link's target. This will always return false if the attribute
+    /// host compatibility is not listed as Unix.
+    pub fn is_symlink(&self) -> bool {
+        self.unix_permissions().map(|mode| mode & S_IFMT == S_IFLNK).unwrap_or(false)
+    }
 }
 
-#[derive(Clone)]
-#[allow(dead_code)]
-pub(crate) struct ZipEntryMeta {
+/// Returns whether or not a filename is empty once normalized (see [`ZipEntry::has_empty_filename()`]).
+pub(crate) fn is_empty_filename(filename: &str) -> bool {
+    filename.split(['/', '\\']).all(|component| component == "." || component.trim().is_empty())
+}
+
+/// Returns whether or not a character is a Unicode bidirectional formatting character.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{061C}' | '\u{200E}' | '\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Structural information about a ZIP entry which is held outside of its [`ZipEntry`].
+///
+/// This is retrieved via [`ZipFile::meta()`](crate::ZipFile::meta) and is intended for tooling which needs to inspect
+/// the layout of a ZIP file (eg. where each entry's headers lie or which entries use a data descriptor).
+#[derive(Debug, Clone)]
+pub struct ZipEntryMeta {
     pub(crate) general_purpose_flag: GeneralPurposeFlag,
+    pub(crate) version_needed: u16,
     pub(crate) file_offset: u64,
+    pub(crate) overhead: EntryOverhead,
+    pub(crate) raw_filename: Vec<u8>,
+    #[cfg(feature = "encoding")]
+    pub(crate) raw_comment: Vec<u8>,
+    #[cfg(feature = "aes")]
+    pub(crate) aes: Option<AesExtraField>,
+}
+
+impl ZipEntryMeta {
+    /// Returns the offset of the entry's local file header from the start of the ZIP file.
+    pub fn header_offset(&self) -> u64 {
+        self.file_offset
+    }
+
+    /// Returns the general purpose flag stored within the entry's central directory record.
+    pub fn general_purpose_flag(&self) -> GeneralPurposeFlag {
+        self.general_purpose_flag
+    }
+
+    /// Returns the version needed to extract the entry, as stored within its central directory record.
+    pub fn version_needed(&self) -> u16 {
+        self.version_needed
+    }
+
+    /// Returns whether or not the entry's sizes & CRC32 value are stored within a data descriptor following its data.
+    pub fn data_descriptor(&self) -> bool {
+        self.general_purpose_flag.data_descriptor
+    }
+
+    /// Returns whether or not the entry is encrypted.
+    pub fn encrypted(&self) -> bool {
+        self.general_purpose_flag.encrypted
+    }
+
+    /// Returns the metadata overhead of the entry.
+    pub fn overhead(&self) -> EntryOverhead {
+        self.overhead
+    }
+
+    /// Returns the entry's filename exactly as stored within its central directory record (ie. before decoding).
+    ///
+    /// This allows entries whose decoded filename is unusable (eg. empty, see [`ZipEntry::has_empty_filename()`]) to be
+    /// inspected.
+    pub fn raw_filename(&self) -> &[u8] {
+        &self.raw_filename
+    }
+}
+
+/// The metadata overhead of a ZIP entry (ie. the space it occupies in addition to its compressed data).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntryOverhead {
+    pub(crate) local: u64,
+    pub(crate) central_directory_record: u64,
+}
+
+impl EntryOverhead {
+    /// Returns the length of the entry's local file header and data descriptor (if present).
+    pub fn local(&self) -> u64 {
+        self.local
+    }
+
+    /// Returns the length of the entry's central directory record.
+    pub fn central_directory_record(&self) -> u64 {
+        self.central_directory_record
+    }
+
+    /// Returns the entry's total metadata overhead.
+    pub fn total(&self) -> u64 {
+        self.local + self.central_directory_record
+    }
 }
@@ -3,6 +3,8 @@
 
 //! A module which holds relevant error reporting structures/types.
 
+use std::fmt::{Display, Formatter};
+
 use thiserror::Error;
 
 /// A Result type alias over ZipError to minimise repetition.
@@ -23,11 +25,172 @@ pub enum ZipError {
 
     #[error("unable to locate the end of central directory record")]
     UnableToLocateEOCDR,
+    #[error("encountered an unexpected header (actual: {0:#x}, expected: {1:#x})")]
+    UnexpectedHeaderError(u32, u32),
 
     #[error("an upstream reader returned an error: {0}")]
     UpstreamReadError(#[from] std::io::Error),
     #[error("a computed CRC32 value did not match the expected value")]
     CRC32CheckError,
+    #[error("the sizes within a data descriptor did not match the entry's data")]
+    DataDescriptorSizeMismatch,
     #[error("entry index was out of bounds")]
     EntryIndexOutOfBounds,
+    #[error("no entry exists with the filename: '{0}'")]
+    EntryNotFound(String),
+    #[error("entry pattern was invalid: {0}")]
+    InvalidPattern(String),
+    #[error("an entry using a data descriptor must be fully read before advancing to the next entry")]
+    EntryNotFullyRead,
+    #[error("entry filename is unsafe to extract: '{0}'")]
+    UnsafeFilename(String),
+    #[error("entry filename is empty once normalized: '{0}'")]
+    EmptyFilename(String),
+    #[error("symlink target is unsafe to extract: '{0}'")]
+    UnsafeSymlinkTarget(String),
+    #[error("restoring ownership on extraction requires running as root")]
+    OwnershipRequiresRoot,
+    #[error("entry filenames collide once normalized: '{0}'")]
+    NormalizedFilenameCollision(String),
+    #[error("local file headers don't match the central directory: {0}")]
+    CentralDirectoryMismatch(String),
+    #[cfg(feature = "tokio")]
+    #[error("ZIP file deviates from the specification: {0}")]
+    SpecViolation(crate::read::diagnostics::OpenWarning),
+    #[error(
+        "entry exceeds the in-memory read limit of {0} bytes (read it incrementally via the entry reader instead)"
+    )]
+    EntryTooLarge(u64),
+    #[cfg(feature = "tokio")]
+    #[error("decompression {0} limit of {1} was exceeded")]
+    LimitExceeded(crate::read::limits::LimitKind, u64),
+    #[error("operation was cancelled after {0} entries were completed")]
+    Cancelled(usize),
+
+    #[error("writing would exceed the maximum archive size of {0} bytes")]
+    ArchiveSizeExceeded(u64),
+    #[error("unable to pad to offset {0} as it's before the end of the smallest possible padding entry")]
+    PaddingOffsetUnreachable(u64),
+    #[error("ZIP file comment of {0} bytes exceeds the maximum length of 65535 bytes")]
+    CommentTooLong(usize),
+    #[error("path is not valid UTF-8 and so can't be written as an entry: '{0}'")]
+    NonUtf8Path(String),
+    #[error("entry filename appears more than once: '{0}'")]
+    DuplicateFilename(String),
+    #[error("a write group is already open")]
+    GroupAlreadyOpen,
+    #[error("no write group is open")]
+    NoGroupOpen,
+
+    #[error("entry is encrypted ({0}) and requires a password to be read")]
+    EntryEncrypted(crate::spec::encryption::EncryptionMethod),
+    #[error("the provided password was incorrect")]
+    IncorrectPassword,
+    #[error("a computed authentication code did not match the expected value")]
+    AuthenticationCodeMismatch,
+
+    #[error("{source} ({context})")]
+    WithContext { source: Box<ZipError>, context: ErrorContext },
+}
+
+impl ZipError {
+    /// Returns the context identifying where within the ZIP file this error occurred, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            ZipError::WithContext { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Returns the underlying error, without any context.
+    ///
+    /// This allows the kind of error to be matched regardless of whether context was attached.
+    pub fn root(&self) -> &ZipError {
+        match self {
+            ZipError::WithContext { source, .. } => source.root(),
+            error => error,
+        }
+    }
+
+    /// Attaches the provided context to this error, filling in any fields missing from context already attached.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn with_context(self, context: ErrorContext) -> ZipError {
+        match self {
+            ZipError::WithContext { source, context: existing } => {
+                let context = ErrorContext {
+                    index: existing.index.or(context.index),
+                    filename: existing.filename.or(context.filename),
+                    offset: existing.offset.or(context.offset),
+                };
+                ZipError::WithContext { source, context }
+            }
+            error => ZipError::WithContext { source: Box::new(error), context },
+        }
+    }
+}
+
+/// Context identifying where within a ZIP file an error occurred.
+///
+/// Attached (via [`ZipError::WithContext`]) to errors raised whilst parsing the central directory, reading an entry's
+/// local file header, or verifying an entry's CRC32 value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub(crate) index: Option<usize>,
+    pub(crate) filename: Option<String>,
+    pub(crate) offset: Option<u64>,
+}
+
+impl ErrorContext {
+    /// Constructs a new context for the entry at the provided index.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn index(index: usize) -> Self {
+        Self { index: Some(index), ..Default::default() }
+    }
+
+    /// Constructs a new context for the entry with the provided filename.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn filename(filename: impl Into<String>) -> Self {
+        Self { filename: Some(filename.into()), ..Default::default() }
+    }
+
+    /// Sets the absolute offset within the ZIP file at which the error occurred.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn at(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Returns the index of the entry (within the central directory) for which the error occurred, if known.
+    pub fn entry_index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the filename of the entry for which the error occurred, if known.
+    pub fn entry_filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    /// Returns the absolute offset within the ZIP file of the record or data being read when the error occurred, if
+    /// known.
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "entry")?;
+
+        if let Some(index) = self.index {
+            write!(f, " {}", index)?;
+        }
+        if let Some(filename) = &self.filename {
+            write!(f, " '{}'", filename)?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {:#x}", offset)?;
+        }
+
+        Ok(())
+    }
 }
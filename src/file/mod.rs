@@ -2,18 +2,54 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 pub(crate) mod builder;
+pub(crate) mod pattern;
 
-use crate::entry::{ZipEntry, ZipEntryMeta};
+use crate::entry::{EntryOverhead, ZipEntry, ZipEntryMeta};
+use crate::error::{Result, ZipError};
+use crate::read::diagnostics::{OpenReport, ReaderOptions};
+use crate::read::io::entry::ZipEntryReader;
+use crate::spec::consts::{EOCDR_LENGTH, SIGNATURE_LENGTH};
 use builder::ZipFileBuilder;
+use pattern::EntryPattern;
+
+use std::future::Future;
+use std::io::Cursor;
+use std::ops::Range;
+use std::task::{Context, Poll, Waker};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
 
 /// An immutable store of data about a ZIP file.
 #[derive(Clone)]
 pub struct ZipFile {
     pub(crate) entries: Vec<ZipEntry>,
-    #[allow(dead_code)]
     pub(crate) metas: Vec<ZipEntryMeta>,
     pub(crate) zip64: bool,
     pub(crate) comment: String,
+    pub(crate) report: Option<OpenReport>,
+    pub(crate) max_read_size: Option<u64>,
+}
+
+/// A group of entries whose data (including headers) spans a single contiguous byte range of a ZIP file.
+///
+/// Groups are constructed via [`ZipFile::group_entries()`] and allow remote backends to fetch the data of several
+/// entries in a single request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryGroup {
+    range: Range<u64>,
+    indices: Vec<usize>,
+}
+
+impl EntryGroup {
+    /// Returns the byte range of the ZIP file which this group spans.
+    pub fn range(&self) -> Range<u64> {
+        self.range.clone()
+    }
+
+    /// Returns the indices of the entries within this group, in the order they appear in the ZIP file.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
 }
 
 impl From<ZipFileBuilder> for ZipFile {
@@ -23,11 +59,197 @@ impl From<ZipFileBuilder> for ZipFile {
 }
 
 impl ZipFile {
+    /// Parses a standalone central directory (ie. the contiguous run of central directory records, as located via the
+    /// end of central directory record) which is already held in memory.
+    ///
+    /// This allows callers which already hold the central directory's bytes (eg. from a cache or a ranged read) to
+    /// construct a listing synchronously. Exactly `expected_entries` records are parsed, with any trailing bytes
+    /// ignored. As the central directory's offset within the ZIP file isn't known, the local overheads reported by
+    /// [`ZipFile::entry_overhead()`] aren't computed, and the offsets within any [`ErrorContext`] are relative to the
+    /// start of the provided bytes.
+    ///
+    /// [`ErrorContext`]: crate::error::ErrorContext
+    pub fn parse_cd(bytes: &[u8], expected_entries: u64) -> Result<ZipFile> {
+        // Reads from a slice never block (and yielding is disabled), so the parse always completes within a single poll.
+        let options = ReaderOptions::new().yield_interval(0);
+        let mut future = std::pin::pin!(crate::read::cd(Cursor::new(bytes), expected_entries, 0, &options));
+        let (entries, metas, _) = match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result?,
+            Poll::Pending => unreachable!("reads from a slice are always ready"),
+        };
+
+        Ok(ZipFile { entries, metas, zip64: false, comment: String::new(), report: None, max_read_size: None })
+    }
+
     /// Returns a list of this ZIP file's entries.
     pub fn entries(&self) -> &[ZipEntry] {
         &self.entries
     }
 
+    /// Returns the index of the first entry with the provided filename, if any.
+    ///
+    /// Filenames are compared exactly as stored (ie. no path normalisation is performed).
+    pub fn entry_index(&self, filename: &str) -> Option<usize> {
+        self.entries.iter().position(|entry| entry.filename() == filename)
+    }
+
+    /// Returns the index of the first entry with the provided filename, or [`ZipError::EntryNotFound`] if none exists.
+    pub(crate) fn named_index(&self, filename: &str) -> Result<usize> {
+        self.entry_index(filename).ok_or_else(|| ZipError::EntryNotFound(filename.to_string()))
+    }
+
+    /// Returns whether or not this ZIP file contains no entries.
+    ///
+    /// An archive consisting solely of an end of central directory record (see [`EMPTY_ARCHIVE`]) is valid, and is
+    /// read by every reader as one with no entries.
+    ///
+    /// [`EMPTY_ARCHIVE`]: crate::write::EMPTY_ARCHIVE
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the structural information of the entry at the provided index.
+    pub fn meta(&self, index: usize) -> Option<&ZipEntryMeta> {
+        self.metas.get(index)
+    }
+
+    /// Reads the local file header of the entry at the provided index, returning the offset at which its data begins.
+    ///
+    /// The provided reader must be over the whole ZIP file. The filename & extra field lengths stored within the local
+    /// file header may differ from those within the central directory, so this can't be computed from the central
+    /// directory alone. Paired with [`ZipEntryMeta::header_offset()`] and the entry's compressed size, this allows
+    /// the data to be located for ranged reads or external indexes. For encrypted entries, this is the offset of the
+    /// encryption header (or AES salt) which precedes the encrypted data.
+    pub async fn data_offset<R>(&self, index: usize, reader: R) -> Result<u64>
+    where
+        R: AsyncRead + AsyncSeek + Unpin,
+    {
+        let meta = self.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        crate::read::compute_data_offset(reader, meta).await
+    }
+
+    /// Returns the diagnostics collected whilst opening this ZIP file, if enabled.
+    ///
+    /// See [`ReaderOptions::diagnostics()`](crate::read::diagnostics::ReaderOptions::diagnostics).
+    pub fn open_report(&self) -> Option<&OpenReport> {
+        self.report.as_ref()
+    }
+
+    /// Returns the metadata overhead of the entry at the provided index.
+    ///
+    /// The local overhead is derived from the space between the start of this entry and the start of the next entry (or
+    /// the central directory), less the entry's compressed size. As such, it includes the local file header, data
+    /// descriptor, and any unused space which may follow them.
+    pub fn entry_overhead(&self, index: usize) -> Option<EntryOverhead> {
+        self.metas.get(index).map(|meta| meta.overhead)
+    }
+
+    /// Returns the total metadata overhead of this ZIP file.
+    ///
+    /// This is the sum of every entry's overhead and the length of the end of central directory record (including the
+    /// trailing comment).
+    pub fn total_overhead(&self) -> u64 {
+        let entries: u64 = self.metas.iter().map(|meta| meta.overhead.total()).sum();
+        entries + (SIGNATURE_LENGTH + EOCDR_LENGTH + self.comment.len()) as u64
+    }
+
+    /// Returns the indices of entries whose compression ratio exceeds the provided threshold.
+    ///
+    /// Entries which claim a non-zero uncompressed size from no compressed data at all are also included. As only the
+    /// sizes stored within the central directory are used, this is a quick triage signal for likely decompression bombs
+    /// ahead of extraction, rather than a guarantee (as the stored sizes may not reflect the actual data).
+    pub fn suspicious_entries(&self, threshold: f64) -> Vec<usize> {
+        let suspicious = |entry: &ZipEntry| match entry.compression_ratio() {
+            Some(ratio) => ratio > threshold,
+            None => entry.uncompressed_size() > 0,
+        };
+
+        self.entries.iter().enumerate().filter(|(_, entry)| suspicious(entry)).map(|(index, _)| index).collect()
+    }
+
+    /// Returns the indices of entries whose stored CRC32 value and uncompressed size match those provided.
+    ///
+    /// This allows entries to be located by their content (eg. from a manifest) where their filenames may have been
+    /// remapped. As different data may share a CRC32 value, each candidate should be verified if a false match matters.
+    /// AE-2 encrypted entries store a zeroed CRC32 value and so won't match their data's CRC32 value.
+    pub fn find_by_crc(&self, crc: u32, size: u32) -> Vec<usize> {
+        let matches = |entry: &ZipEntry| entry.crc32() == crc && entry.uncompressed_size() == size;
+        self.entries.iter().enumerate().filter(|(_, entry)| matches(entry)).map(|(index, _)| index).collect()
+    }
+
+    /// Returns the indices of entries whose filenames match the provided pattern.
+    ///
+    /// See [`EntryPattern`] for the supported syntax.
+    pub fn entries_matching(&self, pattern: &EntryPattern) -> Vec<usize> {
+        let matches = |entry: &ZipEntry| pattern.matches(entry.filename());
+        self.entries.iter().enumerate().filter(|(_, entry)| matches(entry)).map(|(index, _)| index).collect()
+    }
+
+    /// Groups the entries at the provided indices into coalesced byte ranges.
+    ///
+    /// Each entry spans from the start of its local file header to the end of its data (or data descriptor). Entries
+    /// whose spans are adjacent, or separated by no more than `max_gap` bytes, are placed within the same group. The
+    /// data of each group can then be fetched in a single request and passed to [`ZipFile::group_entry()`].
+    pub fn group_entries(&self, indices: &[usize], max_gap: u64) -> Result<Vec<EntryGroup>> {
+        let mut spans = Vec::with_capacity(indices.len());
+
+        for index in indices {
+            let entry = self.entries.get(*index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+            let meta = self.metas.get(*index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+            let end = meta.file_offset + meta.overhead.local + u64::from(entry.compressed_size());
+
+            spans.push((meta.file_offset..end, *index));
+        }
+
+        spans.sort_unstable_by_key(|(range, index)| (range.start, *index));
+        spans.dedup_by_key(|(_, index)| *index);
+
+        let mut groups: Vec<EntryGroup> = Vec::new();
+
+        for (range, index) in spans {
+            match groups.last_mut() {
+                Some(group) if range.start <= group.range.end.saturating_add(max_gap) => {
+                    group.range.end = std::cmp::max(group.range.end, range.end);
+                    group.indices.push(index);
+                }
+                _ => groups.push(EntryGroup { range, indices: vec![index] }),
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /// Constructs a reader for an entry within a group from the group's fetched data.
+    ///
+    /// The provided block must contain the bytes of the ZIP file covered by [`EntryGroup::range()`].
+    pub async fn group_entry<'a>(
+        &self,
+        group: &EntryGroup,
+        index: usize,
+        block: &'a [u8],
+    ) -> Result<ZipEntryReader<'a, Cursor<&'a [u8]>>> {
+        if !group.indices.contains(&index) {
+            return Err(ZipError::EntryIndexOutOfBounds);
+        }
+        if (block.len() as u64) < group.range.end - group.range.start {
+            return Err(ZipError::UpstreamReadError(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+        if let Some(method) = self.entries[index].encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let entry = &self.entries[index];
+        let meta = ZipEntryMeta {
+            file_offset: self.metas[index].file_offset - group.range.start,
+            ..self.metas[index].clone()
+        };
+        let mut cursor = Cursor::new(block);
+        let seek_to = crate::read::compute_data_offset(&mut cursor, &meta).await?;
+
+        cursor.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_with_owned(cursor, entry).with_read_limit(self.max_read_size))
+    }
+
     /// Returns this ZIP file's trailing comment.
     pub fn comment(&self) -> &str {
         &self.comment
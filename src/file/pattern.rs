@@ -0,0 +1,222 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+
+use std::collections::HashSet;
+
+/// A pattern against which entry filenames are matched.
+///
+/// Glob patterns are always available, and regular expressions are available via the `regex` feature. Patterns are
+/// matched against the filename exactly as stored (ie. `/`-separated and without any path normalisation).
+///
+/// ### Example
+/// ```
+/// # use async_zip::EntryPattern;
+/// #
+/// let pattern = EntryPattern::glob("assets/**/*.png").unwrap();
+/// assert!(pattern.matches("assets/icon.png"));
+/// assert!(pattern.matches("assets/icons/large/icon.png"));
+/// assert!(!pattern.matches("assets/icon.jpg"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EntryPattern {
+    kind: PatternKind,
+}
+
+#[derive(Debug, Clone)]
+enum PatternKind {
+    Glob(Vec<Token>),
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    /// `?`, matching any single character other than `/`.
+    Any,
+    /// `*`, matching any run of characters other than `/`.
+    Star,
+    /// A trailing `**` component, matching any run of characters.
+    AnyPath,
+    /// A leading or inner `**/` component, matching zero or more whole directory components.
+    AnyDirs,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl EntryPattern {
+    /// Constructs a new pattern from glob syntax.
+    ///
+    /// The following syntax is supported:
+    /// - `?` matches any single character other than `/`.
+    /// - `*` matches any run of characters other than `/`.
+    /// - `**` as a whole component matches any number of directory components (eg. `assets/**/*.png` matches both
+    ///   `assets/a.png` and `assets/b/c/a.png`, and a trailing `docs/**` matches everything under `docs/`).
+    /// - `[abc]`, `[a-z]`, and their negations `[!abc]` or `[^abc]` match a single character (other than `/`).
+    /// - `\` escapes the following character.
+    ///
+    /// [`ZipError::InvalidPattern`] is returned if a character class is unclosed or the pattern ends with `\`.
+    pub fn glob(pattern: &str) -> Result<EntryPattern> {
+        Ok(EntryPattern { kind: PatternKind::Glob(parse_glob(pattern)?) })
+    }
+
+    /// Constructs a new pattern from a regular expression.
+    ///
+    /// The expression isn't implicitly anchored, so `^` and `$` should be used where the whole filename must match.
+    #[cfg(feature = "regex")]
+    pub fn regex(pattern: &str) -> Result<EntryPattern> {
+        let regex = regex::Regex::new(pattern).map_err(|error| ZipError::InvalidPattern(error.to_string()))?;
+        Ok(EntryPattern { kind: PatternKind::Regex(regex) })
+    }
+
+    /// Returns whether or not the provided filename matches this pattern.
+    pub fn matches(&self, filename: &str) -> bool {
+        match &self.kind {
+            PatternKind::Glob(tokens) => {
+                let chars: Vec<char> = filename.chars().collect();
+                GlobMatcher { tokens, chars: &chars, failed: HashSet::new() }.matches(0, 0)
+            }
+            #[cfg(feature = "regex")]
+            PatternKind::Regex(regex) => regex.is_match(filename),
+        }
+    }
+}
+
+fn parse_glob(pattern: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        match chars[index] {
+            '*' if chars.get(index + 1) == Some(&'*') => {
+                let component_start = index == 0 || chars[index - 1] == '/';
+
+                match chars.get(index + 2) {
+                    Some('/') if component_start => {
+                        tokens.push(Token::AnyDirs);
+                        index += 3;
+                    }
+                    None if component_start => {
+                        tokens.push(Token::AnyPath);
+                        index += 2;
+                    }
+                    // A `**` which isn't a whole component is equivalent to `*`.
+                    _ => {
+                        tokens.push(Token::Star);
+                        index += 2;
+                    }
+                }
+                continue;
+            }
+            '*' => tokens.push(Token::Star),
+            '?' => tokens.push(Token::Any),
+            '\\' => {
+                index += 1;
+                let escaped = chars.get(index).ok_or_else(|| invalid(pattern, "trailing escape"))?;
+                tokens.push(Token::Literal(*escaped));
+            }
+            '[' => {
+                let (token, end) = parse_class(&chars, index + 1).ok_or_else(|| invalid(pattern, "unclosed class"))?;
+                tokens.push(token);
+                index = end;
+            }
+            literal => tokens.push(Token::Literal(literal)),
+        }
+
+        index += 1;
+    }
+
+    Ok(tokens)
+}
+
+/// Parses a character class starting after its opening `[`, returning it alongside the index of its closing `]`.
+fn parse_class(chars: &[char], mut index: usize) -> Option<(Token, usize)> {
+    let negated = matches!(chars.get(index), Some('!') | Some('^'));
+    if negated {
+        index += 1;
+    }
+
+    let mut ranges = Vec::new();
+    let start = index;
+
+    loop {
+        let mut first = *chars.get(index)?;
+
+        // A `]` immediately following the opening bracket (or negation) is taken literally.
+        if first == ']' && index != start {
+            return Some((Token::Class { negated, ranges }, index));
+        }
+        if first == '\\' {
+            index += 1;
+            first = *chars.get(index)?;
+        }
+
+        match (chars.get(index + 1), chars.get(index + 2)) {
+            (Some('-'), Some(&last)) if last != ']' => {
+                ranges.push((first, last));
+                index += 3;
+            }
+            _ => {
+                ranges.push((first, first));
+                index += 1;
+            }
+        }
+    }
+}
+
+fn invalid(pattern: &str, reason: &str) -> ZipError {
+    ZipError::InvalidPattern(format!("{} in '{}'", reason, pattern))
+}
+
+/// A backtracking glob matcher which memoises failed positions, bounding the work to the product of the pattern and
+/// filename lengths.
+struct GlobMatcher<'a> {
+    tokens: &'a [Token],
+    chars: &'a [char],
+    failed: HashSet<(usize, usize)>,
+}
+
+impl GlobMatcher<'_> {
+    fn matches(&mut self, token: usize, char: usize) -> bool {
+        if self.failed.contains(&(token, char)) {
+            return false;
+        }
+
+        let matched = self.matches_uncached(token, char);
+        if !matched {
+            self.failed.insert((token, char));
+        }
+        matched
+    }
+
+    fn matches_uncached(&mut self, token: usize, char: usize) -> bool {
+        let current = match self.tokens.get(token) {
+            Some(current) => current,
+            None => return char == self.chars.len(),
+        };
+        let next = self.chars.get(char).copied();
+
+        match current {
+            Token::Literal(literal) => next == Some(*literal) && self.matches(token + 1, char + 1),
+            Token::Any => next.is_some_and(|next| next != '/') && self.matches(token + 1, char + 1),
+            Token::Class { negated, ranges } => {
+                let in_class = |next: char| ranges.iter().any(|(first, last)| (*first..=*last).contains(&next));
+                next.is_some_and(|next| next != '/' && in_class(next) != *negated) && self.matches(token + 1, char + 1)
+            }
+            Token::Star => {
+                let run = self.chars[char..].iter().take_while(|next| **next != '/').count();
+                (char..=char + run).any(|end| self.matches(token + 1, end))
+            }
+            Token::AnyPath => (char..=self.chars.len()).any(|end| self.matches(token + 1, end)),
+            Token::AnyDirs => {
+                let separators: Vec<usize> = (char..self.chars.len()).filter(|end| self.chars[*end] == '/').collect();
+                self.matches(token + 1, char) || separators.into_iter().any(|end| self.matches(token + 1, end + 1))
+            }
+        }
+    }
+}
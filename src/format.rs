@@ -0,0 +1,33 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A runtime-independent layer for parsing & serialising ZIP structures.
+//!
+//! Nothing within this module performs IO or depends upon tokio's IO traits. Headers are parsed from (and serialised
+//! into) fixed-size byte arrays, locating the end of central directory record is driven by the caller via
+//! [`EocdrLocator`], and CRC32 values are computed over byte slices. The [`read`](crate::read) and
+//! [`write`](crate::write) modules are thin tokio bindings over this layer, and the same can be built for any other
+//! executor. This module remains available when the default `tokio` feature is disabled.
+//!
+//! ### Example
+//! ```
+//! # use async_zip::format::{locate_eocdr, EndOfCentralDirectoryHeader, EOCDR_LENGTH, SIGNATURE_LENGTH};
+//! # use async_zip::error::Result;
+//! #
+//! # fn run(data: &[u8]) -> Result<()> {
+//! let offset = locate_eocdr(data)? as usize;
+//! let buffer = data[offset..offset + SIGNATURE_LENGTH + EOCDR_LENGTH].try_into().unwrap();
+//! let eocdr = EndOfCentralDirectoryHeader::parse_with_signature(&buffer)?;
+//!
+//! println!("{} entries at offset {}", eocdr.num_of_entries, eocdr.cent_dir_offset);
+//! #   Ok(())
+//! # }
+//! ```
+
+pub use crate::spec::consts::{CDH_LENGTH, CDH_SIGNATURE, DATA_DESCRIPTOR_LENGTH, DATA_DESCRIPTOR_SIGNATURE};
+pub use crate::spec::consts::{EOCDR_LENGTH, EOCDR_SIGNATURE, LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH};
+pub use crate::spec::crc::{crc32, Crc32};
+pub use crate::spec::header::{
+    CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader,
+};
+pub use crate::spec::locator::{locate_eocdr, locate_eocdr_with_strategy, EocdrLocator, SearchStrategy};
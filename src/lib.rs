@@ -4,19 +4,36 @@
 //! An asynchronous ZIP archive reading/writing crate with a heavy focus on streaming support.
 //!
 //! ## Features
-//! - Asynchronous design powered by tokio.
+//! - Asynchronous design powered by tokio (via the default `tokio` feature), over a runtime-independent parsing core
+//!   which builds without it (see [`format`](mod@format)).
 //! - Support for Stored, Deflate, bzip2, LZMA, zstd, and xz compression methods.
-//! - Various different reading approaches (seek, stream, filesystem, in-memory buffer).
+//! - Read-only support for the legacy Shrink, Reduce, and Implode compression methods (via the `legacy` feature).
+//! - Optional NFC normalization of filenames when reading (via the `unicode` feature).
+//! - Decoding legacy-encoded filenames & comments (eg. Shift-JIS or GBK) when reading (via the `encoding` feature).
+//! - Various different reading approaches (seek, stream, filesystem, in-memory buffer, positional reads, remote ranged reads).
 //! - Support for writing complete data (u8 slices) or stream writing using data descriptors.
+//! - Appending entries to existing ZIP files without rewriting their data.
+//! - Archiving whole directory trees (via the `fs` feature).
+//! - Streaming generated ZIP files as HTTP response bodies (via the `http-body` feature).
+//! - Filtering entries by glob pattern, or by regular expression (via the `regex` feature).
+//! - Reading ZIP files hosted within S3, GCS, or Azure via ranged reads (via the `object-store` feature).
+//! - Zero-copy concurrent reading of memory-mapped local ZIP files (via the `mmap` feature).
+//! - In-memory reading & writing on `wasm32-unknown-unknown` (via the `wasm` feature, without the `fs` feature).
+//! - Runtime reporting of the functionality compiled in via Cargo features (see [`capabilities()`]).
 //! - Aims for reasonable [specification](https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT) compliance.
 //!
 //! [Read more.](https://github.com/Majored/rs-async-zip)
 
 pub mod error;
+pub mod format;
+#[cfg(feature = "tokio")]
 pub mod read;
+#[cfg(feature = "tokio")]
 pub mod write;
 
+#[cfg(feature = "tokio")]
 pub(crate) mod entry;
+#[cfg(feature = "tokio")]
 pub(crate) mod file;
 pub(crate) mod spec;
 
@@ -24,7 +41,19 @@ pub(crate) mod spec;
 pub(crate) mod tests;
 
 pub use crate::spec::attribute::AttributeCompatibility;
+pub use crate::spec::capabilities::{capabilities, Capabilities, CompressionSupport};
 pub use crate::spec::compression::{Compression, DeflateOption};
+pub use crate::spec::crc::{crc32, Crc32};
+#[cfg(feature = "encoding")]
+pub use crate::spec::encoding::FilenameEncoding;
+pub use crate::spec::encoding::FilenameSource;
+pub use crate::spec::encryption::EncryptionMethod;
+pub use crate::spec::extra_field::ExtraField;
 
-pub use crate::entry::{builder::ZipEntryBuilder, ZipEntry};
-pub use crate::file::{builder::ZipFileBuilder, ZipFile};
+#[cfg(feature = "encoding")]
+pub use encoding_rs;
+
+#[cfg(feature = "tokio")]
+pub use crate::entry::{builder::ZipEntryBuilder, EntryOverhead, ZipEntry, ZipEntryMeta};
+#[cfg(feature = "tokio")]
+pub use crate::file::{builder::ZipFileBuilder, pattern::EntryPattern, EntryGroup, ZipFile};
@@ -0,0 +1,350 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports collecting diagnostics whilst opening ZIP files.
+//!
+//! Opening a ZIP file involves locating the end of central directory record and then parsing the central directory,
+//! both of which may be slow over high-latency storage. When enabled via [`ReaderOptions::diagnostics()`], the time
+//! spent on each step is recorded alongside a summary of the file's structure and any irregularities noticed.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::mem::ZipFileReader;
+//! # use async_zip::read::diagnostics::ReaderOptions;
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run() -> Result<()> {
+//! let data: Vec<u8> = Vec::new();
+//! let reader = ZipFileReader::new_with_options(data, ReaderOptions::new().diagnostics(true)).await?;
+//!
+//! if let Some(report) = reader.file().open_report() {
+//!     println!("located EOCDR in {:?}", report.locate_duration());
+//!     println!("parsed {} entries in {:?}", report.entries(), report.central_directory_duration());
+//! }
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::io::checkpoint::DEFAULT_YIELD_INTERVAL;
+#[cfg(feature = "encoding")]
+use crate::spec::encoding::FilenameEncoding;
+use crate::spec::locator::SearchStrategy;
+use crate::spec::version::SPEC_VERSION_MADE_BY;
+
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A set of options which control how ZIP files are opened by the readers.
+#[derive(Debug, Clone)]
+pub struct ReaderOptions {
+    pub(crate) diagnostics: bool,
+    pub(crate) parse_mode: ParseMode,
+    pub(crate) skip_corrupt_entries: bool,
+    pub(crate) search_strategy: SearchStrategy,
+    #[cfg(feature = "unicode")]
+    pub(crate) normalize_filenames: bool,
+    pub(crate) yield_interval: usize,
+    pub(crate) max_read_size: Option<u64>,
+    #[cfg(feature = "encoding")]
+    pub(crate) filename_encoding: FilenameEncoding,
+}
+
+impl Default for ReaderOptions {
+    fn default() -> Self {
+        ReaderOptions {
+            diagnostics: false,
+            parse_mode: ParseMode::default(),
+            skip_corrupt_entries: false,
+            search_strategy: SearchStrategy::default(),
+            #[cfg(feature = "unicode")]
+            normalize_filenames: false,
+            yield_interval: DEFAULT_YIELD_INTERVAL,
+            max_read_size: None,
+            #[cfg(feature = "encoding")]
+            filename_encoding: FilenameEncoding::Default,
+        }
+    }
+}
+
+impl ReaderOptions {
+    /// Constructs a new set of options with their default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether or not an [`OpenReport`] is collected whilst opening the ZIP file (defaults to false).
+    ///
+    /// Once opened, the report can be retrieved via [`ZipFile::open_report()`]. As timings are taken via
+    /// [`std::time::Instant`], this shouldn't be enabled on targets where it's unavailable (eg. wasm32-unknown-unknown).
+    pub fn diagnostics(mut self, value: bool) -> Self {
+        self.diagnostics = value;
+        self
+    }
+
+    /// Sets how strictly the ZIP file is checked against the specification whilst being opened (defaults to
+    /// [`ParseMode::Lenient`]).
+    pub fn parse_mode(mut self, mode: ParseMode) -> Self {
+        self.parse_mode = mode;
+        self
+    }
+
+    /// Sets whether or not malformed central directory records are skipped, rather than failing the whole ZIP file
+    /// (defaults to false).
+    ///
+    /// When a record can't be parsed, the reader scans forward for the next central directory record signature and
+    /// resumes from there, so the remaining intact entries can still be listed and read. Each record skipped is reported
+    /// as an [`OpenWarning::CorruptRecord`] (and so rejects the ZIP file under [`ParseMode::Strict`]). The
+    /// [`lazy`](crate::read::lazy) reader instead returns an error for each malformed record from its entry iterator,
+    /// and continues on to the next.
+    pub fn skip_corrupt_entries(mut self, value: bool) -> Self {
+        self.skip_corrupt_entries = value;
+        self
+    }
+
+    /// Sets the strategy used to search for the end of central directory record (defaults to a reverse scan).
+    pub fn search_strategy(mut self, strategy: SearchStrategy) -> Self {
+        self.search_strategy = strategy;
+        self
+    }
+
+    /// Sets whether or not decoded filenames are normalized into Unicode Normalization Form C (defaults to false).
+    ///
+    /// Archives created on macOS commonly store decomposed (NFD) filenames, which won't match lookups of their composed
+    /// (NFC) forms elsewhere. If normalization makes two distinct filenames equal, opening the ZIP file fails with
+    /// [`ZipError::NormalizedFilenameCollision`].
+    ///
+    /// [`ZipError::NormalizedFilenameCollision`]: crate::error::ZipError::NormalizedFilenameCollision
+    #[cfg(feature = "unicode")]
+    pub fn normalize_filenames(mut self, value: bool) -> Self {
+        self.normalize_filenames = value;
+        self
+    }
+
+    /// Sets the encoding with which filenames & comments lacking the language encoding flag are decoded (defaults to
+    /// [`FilenameEncoding::Default`]).
+    ///
+    /// Many archives created on Japanese or Chinese systems store filenames in a legacy encoding (eg. Shift-JIS or GBK)
+    /// without flagging as such, which would otherwise be decoded as IBM Code Page 437. Filenames taken from a Unicode
+    /// Path extra field, and fields flagged as UTF-8, are unaffected, as is the ZIP file's trailing comment. Decoding
+    /// takes place before any normalization (see [`ReaderOptions::normalize_filenames()`]). This doesn't apply to the
+    /// [`stream`](crate::read::stream) reader, which doesn't read the central directory.
+    #[cfg(feature = "encoding")]
+    pub fn filename_encoding(mut self, encoding: FilenameEncoding) -> Self {
+        self.filename_encoding = encoding;
+        self
+    }
+
+    /// Sets the number of iterations after which the reader yields to the runtime whilst opening (defaults to 512).
+    ///
+    /// An iteration is a single window searched for the end of central directory record, or a single central directory
+    /// record parsed. Over buffered or in-memory sources, these may never wait on IO, and so opening a ZIP file with
+    /// many entries could otherwise run for a long time without other tasks on the same worker being able to run. A
+    /// value of zero disables yielding.
+    pub fn yield_interval(mut self, interval: usize) -> Self {
+        self.yield_interval = interval;
+        self
+    }
+
+    /// Sets the maximum number of bytes which are read into memory by helpers such as
+    /// [`ZipEntryReader::read_to_end_checked()`] (defaults to no limit).
+    ///
+    /// This applies to every entry reader constructed by the ZIP reader, and can be overridden for a single entry via
+    /// [`ZipEntryReader::with_max_read_size()`]. Entries exceeding the limit are rejected with
+    /// [`ZipError::EntryTooLarge`] rather than being allocated in full, and should instead be read incrementally.
+    ///
+    /// [`ZipEntryReader::read_to_end_checked()`]: crate::read::io::entry::ZipEntryReader::read_to_end_checked
+    /// [`ZipEntryReader::with_max_read_size()`]: crate::read::io::entry::ZipEntryReader::with_max_read_size
+    /// [`ZipError::EntryTooLarge`]: crate::error::ZipError::EntryTooLarge
+    pub fn max_read_size(mut self, limit: u64) -> Self {
+        self.max_read_size = Some(limit);
+        self
+    }
+}
+
+/// How strictly a ZIP file is checked against the specification whilst being opened.
+///
+/// Many ZIP files in the wild deviate from the specification in minor ways (eg. trailing data or an incorrect central
+/// directory size) which don't prevent them from being read. Validators may wish to reject such files, whereas most
+/// extractors will want to read them regardless.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Rejects ZIP files with any irregularity (ie. any [`OpenWarning`]) via [`ZipError::SpecViolation`].
+    Strict,
+    /// Opens ZIP files on a best-effort basis, with any irregularities collected within the [`OpenReport`] (if
+    /// enabled via [`ReaderOptions::diagnostics()`]).
+    #[default]
+    Lenient,
+}
+
+impl ParseMode {
+    /// Returns an error for the first of the provided warnings if in strict mode.
+    pub(crate) fn check(self, warnings: &[OpenWarning]) -> Result<()> {
+        match (self, warnings.first()) {
+            (ParseMode::Strict, Some(warning)) => Err(ZipError::SpecViolation(warning.clone())),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A report of the time spent opening a ZIP file alongside a summary of its structure.
+#[derive(Debug, Clone)]
+pub struct OpenReport {
+    pub(crate) locate_duration: Duration,
+    pub(crate) central_directory_duration: Duration,
+    pub(crate) entries: usize,
+    pub(crate) central_directory_offset: u64,
+    pub(crate) central_directory_size: u64,
+    pub(crate) eocdr_offset: u64,
+    pub(crate) warnings: Vec<OpenWarning>,
+}
+
+impl OpenReport {
+    /// Returns the time spent locating the end of central directory record.
+    pub fn locate_duration(&self) -> Duration {
+        self.locate_duration
+    }
+
+    /// Returns the time spent reading & parsing the central directory.
+    pub fn central_directory_duration(&self) -> Duration {
+        self.central_directory_duration
+    }
+
+    /// Returns the number of entries within the central directory.
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Returns the offset of the central directory.
+    pub fn central_directory_offset(&self) -> u64 {
+        self.central_directory_offset
+    }
+
+    /// Returns the number of bytes read whilst parsing the central directory.
+    pub fn central_directory_size(&self) -> u64 {
+        self.central_directory_size
+    }
+
+    /// Returns the offset of the end of central directory record.
+    pub fn eocdr_offset(&self) -> u64 {
+        self.eocdr_offset
+    }
+
+    /// Returns any irregularities noticed whilst opening the ZIP file.
+    ///
+    /// These didn't prevent the file from being opened, but may indicate that it was written incorrectly or has since
+    /// been modified.
+    pub fn warnings(&self) -> &[OpenWarning] {
+        &self.warnings
+    }
+}
+
+/// An irregularity noticed whilst opening a ZIP file.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenWarning {
+    /// Data precedes the first local file header (eg. a self-extracting stub), holding its length.
+    PrecedingData(u64),
+    /// The central directory's size stored within the end of central directory record differs from the number of bytes
+    /// read whilst parsing it.
+    CentralDirectorySizeMismatch { stored: u64, actual: u64 },
+    /// The central directory doesn't immediately precede the end of central directory record, holding the length of the
+    /// data between them.
+    TrailingCentralDirectoryData(u64),
+    /// More than one entry shares the same filename, holding that filename.
+    DuplicateFilename(String),
+    /// An entry's filename is empty once normalized (see [`ZipEntry::has_empty_filename()`]), holding its index.
+    ///
+    /// [`ZipEntry::has_empty_filename()`]: crate::ZipEntry::has_empty_filename
+    EmptyFilename(usize),
+    /// A malformed central directory record was skipped (see [`ReaderOptions::skip_corrupt_entries()`]), holding its
+    /// index within the central directory, its offset, and the error encountered whilst parsing it.
+    CorruptRecord { index: usize, offset: u64, reason: String },
+    /// Data follows the end of central directory record (and its comment), holding its length.
+    TrailingData(u64),
+    /// An entry's version needed to extract is newer than the latest version of the specification, holding its index and
+    /// that version.
+    UnknownVersionNeeded { index: usize, version: u16 },
+}
+
+impl Display for OpenWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OpenWarning::PrecedingData(length) => write!(f, "{} bytes precede the first entry", length),
+            OpenWarning::CentralDirectorySizeMismatch { stored, actual } => {
+                write!(f, "central directory is {} bytes but {} are stored", actual, stored)
+            }
+            OpenWarning::TrailingCentralDirectoryData(length) => {
+                write!(f, "{} bytes follow the central directory", length)
+            }
+            OpenWarning::DuplicateFilename(filename) => {
+                write!(f, "entry filename appears more than once: '{}'", filename)
+            }
+            OpenWarning::EmptyFilename(index) => write!(f, "entry {} has an empty filename", index),
+            OpenWarning::CorruptRecord { index, offset, reason } => {
+                write!(f, "central directory record {} at offset {:#x} was skipped: {}", index, offset, reason)
+            }
+            OpenWarning::TrailingData(length) => {
+                write!(f, "{} bytes follow the end of central directory record", length)
+            }
+            OpenWarning::UnknownVersionNeeded { index, version } => {
+                write!(f, "entry {} needs unknown version {} to extract", index, version)
+            }
+        }
+    }
+}
+
+/// Collects the warnings for a parsed ZIP file given the offsets & sizes of its central directory, and the length of
+/// any data following its end of central directory record.
+///
+/// The provided warnings for any skipped central directory records are returned first.
+pub(crate) fn warnings(
+    file: &ZipFile,
+    skipped: Vec<OpenWarning>,
+    cd_offset: u64,
+    cd_stored: u64,
+    cd_actual: u64,
+    eocdr: u64,
+    trailing: u64,
+) -> Vec<OpenWarning> {
+    let mut warnings = skipped;
+
+    match file.metas.iter().map(|meta| meta.file_offset).min() {
+        Some(offset) if offset > 0 => warnings.push(OpenWarning::PrecedingData(offset)),
+        None if cd_offset > 0 => warnings.push(OpenWarning::PrecedingData(cd_offset)),
+        _ => (),
+    }
+
+    if cd_stored != cd_actual {
+        warnings.push(OpenWarning::CentralDirectorySizeMismatch { stored: cd_stored, actual: cd_actual });
+    }
+    if eocdr > cd_offset + cd_actual {
+        warnings.push(OpenWarning::TrailingCentralDirectoryData(eocdr - (cd_offset + cd_actual)));
+    }
+
+    let mut filenames = HashSet::new();
+    for entry in file.entries.iter().filter(|entry| !filenames.insert(entry.filename())) {
+        warnings.push(OpenWarning::DuplicateFilename(entry.filename().to_string()));
+    }
+
+    for (index, _) in file.entries.iter().enumerate().filter(|(_, entry)| entry.has_empty_filename()) {
+        warnings.push(OpenWarning::EmptyFilename(index));
+    }
+
+    for (index, meta) in file.metas.iter().enumerate() {
+        // The upper byte is reserved, but is commonly set by writers which copy the version made by field.
+        let version = meta.version_needed & 0xFF;
+
+        if version > SPEC_VERSION_MADE_BY {
+            warnings.push(OpenWarning::UnknownVersionNeeded { index, version });
+        }
+    }
+
+    if trailing > 0 {
+        warnings.push(OpenWarning::TrailingData(trailing));
+    }
+
+    warnings
+}
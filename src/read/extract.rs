@@ -0,0 +1,501 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports extracting ZIP entries into a file system directory.
+//!
+//! Extracting entries from untrusted ZIP files without sanitising their filenames could allow [directory traversal
+//! attacks](https://en.wikipedia.org/wiki/Directory_traversal_attack#Archives). As such, any entry whose filename
+//! contains a `..` component, is absolute, or contains a drive/UNC prefix is rejected with
+//! [`ZipError::UnsafeFilename`] rather than being written outside of the destination directory.
+//!
+//! Symlink entries are recreated as symbolic links by default, provided their target resolves within the destination
+//! directory (otherwise [`ZipError::UnsafeSymlinkTarget`] is returned). This can be disabled via
+//! [`ExtractOptions::symlinks()`], in which case the link's target is written out as a regular file instead. Entries are
+//! never written through a symlink within the destination directory (eg. one extracted from an earlier entry); any
+//! entry whose path passes through an existing symlink is rejected with [`ZipError::UnsafeFilename`].
+//!
+//! Ownership stored within Info-ZIP Unix extra fields (see [`ZipEntry::uid()`]) can be restored via
+//! [`ExtractOptions::ownership()`] when running as root on Unix, optionally translating IDs between systems via
+//! [`ExtractOptions::owner_mapping()`].
+//!
+//! When extracting untrusted ZIP files, limits should also be placed on the amount of data decompressed via
+//! [`ExtractOptions::limits()`] (see the [`limits`](crate::read::limits) module).
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::mem::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run() -> Result<()> {
+//! let data: Vec<u8> = Vec::new();
+//! let reader = ZipFileReader::new(data).await?;
+//!
+//! reader.extract("./output").await?;
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::entry::{is_empty_filename, ZipEntry};
+use crate::error::{Result, ZipError};
+use crate::file::pattern::EntryPattern;
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::limits::{unwrap_limit_error, DecompressionLimits};
+use crate::read::progress::{EntryProgress, ExtractProgress, ProgressCallback};
+
+use std::fmt::{Debug, Formatter};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::fs::File;
+use tokio::io::AsyncRead;
+#[cfg(feature = "cancel")]
+use tokio_util::sync::CancellationToken;
+
+/// The permission bits which are applied on extraction (excluding the setuid, setgid, and sticky bits).
+const PERMISSIONS_MASK: u32 = 0o777;
+
+/// A set of options which control how entries are extracted.
+#[derive(Debug, Clone)]
+pub struct ExtractOptions {
+    pub(crate) symlinks: bool,
+    pub(crate) permissions: bool,
+    pub(crate) ownership: bool,
+    pub(crate) owner_mapping: Option<OwnerMapping>,
+    pub(crate) skip_empty_filenames: bool,
+    pub(crate) limits: DecompressionLimits,
+    #[cfg(feature = "cancel")]
+    pub(crate) cancellation_token: Option<CancellationToken>,
+    pub(crate) progress: Option<ProgressCallback<ExtractProgress>>,
+    pub(crate) filter: Option<EntryPattern>,
+}
+
+impl Default for ExtractOptions {
+    fn default() -> Self {
+        ExtractOptions {
+            symlinks: true,
+            permissions: false,
+            ownership: false,
+            owner_mapping: None,
+            skip_empty_filenames: false,
+            limits: DecompressionLimits::default(),
+            #[cfg(feature = "cancel")]
+            cancellation_token: None,
+            progress: None,
+            filter: None,
+        }
+    }
+}
+
+impl ExtractOptions {
+    /// Constructs a new set of options with their default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether or not symlink entries are recreated as symbolic links (defaults to true).
+    pub fn symlinks(mut self, value: bool) -> Self {
+        self.symlinks = value;
+        self
+    }
+
+    /// Sets whether or not stored Unix permissions are applied to extracted files & directories (defaults to false).
+    ///
+    /// Only the read, write, and execute bits are applied; the setuid, setgid, and sticky bits are always ignored. This
+    /// option has no effect on non-Unix platforms or for entries without stored Unix permissions.
+    pub fn permissions(mut self, value: bool) -> Self {
+        self.permissions = value;
+        self
+    }
+
+    /// Sets whether or not stored ownership is restored on extracted files, directories & symlinks (defaults to false).
+    ///
+    /// Ownership is read from each entry's Info-ZIP Unix extra field (see [`ZipEntry::uid()`] & [`ZipEntry::gid()`]).
+    /// Entries without stored ownership are left owned by the current user. As ownership can only be changed by root,
+    /// extraction fails upfront with [`ZipError::OwnershipRequiresRoot`] if this is enabled without running as root (or
+    /// with [`ZipError::FeatureNotSupported`] on non-Unix platforms).
+    pub fn ownership(mut self, value: bool) -> Self {
+        self.ownership = value;
+        self
+    }
+
+    /// Sets a function which translates each entry's stored UID & GID before ownership is restored (defaults to none).
+    ///
+    /// This allows IDs to be translated between systems (eg. when the same user has a different UID on each). Returning
+    /// [`None`] leaves the entry owned by the current user. This has no effect unless [`ExtractOptions::ownership()`] is
+    /// enabled.
+    pub fn owner_mapping<F>(mut self, mapping: F) -> Self
+    where
+        F: Fn(u32, u32) -> Option<(u32, u32)> + Send + Sync + 'static,
+    {
+        self.owner_mapping = Some(OwnerMapping(Arc::new(mapping)));
+        self
+    }
+
+    /// Sets whether or not entries whose filenames are empty once normalized are skipped (defaults to false).
+    ///
+    /// Such entries (see [`ZipEntry::has_empty_filename()`]) don't name any path within the destination, and so by
+    /// default, extraction fails with [`ZipError::EmptyFilename`] upon reaching one (other than a directory entry, which
+    /// would only ever name the destination itself and so is always skipped). When skipped, their data is still read
+    /// (so that streamed extraction can continue), but nothing is written. When a mapping is used, the mapped path is
+    /// checked rather than the entry's filename.
+    pub fn skip_empty_filenames(mut self, value: bool) -> Self {
+        self.skip_empty_filenames = value;
+        self
+    }
+
+    /// Sets limits on the amount of data decompressed, both per entry and in total (defaults to none).
+    ///
+    /// Extraction fails with [`ZipError::LimitExceeded`] once a limit has been exceeded, leaving any entries already
+    /// extracted (and the part of the entry being extracted) in place. See the [`limits`](crate::read::limits) module for
+    /// more information.
+    pub fn limits(mut self, limits: DecompressionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets a token which, once cancelled, stops extraction before the next entry is extracted.
+    ///
+    /// Entries already extracted are left in place and [`ZipError::Cancelled`] is returned with the number of entries
+    /// which were completed, allowing long-running extractions to be abandoned gracefully (eg. on a client disconnect).
+    #[cfg(feature = "cancel")]
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Sets a callback to which extraction progress is reported, both as each entry's data is read and once each entry
+    /// has been extracted.
+    ///
+    /// See the [`progress`](crate::read::progress) module for more information.
+    pub fn progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ExtractProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(ProgressCallback::new(callback));
+        self
+    }
+
+    /// Sets a pattern which entry filenames must match in order to be extracted (defaults to none).
+    ///
+    /// Entries which don't match are skipped entirely, and aren't counted towards the total reported as progress. When a
+    /// mapping is used, the pattern is matched against the entry's original filename rather than the mapped path.
+    pub fn filter(mut self, pattern: EntryPattern) -> Self {
+        self.filter = Some(pattern);
+        self
+    }
+
+    /// Returns whether or not the provided entry passes the filter, if any.
+    pub(crate) fn includes(&self, entry: &ZipEntry) -> bool {
+        self.filter.as_ref().is_none_or(|pattern| pattern.matches(entry.filename()))
+    }
+
+    /// Returns the (mapped) UID & GID which should be applied to the provided entry, if any.
+    #[cfg(unix)]
+    fn owner(&self, entry: &ZipEntry) -> Option<(u32, u32)> {
+        let owner = entry.uid().zip(entry.gid()).filter(|_| self.ownership)?;
+
+        match &self.owner_mapping {
+            Some(mapping) => (mapping.0)(owner.0, owner.1),
+            None => Some(owner),
+        }
+    }
+}
+
+/// A function which translates a stored UID & GID.
+type OwnerMappingFn = dyn Fn(u32, u32) -> Option<(u32, u32)> + Send + Sync;
+
+/// A shared function which translates stored UIDs & GIDs.
+#[derive(Clone)]
+pub(crate) struct OwnerMapping(Arc<OwnerMappingFn>);
+
+impl Debug for OwnerMapping {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("OwnerMapping")
+    }
+}
+
+/// Returns a relative path which is safe to join onto a destination directory.
+///
+/// Backslashes are treated as path separators, and empty or `.` components are dropped. [`None`] is returned if the
+/// filename is absolute, contains a `..` component, or starts with a drive or UNC prefix.
+pub(crate) fn sanitize_filename(filename: &str) -> Option<PathBuf> {
+    let filename = filename.replace('\\', "/");
+
+    if filename.starts_with('/') {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+
+    for (index, component) in filename.split('/').enumerate() {
+        match component {
+            "" | "." => continue,
+            ".." => return None,
+            _ if index == 0 && is_drive_prefix(component) => return None,
+            _ => path.push(component),
+        }
+    }
+
+    // Guard against anything the platform interprets differently to the above (eg. Windows reserved prefixes).
+    if !path.components().all(|component| matches!(component, Component::Normal(_))) {
+        return None;
+    }
+
+    Some(path)
+}
+
+/// Returns whether or not a path component is a Windows drive prefix (eg. `C:`).
+fn is_drive_prefix(component: &str) -> bool {
+    let bytes = component.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Returns whether or not a symlink's target lexically resolves to a path within the destination directory.
+///
+/// The link itself is provided as a sanitised path relative to the destination directory.
+fn symlink_target_contained(link: &Path, target: &str) -> bool {
+    let target = target.replace('\\', "/");
+
+    if target.starts_with('/') {
+        return false;
+    }
+
+    let mut depth = link.components().count().saturating_sub(1);
+
+    for (index, component) in target.split('/').enumerate() {
+        match component {
+            "" | "." => continue,
+            ".." if depth == 0 => return false,
+            ".." => depth -= 1,
+            _ if index == 0 && is_drive_prefix(component) => return false,
+            _ => depth += 1,
+        }
+    }
+
+    true
+}
+
+/// Returns an error if any existing component of a path relative to the destination directory is a symbolic link.
+///
+/// Each symlink target is only checked lexically, so a chain of links (eg. `x` -> `.` followed by `x/y` -> `..`) could
+/// otherwise be used to write outside of the destination directory by extracting later entries through them.
+async fn check_no_symlinks(dest: &Path, relative: &Path) -> Result<()> {
+    let mut path = dest.to_path_buf();
+
+    for component in relative.components() {
+        path.push(component);
+
+        match tokio::fs::symlink_metadata(&path).await {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                return Err(ZipError::UnsafeFilename(relative.display().to_string()));
+            }
+            Ok(_) => continue,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(())
+}
+
+/// Creates a symbolic link at the provided path, if supported by the platform.
+async fn create_symlink(target: &str, path: &Path) -> Result<()> {
+    #[cfg(unix)]
+    tokio::fs::symlink(target, path).await?;
+    #[cfg(windows)]
+    tokio::fs::symlink_file(target, path).await?;
+    #[cfg(not(any(unix, windows)))]
+    tokio::fs::write(path, target).await?;
+
+    Ok(())
+}
+
+/// Applies Unix permission bits to a path, if supported by the platform.
+#[allow(unused_variables)]
+async fn set_permissions(path: &Path, mode: u32) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).await?;
+    }
+
+    Ok(())
+}
+
+/// Returns an error if ownership can't be restored by the current process (ie. it isn't running as root on Unix).
+fn check_ownership_permitted() -> Result<()> {
+    #[cfg(unix)]
+    // SAFETY: geteuid() has no preconditions and always succeeds.
+    match unsafe { libc::geteuid() } {
+        0 => Ok(()),
+        _ => Err(ZipError::OwnershipRequiresRoot),
+    }
+
+    #[cfg(not(unix))]
+    Err(ZipError::FeatureNotSupported("Restoring ownership on non-Unix platforms"))
+}
+
+/// Applies an owner to a path without following symlinks.
+#[cfg(unix)]
+async fn set_owner(path: &Path, owner: (u32, u32)) -> Result<()> {
+    let path = path.to_path_buf();
+    let result = tokio::task::spawn_blocking(move || std::os::unix::fs::lchown(path, Some(owner.0), Some(owner.1)));
+
+    Ok(result.await.map_err(std::io::Error::other)??)
+}
+
+/// A helper which extracts entries into a destination directory, one at a time.
+pub(crate) struct Extractor<'a> {
+    dest: &'a Path,
+    options: &'a ExtractOptions,
+    deferred_permissions: Vec<(PathBuf, u32)>,
+    completed: usize,
+    total: Option<usize>,
+    read: u64,
+}
+
+impl<'a> Extractor<'a> {
+    /// Constructs a new extractor, creating the destination directory if needed.
+    ///
+    /// The total number of entries to be extracted is only used for reporting progress, and so may be unknown.
+    pub(crate) async fn new(
+        dest: &'a Path,
+        options: &'a ExtractOptions,
+        total: Option<usize>,
+    ) -> Result<Extractor<'a>> {
+        if options.ownership {
+            check_ownership_permitted()?;
+        }
+
+        tokio::fs::create_dir_all(dest).await?;
+        Ok(Extractor { dest, options, deferred_permissions: Vec::new(), completed: 0, total, read: 0 })
+    }
+
+    /// Extracts a single entry into the destination directory, at the provided path relative to it.
+    pub(crate) async fn extract<R>(
+        &mut self,
+        entry: &ZipEntry,
+        path: &Path,
+        reader: ZipEntryReader<'_, R>,
+    ) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+    {
+        #[cfg(feature = "cancel")]
+        if self.options.cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(ZipError::Cancelled(self.completed));
+        }
+
+        let reader = reader.with_limits(self.options.limits).with_read_before(self.read);
+        let reader = match self.options.progress.clone() {
+            Some(callback) => {
+                let (entries_completed, entries_total) = (self.completed, self.total);
+                reader.with_progress(move |entry| {
+                    callback.report(ExtractProgress { entry, entries_completed, entries_total });
+                })
+            }
+            None => reader,
+        };
+
+        let entry = self.extract_entry(entry, path, reader).await?;
+        self.completed += 1;
+        self.read += entry.read();
+
+        if let Some(callback) = &self.options.progress {
+            callback.report(ExtractProgress { entry, entries_completed: self.completed, entries_total: self.total });
+        }
+
+        Ok(())
+    }
+
+    /// Extracts a single entry, without checking for cancellation, and returns the progress made reading its data.
+    async fn extract_entry<R>(
+        &mut self,
+        entry: &ZipEntry,
+        path: &Path,
+        mut reader: ZipEntryReader<'_, R>,
+    ) -> Result<EntryProgress>
+    where
+        R: AsyncRead + Unpin,
+    {
+        // A directory entry whose filename is empty would only ever name the destination itself, so is always skipped.
+        if path.to_str().is_some_and(is_empty_filename) {
+            if !self.options.skip_empty_filenames && !entry.dir() {
+                return Err(ZipError::EmptyFilename(path.display().to_string()));
+            }
+
+            tokio::io::copy(&mut reader, &mut tokio::io::sink()).await.map_err(unwrap_limit_error)?;
+            return Ok(reader.progress());
+        }
+
+        let relative = path
+            .to_str()
+            .and_then(sanitize_filename)
+            .ok_or_else(|| ZipError::UnsafeFilename(path.display().to_string()))?;
+        check_no_symlinks(self.dest, &relative).await?;
+        let path = self.dest.join(&relative);
+        let mode = entry.unix_mode().filter(|_| self.options.permissions).map(|mode| mode & PERMISSIONS_MASK);
+
+        if entry.dir() {
+            tokio::fs::create_dir_all(&path).await?;
+            self.restore_owner(entry, &path).await?;
+
+            // Applying permissions to a directory may prevent its children from being written, so defer until the end.
+            if let Some(mode) = mode {
+                self.deferred_permissions.push((path, mode));
+            }
+
+            return Ok(reader.progress());
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        if entry.is_symlink() && self.options.symlinks {
+            let mut target = String::new();
+            reader.read_to_string_checked(&mut target, entry).await?;
+
+            if !symlink_target_contained(&relative, &target) {
+                return Err(ZipError::UnsafeSymlinkTarget(target));
+            }
+
+            create_symlink(&target, &path).await?;
+            self.restore_owner(entry, &path).await?;
+            return Ok(reader.progress());
+        }
+
+        let mut file = File::create(&path).await?;
+        reader.copy_to_checked(&mut file, entry).await?;
+        self.restore_owner(entry, &path).await?;
+
+        if let Some(mode) = mode {
+            set_permissions(&path, mode).await?;
+        }
+
+        Ok(reader.progress())
+    }
+
+    /// Restores the entry's stored owner onto the extracted path, if enabled.
+    ///
+    /// Whether or not ownership can be restored is checked upfront when this extractor is constructed.
+    #[allow(unused_variables)]
+    async fn restore_owner(&self, entry: &ZipEntry, path: &Path) -> Result<()> {
+        #[cfg(unix)]
+        if let Some(owner) = self.options.owner(entry) {
+            set_owner(path, owner).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Completes any tasks which were deferred until all entries had been extracted.
+    pub(crate) async fn finish(self) -> Result<()> {
+        // Apply in reverse so that nested directories are handled before their parents.
+        for (path, mode) in self.deferred_permissions.iter().rev() {
+            set_permissions(path, *mode).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -5,13 +5,16 @@
 //!
 //! Concurrency is achieved as a result of:
 //! - Wrapping the provided path within an [`Arc`] to allow shared ownership.
-//! - Constructing a new [`File`] from the path when reading.
+//! - Reusing idle [`File`] handles to the path when reading, only opening a new handle when none are idle.
+//!
+//! Each entry reader holds its own handle (as handles carry their own position), which is returned to the reader's
+//! pool once the entry reader is dropped. As such, reading many entries in turn doesn't open the file for each entry,
+//! though the number of handles held at once is still bounded by the number of entry readers open concurrently.
 //!
 //! ### Usage
 //! Unlike the [`seek`] module, we no longer hold a mutable reference to any inner reader which in turn, allows the
-//! construction of concurrent [`ZipEntryReader`]s. Though, note that each individual [`ZipEntryReader`] cannot be sent
-//! between thread boundaries due to the masked lifetime requirement. Therefore, the overarching [`ZipFileReader`]
-//! should be cloned and moved into those contexts when needed.
+//! construction of concurrent [`ZipEntryReader`]s. Each [`ZipEntryReader`] owns its inner reader (and so is `'static`
+//! and [`Send`]), allowing it to be moved onto a separate task without cloning the overarching [`ZipFileReader`].
 //!
 //! ### Concurrent Example
 //! ```no_run
@@ -36,21 +39,19 @@
 //! ```no_run
 //! # use async_zip::read::fs::ZipFileReader;
 //! # use async_zip::error::Result;
+//! # use tokio::io::AsyncReadExt;
 //! #
 //! # async fn run() -> Result<()> {
 //! let reader = ZipFileReader::new("./foo.zip").await?;
+//! let mut entry_reader = reader.entry(0).await?;
 //!
-//! let fut_gen = |index| {
-//!     let local_reader = reader.clone();
-//!
-//!     tokio::spawn(async move {
-//!         let mut entry_reader = local_reader.entry_reader(index).await?;
-//!         let mut data = Vec::new();
-//!         entry_reader.read_to_end(&mut data).await.unwrap();
-//!     })
-//! };
+//! let handle = tokio::spawn(async move {
+//!     let mut data = Vec::new();
+//!     entry_reader.read_to_end(&mut data).await.unwrap();
+//!     data
+//! });
 //!
-//! tokio::join!(fut_gen(0), fut_gen(1)).map(|res| res?);
+//! let data = handle.await.unwrap();
 //! #   Ok(())
 //! # }
 //! ```
@@ -58,19 +59,81 @@
 #[cfg(doc)]
 use crate::read::seek;
 
+use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::extract::{ExtractOptions, Extractor};
 use crate::read::io::entry::ZipEntryReader;
+use crate::read::rewrite::RewriteAction;
+use crate::write::ZipFileWriter;
 
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
 
 use tokio::fs::File;
-use tokio::io::{AsyncSeekExt, SeekFrom};
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, BufReader, ReadBuf, SeekFrom};
+
+/// The maximum number of idle file handles retained by a reader for reuse.
+///
+/// Handles are returned to the pool in whatever state their last borrower left them (eg. part way through an entry, or
+/// with a read still in flight if the borrower was cancelled), so every borrower must seek to an absolute position
+/// before using one. Seeking via [`AsyncSeekExt::seek()`] first waits for any operation still in flight to complete,
+/// and discards any data buffered by the handle.
+const MAX_IDLE_HANDLES: usize = 16;
+
+/// The number of temporary files created by in-place rewrites so far, used to give each a unique name.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 struct Inner {
     path: PathBuf,
     file: ZipFile,
+    handles: Mutex<Vec<File>>,
+}
+
+/// A file handle borrowed from a [`ZipFileReader`]'s pool, which is returned to the pool once dropped.
+///
+/// The handle's position is unspecified when borrowed, so it must be seeked to an absolute position before being read.
+pub struct PooledFile {
+    file: Option<File>,
+    inner: Arc<Inner>,
+}
+
+impl PooledFile {
+    fn file(self: Pin<&mut Self>) -> Pin<&mut File> {
+        Pin::new(self.get_mut().file.as_mut().expect("file should be present until dropped"))
+    }
+}
+
+impl AsyncRead for PooledFile {
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        self.file().poll_read(c, b)
+    }
+}
+
+impl AsyncSeek for PooledFile {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> tokio::io::Result<()> {
+        self.file().start_seek(position)
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<tokio::io::Result<u64>> {
+        self.file().poll_complete(c)
+    }
+}
+
+impl Drop for PooledFile {
+    fn drop(&mut self) {
+        let file = self.file.take();
+
+        if let (Some(file), Ok(mut handles)) = (file, self.inner.handles.lock()) {
+            if handles.len() < MAX_IDLE_HANDLES {
+                handles.push(file);
+            }
+        }
+    }
 }
 
 /// A concurrent ZIP reader which acts over a file system path.
@@ -82,13 +145,23 @@ pub struct ZipFileReader {
 impl ZipFileReader {
     /// Constructs a new ZIP reader from a file system path.
     pub async fn new<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        Self::new_with_options(path, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a file system path, using the provided options.
+    pub async fn new_with_options<P>(path: P, options: ReaderOptions) -> Result<ZipFileReader>
     where
         P: AsRef<Path>,
     {
         let path = path.as_ref().to_owned();
-        let file = crate::read::file(File::open(&path).await?).await?;
+        let mut reader = BufReader::new(File::open(&path).await?);
+        let file = crate::read::file(&mut reader, &options).await?;
+        let handles = Mutex::new(vec![reader.into_inner()]);
 
-        Ok(ZipFileReader { inner: Arc::new(Inner { path, file }) })
+        Ok(ZipFileReader { inner: Arc::new(Inner { path, file, handles }) })
     }
 
     /// Returns this ZIP file's information.
@@ -101,14 +174,251 @@ impl ZipFileReader {
         &self.inner.path
     }
 
+    /// Returns the number of idle file handles currently retained for reuse by entry readers.
+    pub fn idle_handles(&self) -> usize {
+        self.inner.handles.lock().map(|handles| handles.len()).unwrap_or(0)
+    }
+
+    /// Returns an idle file handle from the pool, or opens a new one if none are idle.
+    ///
+    /// The caller must seek the handle to an absolute position before reading from it (see [`MAX_IDLE_HANDLES`]).
+    async fn handle(&self) -> Result<PooledFile> {
+        let idle = self.inner.handles.lock().ok().and_then(|mut handles| handles.pop());
+        let file = match idle {
+            Some(file) => file,
+            None => File::open(&self.inner.path).await?,
+        };
+
+        Ok(PooledFile { file: Some(file), inner: self.inner.clone() })
+    }
+
+    /// Reads the local file header of the entry at the provided index, returning the offset at which its data begins.
+    ///
+    /// See [`ZipFile::data_offset()`] for more information.
+    pub async fn data_offset(&self, index: usize) -> Result<u64> {
+        self.file().data_offset(index, &mut self.handle().await?).await
+    }
+
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<File>> {
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'static, PooledFile>> {
+        let entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.inner.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let mut fs_file = self.handle().await?;
+        let seek_to = crate::read::compute_data_offset(&mut fs_file, meta).await?;
+
+        fs_file.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_with_owned(fs_file, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file().max_read_size))
+    }
+
+    /// Returns a new entry reader for the first entry with the provided filename.
+    ///
+    /// Filenames are matched as per [`ZipFile::entry_index()`]. If no entry matches, [`ZipError::EntryNotFound`] is
+    /// returned.
+    pub async fn entry_by_name(&self, filename: &str) -> Result<ZipEntryReader<'static, PooledFile>> {
+        self.entry(self.inner.file.named_index(filename)?).await
+    }
+
+    /// Returns the first entry with the provided filename alongside a new reader over it.
+    ///
+    /// See [`ZipFileReader::entry_by_name()`] for more information.
+    pub async fn entry_and_reader_by_name(
+        &self,
+        filename: &str,
+    ) -> Result<(&ZipEntry, ZipEntryReader<'static, PooledFile>)> {
+        let index = self.inner.file.named_index(filename)?;
+        Ok((&self.inner.file.entries[index], self.entry(index).await?))
+    }
+
+    /// Returns a new reader over the raw (ie. still compressed) data of an entry if the provided index is valid.
+    ///
+    /// Paired with [`ZipFileWriter::copy_entry_raw()`], this allows entries to be copied between archives without being
+    /// decompressed and recompressed. Encrypted entries aren't supported.
+    ///
+    /// [`ZipFileWriter::copy_entry_raw()`]: crate::write::ZipFileWriter::copy_entry_raw
+    pub async fn entry_raw(&self, index: usize) -> Result<ZipEntryReader<'static, PooledFile>> {
+        let entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.inner.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if meta.general_purpose_flag.encrypted {
+            return Err(ZipError::FeatureNotSupported("Raw reading of encrypted entries"));
+        }
+
+        let mut fs_file = self.handle().await?;
+        let seek_to = crate::read::compute_data_offset(&mut fs_file, meta).await?;
+
+        fs_file.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_raw_with_owned(fs_file, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file().max_read_size))
+    }
+
+    /// Returns a new entry reader which decrypts the entry using the provided password if the provided index is valid.
+    ///
+    /// Traditional PKWARE (ZipCrypto) encryption is supported, as is WinZip AES encryption with the `aes` feature.
+    /// Entries which aren't encrypted are read as if via
+    /// [`ZipFileReader::entry()`], ignoring the password.
+    pub async fn entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'static, PooledFile>> {
         let entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let meta = self.inner.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let seek_to = crate::read::compute_data_offset(entry, meta);
-        let mut fs_file = File::open(&self.inner.path).await?;
+
+        if !meta.general_purpose_flag.encrypted {
+            return self.entry(index).await;
+        }
+
+        let mut fs_file = self.handle().await?;
+        let (header, seek_to) = crate::read::local_file_header(&mut fs_file, meta).await?;
+        let encryption = crate::read::encryption(&header, meta)?;
 
         fs_file.seek(SeekFrom::Start(seek_to)).await?;
-        Ok(ZipEntryReader::new_with_owned(fs_file, entry.compression(), entry.uncompressed_size().into()))
+        let reader = ZipEntryReader::new_with_owned_password(fs_file, entry, password, encryption).await?;
+        Ok(reader.with_read_limit(self.file().max_read_size))
+    }
+
+    /// Rewrites this ZIP file's entries into the provided writer, taking the action decided for each entry.
+    ///
+    /// See the [`rewrite`](crate::read::rewrite) module for more information.
+    pub async fn rewrite<W, F>(&self, writer: &mut ZipFileWriter<W>, mut decide: F) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(&ZipEntry) -> RewriteAction,
+    {
+        for (index, entry) in self.inner.file.entries.iter().enumerate() {
+            if let Some(entry) = decide(entry).apply(entry) {
+                writer.copy_entry_raw(&entry, self.entry_raw(index).await?).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites this ZIP file's entries into a new ZIP file at the provided path, taking the action decided for each
+    /// entry and retaining this ZIP file's comment.
+    ///
+    /// If the path refers to this ZIP file itself, the rewritten ZIP file is first written to a temporary file within
+    /// the same directory and then renamed over this one once complete (keeping this one's permissions), rather than
+    /// being truncated whilst it's still being read. In that case, this reader (and any clones of it) no longer reflect the ZIP file at this path and
+    /// shouldn't be used further.
+    ///
+    /// See the [`rewrite`](crate::read::rewrite) module for more information.
+    pub async fn rewrite_to_path<P, F>(&self, dest: P, decide: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ZipEntry) -> RewriteAction,
+    {
+        let dest = dest.as_ref();
+
+        if !same_file(&self.inner.path, dest).await? {
+            return self.rewrite_to_file(dest, decide).await;
+        }
+
+        // The counter keeps the names of concurrent rewrites within this process apart.
+        let id = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+        let name = dest.file_name().unwrap_or_default().to_string_lossy();
+        let temp = dest.with_file_name(format!(".{}.{}.{}.tmp", name, std::process::id(), id));
+
+        if let Err(err) = self.rewrite_to_temp_file(&temp, dest, decide).await {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(err);
+        }
+
+        Ok(tokio::fs::rename(&temp, dest).await?)
+    }
+
+    /// Rewrites this ZIP file's entries into a temporary file which is to replace the provided destination, copying
+    /// the destination's permissions onto it.
+    async fn rewrite_to_temp_file<F>(&self, temp: &Path, dest: &Path, decide: F) -> Result<()>
+    where
+        F: FnMut(&ZipEntry) -> RewriteAction,
+    {
+        self.rewrite_to_file(temp, decide).await?;
+        let permissions = tokio::fs::metadata(dest).await?.permissions();
+        Ok(tokio::fs::set_permissions(temp, permissions).await?)
+    }
+
+    /// Rewrites this ZIP file's entries into a newly-created file, syncing it to disk once complete.
+    async fn rewrite_to_file<F>(&self, path: &Path, decide: F) -> Result<()>
+    where
+        F: FnMut(&ZipEntry) -> RewriteAction,
+    {
+        let mut file = File::create(path).await?;
+        let mut writer = ZipFileWriter::new(&mut file);
+
+        if !self.inner.file.comment().is_empty() {
+            writer.comment(self.inner.file.comment().to_string())?;
+        }
+
+        self.rewrite(&mut writer, decide).await?;
+        writer.close_synced().await
+    }
+
+    /// Extracts all entries into the provided destination directory, creating it if needed.
+    ///
+    /// Entries whose filenames would escape the destination directory (ie. those containing `..` components, absolute
+    /// paths, or drive prefixes) are rejected with [`ZipError::UnsafeFilename`].
+    pub async fn extract<P>(&self, dest: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_options(dest, ExtractOptions::default()).await
+    }
+
+    /// Extracts all entries into the provided destination directory using the provided options.
+    ///
+    /// See [`ZipFileReader::extract()`] for more information.
+    pub async fn extract_with_options<P>(&self, dest: P, options: ExtractOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_mapping(dest, options, |entry| Some(PathBuf::from(entry.filename()))).await
+    }
+
+    /// Extracts all entries into the provided destination directory using the provided options, relocating each entry
+    /// to the path returned by the provided mapping (or skipping it if [`None`] is returned).
+    ///
+    /// Returned paths are relative to the destination directory and are sanitised as entry filenames are (see
+    /// [`ZipFileReader::extract()`]). This allows entries to be renamed or moved on the fly, eg. to strip an undesirable
+    /// top-level directory. The mapping is applied to every entry before any are extracted, so that the total number of
+    /// entries to be extracted can be reported as progress.
+    pub async fn extract_with_mapping<P, F>(&self, dest: P, options: ExtractOptions, mut map: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ZipEntry) -> Option<PathBuf>,
+    {
+        let paths: Vec<_> = self
+            .inner
+            .file
+            .entries
+            .iter()
+            .map(|entry| if options.includes(entry) { map(entry) } else { None })
+            .collect();
+        let mut extractor = Extractor::new(dest.as_ref(), &options, Some(paths.iter().flatten().count())).await?;
+
+        for (index, (entry, path)) in self.inner.file.entries.iter().zip(paths).enumerate() {
+            if let Some(path) = path {
+                extractor.extract(entry, &path, self.entry(index).await?).await?;
+            }
+        }
+
+        extractor.finish().await
+    }
+}
+
+/// Returns whether or not two paths refer to the same existing file.
+async fn same_file(a: &Path, b: &Path) -> Result<bool> {
+    match (tokio::fs::canonicalize(a).await, tokio::fs::canonicalize(b).await) {
+        (Ok(a), Ok(b)) => Ok(a == b),
+        (_, Err(err)) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        (Err(err), _) | (_, Err(err)) => Err(err.into()),
     }
 }
@@ -0,0 +1,58 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use bytes::Buf;
+use futures_util::stream::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// A reader over a [`Stream`] of byte chunks (eg. a hyper body or a channel's receiver).
+///
+/// This type cannot be directly constructed so instead, [`ZipFileReader::from_stream()`] must be used.
+///
+/// [`ZipFileReader::from_stream()`]: crate::read::stream::ZipFileReader::from_stream
+pub struct ByteStreamReader<S, B> {
+    stream: S,
+    chunk: Option<B>,
+}
+
+impl<S, B> ByteStreamReader<S, B> {
+    /// Constructs a new reader over the provided stream.
+    pub(crate) fn new(stream: S) -> Self {
+        Self { stream, chunk: None }
+    }
+}
+
+impl<S, B, E> AsyncRead for ByteStreamReader<S, B>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: Buf + Unpin,
+    E: Into<std::io::Error>,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let this = self.get_mut();
+
+        if b.remaining() == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Empty chunks are skipped, as returning no data would otherwise signal EOF.
+        loop {
+            if let Some(chunk) = this.chunk.as_mut().filter(|chunk| chunk.has_remaining()) {
+                let length = chunk.chunk().len().min(b.remaining());
+                b.put_slice(&chunk.chunk()[..length]);
+                chunk.advance(length);
+
+                return Poll::Ready(Ok(()));
+            }
+
+            match ready!(Pin::new(&mut this.stream).poll_next(c)) {
+                Some(Ok(chunk)) => this.chunk = Some(chunk),
+                Some(Err(err)) => return Poll::Ready(Err(err.into())),
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Periodically yields to the runtime from within long-running loops.
+//!
+//! Loops which mostly parse data that's already been buffered (eg. the central directory of an in-memory archive) may
+//! never return [`Poll::Pending`], and so can run for a long time without giving other tasks on the same worker a
+//! chance to run. Rather than depending on a specific runtime's yield (eg. `tokio::task::yield_now()`, which requires
+//! tokio's `rt` feature), the task wakes itself and returns [`Poll::Pending`] once, which any executor honours.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// The default number of loop iterations between yields.
+pub(crate) const DEFAULT_YIELD_INTERVAL: usize = 512;
+
+/// A counter of loop iterations which yields to the runtime once every interval.
+pub(crate) struct Checkpoint {
+    interval: usize,
+    count: usize,
+}
+
+impl Checkpoint {
+    /// Constructs a new checkpoint which yields every `interval` iterations, or never if the interval is zero.
+    pub(crate) fn new(interval: usize) -> Self {
+        Self { interval, count: 0 }
+    }
+
+    /// Counts a single iteration, yielding to the runtime if the interval has been reached.
+    pub(crate) async fn tick(&mut self) {
+        if self.interval == 0 {
+            return;
+        }
+
+        self.count += 1;
+        if self.count >= self.interval {
+            self.count = 0;
+            YieldNow(false).await;
+        }
+    }
+}
+
+/// A future which returns [`Poll::Pending`] (having woken its task) on its first poll, and completes on its second.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#61
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md (Appendix E)
+
+#[cfg(feature = "aes")]
+use crate::error::{Result, ZipError};
+use crate::read::io::poll_result_ok;
+#[cfg(feature = "aes")]
+use crate::spec::aes::{AesKeys, AES_AUTH_CODE_LENGTH};
+#[cfg(feature = "aes")]
+use crate::spec::extra_field::AesExtraField;
+use crate::spec::zipcrypto::ZipCryptoKeys;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use pin_project::pin_project;
+use tokio::io::{AsyncRead, ReadBuf, Take};
+
+/// The method & parameters with which an entry's data is encrypted.
+#[derive(Clone, Copy)]
+pub(crate) enum Encryption {
+    /// Traditional PKWARE (ZipCrypto) encryption, holding the byte which the last byte of the decrypted encryption
+    /// header must match.
+    ZipCrypto(u8),
+    /// WinZip AES (AE-1/AE-2) encryption.
+    #[cfg(feature = "aes")]
+    Aes(AesExtraField),
+}
+
+/// The state required to decrypt & authenticate the data of a WinZip AES-encrypted entry.
+#[cfg(feature = "aes")]
+pub(crate) struct AesState {
+    keys: AesKeys,
+    remaining: u64,
+    auth_code: Vec<u8>,
+}
+
+#[cfg(feature = "aes")]
+impl AesState {
+    /// Derives the keys from the password and salt, verifying the password against the stored password verification
+    /// value.
+    ///
+    /// The length provided is that of the encrypted data alone (ie. excluding the salt, verification value, and
+    /// authentication code), to which the limit of the reader this state is paired with must also be set.
+    pub(crate) fn new(
+        field: &AesExtraField,
+        password: &[u8],
+        salt: &[u8],
+        verifier: &[u8],
+        length: u64,
+    ) -> Result<Self> {
+        let keys = AesKeys::derive(field.strength, password, salt);
+
+        if keys.verifier() != verifier {
+            return Err(ZipError::IncorrectPassword);
+        }
+
+        Ok(Self { keys, remaining: length, auth_code: Vec::new() })
+    }
+}
+
+/// A wrapping reader which decrypts data read via [`AsyncRead`], if the entry is encrypted.
+#[pin_project(project = CipherReaderProj)]
+pub(crate) enum CipherReader<R> {
+    Plain(#[pin] R),
+    ZipCrypto(#[pin] R, ZipCryptoKeys),
+    #[cfg(feature = "aes")]
+    Aes(#[pin] R, Box<AesState>),
+}
+
+impl<R> AsyncRead for CipherReader<Take<R>>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        match self.project() {
+            CipherReaderProj::Plain(inner) => inner.poll_read(c, b),
+            CipherReaderProj::ZipCrypto(inner, keys) => {
+                let prev_len = b.filled().len();
+
+                poll_result_ok!(ready!(inner.poll_read(c, b)));
+                keys.decrypt(&mut b.filled_mut()[prev_len..]);
+
+                Poll::Ready(Ok(()))
+            }
+            #[cfg(feature = "aes")]
+            CipherReaderProj::Aes(inner, state) => poll_read_aes(inner, state, c, b),
+        }
+    }
+}
+
+/// Reads & decrypts data from an AES-encrypted entry, verifying its authentication code once all data has been read.
+///
+/// The inner reader's limit is kept to the length of the encrypted data (see [`AesState::new()`]), so data is read and
+/// decrypted in place within the destination buffer, without an intermediate buffer or initialising its unfilled part.
+#[cfg(feature = "aes")]
+fn poll_read_aes<R>(
+    mut inner: Pin<&mut Take<R>>,
+    state: &mut AesState,
+    c: &mut Context<'_>,
+    b: &mut ReadBuf<'_>,
+) -> Poll<tokio::io::Result<()>>
+where
+    R: AsyncRead + Unpin,
+{
+    if b.remaining() == 0 {
+        return Poll::Ready(Ok(()));
+    }
+
+    if state.remaining > 0 {
+        let prev_len = b.filled().len();
+        poll_result_ok!(ready!(inner.poll_read(c, b)));
+        let read = b.filled().len() - prev_len;
+
+        if read == 0 {
+            return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        state.keys.decrypt(&mut b.filled_mut()[prev_len..]);
+        state.remaining -= read as u64;
+
+        return Poll::Ready(Ok(()));
+    }
+
+    while state.auth_code.len() < AES_AUTH_CODE_LENGTH {
+        let mut auth_code = [0; AES_AUTH_CODE_LENGTH];
+        let mut buffer = ReadBuf::new(&mut auth_code[state.auth_code.len()..]);
+
+        inner.as_mut().get_mut().set_limit((AES_AUTH_CODE_LENGTH - state.auth_code.len()) as u64);
+        poll_result_ok!(ready!(inner.as_mut().poll_read(c, &mut buffer)));
+
+        if buffer.filled().is_empty() {
+            return Poll::Ready(Err(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        state.auth_code.extend_from_slice(buffer.filled());
+
+        if state.auth_code.len() == AES_AUTH_CODE_LENGTH && state.auth_code != state.keys.auth_code() {
+            return Poll::Ready(Err(std::io::Error::other(ZipError::AuthenticationCodeMismatch)));
+        }
+    }
+
+    Poll::Ready(Ok(()))
+}
@@ -1,6 +1,9 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::read::io::descriptor::DescriptorScanner;
+#[cfg(feature = "legacy")]
+use crate::read::io::legacy::LegacyDecoder;
 use crate::spec::compression::Compression;
 
 use std::pin::Pin;
@@ -9,7 +12,7 @@ use std::task::{Context, Poll};
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::tokio::bufread;
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
-use tokio::io::BufReader;
+use tokio::io::{AsyncBufRead, BufReader};
 
 use pin_project::pin_project;
 use tokio::io::{AsyncRead, ReadBuf};
@@ -18,6 +21,7 @@ use tokio::io::{AsyncRead, ReadBuf};
 #[pin_project(project = CompressedReaderProj)]
 pub(crate) enum CompressedReader<R> {
     Stored(#[pin] R),
+    StoredDescriptor(#[pin] DescriptorScanner<R>),
     #[cfg(feature = "deflate")]
     Deflate(#[pin] bufread::DeflateDecoder<BufReader<R>>),
     #[cfg(feature = "bzip2")]
@@ -28,6 +32,8 @@ pub(crate) enum CompressedReader<R> {
     Zstd(#[pin] bufread::ZstdDecoder<BufReader<R>>),
     #[cfg(feature = "xz")]
     Xz(#[pin] bufread::XzDecoder<BufReader<R>>),
+    #[cfg(feature = "legacy")]
+    Legacy(#[pin] LegacyDecoder<R>),
 }
 
 impl<R> CompressedReader<R>
@@ -35,7 +41,10 @@ where
     R: AsyncRead + Unpin,
 {
     /// Constructs a new wrapping reader from a generic [`AsyncRead`] implementer.
-    pub(crate) fn new(reader: R, compression: Compression) -> Self {
+    ///
+    /// The uncompressed size is only used by the legacy compression methods, which don't mark the end of their data.
+    #[cfg_attr(not(feature = "legacy"), allow(unused_variables))]
+    pub(crate) fn new(reader: R, compression: Compression, uncompressed_size: u64) -> Self {
         match compression {
             Compression::Stored => CompressedReader::Stored(reader),
             #[cfg(feature = "deflate")]
@@ -48,8 +57,77 @@ where
             Compression::Zstd => CompressedReader::Zstd(bufread::ZstdDecoder::new(BufReader::new(reader))),
             #[cfg(feature = "xz")]
             Compression::Xz => CompressedReader::Xz(bufread::XzDecoder::new(BufReader::new(reader))),
+            #[cfg(feature = "legacy")]
+            Compression::Shrink | Compression::Reduce(_) | Compression::Implode { .. } => {
+                CompressedReader::Legacy(LegacyDecoder::new(reader, compression, uncompressed_size))
+            }
         }
     }
+
+    /// Constructs a new wrapping reader over stored data whose end is located by scanning for its data descriptor.
+    pub(crate) fn new_stored_descriptor(reader: R, zip64: bool) -> Self {
+        CompressedReader::StoredDescriptor(DescriptorScanner::new(reader, zip64))
+    }
+}
+
+impl<R> CompressedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Takes any data which has been buffered from the inner reader but not yet consumed by the decoder.
+    ///
+    /// Once the decoder has reached the end of its compressed stream, this is the data which follows it.
+    pub(crate) fn take_buffered(&mut self) -> Vec<u8> {
+        match self {
+            CompressedReader::Stored(_) => Vec::new(),
+            CompressedReader::StoredDescriptor(inner) => inner.take_buffered(),
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(inner) => take_buffered(inner.get_mut()),
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(inner) => take_buffered(inner.get_mut()),
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(inner) => take_buffered(inner.get_mut()),
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(inner) => take_buffered(inner.get_mut()),
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(inner) => take_buffered(inner.get_mut()),
+            #[cfg(feature = "legacy")]
+            CompressedReader::Legacy(_) => Vec::new(),
+        }
+    }
+}
+
+impl<R> CompressedReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Returns a reference to the inner reader, unless it's held by a legacy decoder.
+    pub(crate) fn get_ref(&self) -> Option<&R> {
+        match self {
+            CompressedReader::Stored(inner) => Some(inner),
+            CompressedReader::StoredDescriptor(inner) => Some(inner.get_ref()),
+            #[cfg(feature = "deflate")]
+            CompressedReader::Deflate(inner) => Some(inner.get_ref().get_ref()),
+            #[cfg(feature = "bzip2")]
+            CompressedReader::Bz(inner) => Some(inner.get_ref().get_ref()),
+            #[cfg(feature = "lzma")]
+            CompressedReader::Lzma(inner) => Some(inner.get_ref().get_ref()),
+            #[cfg(feature = "zstd")]
+            CompressedReader::Zstd(inner) => Some(inner.get_ref().get_ref()),
+            #[cfg(feature = "xz")]
+            CompressedReader::Xz(inner) => Some(inner.get_ref().get_ref()),
+            #[cfg(feature = "legacy")]
+            CompressedReader::Legacy(_) => None,
+        }
+    }
+}
+
+/// Takes any data buffered by a [`BufReader`] but not yet consumed.
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+fn take_buffered<R: AsyncRead + Unpin>(reader: &mut BufReader<R>) -> Vec<u8> {
+    let buffered = reader.buffer().to_vec();
+    Pin::new(reader).consume(buffered.len());
+    buffered
 }
 
 impl<R> AsyncRead for CompressedReader<R>
@@ -59,6 +137,7 @@ where
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
         match self.project() {
             CompressedReaderProj::Stored(inner) => inner.poll_read(c, b),
+            CompressedReaderProj::StoredDescriptor(inner) => inner.poll_read(c, b),
             #[cfg(feature = "deflate")]
             CompressedReaderProj::Deflate(inner) => inner.poll_read(c, b),
             #[cfg(feature = "bzip2")]
@@ -69,6 +148,8 @@ where
             CompressedReaderProj::Zstd(inner) => inner.poll_read(c, b),
             #[cfg(feature = "xz")]
             CompressedReaderProj::Xz(inner) => inner.poll_read(c, b),
+            #[cfg(feature = "legacy")]
+            CompressedReaderProj::Legacy(inner) => inner.poll_read(c, b),
         }
     }
 }
@@ -0,0 +1,173 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::io::poll_result_ok;
+use crate::spec::consts::{DATA_DESCRIPTOR_LENGTH, DATA_DESCRIPTOR_SIGNATURE, SIGNATURE_LENGTH};
+
+use std::io::ErrorKind;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// The number of bytes read from the inner reader at a time.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// A wrapping reader which locates the end of a stored entry's data by scanning for its data descriptor.
+///
+/// As stored data carries no end marker, each occurrence of the data descriptor's signature is treated as a candidate.
+/// A candidate is only accepted once the sizes which follow it match the number of bytes read so far, so data which
+/// happens to contain the signature is passed through. Once accepted, the descriptor (& anything buffered after it) is
+/// left unread so that it can be handed back to the stream reader.
+pub(crate) struct DescriptorScanner<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    position: usize,
+    known: usize,
+    consumed: u64,
+    zip64: bool,
+    eof: bool,
+}
+
+impl<R> DescriptorScanner<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructs a new wrapping reader, given whether or not the data descriptor holds ZIP64 sizes.
+    pub(crate) fn new(reader: R, zip64: bool) -> Self {
+        Self { reader, buffer: Vec::new(), position: 0, known: 0, consumed: 0, zip64, eof: false }
+    }
+
+    /// Returns a reference to the inner reader.
+    pub(crate) fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Takes any data which has been read from the inner reader but not yet passed on (ie. the data descriptor onwards,
+    /// once the end of the entry's data has been reached).
+    pub(crate) fn take_buffered(&mut self) -> Vec<u8> {
+        let buffered = self.buffer.split_off(self.position);
+        self.buffer.clear();
+        self.position = 0;
+
+        buffered
+    }
+
+    /// Returns the buffered data which hasn't yet been passed on.
+    fn remaining(&self) -> &[u8] {
+        &self.buffer[self.position..]
+    }
+
+    /// Returns the length of a data descriptor (incl. its signature) with this reader's size width.
+    fn descriptor_length(&self) -> usize {
+        match self.zip64 {
+            true => SIGNATURE_LENGTH + DATA_DESCRIPTOR_LENGTH + 8,
+            false => SIGNATURE_LENGTH + DATA_DESCRIPTOR_LENGTH,
+        }
+    }
+
+    /// Returns whether or not a complete data descriptor at the start of the buffer matches the data read so far.
+    fn descriptor_matches(&self) -> bool {
+        let sizes = &self.remaining()[SIGNATURE_LENGTH + 4..self.descriptor_length()];
+        let (compressed, uncompressed) = match self.zip64 {
+            true => {
+                (u64::from_le_bytes(sizes[..8].try_into().unwrap()), u64::from_le_bytes(sizes[8..].try_into().unwrap()))
+            }
+            false => (
+                u32::from_le_bytes(sizes[..4].try_into().unwrap()).into(),
+                u32::from_le_bytes(sizes[4..].try_into().unwrap()).into(),
+            ),
+        };
+
+        compressed == self.consumed && uncompressed == self.consumed
+    }
+
+    /// Returns the number of buffered bytes which are known to be entry data, or [`None`] if more data must be read.
+    ///
+    /// Zero is returned once the end of the entry's data has been reached.
+    fn available(&self) -> Option<usize> {
+        let remaining = self.remaining();
+
+        match search_buffer(remaining, &DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()) {
+            Some(0) if remaining.len() < self.descriptor_length() => None,
+            Some(0) if self.descriptor_matches() => Some(0),
+            // The signature is part of the data, so its first byte is passed on and scanning resumes after it.
+            Some(0) => Some(1),
+            Some(position) => Some(position),
+            // The last few bytes may be the start of a signature which hasn't yet been fully read.
+            None => Some(remaining.len().saturating_sub(SIGNATURE_LENGTH - 1)).filter(|length| *length > 0),
+        }
+    }
+}
+
+impl<R> AsyncRead for DescriptorScanner<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let this = &mut *self;
+
+        loop {
+            if this.eof {
+                return Poll::Ready(Ok(()));
+            }
+
+            // The buffer is only rescanned once all data known to precede the data descriptor has been passed on.
+            if this.known > 0 {
+                let length = this.known.min(b.remaining());
+                b.put_slice(&this.remaining()[..length]);
+                this.position += length;
+                this.known -= length;
+                this.consumed += length as u64;
+
+                return Poll::Ready(Ok(()));
+            }
+
+            match this.available() {
+                Some(0) => {
+                    this.eof = true;
+                    return Poll::Ready(Ok(()));
+                }
+                Some(available) => this.known = available,
+                None => {
+                    let mut chunk = [0; CHUNK_SIZE];
+                    let mut chunk = ReadBuf::new(&mut chunk);
+                    poll_result_ok!(ready!(Pin::new(&mut this.reader).poll_read(c, &mut chunk)));
+
+                    if chunk.filled().is_empty() {
+                        let message = "reached EOF before locating the entry's data descriptor";
+                        return Poll::Ready(Err(std::io::Error::new(ErrorKind::UnexpectedEof, message)));
+                    }
+
+                    // Data which has already been passed on is dropped before the buffer is extended.
+                    this.buffer.drain(..this.position);
+                    this.position = 0;
+                    this.buffer.extend_from_slice(chunk.filled());
+                }
+            }
+        }
+    }
+}
+
+/// A forward search along the buffer for the specified signature bytes, returning the index of its first byte.
+#[cfg(not(feature = "memchr"))]
+fn search_buffer(buffer: &[u8], signature: &[u8]) -> Option<usize> {
+    buffer.windows(signature.len()).position(|window| window == signature)
+}
+
+/// A forward search along the buffer for the specified signature bytes, returning the index of its first byte.
+#[cfg(feature = "memchr")]
+fn search_buffer(buffer: &[u8], signature: &[u8]) -> Option<usize> {
+    let (first, _) = signature.split_first()?;
+    let mut start = 0;
+
+    while let Some(index) = memchr::memchr(*first, &buffer[start..]).map(|index| start + index) {
+        if buffer[index..].starts_with(signature) {
+            return Some(index);
+        }
+
+        start = index + 1;
+    }
+
+    None
+}
@@ -2,43 +2,418 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
-use crate::error::{Result, ZipError};
+use crate::error::{ErrorContext, Result, ZipError};
+#[cfg(feature = "aes")]
+use crate::read::io::cipher::AesState;
+use crate::read::io::cipher::{CipherReader, Encryption};
 use crate::read::io::{compressed::CompressedReader, hashed::HashedReader, owned::OwnedReader};
+use crate::read::limits::{unwrap_limit_error, DecompressionLimits};
+use crate::read::progress::{EntryProgress, ProgressCallback};
+#[cfg(feature = "aes")]
+use crate::spec::aes::{AES_AUTH_CODE_LENGTH, AES_VERIFIER_LENGTH};
 use crate::spec::compression::Compression;
+use crate::spec::zipcrypto::{ZipCryptoKeys, ZIPCRYPTO_HEADER_LENGTH};
 
+use std::io::{Error, ErrorKind};
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::task::{ready, Context, Poll};
 
-use pin_project::pin_project;
-use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf, Take};
+#[cfg(feature = "futures")]
+use bytes::{Bytes, BytesMut};
+#[cfg(feature = "futures")]
+use futures_util::stream::{self, Stream};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite, ReadBuf, SeekFrom, Take};
+
+/// The capacity of the buffer used by the [`AsyncBufRead`] implementation of [`ZipEntryReader`].
+const BUFFER_CAPACITY: usize = 8 * 1024;
 
-#[pin_project]
 pub struct ZipEntryReader<'a, R> {
-    #[pin]
-    reader: HashedReader<CompressedReader<Take<OwnedReader<'a, R>>>>,
+    reader: HashedReader<CompressedReader<CipherReader<Take<OwnedReader<'a, R>>>>>,
+    descriptor: Option<&'a mut DescriptorState>,
+    verify_crc: bool,
+    seek: Option<SeekState>,
+    data_offset: Option<u64>,
+    progress: EntryProgress,
+    progress_callback: Option<ProgressCallback<EntryProgress>>,
+    max_read_size: Option<u64>,
+    limits: DecompressionLimits,
+    read_before: u64,
+    compressed_size: Option<u64>,
+    buffer: Box<[u8]>,
+    buffer_pos: usize,
+    buffer_filled: usize,
+}
+
+/// The state of an entry reader which supports seeking (ie. one over data which is neither compressed nor encrypted).
+struct SeekState {
+    start: u64,
+    length: u64,
+    pending: Option<u64>,
+}
+
+/// The state shared between the stream reader and an entry reader for an entry which uses a data descriptor.
+///
+/// As the entry's sizes aren't known upfront, the decoder is relied upon to locate the end of the compressed data (or
+/// for stored entries, the data descriptor is scanned for). Once reached, any data which the decoder buffered beyond
+/// that point is handed back to the stream reader (along with the computed CRC32 hash & sizes) so that the data
+/// descriptor can be read and verified.
+#[derive(Default)]
+pub(crate) struct DescriptorState {
+    pub(crate) zip64: bool,
+    pub(crate) started: bool,
+    pub(crate) eof: bool,
+    pub(crate) buffered: Vec<u8>,
+    pub(crate) hash: u32,
+    pub(crate) compressed_size: u64,
+    pub(crate) uncompressed_size: u64,
+}
+
+impl<'a, R> ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructs a new entry reader for the provided entry (incl. an owned R).
+    pub(crate) fn new_with_owned(reader: R, entry: &ZipEntry) -> Self {
+        let reader = OwnedReader::Owned(reader).take(entry.compressed_size().into());
+        Self::new_with_cipher(CipherReader::Plain(reader), entry.compression(), entry.uncompressed_size().into(), None)
+    }
+
+    /// Constructs a new entry reader for the provided entry (incl. a mutable borrow of an R).
+    pub(crate) fn new_with_borrow(reader: &'a mut R, entry: &ZipEntry) -> Self {
+        let reader = OwnedReader::Borrow(reader).take(entry.compressed_size().into());
+        Self::new_with_cipher(CipherReader::Plain(reader), entry.compression(), entry.uncompressed_size().into(), None)
+    }
+
+    /// Constructs a new entry reader over the raw (ie. still compressed) data of the provided entry (incl. an owned R).
+    pub(crate) fn new_raw_with_owned(reader: R, entry: &ZipEntry) -> Self {
+        Self::new_raw(OwnedReader::Owned(reader).take(entry.compressed_size().into()))
+    }
+
+    /// Constructs a new entry reader over the raw (ie. still compressed) data of the provided entry (incl. a mutable
+    /// borrow of an R).
+    pub(crate) fn new_raw_with_borrow(reader: &'a mut R, entry: &ZipEntry) -> Self {
+        Self::new_raw(OwnedReader::Borrow(reader).take(entry.compressed_size().into()))
+    }
+
+    /// Constructs a new entry reader over raw data, whose CRC32 value is never verified (as it isn't stored).
+    fn new_raw(reader: Take<OwnedReader<'a, R>>) -> Self {
+        let length = reader.limit();
+        let mut entry_reader = Self::new_with_cipher(CipherReader::Plain(reader), Compression::Stored, length, None);
+        entry_reader.verify_crc = false;
+
+        entry_reader
+    }
+
+    /// Constructs a new entry reader which decrypts the entry's data using a password (incl. an owned R).
+    pub(crate) async fn new_with_owned_password(
+        reader: R,
+        entry: &ZipEntry,
+        password: &[u8],
+        encryption: Encryption,
+    ) -> Result<ZipEntryReader<'a, R>> {
+        let reader = OwnedReader::Owned(reader).take(entry.compressed_size().into());
+        Self::new_with_password(reader, entry, password, encryption).await
+    }
+
+    /// Constructs a new entry reader which decrypts the entry's data using a password (incl. a mutable borrow of an R).
+    pub(crate) async fn new_with_borrow_password(
+        reader: &'a mut R,
+        entry: &ZipEntry,
+        password: &[u8],
+        encryption: Encryption,
+    ) -> Result<ZipEntryReader<'a, R>> {
+        let reader = OwnedReader::Borrow(reader).take(entry.compressed_size().into());
+        Self::new_with_password(reader, entry, password, encryption).await
+    }
+
+    /// Reads the encryption header which precedes the entry's data and verifies the password against it before
+    /// constructing the entry reader.
+    ///
+    /// For traditional PKWARE (ZipCrypto) encryption, only a single byte is checked, so roughly 1 in 256 incorrect
+    /// passwords will pass verification. Those will instead produce garbage data which fails decompression or the CRC32
+    /// check. For WinZip AES encryption, a two-byte verification value is checked upfront, and the authentication code
+    /// following the data is verified once EOF has been reached.
+    async fn new_with_password(
+        mut reader: Take<OwnedReader<'a, R>>,
+        entry: &ZipEntry,
+        password: &[u8],
+        encryption: Encryption,
+    ) -> Result<ZipEntryReader<'a, R>> {
+        let (compression, uncompressed_size) = (entry.compression(), entry.uncompressed_size().into());
+
+        match encryption {
+            Encryption::ZipCrypto(check) => {
+                let mut keys = ZipCryptoKeys::new(password);
+                let mut header = [0; ZIPCRYPTO_HEADER_LENGTH];
+
+                reader.read_exact(&mut header).await?;
+                keys.decrypt(&mut header);
+
+                if header[ZIPCRYPTO_HEADER_LENGTH - 1] != check {
+                    return Err(ZipError::IncorrectPassword);
+                }
+
+                let reader = CipherReader::ZipCrypto(reader, keys);
+                Ok(Self::new_with_cipher(reader, compression, uncompressed_size, None))
+            }
+            #[cfg(feature = "aes")]
+            Encryption::Aes(field) => {
+                let mut salt = vec![0; field.salt_length()];
+                let mut verifier = [0; AES_VERIFIER_LENGTH];
+
+                reader.read_exact(&mut salt).await?;
+                reader.read_exact(&mut verifier).await?;
+
+                let overhead = (field.salt_length() + AES_VERIFIER_LENGTH + AES_AUTH_CODE_LENGTH) as u64;
+                let length = u64::from(entry.compressed_size()).saturating_sub(overhead);
+                let state = AesState::new(&field, password, &salt, &verifier, length)?;
+
+                reader.set_limit(length);
+                let reader = CipherReader::Aes(reader, Box::new(state));
+                let mut entry_reader = Self::new_with_cipher(reader, compression, uncompressed_size, None);
+                entry_reader.verify_crc = field.crc_stored();
+
+                Ok(entry_reader)
+            }
+        }
+    }
+
+    fn new_with_cipher(
+        reader: CipherReader<Take<OwnedReader<'a, R>>>,
+        compression: Compression,
+        uncompressed_size: u64,
+        descriptor: Option<&'a mut DescriptorState>,
+    ) -> Self {
+        let verify_crc = descriptor.is_none();
+        let compressed_size = descriptor.is_none().then(|| compressed_limit(&reader));
+        let reader = match &descriptor {
+            Some(state) if compression == Compression::Stored => {
+                CompressedReader::new_stored_descriptor(reader, state.zip64)
+            }
+            _ => CompressedReader::new(reader, compression, uncompressed_size),
+        };
+        let reader = HashedReader::new(reader);
+
+        let progress = EntryProgress { read: 0, total: uncompressed_size };
+
+        Self {
+            reader,
+            descriptor,
+            verify_crc,
+            seek: None,
+            data_offset: None,
+            progress,
+            progress_callback: None,
+            max_read_size: None,
+            limits: DecompressionLimits::default(),
+            read_before: 0,
+            compressed_size,
+            buffer: Box::default(),
+            buffer_pos: 0,
+            buffer_filled: 0,
+        }
+    }
+
+    /// Enables seeking if this reader's data is neither compressed nor encrypted, given the offset of its start within
+    /// the inner reader (which is also reported within the context of any CRC32 check error).
+    pub(crate) fn with_data_offset(mut self, offset: u64) -> Self {
+        self.data_offset = Some(offset);
+
+        if let (Some(reader), None) = (plain_stored(&mut self.reader.reader), &self.descriptor) {
+            self.seek = Some(SeekState { start: offset, length: reader.limit(), pending: None });
+        }
+
+        self
+    }
+
+    /// Constructs a new entry reader for an entry which uses a data descriptor (incl. a mutable borrow of an R).
+    pub(crate) fn new_with_descriptor(
+        reader: &'a mut R,
+        compression: Compression,
+        state: &'a mut DescriptorState,
+    ) -> Self {
+        let reader = OwnedReader::Borrow(reader).take(u64::MAX);
+        Self::new_with_cipher(CipherReader::Plain(reader), compression, 0, Some(state))
+    }
 }
 
 impl<'a, R> ZipEntryReader<'a, R>
 where
     R: AsyncRead + Unpin,
 {
-    /// Constructs a new entry reader from its required parameters (incl. an owned R).
-    pub(crate) fn new_with_owned(reader: R, compression: Compression, size: u64) -> Self {
-        Self { reader: HashedReader::new(CompressedReader::new(OwnedReader::Owned(reader).take(size), compression)) }
+    /// Reads data from the reader stack, tracking progress, limits, and the end of the data for data descriptors.
+    fn poll_read_inner(&mut self, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let prev_len = b.filled().len();
+        let poll = Pin::new(&mut self.reader).poll_read(c, b);
+
+        if let (read @ 1.., Poll::Ready(Ok(()))) = (b.filled().len() - prev_len, &poll) {
+            self.progress.read += read as u64;
+
+            if let Some(callback) = &self.progress_callback {
+                callback.report(self.progress);
+            }
+
+            if let Some(error) = self.check_limits() {
+                return Poll::Ready(Err(Error::other(error)));
+            }
+        }
+
+        if let (Some(state), Poll::Ready(Ok(()))) = (self.descriptor.as_deref_mut(), &poll) {
+            state.started = true;
+
+            if !state.eof && b.filled().len() == prev_len && b.remaining() > 0 {
+                state.eof = true;
+                state.buffered = self.reader.reader.take_buffered();
+                state.hash = self.reader.swap_and_compute_hash();
+                state.uncompressed_size = self.progress.read;
+
+                // The inner reader's limit starts at its maximum, so the distance from it is the amount read (which
+                // includes any data buffered beyond the end of the compressed data).
+                if let Some(reader) = self.reader.reader.get_ref() {
+                    state.compressed_size = u64::MAX - compressed_limit(reader) - state.buffered.len() as u64;
+                }
+            }
+        }
+
+        poll
     }
 
-    /// Constructs a new entry reader from its required parameters (incl. a mutable borrow of an R).
-    pub(crate) fn new_with_borrow(reader: &'a mut R, compression: Compression, size: u64) -> Self {
-        Self { reader: HashedReader::new(CompressedReader::new(OwnedReader::Borrow(reader).take(size), compression)) }
+    /// Returns the number of bytes held within the buffer which haven't yet been consumed.
+    fn buffered(&self) -> usize {
+        self.buffer_filled - self.buffer_pos
     }
 }
 
+/// Data is read directly into the destination buffer throughout the reader stack (with decryption taking place in
+/// place), so large buffers are filled without any intermediate copies. Stored entries never initialise the unfilled
+/// part of the buffer themselves (eg. when read via [`AsyncReadExt::read_buf()`]), though decompressors do so before
+/// decompressing into it. Any data buffered via the [`AsyncBufRead`] implementation is returned first.
 impl<'a, R> AsyncRead for ZipEntryReader<'a, R>
 where
     R: AsyncRead + Unpin,
 {
     fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
-        self.project().reader.poll_read(c, b)
+        let this = self.get_mut();
+
+        if this.buffered() == 0 {
+            return this.poll_read_inner(c, b);
+        }
+
+        let length = this.buffered().min(b.remaining());
+        b.put_slice(&this.buffer[this.buffer_pos..this.buffer_pos + length]);
+        this.buffer_pos += length;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// The inner decoders only buffer compressed data, so a small buffer of decompressed data is held by the entry reader
+/// itself. It's only allocated once first filled, so readers which aren't used via this trait don't pay for it. This
+/// allows line-based reading (eg. [`AsyncBufReadExt::lines()`]) and [`tokio::io::copy_buf()`] over an entry's data
+/// without wrapping the reader in a [`tokio::io::BufReader`].
+///
+/// [`AsyncBufReadExt::lines()`]: tokio::io::AsyncBufReadExt::lines
+impl<'a, R> AsyncBufRead for ZipEntryReader<'a, R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<tokio::io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        if this.buffered() == 0 {
+            let mut buffer = std::mem::take(&mut this.buffer);
+            if buffer.is_empty() {
+                buffer = vec![0; BUFFER_CAPACITY].into_boxed_slice();
+            }
+
+            let mut read_buf = ReadBuf::new(&mut buffer);
+            let poll = this.poll_read_inner(c, &mut read_buf);
+            let filled = read_buf.filled().len();
+            this.buffer = buffer;
+
+            ready!(poll)?;
+            this.buffer_pos = 0;
+            this.buffer_filled = filled;
+        }
+
+        Poll::Ready(Ok(&this.buffer[this.buffer_pos..this.buffer_filled]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.buffer_pos = (this.buffer_pos + amt).min(this.buffer_filled);
+    }
+}
+
+/// Seeking is only supported for entries whose data is neither compressed nor encrypted (ie. Stored entries read
+/// without a password, or readers over an entry's raw data). Positions are relative to the start of the entry's data,
+/// and seeking beyond its end positions the reader at its end. As the CRC32 value is computed over the data read, it's
+/// no longer verified once the reader has been seeked.
+impl<'a, R> AsyncSeek for ZipEntryReader<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> tokio::io::Result<()> {
+        let this = self.get_mut();
+        let buffered = this.buffered() as u64;
+        let (state, reader) = match (this.seek.as_mut(), plain_stored(&mut this.reader.reader)) {
+            (Some(state), Some(reader)) => (state, reader),
+            _ => return Err(Error::new(ErrorKind::Unsupported, "entry isn't seekable")),
+        };
+
+        let target = match position {
+            SeekFrom::Start(offset) => i128::from(offset),
+            SeekFrom::End(offset) => i128::from(state.length) + i128::from(offset),
+            SeekFrom::Current(offset) => i128::from(state.length - reader.limit() - buffered) + i128::from(offset),
+        };
+
+        if target < 0 {
+            return Err(Error::new(ErrorKind::InvalidInput, "invalid seek to a negative position"));
+        }
+
+        let target = u64::try_from(target).unwrap_or(u64::MAX).min(state.length);
+        Pin::new(reader.get_mut()).start_seek(SeekFrom::Start(state.start + target))?;
+
+        state.pending = Some(target);
+        this.verify_crc = false;
+        (this.buffer_pos, this.buffer_filled) = (0, 0);
+
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<tokio::io::Result<u64>> {
+        let this = self.get_mut();
+        let buffered = this.buffered() as u64;
+        let (state, reader) = match (this.seek.as_mut(), plain_stored(&mut this.reader.reader)) {
+            (Some(state), Some(reader)) => (state, reader),
+            _ => return Poll::Ready(Err(Error::new(ErrorKind::Unsupported, "entry isn't seekable"))),
+        };
+
+        if let Some(target) = state.pending {
+            ready!(Pin::new(reader.get_mut()).poll_complete(c))?;
+            reader.set_limit(state.length - target);
+            state.pending = None;
+        }
+
+        Poll::Ready(Ok(state.length - reader.limit() - buffered))
+    }
+}
+
+/// Returns the remaining limit of the reader over an entry's compressed data (or its encrypted data, once any encryption
+/// header has been read).
+fn compressed_limit<R: AsyncRead>(reader: &CipherReader<Take<R>>) -> u64 {
+    match reader {
+        CipherReader::Plain(reader) | CipherReader::ZipCrypto(reader, _) => reader.limit(),
+        #[cfg(feature = "aes")]
+        CipherReader::Aes(reader, _) => reader.limit(),
+    }
+}
+
+/// Returns the inner reader of a compressed reader whose data is neither compressed nor encrypted.
+fn plain_stored<R>(reader: &mut CompressedReader<CipherReader<R>>) -> Option<&mut R> {
+    match reader {
+        CompressedReader::Stored(CipherReader::Plain(reader)) => Some(reader),
+        _ => None,
     }
 }
 
@@ -46,36 +421,186 @@ impl<'a, R> ZipEntryReader<'a, R>
 where
     R: AsyncRead + Unpin,
 {
+    /// Returns the progress made reading this entry's data so far.
+    pub fn progress(&self) -> EntryProgress {
+        self.progress
+    }
+
+    /// Returns whether or not this reader supports seeking (ie. its data is neither compressed nor encrypted).
+    pub fn is_seekable(&self) -> bool {
+        self.seek.is_some()
+    }
+
+    /// Sets a callback to which this reader's progress is reported each time data is read.
+    ///
+    /// See the [`progress`](crate::read::progress) module for more information.
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(EntryProgress) + Send + Sync + 'static,
+    {
+        self.progress_callback = Some(ProgressCallback::new(callback));
+        self
+    }
+
+    /// Consumes this reader and returns a [`Stream`] of its data, in chunks of at most `chunk_size` bytes.
+    ///
+    /// This allows an entry's data to be passed on as-is (eg. as a hyper response body, or into a channel). Each chunk
+    /// is yielded as soon as it's been read, so may be smaller than `chunk_size`. As with this reader's [`AsyncRead`]
+    /// implementation, the CRC32 value isn't verified, though any limits set are still enforced.
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self, chunk_size: usize) -> impl Stream<Item = std::io::Result<Bytes>> + 'a
+    where
+        R: 'a,
+    {
+        let chunk_size = chunk_size.max(1);
+
+        stream::try_unfold(self, move |mut reader| async move {
+            let mut chunk = BytesMut::with_capacity(chunk_size);
+            let read = (&mut reader).take(chunk_size as u64).read_buf(&mut chunk).await?;
+
+            Ok((read != 0).then(|| (chunk.freeze(), reader)))
+        })
+    }
+
+    /// Sets the maximum number of bytes which [`ZipEntryReader::read_to_end_checked()`] and
+    /// [`ZipEntryReader::read_to_string_checked()`] will read into memory.
+    ///
+    /// Entries whose stored uncompressed size exceeds the limit are rejected upfront with [`ZipError::EntryTooLarge`],
+    /// as are entries whose data turns out to exceed it (eg. those using a data descriptor, or with an incorrect stored
+    /// size) once the limit has been reached. Such entries should instead be read incrementally via this reader's
+    /// [`AsyncRead`] implementation (eg. with [`tokio::io::copy()`]). This overrides any limit set via
+    /// [`ReaderOptions::max_read_size()`].
+    ///
+    /// [`ReaderOptions::max_read_size()`]: crate::read::diagnostics::ReaderOptions::max_read_size
+    pub fn with_max_read_size(mut self, limit: u64) -> Self {
+        self.max_read_size = Some(limit);
+        self
+    }
+
+    /// Sets the maximum number of bytes which the helpers reading into memory will read, if any.
+    pub(crate) fn with_read_limit(mut self, limit: Option<u64>) -> Self {
+        self.max_read_size = limit;
+        self
+    }
+
+    /// Sets limits on the amount of data which this reader will decompress, guarding against zip bombs.
+    ///
+    /// Once a limit has been exceeded, reading fails with an IO error wrapping [`ZipError::LimitExceeded`] (which the
+    /// checked helpers such as [`ZipEntryReader::read_to_end_checked()`] return directly). The total size limit only
+    /// applies when extracting. See the [`limits`](crate::read::limits) module for more information.
+    pub fn with_limits(mut self, limits: DecompressionLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the number of bytes decompressed from previous entries, against which the total size limit is checked.
+    #[cfg(feature = "fs")]
+    pub(crate) fn with_read_before(mut self, read: u64) -> Self {
+        self.read_before = read;
+        self
+    }
+
+    /// Returns the error for the first decompression limit exceeded by the data read so far, if any.
+    fn check_limits(&self) -> Option<ZipError> {
+        // The compressed size is unknown upfront for entries using a data descriptor, so use the data consumed so far.
+        let compressed = self.compressed_size.unwrap_or_else(|| {
+            let consumed = self.reader.reader.get_ref().map(compressed_limit);
+            consumed.map_or(0, |limit| u64::MAX - limit)
+        });
+
+        self.limits.check(self.progress.read, self.read_before, compressed)
+    }
+
+    /// Returns the number of bytes which may be read into memory, or an error if the entry is known to exceed it.
+    fn read_limit(&self, entry: &ZipEntry) -> Result<u64> {
+        match self.max_read_size {
+            Some(limit) if u64::from(entry.uncompressed_size()) > limit => Err(ZipError::EntryTooLarge(limit)),
+            Some(limit) => Ok(limit),
+            None => Ok(u64::MAX),
+        }
+    }
+
     /// Computes and returns the CRC32 hash of bytes read by this reader so far.
     ///
     /// This hash should only be computed once EOF has been reached.
-    fn compute_hash(&mut self) -> u32 {
+    pub(crate) fn compute_hash(&mut self) -> u32 {
         self.reader.swap_and_compute_hash()
     }
 
+    /// Verifies the CRC32 hash of bytes read by this reader against the entry's, where this reader is able to do so.
+    ///
+    /// See [`ZipEntryReader::read_to_end_checked()`] for the cases in which the CRC32 value isn't verified. This should
+    /// only be called once EOF has been reached.
+    pub(crate) fn verify_hash(&mut self, entry: &ZipEntry) -> Result<()> {
+        if !self.verify_crc || self.compute_hash() == entry.crc32() {
+            Ok(())
+        } else {
+            let context = ErrorContext { offset: self.data_offset, ..ErrorContext::filename(entry.filename()) };
+            Err(ZipError::CRC32CheckError.with_context(context))
+        }
+    }
+
     /// Reads all bytes until EOF has been reached, appending them to buf, and verifies the CRC32 values.
     ///
     /// This is a helper function synonymous to [`AsyncReadExt::read_to_end()`].
+    ///
+    /// For entries which use a data descriptor (when read via the [`stream`] reader), the CRC32 value isn't known until
+    /// the data descriptor following the entry's data has been read. As such, it's instead verified by the stream reader
+    /// when advancing to the next entry.
+    ///
+    /// AE-2 encrypted entries don't store a CRC32 value, so only their authentication code is verified. Readers over an
+    /// entry's raw data never verify the CRC32 value, as it's computed from the uncompressed data.
+    ///
+    /// If a limit has been set (see [`ZipEntryReader::with_max_read_size()`]), [`ZipError::EntryTooLarge`] is returned
+    /// rather than reading beyond it, in which case buf may hold part of the entry's data.
+    ///
+    /// [`stream`]: crate::read::stream
     pub async fn read_to_end_checked(&mut self, buf: &mut Vec<u8>, entry: &ZipEntry) -> Result<usize> {
-        let read = self.read_to_end(buf).await?;
+        let limit = self.read_limit(entry)?;
+        let read = (&mut *self).take(limit.saturating_add(1)).read_to_end(buf).await.map_err(unwrap_limit_error)?;
 
-        if self.compute_hash() == entry.crc32() {
-            Ok(read)
-        } else {
-            Err(ZipError::CRC32CheckError)
+        if read as u64 > limit {
+            return Err(ZipError::EntryTooLarge(limit));
         }
+
+        self.verify_hash(entry)?;
+        Ok(read)
     }
 
     /// Reads all bytes until EOF has been reached, placing them into buf, and verifies the CRC32 values.
     ///
-    /// This is a helper function synonymous to [`AsyncReadExt::read_to_string()`].
+    /// This is a helper function synonymous to [`AsyncReadExt::read_to_string()`]. See
+    /// [`ZipEntryReader::read_to_end_checked()`] for details on entries which use a data descriptor or
+    /// AES encryption, and on the limit of the number of bytes read.
     pub async fn read_to_string_checked(&mut self, buf: &mut String, entry: &ZipEntry) -> Result<usize> {
-        let read = self.read_to_string(buf).await?;
+        let limit = self.read_limit(entry)?;
+        let read = (&mut *self).take(limit.saturating_add(1)).read_to_string(buf).await.map_err(unwrap_limit_error)?;
 
-        if self.compute_hash() == entry.crc32() {
-            Ok(read)
-        } else {
-            Err(ZipError::CRC32CheckError)
+        if read as u64 > limit {
+            return Err(ZipError::EntryTooLarge(limit));
         }
+
+        self.verify_hash(entry)?;
+        Ok(read)
+    }
+
+    /// Copies all bytes until EOF has been reached into the provided writer, verifies the CRC32 values, and returns the
+    /// number of bytes copied.
+    ///
+    /// This is a helper function synonymous to [`tokio::io::copy()`], allowing an entry's data to be streamed elsewhere
+    /// (eg. to a file or socket) without first being read into memory. As such, any limit set via
+    /// [`ZipEntryReader::with_max_read_size()`] doesn't apply, though limits set via [`ZipEntryReader::with_limits()`]
+    /// do. See [`ZipEntryReader::read_to_end_checked()`] for details on entries whose CRC32 value isn't verified.
+    ///
+    /// The writer is flushed once all bytes have been copied. If the CRC32 check fails, the data will already have been
+    /// written, so callers should discard it.
+    pub async fn copy_to_checked<W>(&mut self, writer: &mut W, entry: &ZipEntry) -> Result<u64>
+    where
+        W: AsyncWrite + Unpin + ?Sized,
+    {
+        let copied = tokio::io::copy(self, writer).await.map_err(unwrap_limit_error)?;
+
+        self.verify_hash(entry)?;
+        Ok(copied)
     }
 }
@@ -2,11 +2,11 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::read::io::poll_result_ok;
+use crate::spec::crc::Crc32;
 
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
 
-use crc32fast::Hasher;
 use pin_project::pin_project;
 use tokio::io::{AsyncRead, ReadBuf};
 
@@ -15,7 +15,7 @@ use tokio::io::{AsyncRead, ReadBuf};
 pub(crate) struct HashedReader<R> {
     #[pin]
     pub(crate) reader: R,
-    pub(crate) hasher: Hasher,
+    pub(crate) hasher: Crc32,
 }
 
 impl<R> HashedReader<R>
@@ -24,7 +24,7 @@ where
 {
     /// Constructs a new wrapping reader from a generic [`AsyncRead`] implementer.
     pub(crate) fn new(reader: R) -> Self {
-        Self { reader, hasher: Hasher::default() }
+        Self { reader, hasher: Crc32::new() }
     }
 
     /// Swaps the internal hasher and returns the computed CRC32 hash.
@@ -0,0 +1,46 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Thin tokio bindings which read headers from a source and hand them to the runtime-independent parsers.
+
+use crate::error::Result;
+use crate::spec::consts::{CDH_LENGTH, EOCDR_LENGTH, LFH_LENGTH, SIGNATURE_LENGTH};
+use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader, LocalFileHeader};
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+impl LocalFileHeader {
+    pub(crate) async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<LocalFileHeader> {
+        let mut buffer: [u8; LFH_LENGTH] = [0; LFH_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        Ok(LocalFileHeader::from(buffer))
+    }
+
+    /// Reads the header's signature alongside its fixed-size fields in a single call, returning an error if the
+    /// signature doesn't match.
+    pub(crate) async fn from_reader_with_signature<R: AsyncRead + Unpin>(reader: &mut R) -> Result<LocalFileHeader> {
+        let mut buffer: [u8; SIGNATURE_LENGTH + LFH_LENGTH] = [0; SIGNATURE_LENGTH + LFH_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        LocalFileHeader::parse_with_signature(&buffer)
+    }
+}
+
+impl EndOfCentralDirectoryHeader {
+    pub(crate) async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<EndOfCentralDirectoryHeader> {
+        let mut buffer: [u8; EOCDR_LENGTH] = [0; EOCDR_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        Ok(EndOfCentralDirectoryHeader::from(buffer))
+    }
+}
+
+impl CentralDirectoryRecord {
+    /// Reads the record's signature alongside its fixed-size fields in a single call, returning an error if the
+    /// signature doesn't match.
+    pub(crate) async fn from_reader_with_signature<R: AsyncRead + Unpin>(
+        reader: &mut R,
+    ) -> Result<CentralDirectoryRecord> {
+        let mut buffer: [u8; SIGNATURE_LENGTH + CDH_LENGTH] = [0; SIGNATURE_LENGTH + CDH_LENGTH];
+        reader.read_exact(&mut buffer).await?;
+        CentralDirectoryRecord::parse_with_signature(&buffer)
+    }
+}
@@ -0,0 +1,131 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Implode is an LZ77 variant whose lengths, distances, and (optionally) literals are encoded using Shannon-Fano trees
+//! stored at the start of the compressed data.
+//!
+//! https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#53
+
+use crate::read::io::legacy::{copy_match, invalid, BitReader};
+
+/// The maximum length of a Shannon-Fano code.
+const MAX_CODE_LENGTH: usize = 16;
+
+/// The length symbol which is followed by an additional byte of length.
+const MAX_LENGTH_SYMBOL: u16 = 63;
+
+/// A Shannon-Fano tree, represented by the number of codes of each length and its symbols ordered by code.
+struct Tree {
+    counts: [u16; MAX_CODE_LENGTH + 1],
+    symbols: Vec<u16>,
+}
+
+impl Tree {
+    /// Reads a tree of the provided number of symbols, stored as run-length encoded code lengths.
+    fn read(bits: &mut BitReader<'_>, count: usize) -> std::io::Result<Self> {
+        let mut lengths = Vec::with_capacity(count);
+
+        for _ in 0..=bits.read(8)? {
+            let byte = bits.read(8)?;
+            let length = (byte & 0xF) as usize + 1;
+            let run = (byte >> 4) as usize + 1;
+
+            lengths.resize(lengths.len() + run, length);
+        }
+
+        if lengths.len() != count {
+            return Err(invalid("implode tree has an incorrect number of code lengths"));
+        }
+
+        let mut counts = [0; MAX_CODE_LENGTH + 1];
+        for length in lengths.iter() {
+            counts[*length] += 1;
+        }
+
+        // The codes of a tree must exactly fill the code space (ie. be neither over-subscribed nor incomplete).
+        let mut remaining: i32 = 1;
+        for count in counts.iter().skip(1) {
+            remaining = (remaining << 1) - *count as i32;
+            if remaining < 0 {
+                return Err(invalid("implode tree is over-subscribed"));
+            }
+        }
+        if remaining != 0 {
+            return Err(invalid("implode tree is incomplete"));
+        }
+
+        let mut symbols = Vec::with_capacity(count);
+        for code_length in 1..=MAX_CODE_LENGTH {
+            let matching = lengths.iter().enumerate().filter(|(_, length)| **length == code_length);
+            symbols.extend(matching.map(|(symbol, _)| symbol as u16));
+        }
+
+        Ok(Self { counts, symbols })
+    }
+
+    /// Decodes the next symbol.
+    ///
+    /// Codes are assigned in the same manner as Deflate's canonical Huffman codes but are stored with their bits
+    /// inverted.
+    fn decode(&self, bits: &mut BitReader<'_>) -> std::io::Result<u16> {
+        let (mut code, mut first, mut index) = (0, 0, 0);
+
+        for count in self.counts.iter().skip(1) {
+            code |= (bits.read(1)? ^ 1) as i32;
+            let count = *count as i32;
+
+            if code - first < count {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(invalid("implode code is invalid"))
+    }
+}
+
+/// Decodes Implode-compressed data of the provided uncompressed size and options.
+pub(crate) fn decode(input: &[u8], size: usize, large_window: bool, literal_tree: bool) -> std::io::Result<Vec<u8>> {
+    let mut bits = BitReader::new(input);
+    let mut output = Vec::new();
+
+    let literals = match literal_tree {
+        true => Some(Tree::read(&mut bits, 256)?),
+        false => None,
+    };
+    let lengths = Tree::read(&mut bits, 64)?;
+    let distances = Tree::read(&mut bits, 64)?;
+
+    let min_length = if literal_tree { 3 } else { 2 };
+    let distance_bits = if large_window { 7 } else { 6 };
+
+    while output.len() < size {
+        if bits.read(1)? == 1 {
+            let literal = match &literals {
+                Some(tree) => tree.decode(&mut bits)?,
+                None => bits.read(8)?,
+            };
+
+            output.push(literal as u8);
+            continue;
+        }
+
+        let lower = bits.read(distance_bits)? as usize;
+        let upper = distances.decode(&mut bits)? as usize;
+        let distance = (upper << distance_bits | lower) + 1;
+
+        let symbol = lengths.decode(&mut bits)?;
+        let mut length = symbol as usize + min_length;
+        if symbol == MAX_LENGTH_SYMBOL {
+            length += bits.read(8)? as usize;
+        }
+
+        copy_match(&mut output, distance, length);
+    }
+
+    output.truncate(size);
+    Ok(output)
+}
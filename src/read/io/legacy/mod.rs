@@ -0,0 +1,138 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Decoders for the legacy Shrink, Reduce, and Implode compression methods written by DOS-era versions of PKZIP.
+//!
+//! https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#50
+
+pub(crate) mod implode;
+pub(crate) mod reduce;
+pub(crate) mod shrink;
+
+use crate::read::io::poll_result_ok;
+use crate::spec::compression::Compression;
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use pin_project::pin_project;
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// The size of the buffer used when reading compressed data from the inner reader.
+const BUFFER_SIZE: usize = 8192;
+
+/// A wrapping reader which decodes data compressed using a legacy compression method.
+///
+/// None of the legacy methods mark the end of their compressed data, so decoding is driven by the entry's uncompressed
+/// size. As these methods are only found within small archives from the era, all compressed data is read and decoded
+/// in one go on the first read, with the decoded data then served from memory.
+#[pin_project]
+pub(crate) struct LegacyDecoder<R> {
+    #[pin]
+    reader: R,
+    compression: Compression,
+    uncompressed_size: u64,
+    input: Vec<u8>,
+    output: Option<Vec<u8>>,
+    position: usize,
+}
+
+impl<R> LegacyDecoder<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructs a new decoder from a generic [`AsyncRead`] implementer.
+    pub(crate) fn new(reader: R, compression: Compression, uncompressed_size: u64) -> Self {
+        Self { reader, compression, uncompressed_size, input: Vec::new(), output: None, position: 0 }
+    }
+}
+
+impl<R> AsyncRead for LegacyDecoder<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let mut this = self.project();
+
+        if this.output.is_none() {
+            loop {
+                let mut chunk = [0; BUFFER_SIZE];
+                let mut buffer = ReadBuf::new(&mut chunk);
+                poll_result_ok!(ready!(this.reader.as_mut().poll_read(c, &mut buffer)));
+
+                if buffer.filled().is_empty() {
+                    break;
+                }
+
+                this.input.extend_from_slice(buffer.filled());
+            }
+
+            let input = std::mem::take(this.input);
+            *this.output = Some(poll_result_ok!(decode(*this.compression, &input, *this.uncompressed_size)));
+        }
+
+        let output = this.output.as_ref().expect("output should be set after decoding");
+        let length = std::cmp::min(output.len() - *this.position, b.remaining());
+
+        b.put_slice(&output[*this.position..*this.position + length]);
+        *this.position += length;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Decodes all compressed data using the provided legacy compression method.
+fn decode(compression: Compression, input: &[u8], uncompressed_size: u64) -> std::io::Result<Vec<u8>> {
+    let size = usize::try_from(uncompressed_size).map_err(|_| invalid("uncompressed size exceeds target usize"))?;
+
+    match compression {
+        Compression::Shrink => shrink::decode(input, size),
+        Compression::Reduce(factor) => reduce::decode(input, size, factor),
+        Compression::Implode { large_window, literal_tree } => implode::decode(input, size, large_window, literal_tree),
+        _ => unreachable!(),
+    }
+}
+
+/// Constructs an error for compressed data which couldn't be decoded.
+pub(crate) fn invalid(message: &'static str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Copies a back-reference of the provided length & distance onto the end of the output.
+///
+/// Both Reduce and Implode treat positions before the start of the output as zeroed bytes.
+pub(crate) fn copy_match(output: &mut Vec<u8>, distance: usize, length: usize) {
+    for _ in 0..length {
+        let byte = output.len().checked_sub(distance).map_or(0, |index| output[index]);
+        output.push(byte);
+    }
+}
+
+/// A reader of individual bits from compressed data, with bits packed starting from the least-significant bit.
+pub(crate) struct BitReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    /// Reads the provided number of bits (at most 16), with the first bit read being the least-significant.
+    pub(crate) fn read(&mut self, count: u8) -> std::io::Result<u16> {
+        if self.position + count as usize > self.data.len() * 8 {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "compressed data ended unexpectedly"));
+        }
+
+        let mut value = 0;
+        for index in 0..count {
+            let bit = (self.data[self.position / 8] >> (self.position % 8)) & 1;
+            value |= u16::from(bit) << index;
+            self.position += 1;
+        }
+
+        Ok(value)
+    }
+}
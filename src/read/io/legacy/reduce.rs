@@ -0,0 +1,105 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Reduce combines a simple back-reference scheme (escaped via a DLE byte) with a probabilistic encoding of each byte
+//! based on the byte preceding it, using per-byte 'follower sets' stored at the start of the compressed data.
+//!
+//! https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#52
+
+use crate::read::io::legacy::{copy_match, invalid, BitReader};
+
+/// The byte which escapes a back-reference (or a literal DLE byte if followed by zero).
+const DLE: u8 = 144;
+
+/// The maximum number of bytes within a follower set.
+const MAX_FOLLOWERS: u16 = 32;
+
+/// A reader of the probabilistically-encoded byte stream.
+struct FollowerReader<'a> {
+    bits: BitReader<'a>,
+    sets: Vec<Vec<u8>>,
+    last: u8,
+}
+
+impl<'a> FollowerReader<'a> {
+    /// Reads the follower sets which precede the compressed data, stored in reverse order.
+    fn new(input: &'a [u8]) -> std::io::Result<Self> {
+        let mut bits = BitReader::new(input);
+        let mut sets = vec![Vec::new(); 256];
+
+        for set in sets.iter_mut().rev() {
+            let length = bits.read(6)?;
+            if length > MAX_FOLLOWERS {
+                return Err(invalid("reduce follower set is too large"));
+            }
+
+            for _ in 0..length {
+                set.push(bits.read(8)? as u8);
+            }
+        }
+
+        Ok(Self { bits, sets, last: 0 })
+    }
+
+    /// Reads the next byte, either stored as-is or as an index into the follower set of the last byte read.
+    fn next(&mut self) -> std::io::Result<u8> {
+        let set = &self.sets[self.last as usize];
+
+        self.last = if set.is_empty() || self.bits.read(1)? == 1 {
+            self.bits.read(8)? as u8
+        } else {
+            let index = self.bits.read(index_bits(set.len()))?;
+            *set.get(index as usize).ok_or_else(|| invalid("reduce follower index is out of bounds"))?
+        };
+
+        Ok(self.last)
+    }
+}
+
+/// Returns the minimum number of bits required to encode an index into a follower set of the provided length.
+fn index_bits(length: usize) -> u8 {
+    match length {
+        1 => 1,
+        _ => (usize::BITS - (length - 1).leading_zeros()) as u8,
+    }
+}
+
+/// Decodes Reduce-compressed data of the provided uncompressed size and compression factor (1-4).
+pub(crate) fn decode(input: &[u8], size: usize, factor: u8) -> std::io::Result<Vec<u8>> {
+    if !(1..=4).contains(&factor) {
+        return Err(invalid("reduce compression factor must be between 1 and 4"));
+    }
+
+    let mut reader = FollowerReader::new(input)?;
+    let mut output = Vec::new();
+
+    // The lower bits of the byte following a DLE hold the length, with the remaining upper bits forming the upper byte
+    // of the distance.
+    let length_bits = 8 - factor;
+    let length_mask = (1 << length_bits) - 1;
+
+    while output.len() < size {
+        let byte = reader.next()?;
+        if byte != DLE {
+            output.push(byte);
+            continue;
+        }
+
+        let value = reader.next()?;
+        if value == 0 {
+            output.push(DLE);
+            continue;
+        }
+
+        let mut length = (value & length_mask) as usize;
+        if length == length_mask as usize {
+            length += reader.next()? as usize;
+        }
+
+        let distance = ((value >> length_bits) as usize) * 256 + reader.next()? as usize + 1;
+        copy_match(&mut output, distance, length + 3);
+    }
+
+    output.truncate(size);
+    Ok(output)
+}
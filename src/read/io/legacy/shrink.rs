@@ -0,0 +1,153 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Shrink is a dynamic LZW variant whose code size grows from 9 to 13 bits. Rather than resetting the dictionary once
+//! it's full, the compressor may signal a partial clear, which frees all codes not used as the prefix of another.
+//!
+//! https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#51
+
+use crate::read::io::legacy::{invalid, BitReader};
+
+use std::collections::VecDeque;
+
+const MIN_CODE_SIZE: u8 = 9;
+const MAX_CODE_SIZE: u8 = 13;
+const MAX_CODE: u16 = (1 << MAX_CODE_SIZE) - 1;
+
+const CONTROL_CODE: u16 = 256;
+const INCREASE_CODE_SIZE: u16 = 1;
+const PARTIAL_CLEAR: u16 = 2;
+
+/// The prefix held by codes which are free (ie. not currently assigned a string).
+const FREE: u16 = u16::MAX;
+
+/// The dictionary of strings, each represented as the code of its prefix string extended by a single byte.
+struct Dictionary {
+    prefixes: Vec<u16>,
+    suffixes: Vec<u8>,
+    free: VecDeque<u16>,
+}
+
+impl Dictionary {
+    fn new() -> Self {
+        let mut prefixes = vec![FREE; MAX_CODE as usize + 1];
+        let mut suffixes = vec![0; MAX_CODE as usize + 1];
+
+        for byte in 0..=u8::MAX {
+            prefixes[byte as usize] = byte as u16;
+            suffixes[byte as usize] = byte;
+        }
+
+        Self { prefixes, suffixes, free: (CONTROL_CODE + 1..=MAX_CODE).collect() }
+    }
+
+    fn is_free(&self, code: u16) -> bool {
+        code > u8::MAX as u16 && self.prefixes[code as usize] == FREE
+    }
+
+    /// Frees all codes which aren't the prefix of another code, queueing them for reuse in ascending order.
+    fn partial_clear(&mut self) {
+        let mut is_prefix = vec![false; MAX_CODE as usize + 1];
+        for code in CONTROL_CODE + 1..=MAX_CODE {
+            let prefix = self.prefixes[code as usize];
+            if prefix != FREE {
+                is_prefix[prefix as usize] = true;
+            }
+        }
+
+        self.free.clear();
+        for code in CONTROL_CODE + 1..=MAX_CODE {
+            if !is_prefix[code as usize] {
+                self.prefixes[code as usize] = FREE;
+                self.free.push_back(code);
+            }
+        }
+    }
+
+    /// Writes the string represented by the provided code into the buffer.
+    fn string(&self, mut code: u16, buffer: &mut Vec<u8>) -> std::io::Result<()> {
+        buffer.clear();
+
+        while code > u8::MAX as u16 {
+            let prefix = self.prefixes[code as usize];
+
+            // A chain longer than the number of codes can only occur if it's cyclic.
+            if prefix == FREE || buffer.len() > MAX_CODE as usize {
+                return Err(invalid("shrink code refers to an unassigned prefix"));
+            }
+
+            buffer.push(self.suffixes[code as usize]);
+            code = prefix;
+        }
+
+        buffer.push(code as u8);
+        buffer.reverse();
+        Ok(())
+    }
+}
+
+/// Reads the next code, handling any control codes which precede it.
+fn next_code(bits: &mut BitReader<'_>, code_size: &mut u8, dictionary: &mut Dictionary) -> std::io::Result<u16> {
+    loop {
+        let code = bits.read(*code_size)?;
+        if code != CONTROL_CODE {
+            return Ok(code);
+        }
+
+        match bits.read(*code_size)? {
+            INCREASE_CODE_SIZE if *code_size < MAX_CODE_SIZE => *code_size += 1,
+            PARTIAL_CLEAR => dictionary.partial_clear(),
+            _ => return Err(invalid("invalid shrink control code")),
+        }
+    }
+}
+
+/// Decodes Shrink-compressed data of the provided uncompressed size.
+pub(crate) fn decode(input: &[u8], size: usize) -> std::io::Result<Vec<u8>> {
+    let mut bits = BitReader::new(input);
+    let mut output = Vec::new();
+
+    if size == 0 {
+        return Ok(output);
+    }
+
+    let mut code_size = MIN_CODE_SIZE;
+    let mut dictionary = Dictionary::new();
+    let mut string = Vec::new();
+
+    let mut previous = next_code(&mut bits, &mut code_size, &mut dictionary)?;
+    if previous > u8::MAX as u16 {
+        return Err(invalid("first shrink code must be a literal"));
+    }
+    output.push(previous as u8);
+
+    while output.len() < size {
+        let code = next_code(&mut bits, &mut code_size, &mut dictionary)?;
+
+        if dictionary.is_free(code) {
+            // The code about to be assigned is being used before it's known by the decoder, which is only possible if
+            // it's the previous string extended by its own first byte.
+            if dictionary.free.front() != Some(&code) {
+                return Err(invalid("shrink code is unassigned"));
+            }
+
+            dictionary.string(previous, &mut string)?;
+            string.push(string[0]);
+        } else {
+            dictionary.string(code, &mut string)?;
+        }
+
+        // If the previous code was freed by a partial clear, the assigned string remains undetermined until that
+        // code is itself reassigned.
+        if let Some(assigned) = dictionary.free.pop_front() {
+            dictionary.prefixes[assigned as usize] = previous;
+            dictionary.suffixes[assigned as usize] = string[0];
+        }
+
+        output.extend_from_slice(&string);
+        previous = code;
+    }
+
+    output.truncate(size);
+    Ok(output)
+}
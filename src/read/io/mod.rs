@@ -1,28 +1,72 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "futures")]
+pub(crate) mod byte_stream;
+pub(crate) mod checkpoint;
+pub(crate) mod cipher;
 pub(crate) mod compressed;
+pub(crate) mod descriptor;
 pub(crate) mod entry;
 pub(crate) mod hashed;
+pub(crate) mod header;
+#[cfg(feature = "legacy")]
+pub(crate) mod legacy;
 pub(crate) mod locator;
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub(crate) mod outer;
 pub(crate) mod owned;
+pub(crate) mod prefixed;
 
 use tokio::io::{AsyncRead, AsyncReadExt};
 
-/// Read and return a dynamic length string from a reader which impls AsyncRead.
-pub(crate) async fn read_string<R: AsyncRead + Unpin>(reader: R, length: usize) -> std::io::Result<String> {
-    let mut buffer = String::with_capacity(length);
-    reader.take(length as u64).read_to_string(&mut buffer).await?;
+/// Read up to `max_length` bytes from a reader which impls AsyncRead into the provided scratch buffer.
+///
+/// The scratch buffer's allocation is reused across calls, and only grows as data is actually read (rather than being
+/// sized upfront from a length which may not be trustworthy). Fewer bytes are returned if the reader ends early.
+pub(crate) async fn read_bytes<R>(reader: R, max_length: usize, scratch: &mut Vec<u8>) -> std::io::Result<&[u8]>
+where
+    R: AsyncRead + Unpin,
+{
+    scratch.clear();
+    reader.take(max_length as u64).read_to_end(scratch).await?;
 
-    Ok(buffer)
+    Ok(scratch)
 }
 
-/// Read and return a dynamic length vector of bytes from a reader which impls AsyncRead.
-pub(crate) async fn read_bytes<R: AsyncRead + Unpin>(reader: R, length: usize) -> std::io::Result<Vec<u8>> {
-    let mut buffer = Vec::with_capacity(length);
-    reader.take(length as u64).read_to_end(&mut buffer).await?;
+/// Read up to `max_length` bytes from a reader which impls AsyncRead into the provided scratch buffer, as a string.
+///
+/// The data is validated as UTF-8 in place, and so isn't copied unless the caller requires an owned string.
+pub(crate) async fn read_string<R>(reader: R, max_length: usize, scratch: &mut Vec<u8>) -> std::io::Result<&str>
+where
+    R: AsyncRead + Unpin,
+{
+    let bytes = read_bytes(reader, max_length, scratch).await?;
+    std::str::from_utf8(bytes).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+/// Read and return a run of consecutive dynamic length fields from a reader which impls AsyncRead.
+///
+/// All fields are read into the provided scratch buffer in a single call (rather than one call per field), which
+/// reduces the overhead of parsing a header on readers with high per-call latency. Reusing the scratch buffer across
+/// headers leaves a single exactly-sized allocation per non-empty field.
+pub(crate) async fn read_fields<R, const N: usize>(
+    mut reader: R,
+    lengths: [usize; N],
+    scratch: &mut Vec<u8>,
+) -> std::io::Result<[Vec<u8>; N]>
+where
+    R: AsyncRead + Unpin,
+{
+    scratch.clear();
+    scratch.resize(lengths.iter().sum(), 0);
+    reader.read_exact(scratch).await?;
 
-    Ok(buffer)
+    let mut offset = 0;
+    Ok(lengths.map(|length| {
+        offset += length;
+        scratch[offset - length..offset].to_vec()
+    }))
 }
 
 /// A macro that returns the inner value of an Ok or early-returns in the case of an Err.
@@ -0,0 +1,61 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_compression::tokio::bufread;
+use pin_project::pin_project;
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+/// A compression format applied to the ZIP file as a whole (eg. a `.zip.gz` file).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OuterCompression {
+    #[cfg(feature = "gzip")]
+    Gzip,
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+/// A wrapping reader which decodes an outer compression format applied to the ZIP file as a whole.
+#[pin_project(project = OuterReaderProj)]
+pub enum OuterReader<R> {
+    #[cfg(feature = "gzip")]
+    Gzip(#[pin] bufread::GzipDecoder<R>),
+    #[cfg(feature = "zstd")]
+    Zstd(#[pin] bufread::ZstdDecoder<R>),
+}
+
+impl<R> OuterReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    /// Constructs a new wrapping reader from a generic [`AsyncBufRead`] implementer.
+    pub(crate) fn new(reader: R, compression: OuterCompression) -> Self {
+        match compression {
+            #[cfg(feature = "gzip")]
+            OuterCompression::Gzip => {
+                let mut decoder = bufread::GzipDecoder::new(reader);
+                decoder.multiple_members(true);
+                OuterReader::Gzip(decoder)
+            }
+            #[cfg(feature = "zstd")]
+            OuterCompression::Zstd => OuterReader::Zstd(bufread::ZstdDecoder::new(reader)),
+        }
+    }
+}
+
+impl<R> AsyncRead for OuterReader<R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        match self.project() {
+            #[cfg(feature = "gzip")]
+            OuterReaderProj::Gzip(inner) => inner.poll_read(c, b),
+            #[cfg(feature = "zstd")]
+            OuterReaderProj::Zstd(inner) => inner.poll_read(c, b),
+        }
+    }
+}
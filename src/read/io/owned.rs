@@ -5,7 +5,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use pin_project::pin_project;
-use tokio::io::{AsyncRead, ReadBuf};
+use tokio::io::{AsyncRead, AsyncSeek, ReadBuf, SeekFrom};
 
 /// A wrapping reader which holds an owned R or a mutable borrow to R.
 ///
@@ -28,3 +28,22 @@ where
         }
     }
 }
+
+impl<'a, R> AsyncSeek for OwnedReader<'a, R>
+where
+    R: AsyncSeek + Unpin,
+{
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> tokio::io::Result<()> {
+        match self.project() {
+            OwnedReaderProj::Owned(inner) => inner.start_seek(position),
+            OwnedReaderProj::Borrow(inner) => inner.start_seek(position),
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<tokio::io::Result<u64>> {
+        match self.project() {
+            OwnedReaderProj::Owned(inner) => inner.poll_complete(c),
+            OwnedReaderProj::Borrow(inner) => inner.poll_complete(c),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use pin_project::pin_project;
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+/// A wrapping reader which serves a prefix of previously-read bytes before reading from the inner reader.
+///
+/// This allows bytes which have been over-read by a consumer (eg. a decoder's internal buffer) to be returned to the
+/// front of the stream.
+#[pin_project]
+pub struct PrefixedReader<R> {
+    prefix: Vec<u8>,
+    position: usize,
+    #[pin]
+    inner: R,
+}
+
+impl<R> PrefixedReader<R> {
+    /// Constructs a new wrapping reader with an empty prefix.
+    pub(crate) fn new(inner: R) -> Self {
+        Self { prefix: Vec::new(), position: 0, inner }
+    }
+
+    /// Returns bytes to the front of the stream so that they're read before any remaining prefix or inner data.
+    pub(crate) fn unread(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
+        let mut prefix = bytes.to_vec();
+        prefix.extend_from_slice(&self.prefix[self.position..]);
+
+        self.prefix = prefix;
+        self.position = 0;
+    }
+
+    /// Consumes this reader and returns the inner reader, discarding any remaining prefix.
+    #[cfg(feature = "fs")]
+    pub(crate) fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R> AsyncRead for PrefixedReader<R>
+where
+    R: AsyncRead,
+{
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let project = self.project();
+        let remaining = &project.prefix[*project.position..];
+
+        if remaining.is_empty() {
+            return project.inner.poll_read(c, b);
+        }
+
+        let length = std::cmp::min(remaining.len(), b.remaining());
+        b.put_slice(&remaining[..length]);
+        *project.position += length;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R> AsyncBufRead for PrefixedReader<R>
+where
+    R: AsyncBufRead,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<tokio::io::Result<&[u8]>> {
+        let project = self.project();
+
+        if *project.position < project.prefix.len() {
+            return Poll::Ready(Ok(&project.prefix[*project.position..]));
+        }
+
+        project.inner.poll_fill_buf(c)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let project = self.project();
+
+        if *project.position < project.prefix.len() {
+            *project.position += amt;
+        } else {
+            project.inner.consume(amt);
+        }
+    }
+}
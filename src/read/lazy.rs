@@ -0,0 +1,268 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over a seekable source, indexing the central directory rather than parsing it eagerly.
+//!
+//! ### Usage
+//! When opened, the other readers parse every central directory record into a [`ZipEntry`] up front. For archives with
+//! hundreds of thousands of entries, this costs a considerable amount of memory and time before any entry is read.
+//! Here, only the offset of each record is kept, and entries are parsed on demand (via [`ZipFileReader::entry_info()`])
+//! or in order from an async iterator (via [`ZipFileReader::entries()`]).
+//!
+//! As entries are never all held at once, filename normalisation collisions (see
+//! [`ReaderOptions::normalize_filenames()`]) aren't detected, and the local overheads reported by
+//! [`ZipEntryMeta::overhead()`] aren't computed.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::lazy::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use tokio::fs::File;
+//! #
+//! # async fn run() -> Result<()> {
+//! let file = File::open("./foo.zip").await?;
+//! let mut reader = ZipFileReader::new(file).await?;
+//!
+//! let mut entries = reader.entries();
+//! while let Some(entry) = entries.next().await {
+//!     println!("{}", entry?.filename());
+//! }
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! [`ZipEntryMeta::overhead()`]: crate::ZipEntryMeta::overhead
+
+use crate::entry::{ZipEntry, ZipEntryMeta};
+use crate::error::{Result, ZipError};
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::io::checkpoint::Checkpoint;
+use crate::read::io::entry::ZipEntryReader;
+use crate::spec::consts::{CDH_LENGTH, SIGNATURE_LENGTH};
+use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader};
+
+#[cfg(feature = "futures")]
+use futures_util::stream::{self, Stream};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader, SeekFrom};
+
+/// A ZIP reader which acts over a seekable source, indexing the central directory rather than parsing it eagerly.
+pub struct ZipFileReader<R> {
+    reader: R,
+    offsets: Vec<u64>,
+    comment: String,
+    options: ReaderOptions,
+}
+
+impl<R> ZipFileReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader from a seekable source.
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Self::new_with_options(reader, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, using the provided options.
+    ///
+    /// Diagnostics aren't supported by this reader, and so that option is ignored.
+    pub async fn new_with_options(mut reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        let mut buffered = BufReader::new(&mut reader);
+        let eocdr_offset = crate::read::io::locator::eocdr_with_strategy(
+            &mut buffered,
+            options.search_strategy,
+            options.yield_interval,
+        )
+        .await?;
+
+        buffered.seek(SeekFrom::Start(eocdr_offset + SIGNATURE_LENGTH as u64)).await?;
+        let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut buffered).await?;
+        let comment = crate::read::io::read_string(&mut buffered, eocdr.file_comm_length.into(), &mut Vec::new())
+            .await?
+            .to_owned();
+
+        buffered.seek(SeekFrom::Start(eocdr.cent_dir_offset.into())).await?;
+        let offsets = index(&mut buffered, &eocdr, &options).await?;
+
+        Ok(ZipFileReader { reader, offsets, comment, options })
+    }
+
+    /// Returns the number of entries within this ZIP file.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Returns whether or not this ZIP file contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Returns this ZIP file's trailing comment.
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Parses and returns the entry at the provided index, if valid.
+    pub async fn entry_info(&mut self, index: usize) -> Result<ZipEntry> {
+        Ok(self.record(index).await?.0)
+    }
+
+    /// Returns an async iterator which parses each entry in turn, in central directory order.
+    pub fn entries(&mut self) -> Entries<'_, R> {
+        Entries {
+            reader: BufReader::new(&mut self.reader),
+            offsets: &self.offsets,
+            next: 0,
+            seek: true,
+            options: &self.options,
+            scratch: Vec::new(),
+        }
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
+        let (entry, meta) = self.record(index).await?;
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let seek_to = crate::read::compute_data_offset(&mut self.reader, &meta).await?;
+
+        self.reader.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_with_borrow(&mut self.reader, &entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.options.max_read_size))
+    }
+
+    async fn record(&mut self, index: usize) -> Result<(ZipEntry, ZipEntryMeta)> {
+        let offset = *self.offsets.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        self.reader.seek(SeekFrom::Start(offset)).await?;
+        record(BufReader::new(&mut self.reader), &self.options, &mut Vec::new()).await
+    }
+}
+
+/// An async iterator over the entries of a [`ZipFileReader`], parsing each in turn.
+pub struct Entries<'a, R> {
+    reader: BufReader<&'a mut R>,
+    offsets: &'a [u64],
+    next: usize,
+    seek: bool,
+    options: &'a ReaderOptions,
+    scratch: Vec<u8>,
+}
+
+impl<'a, R> Entries<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Parses and returns the next entry, or [`None`] once all entries have been returned.
+    ///
+    /// No further entries are returned after an error, unless skipping corrupt entries (see
+    /// [`ReaderOptions::skip_corrupt_entries()`]), in which case an error is returned for each malformed record.
+    pub async fn next(&mut self) -> Option<Result<ZipEntry>> {
+        let offset = *self.offsets.get(self.next)?;
+        let result = self.next_record(offset).await;
+
+        self.next = match (&result, self.options.skip_corrupt_entries) {
+            (Err(_), false) => self.offsets.len(),
+            _ => self.next + 1,
+        };
+        self.seek = result.is_err();
+
+        Some(result)
+    }
+
+    /// Converts this iterator into a [`Stream`], so that stream combinators can be used over the entries.
+    ///
+    /// As with [`Entries::next()`], the stream ends after an error (unless skipping corrupt entries).
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self) -> impl Stream<Item = Result<ZipEntry>> + 'a {
+        stream::unfold(self, |mut entries| async move { entries.next().await.map(|result| (result, entries)) })
+    }
+
+    async fn next_record(&mut self, offset: u64) -> Result<ZipEntry> {
+        // Records are contiguous, so a seek is only needed before the first (or after a malformed record).
+        if self.seek {
+            self.reader.seek(SeekFrom::Start(offset)).await?;
+        }
+
+        Ok(record(&mut self.reader, self.options, &mut self.scratch).await?.0)
+    }
+}
+
+/// Reads the offset of each central directory record, skipping over its variable-length fields.
+///
+/// If skipping corrupt entries, the offset of a malformed record is still kept (so that parsing it later returns the
+/// error encountered), and indexing resumes from the next central directory record signature found.
+async fn index<R>(mut reader: R, eocdr: &EndOfCentralDirectoryHeader, options: &ReaderOptions) -> Result<Vec<u64>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    // Outdated feature so unlikely to ever make it into this crate.
+    if eocdr.disk_num != eocdr.start_cent_dir_disk || eocdr.num_of_entries != eocdr.num_of_entries_disk {
+        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+    }
+
+    let num_of_entries = eocdr.num_of_entries.into();
+    let mut offsets = Vec::with_capacity(num_of_entries);
+    let mut offset = eocdr.cent_dir_offset.into();
+    let mut checkpoint = Checkpoint::new(options.yield_interval);
+
+    for _ in 0..num_of_entries {
+        checkpoint.tick().await;
+        offsets.push(offset);
+
+        match skip_record(&mut reader).await {
+            Ok(length) => offset += length,
+            Err(error) if !options.skip_corrupt_entries => return Err(error),
+            Err(_) => match crate::read::resync(&mut reader, offset + 1).await? {
+                Some(position) => offset = position,
+                None => break,
+            },
+        }
+    }
+
+    Ok(offsets)
+}
+
+/// Skips over a single central directory record, returning its length.
+async fn skip_record<R>(mut reader: R) -> Result<u64>
+where
+    R: AsyncRead + Unpin,
+{
+    let header = CentralDirectoryRecord::from_reader_with_signature(&mut reader).await?;
+    let skip = header.file_name_length as u64 + header.extra_field_length as u64 + header.file_comment_length as u64;
+
+    // Copying into a sink (rather than seeking) keeps the reader's buffer intact.
+    if tokio::io::copy(&mut (&mut reader).take(skip), &mut tokio::io::sink()).await? != skip {
+        return Err(ZipError::UpstreamReadError(std::io::ErrorKind::UnexpectedEof.into()));
+    }
+
+    Ok((SIGNATURE_LENGTH + CDH_LENGTH) as u64 + skip)
+}
+
+/// Parses a single central directory record, decoding & normalising its filename if requested.
+async fn record<R>(
+    reader: R,
+    #[cfg_attr(not(any(feature = "unicode", feature = "encoding")), allow(unused_variables))] options: &ReaderOptions,
+    scratch: &mut Vec<u8>,
+) -> Result<(ZipEntry, ZipEntryMeta)>
+where
+    R: AsyncRead + Unpin,
+{
+    #[cfg_attr(not(any(feature = "unicode", feature = "encoding")), allow(unused_mut))]
+    let (mut entry, meta) = crate::read::cd_record(reader, scratch).await?;
+    #[cfg(feature = "encoding")]
+    crate::read::decode_with_encoding(
+        std::slice::from_mut(&mut entry),
+        std::slice::from_ref(&meta),
+        options.filename_encoding,
+    );
+    #[cfg(feature = "unicode")]
+    if options.normalize_filenames {
+        crate::read::normalize_filenames(std::slice::from_mut(&mut entry))?;
+    }
+
+    Ok((entry, meta))
+}
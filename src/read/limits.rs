@@ -0,0 +1,138 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports limiting the amount of data produced when decompressing entries.
+//!
+//! A maliciously crafted ZIP file (a "zip bomb") may hold entries which decompress to many orders of magnitude more
+//! data than they occupy, exhausting the memory or disk space of whoever extracts them. Entry sizes stored within the
+//! headers can't be trusted to guard against this, so the limits are instead enforced against the data actually
+//! produced as it's read. Once a limit has been exceeded, reading fails with [`ZipError::LimitExceeded`].
+//!
+//! Limits are applied to a single entry via [`ZipEntryReader::with_limits()`], or to every entry (alongside a cap on
+//! the total amount of data extracted) via [`ExtractOptions::limits()`].
+//!
+//! ### Example
+//! ```no_run
+//! # #[cfg(feature = "fs")]
+//! # {
+//! # use async_zip::read::extract::ExtractOptions;
+//! # use async_zip::read::limits::DecompressionLimits;
+//! # use async_zip::read::mem::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run(data: Vec<u8>) -> Result<()> {
+//! let reader = ZipFileReader::new(data).await?;
+//! let limits = DecompressionLimits::new()
+//!     .max_entry_size(64 * 1024 * 1024)
+//!     .max_ratio(100)
+//!     .max_total_size(1024 * 1024 * 1024);
+//!
+//! reader.extract_with_options("./output", ExtractOptions::new().limits(limits)).await?;
+//! #   Ok(())
+//! # }
+//! # }
+//! ```
+//!
+//! [`ZipEntryReader::with_limits()`]: crate::read::io::entry::ZipEntryReader::with_limits
+//! [`ExtractOptions::limits()`]: crate::read::extract::ExtractOptions::limits
+
+use crate::error::ZipError;
+
+use std::fmt::{Display, Formatter};
+
+/// A set of limits on the amount of data produced when decompressing entries.
+///
+/// No limits are set by default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DecompressionLimits {
+    pub(crate) max_entry_size: Option<u64>,
+    pub(crate) max_ratio: Option<u64>,
+    pub(crate) max_total_size: Option<u64>,
+}
+
+impl DecompressionLimits {
+    /// Constructs a new set of limits, none of which are set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of bytes which a single entry may decompress to.
+    pub fn max_entry_size(mut self, limit: u64) -> Self {
+        self.max_entry_size = Some(limit);
+        self
+    }
+
+    /// Sets the maximum ratio of an entry's decompressed size to its compressed size.
+    ///
+    /// The ratio is measured against the entry's stored compressed size or, for entries whose sizes aren't known
+    /// upfront (ie. those using a data descriptor, when read via the [`stream`] reader), against the compressed data
+    /// read so far. Compressed sizes below 1 KiB are treated as 1 KiB, so that tiny (but legitimate) entries aren't
+    /// rejected.
+    ///
+    /// [`stream`]: crate::read::stream
+    pub fn max_ratio(mut self, ratio: u64) -> Self {
+        self.max_ratio = Some(ratio);
+        self
+    }
+
+    /// Sets the maximum number of bytes which all entries may decompress to in total.
+    ///
+    /// This only applies when extracting (see [`ExtractOptions::limits()`]), as entry readers are otherwise
+    /// independent of one another.
+    ///
+    /// [`ExtractOptions::limits()`]: crate::read::extract::ExtractOptions::limits
+    pub fn max_total_size(mut self, limit: u64) -> Self {
+        self.max_total_size = Some(limit);
+        self
+    }
+
+    /// Returns the error for the first limit exceeded, given the bytes decompressed from an entry and before it, and
+    /// the compressed bytes the entry's ratio is measured against.
+    pub(crate) fn check(&self, read: u64, read_before: u64, compressed: u64) -> Option<ZipError> {
+        if let Some(limit) = self.max_entry_size.filter(|limit| read > *limit) {
+            return Some(ZipError::LimitExceeded(LimitKind::EntrySize, limit));
+        }
+
+        if let Some(ratio) = self.max_ratio.filter(|ratio| read > ratio.saturating_mul(compressed.max(1024))) {
+            return Some(ZipError::LimitExceeded(LimitKind::ExpansionRatio, ratio));
+        }
+
+        if let Some(limit) = self.max_total_size.filter(|limit| read_before.saturating_add(read) > *limit) {
+            return Some(ZipError::LimitExceeded(LimitKind::TotalSize, limit));
+        }
+
+        None
+    }
+}
+
+/// The kind of decompression limit which was exceeded.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitKind {
+    /// The maximum decompressed size of a single entry.
+    EntrySize,
+    /// The maximum ratio of an entry's decompressed size to its compressed size.
+    ExpansionRatio,
+    /// The maximum decompressed size of all entries in total.
+    TotalSize,
+}
+
+impl Display for LimitKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitKind::EntrySize => write!(f, "entry size"),
+            LimitKind::ExpansionRatio => write!(f, "expansion ratio"),
+            LimitKind::TotalSize => write!(f, "total size"),
+        }
+    }
+}
+
+/// Converts an IO error into a [`ZipError`], unwrapping it if it holds a [`ZipError::LimitExceeded`] error.
+///
+/// Limits are enforced within [`AsyncRead`](tokio::io::AsyncRead) implementations, so are raised as IO errors.
+pub(crate) fn unwrap_limit_error(error: std::io::Error) -> ZipError {
+    match error.get_ref().and_then(|inner| inner.downcast_ref::<ZipError>()) {
+        Some(ZipError::LimitExceeded(kind, limit)) => ZipError::LimitExceeded(*kind, *limit),
+        _ => ZipError::UpstreamReadError(error),
+    }
+}
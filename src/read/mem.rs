@@ -5,13 +5,12 @@
 //!
 //! Concurrency is achieved as a result of:
 //! - Wrapping the provided vector of bytes within an [`Arc`] to allow shared ownership.
-//! - Wrapping this [`Arc`] around a [`Cursor`] when reading (as the [`Arc`] can deref and coerce into a `&[u8]`).
+//! - Wrapping this [`Arc`] around a [`Cursor`] when reading (via [`SharedData`], which exposes the bytes as a `&[u8]`).
 //!
 //! ### Usage
 //! Unlike the [`seek`] module, we no longer hold a mutable reference to any inner reader which in turn, allows the
-//! construction of concurrent [`ZipEntryReader`]s. Though, note that each individual [`ZipEntryReader`] cannot be sent
-//! between thread boundaries due to the masked lifetime requirement. Therefore, the overarching [`ZipFileReader`]
-//! should be cloned and moved into those contexts when needed.
+//! construction of concurrent [`ZipEntryReader`]s. Each [`ZipEntryReader`] owns its inner reader (and so is `'static`
+//! and [`Send`]), allowing it to be moved onto a separate task without cloning the overarching [`ZipFileReader`].
 //!
 //! ### Concurrent Example
 //! ```no_run
@@ -37,22 +36,20 @@
 //! ```no_run
 //! # use async_zip::read::mem::ZipFileReader;
 //! # use async_zip::error::Result;
+//! # use tokio::io::AsyncReadExt;
 //! #
 //! # async fn run() -> Result<()> {
 //! let data: Vec<u8> = Vec::new();
 //! let reader = ZipFileReader::new(data).await?;
+//! let mut entry_reader = reader.entry(0).await?;
 //!
-//! let fut_gen = |index| {
-//!     let local_reader = reader.clone();
-//!
-//!     tokio::spawn(async move {
-//!         let mut entry_reader = local_reader.entry_reader(index).await?;
-//!         let mut data = Vec::new();
-//!         entry_reader.read_to_end(&mut data).await.unwrap();
-//!     })
-//! };
+//! let handle = tokio::spawn(async move {
+//!     let mut data = Vec::new();
+//!     entry_reader.read_to_end(&mut data).await.unwrap();
+//!     data
+//! });
 //!
-//! tokio::join!(fut_gen(0), fut_gen(1)).map(|res| res?);
+//! let data = handle.await.unwrap();
 //! #   Ok(())
 //! # }
 //! ```
@@ -60,20 +57,38 @@
 #[cfg(doc)]
 use crate::read::seek;
 
+use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
+use crate::read::diagnostics::ReaderOptions;
+#[cfg(feature = "fs")]
+use crate::read::extract::{ExtractOptions, Extractor};
 use crate::read::io::entry::ZipEntryReader;
+use crate::read::rewrite::RewriteAction;
+use crate::write::ZipFileWriter;
 
 use std::io::Cursor;
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use tokio::io::{AsyncSeekExt, SeekFrom};
+use tokio::io::{AsyncSeekExt, AsyncWrite, SeekFrom};
 
 struct Inner {
     data: Vec<u8>,
     file: ZipFile,
 }
 
+/// A shared reference to the bytes provided to a [`ZipFileReader`], over which its entry readers act.
+#[derive(Clone)]
+pub struct SharedData(Arc<Inner>);
+
+impl AsRef<[u8]> for SharedData {
+    fn as_ref(&self) -> &[u8] {
+        &self.0.data
+    }
+}
+
 // A concurrent ZIP reader which acts over an owned vector of bytes.
 #[derive(Clone)]
 pub struct ZipFileReader {
@@ -82,8 +97,23 @@ pub struct ZipFileReader {
 
 impl ZipFileReader {
     /// Constructs a new ZIP reader from an owned vector of bytes.
-    pub async fn new(data: Vec<u8>) -> Result<ZipFileReader> {
-        let file = crate::read::file(Cursor::new(&data)).await?;
+    ///
+    /// Anything which converts into a vector of bytes is accepted (eg. a boxed slice, or a `bytes::Bytes` received from
+    /// a browser's `fetch()` under wasm-bindgen).
+    pub async fn new<D>(data: D) -> Result<ZipFileReader>
+    where
+        D: Into<Vec<u8>>,
+    {
+        Self::new_with_options(data, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from an owned vector of bytes, using the provided options.
+    pub async fn new_with_options<D>(data: D, options: ReaderOptions) -> Result<ZipFileReader>
+    where
+        D: Into<Vec<u8>>,
+    {
+        let data = data.into();
+        let file = crate::read::file(Cursor::new(&data), &options).await?;
         Ok(ZipFileReader { inner: Arc::new(Inner { data, file }) })
     }
 
@@ -97,14 +127,167 @@ impl ZipFileReader {
         &self.inner.data
     }
 
+    /// Reads the local file header of the entry at the provided index, returning the offset at which its data begins.
+    ///
+    /// See [`ZipFile::data_offset()`] for more information.
+    pub async fn data_offset(&self, index: usize) -> Result<u64> {
+        self.file().data_offset(index, Cursor::new(self.data())).await
+    }
+
     /// Returns a new entry reader if the provided index is valid.
-    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<Cursor<&[u8]>>> {
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'static, Cursor<SharedData>>> {
         let entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let meta = self.inner.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let seek_to = crate::read::compute_data_offset(entry, meta);
-        let mut cursor = Cursor::new(&self.inner.data[..]);
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let mut cursor = Cursor::new(SharedData(self.inner.clone()));
+        let seek_to = crate::read::compute_data_offset(&mut cursor, meta).await?;
 
         cursor.seek(SeekFrom::Start(seek_to)).await?;
-        Ok(ZipEntryReader::new_with_owned(cursor, entry.compression(), entry.uncompressed_size().into()))
+        Ok(ZipEntryReader::new_with_owned(cursor, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file().max_read_size))
+    }
+
+    /// Returns a new entry reader for the first entry with the provided filename.
+    ///
+    /// Filenames are matched as per [`ZipFile::entry_index()`]. If no entry matches, [`ZipError::EntryNotFound`] is
+    /// returned.
+    pub async fn entry_by_name(&self, filename: &str) -> Result<ZipEntryReader<'static, Cursor<SharedData>>> {
+        self.entry(self.inner.file.named_index(filename)?).await
+    }
+
+    /// Returns the first entry with the provided filename alongside a new reader over it.
+    ///
+    /// See [`ZipFileReader::entry_by_name()`] for more information.
+    pub async fn entry_and_reader_by_name(
+        &self,
+        filename: &str,
+    ) -> Result<(&ZipEntry, ZipEntryReader<'static, Cursor<SharedData>>)> {
+        let index = self.inner.file.named_index(filename)?;
+        Ok((&self.inner.file.entries[index], self.entry(index).await?))
+    }
+
+    /// Returns a new reader over the raw (ie. still compressed) data of an entry if the provided index is valid.
+    ///
+    /// Paired with [`ZipFileWriter::copy_entry_raw()`], this allows entries to be copied between archives without being
+    /// decompressed and recompressed. Encrypted entries aren't supported.
+    ///
+    /// [`ZipFileWriter::copy_entry_raw()`]: crate::write::ZipFileWriter::copy_entry_raw
+    pub async fn entry_raw(&self, index: usize) -> Result<ZipEntryReader<'static, Cursor<SharedData>>> {
+        let entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.inner.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if meta.general_purpose_flag.encrypted {
+            return Err(ZipError::FeatureNotSupported("Raw reading of encrypted entries"));
+        }
+
+        let mut cursor = Cursor::new(SharedData(self.inner.clone()));
+        let seek_to = crate::read::compute_data_offset(&mut cursor, meta).await?;
+
+        cursor.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_raw_with_owned(cursor, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file().max_read_size))
+    }
+
+    /// Returns a new entry reader which decrypts the entry using the provided password if the provided index is valid.
+    ///
+    /// Traditional PKWARE (ZipCrypto) encryption is supported, as is WinZip AES encryption with the `aes` feature.
+    /// Entries which aren't encrypted are read as if via
+    /// [`ZipFileReader::entry()`], ignoring the password.
+    pub async fn entry_with_password(
+        &self,
+        index: usize,
+        password: &[u8],
+    ) -> Result<ZipEntryReader<'static, Cursor<SharedData>>> {
+        let entry = self.inner.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.inner.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if !meta.general_purpose_flag.encrypted {
+            return self.entry(index).await;
+        }
+
+        let mut cursor = Cursor::new(SharedData(self.inner.clone()));
+        let (header, seek_to) = crate::read::local_file_header(&mut cursor, meta).await?;
+        let encryption = crate::read::encryption(&header, meta)?;
+
+        cursor.seek(SeekFrom::Start(seek_to)).await?;
+        let reader = ZipEntryReader::new_with_owned_password(cursor, entry, password, encryption).await?;
+        Ok(reader.with_read_limit(self.file().max_read_size))
+    }
+
+    /// Rewrites this ZIP file's entries into the provided writer, taking the action decided for each entry.
+    ///
+    /// See the [`rewrite`](crate::read::rewrite) module for more information.
+    pub async fn rewrite<W, F>(&self, writer: &mut ZipFileWriter<W>, mut decide: F) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(&ZipEntry) -> RewriteAction,
+    {
+        for (index, entry) in self.inner.file.entries.iter().enumerate() {
+            if let Some(entry) = decide(entry).apply(entry) {
+                writer.copy_entry_raw(&entry, self.entry_raw(index).await?).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Extracts all entries into the provided destination directory, creating it if needed.
+    ///
+    /// Entries whose filenames would escape the destination directory (ie. those containing `..` components, absolute
+    /// paths, or drive prefixes) are rejected with [`ZipError::UnsafeFilename`].
+    #[cfg(feature = "fs")]
+    pub async fn extract<P>(&self, dest: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_options(dest, ExtractOptions::default()).await
+    }
+
+    /// Extracts all entries into the provided destination directory using the provided options.
+    ///
+    /// See [`ZipFileReader::extract()`] for more information.
+    #[cfg(feature = "fs")]
+    pub async fn extract_with_options<P>(&self, dest: P, options: ExtractOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_mapping(dest, options, |entry| Some(PathBuf::from(entry.filename()))).await
+    }
+
+    /// Extracts all entries into the provided destination directory using the provided options, relocating each entry
+    /// to the path returned by the provided mapping (or skipping it if [`None`] is returned).
+    ///
+    /// Returned paths are relative to the destination directory and are sanitised as entry filenames are (see
+    /// [`ZipFileReader::extract()`]). This allows entries to be renamed or moved on the fly, eg. to strip an undesirable
+    /// top-level directory. The mapping is applied to every entry before any are extracted, so that the total number of
+    /// entries to be extracted can be reported as progress.
+    #[cfg(feature = "fs")]
+    pub async fn extract_with_mapping<P, F>(&self, dest: P, options: ExtractOptions, mut map: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ZipEntry) -> Option<PathBuf>,
+    {
+        let paths: Vec<_> = self
+            .inner
+            .file
+            .entries
+            .iter()
+            .map(|entry| if options.includes(entry) { map(entry) } else { None })
+            .collect();
+        let mut extractor = Extractor::new(dest.as_ref(), &options, Some(paths.iter().flatten().count())).await?;
+
+        for (index, (entry, path)) in self.inner.file.entries.iter().zip(paths).enumerate() {
+            if let Some(path) = path {
+                extractor.extract(entry, &path, self.entry(index).await?).await?;
+            }
+        }
+
+        extractor.finish().await
     }
 }
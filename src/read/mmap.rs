@@ -0,0 +1,68 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a memory-mapped local file.
+//!
+//! The mapping is read via the [`positional`] reader, so the archive is parsed and read exactly as an in-memory buffer
+//! would be, without first reading it into a `Vec<u8>`. Any number of entry readers can be open concurrently over the
+//! single shared mapping, and pages are only faulted in as the data within them is read.
+//!
+//! ### Safety
+//! As with any memory mapping, the behaviour is undefined if the underlying file is modified (eg. truncated) by this or
+//! another process whilst mapped. As such, constructing a reader from a path is `unsafe`. A [`Mmap`] which has already
+//! been created can instead be passed to [`ZipFileReader::new()`].
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::mmap::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run() -> Result<()> {
+//! // SAFETY: The archive isn't modified whilst mapped.
+//! let reader = unsafe { ZipFileReader::map("./foo.zip").await? };
+//!
+//! let mut data = Vec::new();
+//! let entry = &reader.file().entries()[0];
+//! reader.entry(0).await?.read_to_end_checked(&mut data, entry).await?;
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::positional;
+
+use std::path::Path;
+
+pub use memmap2::Mmap;
+
+/// A concurrent ZIP reader which acts over a memory-mapped local file.
+pub type ZipFileReader = positional::ZipFileReader<Mmap>;
+
+impl positional::ZipFileReader<Mmap> {
+    /// Memory-maps the file at the provided path and constructs a new ZIP reader over the mapping.
+    ///
+    /// # Safety
+    /// The file must not be modified whilst mapped (see the [`mmap`](crate::read::mmap) module for more information).
+    pub async unsafe fn map<P>(path: P) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        Self::map_with_options(path, ReaderOptions::default()).await
+    }
+
+    /// Memory-maps the file at the provided path and constructs a new ZIP reader over the mapping, using the provided
+    /// options.
+    ///
+    /// # Safety
+    /// The file must not be modified whilst mapped (see the [`mmap`](crate::read::mmap) module for more information).
+    pub async unsafe fn map_with_options<P>(path: P, options: ReaderOptions) -> Result<ZipFileReader>
+    where
+        P: AsRef<Path>,
+    {
+        let file = std::fs::File::open(path)?;
+        let mmap = Mmap::map(&file)?;
+
+        Self::new_with_options(mmap, options).await
+    }
+}
@@ -3,81 +3,262 @@
 
 //! A module which supports reading ZIP files.
 
+pub mod diagnostics;
+pub mod lazy;
+pub mod limits;
 pub mod mem;
+pub mod nested;
+pub mod positional;
+pub mod progress;
+pub mod remote;
+pub mod rewrite;
 pub mod seek;
+pub mod stream;
 
 #[cfg(feature = "fs")]
 pub mod fs;
 
+#[cfg(feature = "fs")]
+pub mod extract;
+
+#[cfg(feature = "mmap")]
+pub mod mmap;
+
+#[cfg(feature = "object-store")]
+pub mod object_store;
+
 pub(crate) mod io;
 
-use crate::entry::{ZipEntry, ZipEntryMeta};
-use crate::error::{Result, ZipError};
+use crate::entry::{EntryOverhead, Timestamps, ZipEntry, ZipEntryMeta};
+use crate::error::{ErrorContext, Result, ZipError};
 use crate::file::ZipFile;
+use crate::read::diagnostics::{OpenReport, OpenWarning, ParseMode, ReaderOptions};
+use crate::read::io::checkpoint::Checkpoint;
+use crate::read::io::cipher::Encryption;
 use crate::spec::attribute::AttributeCompatibility;
 use crate::spec::compression::Compression;
-use crate::spec::consts::{LFH_LENGTH, SIGNATURE_LENGTH};
-use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader};
+use crate::spec::consts::SIGNATURE_LENGTH;
+use crate::spec::consts::{CDH_LENGTH, CDH_SIGNATURE, DATA_DESCRIPTOR_SIGNATURE, LFH_LENGTH};
+use crate::spec::encoding::FilenameSource;
+#[cfg(feature = "aes")]
+use crate::spec::extra_field::{AesExtraField, AES_COMPRESSION_METHOD, AES_HEADER_ID};
+use crate::spec::extra_field::{EXTENDED_TIMESTAMP_HEADER_ID, NTFS_HEADER_ID, UNICODE_PATH_HEADER_ID};
+use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader};
 
-use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
+use std::time::{Duration, Instant};
 
-pub(crate) async fn file<R>(mut reader: R) -> Result<ZipFile>
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, SeekFrom};
+
+pub(crate) async fn file<R>(mut reader: R, options: &ReaderOptions) -> Result<ZipFile>
 where
-    R: AsyncRead + AsyncSeek + Unpin,
+    R: AsyncBufRead + AsyncSeek + Unpin,
 {
-    let eocdr_offset = crate::read::io::locator::eocdr(&mut reader).await?;
+    // Timings are only taken when needed, as `Instant` isn't available on all targets (eg. wasm32-unknown-unknown).
+    let start = options.diagnostics.then(Instant::now);
+    let eocdr_offset =
+        crate::read::io::locator::eocdr_with_strategy(&mut reader, options.search_strategy, options.yield_interval)
+            .await?;
+    let locate_duration = elapsed(start);
 
-    reader.seek(SeekFrom::Start(eocdr_offset)).await?;
+    reader.seek(SeekFrom::Start(eocdr_offset + SIGNATURE_LENGTH as u64)).await?;
     let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
-    let comment = crate::read::io::read_string(&mut reader, eocdr.file_comm_length.into()).await?;
+    let comment =
+        crate::read::io::read_string(&mut reader, eocdr.file_comm_length.into(), &mut Vec::new()).await?.to_owned();
+
+    let check = options.diagnostics || options.parse_mode == ParseMode::Strict;
+    let trailing = if check {
+        let eocdr_end = reader.stream_position().await?;
+        reader.seek(SeekFrom::End(0)).await? - eocdr_end
+    } else {
+        0
+    };
+
+    let start = options.diagnostics.then(Instant::now);
+    reader.seek(SeekFrom::Start(eocdr.cent_dir_offset.into())).await?;
+    let (entries, metas, skipped) = central_directory(&mut reader, &eocdr, options).await?;
+    let central_directory_duration = elapsed(start);
+
+    let mut file =
+        ZipFile { entries, metas, comment, zip64: false, report: None, max_read_size: options.max_read_size };
+
+    if check {
+        let cd_offset = eocdr.cent_dir_offset.into();
+        let cd_size = reader.stream_position().await? - cd_offset;
+        let cd_stored = eocdr.size_cent_dir.into();
+        let warnings = diagnostics::warnings(&file, skipped, cd_offset, cd_stored, cd_size, eocdr_offset, trailing);
+        options.parse_mode.check(&warnings)?;
+
+        file.report = options.diagnostics.then_some(OpenReport {
+            locate_duration,
+            central_directory_duration,
+            entries: file.entries.len(),
+            central_directory_offset: cd_offset,
+            central_directory_size: cd_size,
+            eocdr_offset,
+            warnings,
+        });
+    }
+
+    Ok(file)
+}
 
+/// Parses the central directory described by an end of central directory record, from a reader positioned at its start.
+///
+/// Alongside the entries, warnings are returned for any malformed records skipped (see
+/// [`ReaderOptions::skip_corrupt_entries()`]).
+pub(crate) async fn central_directory<R>(
+    reader: R,
+    eocdr: &EndOfCentralDirectoryHeader,
+    options: &ReaderOptions,
+) -> Result<(Vec<ZipEntry>, Vec<ZipEntryMeta>, Vec<OpenWarning>)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
     // Outdated feature so unlikely to ever make it into this crate.
     if eocdr.disk_num != eocdr.start_cent_dir_disk || eocdr.num_of_entries != eocdr.num_of_entries_disk {
         return Err(ZipError::FeatureNotSupported("Spanned/split files"));
     }
 
-    reader.seek(SeekFrom::Start(eocdr.cent_dir_offset.into())).await?;
-    let (entries, metas) = crate::read::cd(&mut reader, eocdr.num_of_entries.into()).await?;
+    #[cfg_attr(not(any(feature = "unicode", feature = "encoding")), allow(unused_mut))]
+    let (mut entries, mut metas, skipped) =
+        crate::read::cd(reader, eocdr.num_of_entries.into(), eocdr.cent_dir_offset.into(), options).await?;
+    #[cfg(feature = "encoding")]
+    decode_with_encoding(&mut entries, &metas, options.filename_encoding);
+    #[cfg(feature = "unicode")]
+    if options.normalize_filenames {
+        normalize_filenames(&mut entries)?;
+    }
+    compute_local_overheads(&entries, &mut metas, eocdr.cent_dir_offset.into());
+
+    Ok((entries, metas, skipped))
+}
 
-    Ok(ZipFile { entries, metas, comment, zip64: false })
+/// Returns the time elapsed since the provided instant, or zero if no instant was taken.
+fn elapsed(start: Option<Instant>) -> Duration {
+    start.map(|start| start.elapsed()).unwrap_or_default()
 }
 
-pub(crate) async fn cd<R>(mut reader: R, num_of_entries: u64) -> Result<(Vec<ZipEntry>, Vec<ZipEntryMeta>)>
+/// Parses the provided number of central directory records, yielding to the runtime as per the provided options.
+///
+/// Errors are given the index & offset (relative to the provided offset of the first record) of the failing record. If
+/// skipping corrupt entries, such records are instead returned as warnings, and parsing resumes from the next
+/// central directory record signature found.
+pub(crate) async fn cd<R>(
+    mut reader: R,
+    num_of_entries: u64,
+    cd_offset: u64,
+    options: &ReaderOptions,
+) -> Result<(Vec<ZipEntry>, Vec<ZipEntryMeta>, Vec<OpenWarning>)>
 where
-    R: AsyncRead + Unpin,
+    R: AsyncRead + AsyncSeek + Unpin,
 {
     let num_of_entries = num_of_entries.try_into().map_err(|_| ZipError::TargetZip64NotSupported)?;
     let mut entries = Vec::with_capacity(num_of_entries);
     let mut metas = Vec::with_capacity(num_of_entries);
+    let mut skipped = Vec::new();
+    let mut checkpoint = Checkpoint::new(options.yield_interval);
+    let mut scratch = Vec::new();
+
+    // The reader's own position is only needed to seek back to a malformed record when skipping it.
+    let start = if options.skip_corrupt_entries { reader.stream_position().await? } else { 0 };
+    let mut offset = cd_offset;
+
+    for index in 0..num_of_entries {
+        checkpoint.tick().await;
+        let error = match cd_record(&mut reader, &mut scratch).await {
+            Ok((entry, meta)) => {
+                offset += meta.overhead.central_directory_record;
+                entries.push(entry);
+                metas.push(meta);
+                continue;
+            }
+            Err(error) => error.with_context(ErrorContext::index(index).at(offset)),
+        };
+
+        if !options.skip_corrupt_entries {
+            return Err(error);
+        }
 
-    for _ in 0..num_of_entries {
-        let (entry, meta) = cd_record(&mut reader).await?;
+        skipped.push(OpenWarning::CorruptRecord { index, offset, reason: error.to_string() });
 
-        entries.push(entry);
-        metas.push(meta);
+        match resync(&mut reader, start + (offset - cd_offset) + 1).await? {
+            Some(position) => offset = cd_offset + (position - start),
+            None => break,
+        }
     }
 
-    Ok((entries, metas))
+    Ok((entries, metas, skipped))
 }
 
-pub(crate) async fn cd_record<R>(mut reader: R) -> Result<(ZipEntry, ZipEntryMeta)>
+/// Scans forward from the provided position for the next central directory record signature, leaving the reader
+/// positioned at its start.
+///
+/// Returns the position of the signature found, or [`None`] if the end of the reader was reached first.
+pub(crate) async fn resync<R>(mut reader: R, position: u64) -> Result<Option<u64>>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let mut window = Vec::new();
+    let mut buffer = [0; 4096];
+    let mut window_start = position;
+
+    reader.seek(SeekFrom::Start(position)).await?;
+
+    loop {
+        let read = reader.read(&mut buffer).await?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        window.extend_from_slice(&buffer[..read]);
+
+        if let Some(index) = window.windows(signature.len()).position(|candidate| candidate == signature) {
+            let found = window_start + index as u64;
+            reader.seek(SeekFrom::Start(found)).await?;
+            return Ok(Some(found));
+        }
+
+        // Keep enough of the window to match a signature which straddles the next read.
+        let consumed = window.len().saturating_sub(signature.len() - 1);
+        window.drain(..consumed);
+        window_start += consumed as u64;
+    }
+}
+
+/// Parses a single central directory record, reading its dynamic length fields via the provided scratch buffer.
+pub(crate) async fn cd_record<R>(mut reader: R, scratch: &mut Vec<u8>) -> Result<(ZipEntry, ZipEntryMeta)>
 where
     R: AsyncRead + Unpin,
 {
-    let header = CentralDirectoryRecord::from_reader(&mut reader).await?;
-    let filename = crate::read::io::read_string(&mut reader, header.file_name_length.into()).await?;
-    let compression = Compression::try_from(header.compression)?;
-    let extra_field = crate::read::io::read_bytes(&mut reader, header.extra_field_length.into()).await?;
-    let comment = crate::read::io::read_string(reader, header.file_comment_length.into()).await?;
+    let header = CentralDirectoryRecord::from_reader_with_signature(&mut reader).await?;
+    let lengths = [header.file_name_length, header.extra_field_length, header.file_comment_length].map(usize::from);
+    let [raw_filename, extra_field, raw_comment] = crate::read::io::read_fields(reader, lengths, scratch).await?;
+    let compression = resolve_compression(header.compression, header.flags, &extra_field)?;
+    let encryption = crate::spec::encryption::method(header.flags, header.compression, &extra_field);
+    #[cfg(feature = "aes")]
+    let aes = aes_extra_field(header.compression, &extra_field);
+    let (filename, filename_source) = decode_filename(raw_filename.clone(), &extra_field, header.flags);
+    let ntfs_timestamps = parse_ntfs_timestamps(&extra_field);
+    let extended_timestamps = parse_extended_timestamps(&extra_field);
+    #[cfg(feature = "encoding")]
+    let raw_comment_field = raw_comment.clone();
+    let (comment, _) = crate::spec::encoding::decode_header_string(raw_comment, header.flags.filename_unicode);
+    // Only MS-DOS & Unix hosts are supported, so the attributes of entries made by any other host are treated as Unix.
+    let attribute_compatibility =
+        AttributeCompatibility::try_from(header.v_made_by >> 8).unwrap_or(AttributeCompatibility::Unix);
     #[cfg(feature = "date")]
-    let last_modification_date = crate::spec::date::zip_date_to_chrono(header.mod_date, header.mod_time);
+    let last_modification_date = extended_timestamps
+        .as_ref()
+        .and_then(|timestamps| timestamps.last_modification_date)
+        .unwrap_or_else(|| crate::spec::date::zip_date_to_chrono(header.mod_date, header.mod_time));
 
     let entry = ZipEntry {
         filename,
         compression,
         compression_level: async_compression::Level::Default,
-        attribute_compatibility: AttributeCompatibility::Unix,
-        /// FIXME: Default to Unix for the moment
+        attribute_compatibility,
         crc32: header.crc,
         uncompressed_size: header.uncompressed_size,
         compressed_size: header.compressed_size,
@@ -87,16 +268,299 @@ where
         external_file_attribute: header.exter_attr,
         extra_field,
         comment,
+        filename_source,
+        unicode_path_extra_field: filename_source == FilenameSource::UnicodePathExtraField,
+        extended_timestamp: false,
+        ntfs_timestamps,
+        extended_timestamps,
+        zipcrypto_password: None,
+        encryption,
+        alignment: 0,
     };
 
-    let meta = ZipEntryMeta { general_purpose_flag: header.flags, file_offset: header.lh_offset as u64 };
+    let central_directory_record = SIGNATURE_LENGTH as u64
+        + CDH_LENGTH as u64
+        + header.file_name_length as u64
+        + header.extra_field_length as u64
+        + header.file_comment_length as u64;
+    let overhead = EntryOverhead { local: 0, central_directory_record };
+    let meta = ZipEntryMeta {
+        general_purpose_flag: header.flags,
+        version_needed: header.v_needed,
+        file_offset: header.lh_offset as u64,
+        overhead,
+        raw_filename,
+        #[cfg(feature = "encoding")]
+        raw_comment: raw_comment_field,
+        #[cfg(feature = "aes")]
+        aes,
+    };
 
     Ok((entry, meta))
 }
 
-pub(crate) fn compute_data_offset(entry: &ZipEntry, meta: &ZipEntryMeta) -> u64 {
+pub(crate) async fn lfh<R>(mut reader: R) -> Result<(ZipEntry, GeneralPurposeFlag)>
+where
+    R: AsyncRead + Unpin,
+{
+    let header = LocalFileHeader::from_reader(&mut reader).await?;
+    let lengths = [header.file_name_length, header.extra_field_length].map(usize::from);
+    let [raw_filename, extra_field] = crate::read::io::read_fields(reader, lengths, &mut Vec::new()).await?;
+    let compression = resolve_compression(header.compression, header.flags, &extra_field)?;
+    let encryption = crate::spec::encryption::method(header.flags, header.compression, &extra_field);
+    let (filename, filename_source) = decode_filename(raw_filename, &extra_field, header.flags);
+    let ntfs_timestamps = parse_ntfs_timestamps(&extra_field);
+    let extended_timestamps = parse_extended_timestamps(&extra_field);
+    #[cfg(feature = "date")]
+    let last_modification_date = extended_timestamps
+        .as_ref()
+        .and_then(|timestamps| timestamps.last_modification_date)
+        .unwrap_or_else(|| crate::spec::date::zip_date_to_chrono(header.mod_date, header.mod_time));
+
+    let entry = ZipEntry {
+        filename,
+        compression,
+        compression_level: async_compression::Level::Default,
+        attribute_compatibility: AttributeCompatibility::Unix,
+        crc32: header.crc,
+        uncompressed_size: header.uncompressed_size,
+        compressed_size: header.compressed_size,
+        #[cfg(feature = "date")]
+        last_modification_date,
+        internal_file_attribute: 0,
+        external_file_attribute: 0,
+        extra_field,
+        comment: String::new(),
+        filename_source,
+        unicode_path_extra_field: filename_source == FilenameSource::UnicodePathExtraField,
+        extended_timestamp: false,
+        ntfs_timestamps,
+        extended_timestamps,
+        zipcrypto_password: None,
+        encryption,
+        alignment: 0,
+    };
+
+    Ok((entry, header.flags))
+}
+
+/// Re-decodes the filenames & comments of all entries which lack the language encoding flag using the provided
+/// encoding (or the encoding detected from them). Filenames taken from a Unicode Path extra field are left as-is.
+#[cfg(feature = "encoding")]
+pub(crate) fn decode_with_encoding(
+    entries: &mut [ZipEntry],
+    metas: &[ZipEntryMeta],
+    encoding: crate::spec::encoding::FilenameEncoding,
+) {
+    let unflagged = || entries.iter().zip(metas).filter(|(_, meta)| !meta.general_purpose_flag.filename_unicode);
+    let filenames = unflagged()
+        .filter(|(entry, _)| entry.filename_source != FilenameSource::UnicodePathExtraField)
+        .map(|(_, meta)| meta.raw_filename.as_slice());
+    let comments = unflagged().map(|(_, meta)| meta.raw_comment.as_slice());
+
+    let Some(encoding) = crate::spec::encoding::resolve_encoding(encoding, filenames.chain(comments)) else {
+        return;
+    };
+
+    for (entry, meta) in entries.iter_mut().zip(metas) {
+        if meta.general_purpose_flag.filename_unicode {
+            continue;
+        }
+
+        if entry.filename_source != FilenameSource::UnicodePathExtraField {
+            entry.filename = encoding.decode_without_bom_handling(&meta.raw_filename).0.into_owned();
+            entry.filename_source = FilenameSource::Encoding(encoding);
+        }
+
+        entry.comment = encoding.decode_without_bom_handling(&meta.raw_comment).0.into_owned();
+    }
+}
+
+/// Computes the local overhead of each entry from the space between its local file header and the next entry's (or
+/// the start of the central directory), less its compressed size.
+/// Normalizes the filenames of all entries into NFC, returning an error if doing so has made two distinct filenames
+/// equal.
+#[cfg(feature = "unicode")]
+fn normalize_filenames(entries: &mut [ZipEntry]) -> Result<()> {
+    use unicode_normalization::UnicodeNormalization;
+
+    let mut originals = std::collections::HashMap::new();
+
+    for entry in entries.iter_mut() {
+        let normalized: String = entry.filename.nfc().collect();
+
+        match originals.get(&normalized) {
+            Some(original) if *original != entry.filename => {
+                return Err(ZipError::NormalizedFilenameCollision(normalized));
+            }
+            _ => originals.insert(normalized.clone(), std::mem::replace(&mut entry.filename, normalized)),
+        };
+    }
+
+    Ok(())
+}
+
+fn compute_local_overheads(entries: &[ZipEntry], metas: &mut [ZipEntryMeta], cd_offset: u64) {
+    let mut offsets: Vec<u64> = metas.iter().map(|meta| meta.file_offset).collect();
+    offsets.sort_unstable();
+
+    for (entry, meta) in entries.iter().zip(metas.iter_mut()) {
+        let next_index = offsets.partition_point(|offset| *offset <= meta.file_offset);
+        let next = offsets.get(next_index).copied().unwrap_or(cd_offset);
+        let span = next.saturating_sub(meta.file_offset);
+        meta.overhead.local = span.saturating_sub(entry.compressed_size().into());
+    }
+}
+
+/// Resolves an entry's compression method from the value stored within its header.
+///
+/// AES-encrypted entries store a placeholder value (99) within their headers, with the actual compression method stored
+/// within the WinZip AES extra field instead. Implode's options are stored within the general purpose flag.
+#[cfg_attr(not(all(feature = "aes", feature = "legacy")), allow(unused_variables))]
+fn resolve_compression(value: u16, flags: GeneralPurposeFlag, extra_field: &[u8]) -> Result<Compression> {
+    #[cfg(feature = "aes")]
+    let value = aes_extra_field(value, extra_field).map(|aes| aes.compression).unwrap_or(value);
+
+    match Compression::try_from(value)? {
+        #[cfg(feature = "legacy")]
+        Compression::Implode { .. } => Ok(Compression::Implode {
+            large_window: flags.compression_options & 0b01 != 0,
+            literal_tree: flags.compression_options & 0b10 != 0,
+        }),
+        compression => Ok(compression),
+    }
+}
+
+/// Parses an entry's WinZip AES extra field if its header's compression method denotes that it's AES-encrypted.
+#[cfg(feature = "aes")]
+fn aes_extra_field(value: u16, extra_field: &[u8]) -> Option<AesExtraField> {
+    match value {
+        AES_COMPRESSION_METHOD => crate::spec::extra_field::find_extra_field(extra_field, AES_HEADER_ID)
+            .and_then(crate::spec::extra_field::parse_aes),
+        _ => None,
+    }
+}
+
+/// Decodes an entry's filename, preferring an Info-ZIP Unicode Path extra field whose CRC32 matches the raw filename.
+fn decode_filename(raw_filename: Vec<u8>, extra_field: &[u8], flags: GeneralPurposeFlag) -> (String, FilenameSource) {
+    let unicode_path = crate::spec::extra_field::find_extra_field(extra_field, UNICODE_PATH_HEADER_ID)
+        .and_then(|data| crate::spec::extra_field::parse_unicode_path(data, &raw_filename));
+
+    match unicode_path {
+        Some(filename) => (filename, FilenameSource::UnicodePathExtraField),
+        None => crate::spec::encoding::decode_header_string(raw_filename, flags.filename_unicode),
+    }
+}
+
+/// Reads a data descriptor, returning the CRC32 value alongside the compressed & uncompressed sizes.
+///
+/// As the data descriptor's signature is optional, the first four bytes are treated as the CRC32 value if they don't
+/// match it. Should the CRC32 value computed for the entry's data itself match the signature, the following four bytes
+/// are used to tell the two forms apart instead. The sizes are eight bytes each if the entry's local file header
+/// contained a ZIP64 extra field.
+pub(crate) async fn data_descriptor<R>(mut reader: R, zip64: bool, computed_crc: u32) -> Result<(u32, u64, u64)>
+where
+    R: AsyncRead + Unpin,
+{
+    // Holds the low four bytes of the compressed size, if they were read whilst telling the two forms apart.
+    let (crc, compressed_low) = match reader.read_u32_le().await? {
+        DATA_DESCRIPTOR_SIGNATURE if computed_crc != DATA_DESCRIPTOR_SIGNATURE => (reader.read_u32_le().await?, None),
+        DATA_DESCRIPTOR_SIGNATURE => match reader.read_u32_le().await? {
+            DATA_DESCRIPTOR_SIGNATURE => (DATA_DESCRIPTOR_SIGNATURE, None),
+            compressed_low => (DATA_DESCRIPTOR_SIGNATURE, Some(compressed_low)),
+        },
+        crc => (crc, None),
+    };
+
+    let compressed_size = match (zip64, compressed_low) {
+        (true, Some(low)) => u64::from(low) | u64::from(reader.read_u32_le().await?) << 32,
+        (true, None) => reader.read_u64_le().await?,
+        (false, Some(low)) => low.into(),
+        (false, None) => reader.read_u32_le().await?.into(),
+    };
+    let uncompressed_size = match zip64 {
+        true => reader.read_u64_le().await?,
+        false => reader.read_u32_le().await?.into(),
+    };
+
+    Ok((crc, compressed_size, uncompressed_size))
+}
+
+/// Parses the high-resolution timestamps stored within an NTFS extra field, if one is present.
+fn parse_ntfs_timestamps(extra_field: &[u8]) -> Option<Timestamps> {
+    let data = crate::spec::extra_field::find_extra_field(extra_field, NTFS_HEADER_ID)?;
+    let [modified, accessed, created] = crate::spec::extra_field::parse_ntfs_timestamps(data)?;
+
+    Some(Timestamps {
+        last_modification_date: crate::spec::date::ntfs_to_chrono(modified),
+        last_access_date: crate::spec::date::ntfs_to_chrono(accessed),
+        creation_date: crate::spec::date::ntfs_to_chrono(created),
+    })
+}
+
+/// Parses the timestamps stored within an extended timestamp extra field, if one is present.
+fn parse_extended_timestamps(extra_field: &[u8]) -> Option<Timestamps> {
+    let data = crate::spec::extra_field::find_extra_field(extra_field, EXTENDED_TIMESTAMP_HEADER_ID)?;
+    let [modified, accessed, created] = crate::spec::extra_field::parse_extended_timestamp(data);
+
+    Some(Timestamps {
+        last_modification_date: modified.and_then(crate::spec::date::unix_to_chrono),
+        last_access_date: accessed.and_then(crate::spec::date::unix_to_chrono),
+        creation_date: created.and_then(crate::spec::date::unix_to_chrono),
+    })
+}
+
+/// Reads the local file header of an entry and returns the offset at which its data begins.
+///
+/// The filename & extra field lengths stored in the local file header may differ from those stored in the central
+/// directory, so the local file header must be read rather than computing the offset from the [`ZipEntry`] alone.
+pub(crate) async fn compute_data_offset<R>(reader: R, meta: &ZipEntryMeta) -> Result<u64>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    Ok(local_file_header(reader, meta).await?.1)
+}
+
+/// Reads the local file header of an entry, returning it alongside the offset at which the entry's data begins.
+pub(crate) async fn local_file_header<R>(mut reader: R, meta: &ZipEntryMeta) -> Result<(LocalFileHeader, u64)>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    let context = || ErrorContext::filename(String::from_utf8_lossy(&meta.raw_filename)).at(meta.file_offset);
+
+    reader.seek(SeekFrom::Start(meta.file_offset)).await?;
+
+    let header = LocalFileHeader::from_reader_with_signature(&mut reader)
+        .await
+        .map_err(|error| error.with_context(context()))?;
     let header_length = SIGNATURE_LENGTH + LFH_LENGTH;
-    let trailing_length = entry.comment().as_bytes().len() + entry.extra_field().len();
+    let trailing_length = header.file_name_length as usize + header.extra_field_length as usize;
+    let data_offset = meta.file_offset + (header_length as u64) + (trailing_length as u64);
 
-    meta.file_offset + (header_length as u64) + (trailing_length as u64)
+    Ok((header, data_offset))
+}
+
+/// Returns the method & parameters with which an entry's data is encrypted.
+///
+/// For traditional PKWARE (ZipCrypto) encryption, the last byte of the decrypted encryption header must match the high
+/// byte of the CRC32 value. When a data descriptor is used, the CRC32 value may not have been known when the header was
+/// written, so the high byte of the last modification time is used instead.
+///
+/// PKWARE strong encryption isn't supported, and so is rejected.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#616
+pub(crate) fn encryption(header: &LocalFileHeader, meta: &ZipEntryMeta) -> Result<Encryption> {
+    if meta.general_purpose_flag.strong_encryption {
+        return Err(ZipError::FeatureNotSupported("Strong encryption"));
+    }
+
+    #[cfg(feature = "aes")]
+    if let Some(aes) = meta.aes {
+        return Ok(Encryption::Aes(aes));
+    }
+
+    match header.flags.data_descriptor {
+        true => Ok(Encryption::ZipCrypto((header.mod_time >> 8) as u8)),
+        false => Ok(Encryption::ZipCrypto((header.crc >> 24) as u8)),
+    }
 }
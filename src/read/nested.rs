@@ -0,0 +1,84 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports reading ZIP files nested within the entries of another (eg. JARs within a wheel).
+//!
+//! An inner ZIP file stored without compression or encryption is read in place, as its entry reader supports seeking
+//! within the outer source. Otherwise, the entry's data is decompressed (and verified) into memory before being read,
+//! subject to any read limits set on the entry reader.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::nested::NestedZipFileReader;
+//! # use async_zip::read::seek::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use tokio::fs::File;
+//! #
+//! # async fn run() -> Result<()> {
+//! let mut reader = ZipFileReader::new(File::open("./foo.zip").await?).await?;
+//! let entry = reader.file().entries()[0].clone();
+//! let nested = NestedZipFileReader::new(reader.entry(0).await?, &entry).await?;
+//!
+//! for entry in nested.file().entries() {
+//!     println!("{}", entry.filename());
+//! }
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+use crate::file::ZipFile;
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::io::entry::ZipEntryReader;
+use crate::read::{mem, seek};
+
+use tokio::io::{AsyncRead, AsyncSeek};
+
+/// A ZIP reader over a ZIP file nested within an entry of another.
+pub enum NestedZipFileReader<'a, R> {
+    /// The inner ZIP file is read in place via its (seekable) entry reader.
+    Seek(Box<seek::ZipFileReader<ZipEntryReader<'a, R>>>),
+    /// The inner ZIP file was compressed or encrypted, and so has been buffered into memory.
+    Mem(mem::ZipFileReader),
+}
+
+impl<'a, R> NestedZipFileReader<'a, R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Constructs a new ZIP reader over the inner ZIP file held by the provided entry.
+    pub async fn new(reader: ZipEntryReader<'a, R>, entry: &ZipEntry) -> Result<NestedZipFileReader<'a, R>> {
+        Self::new_with_options(reader, entry, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader over the inner ZIP file held by the provided entry, using the provided options.
+    pub async fn new_with_options(
+        mut reader: ZipEntryReader<'a, R>,
+        entry: &ZipEntry,
+        options: ReaderOptions,
+    ) -> Result<NestedZipFileReader<'a, R>> {
+        if reader.is_seekable() {
+            let reader = seek::ZipFileReader::new_with_options(reader, options).await?;
+            return Ok(NestedZipFileReader::Seek(Box::new(reader)));
+        }
+
+        let mut data = Vec::new();
+        reader.read_to_end_checked(&mut data, entry).await?;
+
+        Ok(NestedZipFileReader::Mem(mem::ZipFileReader::new_with_options(data, options).await?))
+    }
+
+    /// Returns the inner ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        match self {
+            NestedZipFileReader::Seek(reader) => reader.file(),
+            NestedZipFileReader::Mem(reader) => reader.file(),
+        }
+    }
+
+    /// Returns whether or not the inner ZIP file was buffered into memory.
+    pub fn is_buffered(&self) -> bool {
+        matches!(self, NestedZipFileReader::Mem(_))
+    }
+}
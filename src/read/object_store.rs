@@ -0,0 +1,76 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A remote source for ZIP files hosted within an object store (eg. S3, GCS, or Azure Blob Storage).
+//!
+//! [`ObjectStoreSource`] implements [`RangeSource`] over any [`ObjectStore`], so that the [`remote`] reader only
+//! fetches the tail of the object (including the central directory where possible) upon construction, and the byte
+//! range of each entry once a reader for it is requested.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::object_store::ObjectStoreSource;
+//! # use async_zip::read::remote::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use object_store::{path::Path, ObjectStore};
+//! # use std::sync::Arc;
+//! #
+//! # async fn run(store: Arc<dyn ObjectStore>) -> Result<()> {
+//! let source = ObjectStoreSource::new(store, Path::from("archives/foo.zip"));
+//! let reader = ZipFileReader::new(source).await?;
+//!
+//! let mut data = String::new();
+//! let entry = &reader.file().entries()[0];
+//! reader.entry(0).await?.read_to_string_checked(&mut data, entry).await?;
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! [`remote`]: crate::read::remote
+
+use crate::read::remote::RangeSource;
+
+use std::ops::Range;
+
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+/// A remote source over a single object within an object store.
+#[derive(Debug, Clone)]
+pub struct ObjectStoreSource<S> {
+    store: S,
+    location: Path,
+}
+
+impl<S> ObjectStoreSource<S>
+where
+    S: ObjectStore,
+{
+    /// Constructs a new source over the object at the provided location within the provided store.
+    pub fn new(store: S, location: Path) -> Self {
+        Self { store, location }
+    }
+
+    /// Returns a reference to the object store provided during construction.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Returns the location of the object within the store.
+    pub fn location(&self) -> &Path {
+        &self.location
+    }
+}
+
+impl<S> RangeSource for ObjectStoreSource<S>
+where
+    S: ObjectStore,
+{
+    async fn length(&self) -> std::io::Result<u64> {
+        Ok(self.store.head(&self.location).await?.size)
+    }
+
+    async fn read_range(&self, range: Range<u64>) -> std::io::Result<Vec<u8>> {
+        Ok(self.store.get_range(&self.location, range).await?.to_vec())
+    }
+}
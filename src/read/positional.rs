@@ -0,0 +1,278 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A concurrent ZIP reader which acts over a source supporting positional reads.
+//!
+//! Concurrency is achieved as a result of:
+//! - Wrapping the provided source within an [`Arc`] to allow shared ownership.
+//! - Reading from the source at absolute offsets (via [`AsyncReadAt`]) rather than seeking it, so that each entry
+//!   reader tracks its own position within a [`PositionalReader`].
+//!
+//! ### Usage
+//! Unlike the [`seek`] module, no mutable reference to the source is held, and unlike the [`fs`] module, the source
+//! isn't reopened for each entry. As such, any number of [`ZipEntryReader`]s can be open concurrently over a single
+//! shared handle (eg. an in-memory buffer, a memory map, or a file).
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::positional::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use tokio::io::AsyncReadExt;
+//! #
+//! # async fn run() -> Result<()> {
+//! let data: Vec<u8> = Vec::new();
+//! let reader = ZipFileReader::new(data).await?;
+//!
+//! let (mut foo, mut bar) = (reader.entry(0).await?, reader.entry(1).await?);
+//! let (mut foo_data, mut bar_data) = (Vec::new(), Vec::new());
+//!
+//! let (foo_read, bar_read) = tokio::join!(foo.read_to_end(&mut foo_data), bar.read_to_end(&mut bar_data));
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! [`fs`]: crate::read::fs
+
+#[cfg(doc)]
+use crate::read::seek;
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::io::entry::ZipEntryReader;
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, BufReader, ReadBuf, SeekFrom};
+
+/// A source which supports reading from absolute offsets, without holding a position of its own.
+///
+/// As reads don't require a mutable reference, a single source can be shared between any number of readers.
+pub trait AsyncReadAt {
+    /// Attempts to read bytes from the provided offset into the buffer's unfilled part, as [`AsyncRead::poll_read()`]
+    /// would.
+    ///
+    /// Reading no bytes signals that the offset is at or beyond the end of the source (unless the buffer is full). As
+    /// with [`AsyncRead`], taking a [`ReadBuf`] allows sources which copy from memory to do so without first initialising
+    /// the buffer.
+    fn poll_read_at(&self, cx: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>>;
+
+    /// Attempts to return the total length of the source in bytes.
+    fn poll_length(&self, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>>;
+}
+
+impl AsyncReadAt for [u8] {
+    fn poll_read_at(&self, _: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let start = usize::try_from(offset).unwrap_or(usize::MAX).min(self.len());
+        let length = buf.remaining().min(self.len() - start);
+
+        buf.put_slice(&self[start..start + length]);
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_length(&self, _: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        Poll::Ready(Ok(self.len() as u64))
+    }
+}
+
+impl AsyncReadAt for Vec<u8> {
+    fn poll_read_at(&self, cx: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        self.as_slice().poll_read_at(cx, offset, buf)
+    }
+
+    fn poll_length(&self, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        self.as_slice().poll_length(cx)
+    }
+}
+
+impl<T: AsyncReadAt + ?Sized> AsyncReadAt for &T {
+    fn poll_read_at(&self, cx: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        (**self).poll_read_at(cx, offset, buf)
+    }
+
+    fn poll_length(&self, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        (**self).poll_length(cx)
+    }
+}
+
+impl<T: AsyncReadAt + ?Sized> AsyncReadAt for Arc<T> {
+    fn poll_read_at(&self, cx: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        (**self).poll_read_at(cx, offset, buf)
+    }
+
+    fn poll_length(&self, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        (**self).poll_length(cx)
+    }
+}
+
+/// Reads are issued directly against the file via `pread()` and so block the current task until complete. Reads of
+/// local files are typically served from the page cache, but for slower storage, prefer the [`fs`] module.
+///
+/// [`fs`]: crate::read::fs
+#[cfg(all(feature = "fs", unix))]
+impl AsyncReadAt for std::fs::File {
+    fn poll_read_at(&self, _: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let read = std::os::unix::fs::FileExt::read_at(self, buf.initialize_unfilled(), offset)?;
+
+        buf.advance(read);
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_length(&self, _: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        Poll::Ready(self.metadata().map(|metadata| metadata.len()))
+    }
+}
+
+#[cfg(feature = "mmap")]
+impl AsyncReadAt for memmap2::Mmap {
+    fn poll_read_at(&self, cx: &mut Context<'_>, offset: u64, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        (**self).poll_read_at(cx, offset, buf)
+    }
+
+    fn poll_length(&self, cx: &mut Context<'_>) -> Poll<std::io::Result<u64>> {
+        (**self).poll_length(cx)
+    }
+}
+
+/// A reader over a shared positional source, which tracks its own position within it.
+pub struct PositionalReader<S: ?Sized> {
+    source: Arc<S>,
+    position: u64,
+    pending: Option<SeekFrom>,
+}
+
+impl<S: ?Sized> PositionalReader<S> {
+    /// Constructs a new reader over the shared source, positioned at its start.
+    pub fn new(source: Arc<S>) -> Self {
+        Self { source, position: 0, pending: None }
+    }
+}
+
+impl<S: AsyncReadAt + ?Sized> AsyncRead for PositionalReader<S> {
+    fn poll_read(self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<tokio::io::Result<()>> {
+        let this = self.get_mut();
+        let prev_len = b.filled().len();
+
+        ready!(this.source.poll_read_at(c, this.position, b))?;
+        this.position += (b.filled().len() - prev_len) as u64;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S: AsyncReadAt + ?Sized> AsyncSeek for PositionalReader<S> {
+    fn start_seek(self: Pin<&mut Self>, position: SeekFrom) -> tokio::io::Result<()> {
+        self.get_mut().pending = Some(position);
+        Ok(())
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<tokio::io::Result<u64>> {
+        let this = self.get_mut();
+
+        let target = match this.pending {
+            None => return Poll::Ready(Ok(this.position)),
+            Some(SeekFrom::Start(offset)) => Some(offset),
+            Some(SeekFrom::Current(offset)) => this.position.checked_add_signed(offset),
+            Some(SeekFrom::End(offset)) => ready!(this.source.poll_length(c))?.checked_add_signed(offset),
+        };
+
+        this.pending = None;
+        this.position = target.ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid seek position"))?;
+
+        Poll::Ready(Ok(this.position))
+    }
+}
+
+/// A concurrent ZIP reader which acts over a source supporting positional reads.
+pub struct ZipFileReader<S: ?Sized> {
+    source: Arc<S>,
+    file: Arc<ZipFile>,
+}
+
+impl<S: ?Sized> Clone for ZipFileReader<S> {
+    fn clone(&self) -> Self {
+        Self { source: self.source.clone(), file: self.file.clone() }
+    }
+}
+
+impl<S> ZipFileReader<S>
+where
+    S: AsyncReadAt + 'static,
+{
+    /// Constructs a new ZIP reader from a positional source.
+    pub async fn new(source: S) -> Result<ZipFileReader<S>> {
+        Self::from_arc(Arc::new(source), ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a positional source, using the provided options.
+    pub async fn new_with_options(source: S, options: ReaderOptions) -> Result<ZipFileReader<S>> {
+        Self::from_arc(Arc::new(source), options).await
+    }
+}
+
+impl<S> ZipFileReader<S>
+where
+    S: AsyncReadAt + ?Sized + 'static,
+{
+    /// Constructs a new ZIP reader from a positional source which is already shared, using the provided options.
+    pub async fn from_arc(source: Arc<S>, options: ReaderOptions) -> Result<ZipFileReader<S>> {
+        let reader = BufReader::new(PositionalReader::new(source.clone()));
+        let file = crate::read::file(reader, &options).await?;
+
+        Ok(ZipFileReader { source, file: Arc::new(file) })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Returns the shared source provided to the reader during construction.
+    pub fn source(&self) -> &Arc<S> {
+        &self.source
+    }
+
+    /// Returns a new entry reader if the provided index is valid.
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'static, PositionalReader<S>>> {
+        let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let mut reader = PositionalReader::new(self.source.clone());
+        let seek_to = crate::read::compute_data_offset(&mut reader, meta).await?;
+
+        reader.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_with_owned(reader, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file.max_read_size))
+    }
+
+    /// Returns a new reader over the raw (ie. still compressed) data of an entry if the provided index is valid.
+    ///
+    /// Paired with [`ZipFileWriter::copy_entry_raw()`], this allows entries to be copied between archives without being
+    /// decompressed and recompressed. Encrypted entries aren't supported.
+    ///
+    /// [`ZipFileWriter::copy_entry_raw()`]: crate::write::ZipFileWriter::copy_entry_raw
+    pub async fn entry_raw(&self, index: usize) -> Result<ZipEntryReader<'static, PositionalReader<S>>> {
+        let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if meta.general_purpose_flag.encrypted {
+            return Err(ZipError::FeatureNotSupported("Raw reading of encrypted entries"));
+        }
+
+        let mut reader = PositionalReader::new(self.source.clone());
+        let seek_to = crate::read::compute_data_offset(&mut reader, meta).await?;
+
+        reader.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_raw_with_owned(reader, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file.max_read_size))
+    }
+}
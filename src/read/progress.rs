@@ -0,0 +1,115 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports reporting the progress of reading & extracting entries.
+//!
+//! Progress is reported via callbacks, which are invoked each time data is read. As such, they should be cheap (eg.
+//! updating a progress bar or sending into a channel) so as not to hold up reading.
+//!
+//! ### Example
+//! ```no_run
+//! # #[cfg(feature = "fs")]
+//! # {
+//! # use async_zip::read::extract::ExtractOptions;
+//! # use async_zip::read::mem::ZipFileReader;
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run() -> Result<()> {
+//! let data: Vec<u8> = Vec::new();
+//! let reader = ZipFileReader::new(data).await?;
+//!
+//! let options = ExtractOptions::new().progress(|progress| {
+//!     println!("{}/{:?} entries completed", progress.entries_completed(), progress.entries_total());
+//! });
+//!
+//! reader.extract_with_options("./output", options).await?;
+//! #   Ok(())
+//! # }
+//! # }
+//! ```
+
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// A snapshot of the progress made reading an entry's data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryProgress {
+    pub(crate) read: u64,
+    pub(crate) total: u64,
+}
+
+impl EntryProgress {
+    /// Returns the number of bytes read so far (ie. decompressed bytes, unless reading an entry's raw data).
+    ///
+    /// Seeking an entry reader isn't reflected, as this only counts the bytes which have been read.
+    pub fn read(&self) -> u64 {
+        self.read
+    }
+
+    /// Returns the number of bytes expected to be read in total.
+    ///
+    /// This is the entry's uncompressed size (or compressed size, when reading its raw data), and so is zero for entries
+    /// whose sizes aren't known upfront (ie. those using a data descriptor, when read via the [`stream`] reader).
+    ///
+    /// [`stream`]: crate::read::stream
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// A snapshot of the progress made extracting a ZIP file's entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExtractProgress {
+    pub(crate) entry: EntryProgress,
+    pub(crate) entries_completed: usize,
+    pub(crate) entries_total: Option<usize>,
+}
+
+impl ExtractProgress {
+    /// Returns the progress made reading the entry currently being extracted (or the entry last extracted).
+    pub fn entry(&self) -> EntryProgress {
+        self.entry
+    }
+
+    /// Returns the number of entries which have been extracted so far.
+    pub fn entries_completed(&self) -> usize {
+        self.entries_completed
+    }
+
+    /// Returns the number of entries to be extracted in total.
+    ///
+    /// This isn't known upfront when extracting via the [`stream`] reader, in which case [`None`] is returned.
+    ///
+    /// [`stream`]: crate::read::stream
+    pub fn entries_total(&self) -> Option<usize> {
+        self.entries_total
+    }
+}
+
+/// A shared callback to which progress snapshots are reported.
+pub(crate) struct ProgressCallback<T>(Arc<dyn Fn(T) + Send + Sync>);
+
+impl<T> ProgressCallback<T> {
+    pub(crate) fn new<F>(callback: F) -> Self
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    pub(crate) fn report(&self, progress: T) {
+        (self.0)(progress)
+    }
+}
+
+impl<T> Clone for ProgressCallback<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Debug for ProgressCallback<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over a remote source supporting ranged reads (eg. HTTP range requests or S3 `GetObject`).
+//!
+//! Opening a ZIP file takes at most two ranged reads: one for the trailing bytes of the file (within which the end of
+//! central directory record must lie), and one for the central directory if it wasn't already covered by the first.
+//! Each entry's byte range is then only fetched once a reader for it is requested, allowing entries to be listed and
+//! selectively extracted from large ZIP files without downloading them in full.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::remote::{RangeSource, ZipFileReader};
+//! # use async_zip::error::Result;
+//! # use std::future::Future;
+//! # use std::ops::Range;
+//! # use tokio::io::AsyncReadExt;
+//! #
+//! struct HttpSource;
+//!
+//! impl RangeSource for HttpSource {
+//!     fn length(&self) -> impl Future<Output = std::io::Result<u64>> + Send {
+//!         async { todo!("issue a HEAD request and return its Content-Length") }
+//!     }
+//!
+//!     fn read_range(&self, range: Range<u64>) -> impl Future<Output = std::io::Result<Vec<u8>>> + Send {
+//!         async move { todo!("issue a GET request with a Range header covering {:?}", range) }
+//!     }
+//! }
+//!
+//! # async fn run() -> Result<()> {
+//! let reader = ZipFileReader::new(HttpSource).await?;
+//! let index = reader.file().entries().iter().position(|entry| entry.filename() == "foo.txt").unwrap();
+//!
+//! let mut data = Vec::new();
+//! reader.entry(index).await?.read_to_end(&mut data).await?;
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::error::{Result, ZipError};
+use crate::file::ZipFile;
+use crate::read::diagnostics::{ParseMode, ReaderOptions};
+use crate::read::io::entry::ZipEntryReader;
+use crate::spec::consts::{EOCDR_LENGTH, SIGNATURE_LENGTH};
+use crate::spec::header::EndOfCentralDirectoryHeader;
+
+use std::future::Future;
+use std::io::Cursor;
+use std::ops::Range;
+
+use tokio::io::{AsyncSeekExt, SeekFrom};
+
+/// The maximum distance of the start of the EOCDR from the end of a ZIP file (ie. with a maximum length comment).
+const TAIL_LENGTH: u64 = (SIGNATURE_LENGTH + EOCDR_LENGTH) as u64 + u16::MAX as u64;
+
+/// A source which supports reading arbitrary byte ranges.
+pub trait RangeSource {
+    /// Returns the total length of the source in bytes.
+    fn length(&self) -> impl Future<Output = std::io::Result<u64>> + Send;
+
+    /// Returns the bytes within the provided range of the source.
+    ///
+    /// The range is always within the length of the source, and exactly its length in bytes must be returned.
+    fn read_range(&self, range: Range<u64>) -> impl Future<Output = std::io::Result<Vec<u8>>> + Send;
+}
+
+/// A ZIP reader which acts over a remote source supporting ranged reads.
+pub struct ZipFileReader<S> {
+    source: S,
+    file: ZipFile,
+}
+
+impl<S> ZipFileReader<S>
+where
+    S: RangeSource,
+{
+    /// Constructs a new ZIP reader from a remote source.
+    pub async fn new(source: S) -> Result<ZipFileReader<S>> {
+        Self::new_with_options(source, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a remote source, using the provided options.
+    ///
+    /// Diagnostics aren't collected by this reader, so [`ReaderOptions::diagnostics()`] has no effect. The ZIP file is
+    /// still checked if [`ParseMode::Strict`] is set, however.
+    pub async fn new_with_options(source: S, options: ReaderOptions) -> Result<ZipFileReader<S>> {
+        let length = source.length().await?;
+        let tail_start = length.saturating_sub(TAIL_LENGTH);
+        let tail = read_exact_range(&source, tail_start..length).await?;
+
+        let eocdr_start = crate::spec::locator::locate_eocdr_with_strategy(&tail, options.search_strategy)? as usize;
+        let mut cursor = Cursor::new(&tail[eocdr_start..]);
+        cursor.seek(SeekFrom::Start(SIGNATURE_LENGTH as u64)).await?;
+        let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut cursor).await?;
+        let comment =
+            crate::read::io::read_string(&mut cursor, eocdr.file_comm_length.into(), &mut Vec::new()).await?.to_owned();
+
+        let cd_start = u64::from(eocdr.cent_dir_offset);
+        let cd_end = cd_start + u64::from(eocdr.size_cent_dir);
+
+        if cd_end > length {
+            return Err(ZipError::UpstreamReadError(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        // Small ZIP files (or those with a long comment) will often have their central directory within the tail.
+        let cd_data = match cd_start.checked_sub(tail_start).and_then(|start| tail.get(start as usize..)) {
+            Some(data) => data.to_vec(),
+            None => read_exact_range(&source, cd_start..cd_end).await?,
+        };
+
+        let eocdr_offset = tail_start + eocdr_start as u64;
+        let trailing = length - (eocdr_offset + cursor.position());
+        let (entries, metas, skipped) = crate::read::central_directory(Cursor::new(cd_data), &eocdr, &options).await?;
+        let file =
+            ZipFile { entries, metas, comment, zip64: false, report: None, max_read_size: options.max_read_size };
+
+        if options.parse_mode == ParseMode::Strict {
+            let cd_size = file.metas.iter().map(|meta| meta.overhead.central_directory_record).sum();
+            let warnings = crate::read::diagnostics::warnings(
+                &file,
+                skipped,
+                cd_start,
+                eocdr.size_cent_dir.into(),
+                cd_size,
+                eocdr_offset,
+                trailing,
+            );
+            options.parse_mode.check(&warnings)?;
+        }
+
+        Ok(ZipFileReader { source, file })
+    }
+
+    /// Returns this ZIP file's information.
+    pub fn file(&self) -> &ZipFile {
+        &self.file
+    }
+
+    /// Returns a reference to the remote source provided during construction.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Fetches the byte range of an entry and returns a new reader over it if the provided index is valid.
+    ///
+    /// The range fetched spans from the start of the entry's local file header to the end of its data (or data
+    /// descriptor), as per [`ZipFile::group_entries()`]. Several entries can be fetched in a single ranged read by
+    /// grouping them via that method and reading each group's range from the source directly.
+    pub async fn entry(&self, index: usize) -> Result<ZipEntryReader<'static, Cursor<Vec<u8>>>> {
+        let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let end = meta.file_offset + meta.overhead.local + u64::from(entry.compressed_size());
+        let mut cursor = Cursor::new(read_exact_range(&self.source, meta.file_offset..end).await?);
+
+        let meta = crate::entry::ZipEntryMeta { file_offset: 0, ..meta.clone() };
+        let seek_to = crate::read::compute_data_offset(&mut cursor, &meta).await?;
+
+        cursor.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_with_owned(cursor, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file.max_read_size))
+    }
+}
+
+/// Reads a range from the source, returning an error if fewer bytes than requested were returned.
+async fn read_exact_range<S: RangeSource>(source: &S, range: Range<u64>) -> Result<Vec<u8>> {
+    let expected = range.end - range.start;
+    let data = source.read_range(range).await?;
+
+    if (data.len() as u64) < expected {
+        return Err(ZipError::UpstreamReadError(std::io::ErrorKind::UnexpectedEof.into()));
+    }
+
+    Ok(data)
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports rewriting ZIP files into a new writer, dropping, renaming, or re-commenting entries.
+//!
+//! Retained entries are copied as-is via their raw (ie. still compressed) data, so rewriting an archive never
+//! decompresses or recompresses any entry. As a result, encrypted entries can't be retained (though they can be
+//! dropped).
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::mem::ZipFileReader;
+//! # use async_zip::read::rewrite::RewriteAction;
+//! # use async_zip::write::ZipFileWriter;
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run() -> Result<()> {
+//! let data: Vec<u8> = Vec::new();
+//! let reader = ZipFileReader::new(data).await?;
+//! let mut writer = ZipFileWriter::new(Vec::new());
+//!
+//! reader
+//!     .rewrite(&mut writer, |entry| match entry.filename() {
+//!         "secret.txt" => RewriteAction::Drop,
+//!         "old.txt" => RewriteAction::Rename(String::from("new.txt")),
+//!         _ => RewriteAction::Keep,
+//!     })
+//!     .await?;
+//!
+//! writer.close().await?;
+//! #   Ok(())
+//! # }
+//! ```
+
+use crate::entry::ZipEntry;
+
+/// The action taken for an entry of a ZIP file which is being rewritten.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RewriteAction {
+    /// Copy the entry unchanged.
+    Keep,
+    /// Omit the entry from the rewritten ZIP file.
+    Drop,
+    /// Copy the entry under the provided filename.
+    Rename(String),
+    /// Copy the entry with the provided comment.
+    Comment(String),
+}
+
+impl RewriteAction {
+    /// Applies this action to an entry, returning the entry to be written (if any).
+    pub(crate) fn apply(self, entry: &ZipEntry) -> Option<ZipEntry> {
+        let mut entry = entry.clone();
+
+        match self {
+            RewriteAction::Keep => {}
+            RewriteAction::Drop => return None,
+            RewriteAction::Rename(filename) => entry.filename = filename,
+            RewriteAction::Comment(comment) => entry.comment = comment,
+        };
+
+        Some(entry)
+    }
+}
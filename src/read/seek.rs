@@ -20,11 +20,20 @@
 //! # }
 //! ```
 
+use crate::entry::ZipEntry;
 use crate::error::{Result, ZipError};
 use crate::file::ZipFile;
+use crate::read::diagnostics::ReaderOptions;
+#[cfg(feature = "fs")]
+use crate::read::extract::{ExtractOptions, Extractor};
 use crate::read::io::entry::ZipEntryReader;
+use crate::read::rewrite::RewriteAction;
+use crate::write::ZipFileWriter;
 
-use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, SeekFrom};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncSeekExt, AsyncWrite, BufReader, SeekFrom};
 
 /// A ZIP reader which acts over a seekable source.
 pub struct ZipFileReader<R> {
@@ -37,8 +46,13 @@ where
     R: AsyncRead + AsyncSeek + Unpin,
 {
     /// Constructs a new ZIP reader from a seekable source.
-    pub async fn new(mut reader: R) -> Result<ZipFileReader<R>> {
-        let file = crate::read::file(&mut reader).await?;
+    pub async fn new(reader: R) -> Result<ZipFileReader<R>> {
+        Self::new_with_options(reader, ReaderOptions::default()).await
+    }
+
+    /// Constructs a new ZIP reader from a seekable source, using the provided options.
+    pub async fn new_with_options(mut reader: R, options: ReaderOptions) -> Result<ZipFileReader<R>> {
+        let file = crate::read::file(BufReader::new(&mut reader), &options).await?;
         Ok(ZipFileReader { reader, file })
     }
 
@@ -47,13 +61,178 @@ where
         &self.file
     }
 
+    /// Reads the local file header of the entry at the provided index, returning the offset at which its data begins.
+    ///
+    /// See [`ZipFile::data_offset()`] for more information.
+    pub async fn data_offset(&mut self, index: usize) -> Result<u64> {
+        self.file.data_offset(index, &mut self.reader).await
+    }
+
     /// Returns a new entry reader if the provided index is valid.
     pub async fn entry(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
         let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
         let meta = self.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
-        let seek_to = crate::read::compute_data_offset(entry, meta);
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let seek_to = crate::read::compute_data_offset(&mut self.reader, meta).await?;
+
+        self.reader.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_with_borrow(&mut self.reader, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file.max_read_size))
+    }
+
+    /// Returns a new entry reader for the first entry with the provided filename.
+    ///
+    /// Filenames are matched as per [`ZipFile::entry_index()`]. If no entry matches, [`ZipError::EntryNotFound`] is
+    /// returned.
+    pub async fn entry_by_name(&mut self, filename: &str) -> Result<ZipEntryReader<'_, R>> {
+        self.entry(self.file.named_index(filename)?).await
+    }
+
+    /// Returns the first entry with the provided filename alongside a new reader over it.
+    ///
+    /// See [`ZipFileReader::entry_by_name()`] for more information.
+    pub async fn entry_and_reader_by_name(&mut self, filename: &str) -> Result<(&ZipEntry, ZipEntryReader<'_, R>)> {
+        let index = self.file.named_index(filename)?;
+        let entry = &self.file.entries[index];
+        let meta = &self.file.metas[index];
+
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let seek_to = crate::read::compute_data_offset(&mut self.reader, meta).await?;
+
+        self.reader.seek(SeekFrom::Start(seek_to)).await?;
+        let reader = ZipEntryReader::new_with_borrow(&mut self.reader, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file.max_read_size);
+        Ok((entry, reader))
+    }
+
+    /// Returns a new reader over the raw (ie. still compressed) data of an entry if the provided index is valid.
+    ///
+    /// Paired with [`ZipFileWriter::copy_entry_raw()`], this allows entries to be copied between archives without being
+    /// decompressed and recompressed. Encrypted entries aren't supported.
+    ///
+    /// [`ZipFileWriter::copy_entry_raw()`]: crate::write::ZipFileWriter::copy_entry_raw
+    pub async fn entry_raw(&mut self, index: usize) -> Result<ZipEntryReader<'_, R>> {
+        let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if meta.general_purpose_flag.encrypted {
+            return Err(ZipError::FeatureNotSupported("Raw reading of encrypted entries"));
+        }
+
+        let seek_to = crate::read::compute_data_offset(&mut self.reader, meta).await?;
+
+        self.reader.seek(SeekFrom::Start(seek_to)).await?;
+        Ok(ZipEntryReader::new_raw_with_borrow(&mut self.reader, entry)
+            .with_data_offset(seek_to)
+            .with_read_limit(self.file.max_read_size))
+    }
+
+    /// Returns a new entry reader which decrypts the entry using the provided password if the provided index is valid.
+    ///
+    /// Traditional PKWARE (ZipCrypto) encryption is supported, as is WinZip AES encryption with the `aes` feature.
+    /// Entries which aren't encrypted are read as if via
+    /// [`ZipFileReader::entry()`], ignoring the password.
+    pub async fn entry_with_password(&mut self, index: usize, password: &[u8]) -> Result<ZipEntryReader<'_, R>> {
+        let entry = self.file.entries.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+        let meta = self.file.metas.get(index).ok_or(ZipError::EntryIndexOutOfBounds)?;
+
+        if !meta.general_purpose_flag.encrypted {
+            return self.entry(index).await;
+        }
+
+        let (header, seek_to) = crate::read::local_file_header(&mut self.reader, meta).await?;
+        let encryption = crate::read::encryption(&header, meta)?;
 
         self.reader.seek(SeekFrom::Start(seek_to)).await?;
-        Ok(ZipEntryReader::new_with_borrow(&mut self.reader, entry.compression(), entry.uncompressed_size().into()))
+        let reader = ZipEntryReader::new_with_borrow_password(&mut self.reader, entry, password, encryption).await?;
+        Ok(reader.with_read_limit(self.file.max_read_size))
+    }
+
+    /// Rewrites this ZIP file's entries into the provided writer, taking the action decided for each entry.
+    ///
+    /// See the [`rewrite`](crate::read::rewrite) module for more information.
+    pub async fn rewrite<W, F>(&mut self, writer: &mut ZipFileWriter<W>, mut decide: F) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+        F: FnMut(&ZipEntry) -> RewriteAction,
+    {
+        for index in 0..self.file.entries.len() {
+            let entry = &self.file.entries[index];
+
+            if let Some(entry) = decide(entry).apply(entry) {
+                writer.copy_entry_raw(&entry, self.entry_raw(index).await?).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Extracts all entries into the provided destination directory, creating it if needed.
+    ///
+    /// Entries whose filenames would escape the destination directory (ie. those containing `..` components, absolute
+    /// paths, or drive prefixes) are rejected with [`ZipError::UnsafeFilename`].
+    #[cfg(feature = "fs")]
+    pub async fn extract<P>(&mut self, dest: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_options(dest, ExtractOptions::default()).await
+    }
+
+    /// Extracts all entries into the provided destination directory using the provided options.
+    ///
+    /// See [`ZipFileReader::extract()`] for more information.
+    #[cfg(feature = "fs")]
+    pub async fn extract_with_options<P>(&mut self, dest: P, options: ExtractOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_mapping(dest, options, |entry| Some(PathBuf::from(entry.filename()))).await
+    }
+
+    /// Extracts all entries into the provided destination directory using the provided options, relocating each entry
+    /// to the path returned by the provided mapping (or skipping it if [`None`] is returned).
+    ///
+    /// Returned paths are relative to the destination directory and are sanitised as entry filenames are (see
+    /// [`ZipFileReader::extract()`]). This allows entries to be renamed or moved on the fly, eg. to strip an undesirable
+    /// top-level directory. The mapping is applied to every entry before any are extracted, so that the total number of
+    /// entries to be extracted can be reported as progress.
+    #[cfg(feature = "fs")]
+    pub async fn extract_with_mapping<P, F>(&mut self, dest: P, options: ExtractOptions, mut map: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&ZipEntry) -> Option<PathBuf>,
+    {
+        let paths: Vec<_> =
+            self.file.entries.iter().map(|entry| if options.includes(entry) { map(entry) } else { None }).collect();
+        let mut extractor = Extractor::new(dest.as_ref(), &options, Some(paths.iter().flatten().count())).await?;
+
+        for ((entry, meta), path) in self.file.entries.iter().zip(self.file.metas.iter()).zip(paths) {
+            let path = match path {
+                Some(path) => path,
+                None => continue,
+            };
+
+            if let Some(method) = entry.encryption() {
+                return Err(ZipError::EntryEncrypted(method));
+            }
+
+            let seek_to = crate::read::compute_data_offset(&mut self.reader, meta).await?;
+            self.reader.seek(SeekFrom::Start(seek_to)).await?;
+
+            let reader = ZipEntryReader::new_with_borrow(&mut self.reader, entry);
+            extractor.extract(entry, &path, reader).await?;
+        }
+
+        extractor.finish().await
     }
 }
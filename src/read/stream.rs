@@ -0,0 +1,500 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A ZIP reader which acts over a non-seekable source.
+//!
+//! Entries are read sequentially via their local file headers, so the central directory is never consulted. As a
+//! result, any information only stored within the central directory (eg. file comments & attributes) won't be
+//! available. Unread data from the previous entry is skipped when advancing to the next one.
+//!
+//! ### Data descriptors
+//! Entries written with a data descriptor (eg. via [`EntryStreamWriter`]) store zeroed sizes & CRC32 values within
+//! their local file headers, so the decoder is relied upon to locate the end of their compressed data. As a result:
+//! - For entries stored without compression, the data is instead scanned for the data descriptor's signature. A match
+//!   is only accepted once the sizes which follow it agree with the number of bytes read, so such entries can only be
+//!   read if their data descriptor includes its (optional) signature.
+//! - If reading of such an entry is started, it must be read to completion before advancing to the next entry. Entries
+//!   which haven't been read from at all are skipped as usual.
+//! - The CRC32 value & sizes are verified against the data descriptor when advancing to the next entry.
+//! - Data descriptors are read both with and without their optional signature.
+//!
+//! [`EntryStreamWriter`]: crate::write::EntryStreamWriter
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::read::stream::ZipFileReader;
+//! # use async_zip::error::Result;
+//! # use tokio::fs::File;
+//! #
+//! # async fn run() -> Result<()> {
+//! let file = File::open("./foo.zip").await?;
+//! let mut reader = ZipFileReader::new(file);
+//!
+//! while let Some((entry, mut entry_reader)) = reader.next_entry().await? {
+//!     let entry = entry.clone();
+//!     let mut data = Vec::new();
+//!     entry_reader.read_to_end_checked(&mut data, &entry).await?;
+//! }
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! ### In-memory round trips
+//! A [`ZipFileWriter`] can be wired directly to a stream reader via [`duplex()`], allowing streaming behaviour to be
+//! exercised end-to-end (eg. within integration tests) without touching disk.
+//!
+//! [`ZipFileWriter`]: crate::write::ZipFileWriter
+//!
+//! ### Byte streams
+//! ZIP files received as a [`Stream`](futures_util::stream::Stream) of byte chunks (eg. a hyper request body) can be
+//! read directly via [`ZipFileReader::from_stream()`], and each entry's data converted back into such a stream via
+//! [`ZipEntryReader::into_stream()`], when the `futures` feature is enabled.
+//!
+//! [`ZipEntryReader::into_stream()`]: crate::read::io::entry::ZipEntryReader::into_stream
+//!
+//! ### Outer compression
+//! ZIP files are sometimes distributed with an additional layer of compression applied to the file as a whole (eg.
+//! `.zip.gz` files). Such files can be read directly via [`ZipFileReader::with_outer()`] when the relevant feature
+//! (`gzip` or `zstd`) is enabled.
+//!
+//! ### Sequential extraction
+//! All entries can be extracted via [`ZipFileReader::extract()`], which walks the local file headers in order without
+//! consulting the central directory. Over a seekable source such as a [`File`], this reads the ZIP file front-to-back
+//! in a single pass, which is generally the most cache-friendly way to extract everything. As the central directory
+//! is what other readers (& tools) treat as authoritative, [`ZipFileReader::extract_strict()`] additionally
+//! cross-checks the entries walked against it once extraction has completed.
+//!
+//! [`File`]: tokio::fs::File
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub use crate::read::io::outer::OuterCompression;
+
+use crate::entry::ZipEntry;
+use crate::error::{ErrorContext, Result, ZipError};
+#[cfg(feature = "fs")]
+use crate::read::diagnostics::ReaderOptions;
+#[cfg(feature = "fs")]
+use crate::read::extract::{ExtractOptions, Extractor};
+use crate::read::io::entry::{DescriptorState, ZipEntryReader};
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+use crate::read::io::outer::OuterReader;
+use crate::read::io::prefixed::PrefixedReader;
+use crate::spec::consts::{CDH_SIGNATURE, EOCDR_SIGNATURE, LFH_SIGNATURE};
+use crate::spec::extra_field::ZIP64_HEADER_ID;
+
+use crate::write::ZipFileWriter;
+
+#[cfg(feature = "futures")]
+use std::io::Cursor;
+#[cfg(feature = "fs")]
+use std::path::Path;
+
+#[cfg(feature = "futures")]
+use bytes::Buf;
+#[cfg(feature = "futures")]
+use futures_util::stream::{self, Stream};
+
+#[cfg(feature = "fs")]
+use tokio::io::AsyncSeek;
+use tokio::io::{AsyncRead, AsyncReadExt, BufReader, DuplexStream, Take};
+
+#[cfg(feature = "futures")]
+pub use crate::read::io::byte_stream::ByteStreamReader;
+
+/// The underlying reader of a stream reader.
+type StreamReader<R> = Take<PrefixedReader<BufReader<R>>>;
+
+/// A ZIP reader which acts over a non-seekable source.
+pub struct ZipFileReader<R> {
+    reader: StreamReader<R>,
+    entry: Option<ZipEntry>,
+    descriptor: Option<DescriptorState>,
+    finished: bool,
+    max_read_size: Option<u64>,
+}
+
+impl<R> ZipFileReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructs a new ZIP reader from a non-seekable source.
+    pub fn new(reader: R) -> Self {
+        let reader = PrefixedReader::new(BufReader::new(reader)).take(u64::MAX);
+        Self { reader, entry: None, descriptor: None, finished: false, max_read_size: None }
+    }
+
+    /// Sets the maximum number of bytes which are read into memory by helpers such as
+    /// [`ZipEntryReader::read_to_end_checked()`] for each entry (defaults to no limit).
+    ///
+    /// As the sizes of entries which use a data descriptor aren't known upfront, such entries are only rejected (with
+    /// [`ZipError::EntryTooLarge`]) once the limit has been reached. This also applies to the entries buffered when this
+    /// reader is converted into a stream. See [`ZipEntryReader::with_max_read_size()`] for more information.
+    pub fn max_read_size(&mut self, limit: u64) {
+        self.max_read_size = Some(limit);
+    }
+
+    /// Returns whether or not all entries have been read.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the next entry and a reader for its data, or [`None`] if all entries have been read.
+    ///
+    /// Any data from the previous entry which wasn't read will be skipped. For entries which use a data descriptor, the
+    /// returned entry's sizes & CRC32 value will be zero (see the [module-level docs](self) for more information).
+    ///
+    /// Encrypted entries can't be read by this reader, and so [`ZipError::EntryEncrypted`] is returned for them. Further
+    /// calls will skip over such an entry (if its compressed size is known) and continue with the next.
+    pub async fn next_entry(&mut self) -> Result<Option<(&ZipEntry, ZipEntryReader<'_, StreamReader<R>>)>> {
+        if !self.advance().await? {
+            return Ok(None);
+        }
+
+        let entry = self.entry.as_ref().expect("entry should be set after advancing");
+        if let Some(method) = entry.encryption() {
+            return Err(ZipError::EntryEncrypted(method));
+        }
+
+        let reader = match self.descriptor.as_mut() {
+            Some(state) => ZipEntryReader::new_with_descriptor(&mut self.reader, entry.compression(), state),
+            None => ZipEntryReader::new_with_borrow(&mut self.reader, entry),
+        };
+
+        Ok(Some((entry, reader.with_read_limit(self.max_read_size))))
+    }
+
+    /// Consumes this reader, reading & discarding all remaining data until EOF has been reached.
+    ///
+    /// Once all entries have been read, the central directory (& anything else which follows it) is left unread within
+    /// the source. This can be used when the source must be consumed in full, eg. when its writer would otherwise be
+    /// blocked (see [`duplex()`]). Returns the number of bytes discarded.
+    pub async fn drain(mut self) -> Result<u64> {
+        self.reader.set_limit(u64::MAX);
+        Ok(tokio::io::copy(&mut self.reader, &mut tokio::io::sink()).await?)
+    }
+
+    /// Consumes this reader and returns a [`Stream`] of the remaining entries alongside their data.
+    ///
+    /// As entries are read sequentially from a single source, each entry's data is read into memory (and its CRC32
+    /// value verified) before it's yielded. This allows the data to be held onto whilst later entries are read (eg. when
+    /// using stream combinators which buffer items), at the cost of holding each entry's data in memory. Use
+    /// [`ZipFileReader::next_entry()`] to read entries without buffering them. The stream ends after an error.
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self) -> impl Stream<Item = Result<(ZipEntry, Cursor<Vec<u8>>)>> {
+        stream::try_unfold(
+            self,
+            |mut reader| async move { Ok(reader.next_buffered().await?.map(|item| (item, reader))) },
+        )
+    }
+
+    /// Reads the next entry's data into memory, returning it alongside the entry.
+    #[cfg(feature = "futures")]
+    async fn next_buffered(&mut self) -> Result<Option<(ZipEntry, Cursor<Vec<u8>>)>> {
+        let (entry, mut reader) = match self.next_entry().await? {
+            Some((entry, reader)) => (entry.clone(), reader),
+            None => return Ok(None),
+        };
+
+        let mut data = Vec::new();
+        reader.read_to_end_checked(&mut data, &entry).await?;
+
+        Ok(Some((entry, Cursor::new(data))))
+    }
+
+    /// Consumes this reader and returns an incremental listing of the remaining entries.
+    ///
+    /// This is intended for previewing the contents of a ZIP file as no entry data is decompressed. Instead, the data
+    /// of each entry is skipped over without any seeking taking place.
+    pub fn list(self) -> ZipEntryListing<R> {
+        ZipEntryListing { reader: self }
+    }
+
+    /// Extracts all remaining entries into the provided destination directory, creating it if needed.
+    ///
+    /// Entries are extracted in the order of their local file headers and the central directory is never consulted.
+    /// As with the other readers, entries whose filenames would escape the destination directory are rejected with
+    /// [`ZipError::UnsafeFilename`].
+    #[cfg(feature = "fs")]
+    pub async fn extract<P>(&mut self, dest: P) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_with_options(dest, ExtractOptions::default()).await
+    }
+
+    /// Extracts all remaining entries into the provided destination directory using the provided options.
+    ///
+    /// See [`ZipFileReader::extract()`] for more information.
+    #[cfg(feature = "fs")]
+    pub async fn extract_with_options<P>(&mut self, dest: P, options: ExtractOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        self.extract_entries(dest.as_ref(), &options).await.map(|_| ())
+    }
+
+    /// Extracts all remaining entries and returns them, with the values from any data descriptors filled in.
+    #[cfg(feature = "fs")]
+    async fn extract_entries(&mut self, dest: &Path, options: &ExtractOptions) -> Result<Vec<ZipEntry>> {
+        let mut extractor = Extractor::new(dest, options, None).await?;
+        let mut entries = Vec::new();
+
+        loop {
+            entries.extend(self.skip_remaining().await?);
+
+            if !self.advance().await? {
+                break;
+            }
+
+            let entry = self.entry.as_ref().expect("entry should be set after advancing");
+            if !options.includes(entry) {
+                continue;
+            }
+            if let Some(method) = entry.encryption() {
+                return Err(ZipError::EntryEncrypted(method));
+            }
+
+            let reader = match self.descriptor.as_mut() {
+                Some(state) => ZipEntryReader::new_with_descriptor(&mut self.reader, entry.compression(), state),
+                None => ZipEntryReader::new_with_borrow(&mut self.reader, entry),
+            };
+
+            extractor.extract(entry, Path::new(entry.filename()), reader).await?;
+        }
+
+        extractor.finish().await?;
+        Ok(entries)
+    }
+
+    /// Skips any remaining data of the current entry and reads the next local file header.
+    ///
+    /// Returns false if all entries have been read.
+    async fn advance(&mut self) -> Result<bool> {
+        if self.finished {
+            return Ok(false);
+        }
+
+        self.skip_remaining().await?;
+
+        match self.reader.read_u32_le().await? {
+            LFH_SIGNATURE => (),
+            CDH_SIGNATURE | EOCDR_SIGNATURE => {
+                self.finished = true;
+                return Ok(false);
+            }
+            actual => return Err(ZipError::UnexpectedHeaderError(actual, LFH_SIGNATURE)),
+        };
+
+        let (entry, flags) = crate::read::lfh(&mut self.reader).await?;
+
+        if flags.data_descriptor {
+            if entry.compression().is_legacy() {
+                return Err(ZipError::FeatureNotSupported("Stream reading legacy entries with data descriptors"));
+            }
+
+            let zip64 = crate::spec::extra_field::find_extra_field(entry.extra_field(), ZIP64_HEADER_ID).is_some();
+            self.descriptor = Some(DescriptorState { zip64, ..Default::default() });
+        } else {
+            self.reader.set_limit(entry.compressed_size().into());
+        }
+
+        self.entry = Some(entry);
+        Ok(true)
+    }
+
+    /// Skips any data remaining from the previous entry and resets the reader's limit.
+    ///
+    /// If the previous entry used a data descriptor, the descriptor is also read and its CRC32 value verified. Returns
+    /// the previous entry (if any), with the values from its data descriptor filled in.
+    async fn skip_remaining(&mut self) -> Result<Option<ZipEntry>> {
+        let mut entry = match self.entry.take() {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+
+        match self.descriptor.take() {
+            Some(mut state) => {
+                if !state.started {
+                    let mut reader =
+                        ZipEntryReader::new_with_descriptor(&mut self.reader, entry.compression(), &mut state);
+                    tokio::io::copy(&mut reader, &mut tokio::io::sink()).await?;
+                }
+                if !state.eof {
+                    return Err(ZipError::EntryNotFullyRead);
+                }
+
+                self.reader.get_mut().unread(&state.buffered);
+                let (crc, compressed_size, uncompressed_size) =
+                    crate::read::data_descriptor(&mut self.reader, state.zip64, state.hash).await?;
+
+                if crc != state.hash {
+                    return Err(ZipError::CRC32CheckError.with_context(ErrorContext::filename(entry.filename())));
+                }
+                if compressed_size != state.compressed_size || uncompressed_size != state.uncompressed_size {
+                    let error = ZipError::DataDescriptorSizeMismatch;
+                    return Err(error.with_context(ErrorContext::filename(entry.filename())));
+                }
+
+                // Sizes beyond 32 bits are saturated, as they would be within the central directory.
+                entry.crc32 = crc;
+                entry.compressed_size = u32::try_from(compressed_size).unwrap_or(u32::MAX);
+                entry.uncompressed_size = u32::try_from(uncompressed_size).unwrap_or(u32::MAX);
+            }
+            None => {
+                tokio::io::copy_buf(&mut self.reader, &mut tokio::io::sink()).await?;
+            }
+        }
+
+        self.reader.set_limit(u64::MAX);
+        Ok(Some(entry))
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<R> ZipFileReader<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Extracts all remaining entries as per [`ZipFileReader::extract_with_options()`] and then cross-checks them
+    /// against the central directory.
+    ///
+    /// Each entry walked must have a counterpart within the central directory (in the same order of offset) with an
+    /// identical filename, CRC32 value, and sizes. Otherwise, [`ZipError::CentralDirectoryMismatch`] is returned. As
+    /// such, this should be called before any entries have been read from this reader.
+    pub async fn extract_strict<P>(mut self, dest: P, options: ExtractOptions) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let local = self.extract_entries(dest.as_ref(), &options).await?;
+
+        let mut reader = self.reader.into_inner().into_inner().into_inner();
+        let file = crate::read::file(BufReader::new(&mut reader), &ReaderOptions::default()).await?;
+
+        let mut central: Vec<_> = file.entries.iter().zip(file.metas.iter()).collect();
+        central.sort_by_key(|(_, meta)| meta.file_offset);
+
+        if local.len() != central.len() {
+            let message = format!("{} local file headers but {} central entries", local.len(), central.len());
+            return Err(ZipError::CentralDirectoryMismatch(message));
+        }
+
+        for (local, (central, _)) in local.iter().zip(central) {
+            if local.filename() != central.filename() {
+                let message = format!("'{}' is listed as '{}'", local.filename(), central.filename());
+                return Err(ZipError::CentralDirectoryMismatch(message));
+            }
+
+            if local.crc32() != central.crc32()
+                || local.compressed_size() != central.compressed_size()
+                || local.uncompressed_size() != central.uncompressed_size()
+            {
+                let message = format!("'{}' has a differing CRC32 value or sizes", local.filename());
+                return Err(ZipError::CentralDirectoryMismatch(message));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An incremental listing of the entries within a ZIP file read via a non-seekable source.
+///
+/// This type cannot be directly constructed so instead, [`ZipFileReader::list()`] must be used.
+pub struct ZipEntryListing<R> {
+    reader: ZipFileReader<R>,
+}
+
+impl<R> ZipEntryListing<R>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Returns the next entry, or [`None`] if all entries have been listed.
+    pub async fn next(&mut self) -> Result<Option<&ZipEntry>> {
+        match self.reader.advance().await? {
+            true => Ok(self.reader.entry.as_ref()),
+            false => Ok(None),
+        }
+    }
+
+    /// Converts this listing into a [`Stream`] of the remaining entries. The stream ends after an error.
+    #[cfg(feature = "futures")]
+    pub fn into_stream(self) -> impl Stream<Item = Result<ZipEntry>> {
+        stream::try_unfold(self, |mut listing| async move {
+            Ok(listing.next().await?.cloned().map(|entry| (entry, listing)))
+        })
+    }
+
+    /// Consumes this listing and returns the underlying reader.
+    pub fn into_inner(self) -> ZipFileReader<R> {
+        self.reader
+    }
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+impl<R> ZipFileReader<OuterReader<BufReader<R>>>
+where
+    R: AsyncRead + Unpin,
+{
+    /// Constructs a new ZIP reader from a non-seekable source which has an outer compression format applied to it.
+    pub fn with_outer(reader: R, compression: OuterCompression) -> Self {
+        Self::new(OuterReader::new(BufReader::new(reader), compression))
+    }
+}
+
+#[cfg(feature = "futures")]
+impl<S, B, E> ZipFileReader<ByteStreamReader<S, B>>
+where
+    S: Stream<Item = std::result::Result<B, E>> + Unpin,
+    B: Buf + Unpin,
+    E: Into<std::io::Error>,
+{
+    /// Constructs a new ZIP reader from a [`Stream`] of byte chunks (eg. a hyper request body or a channel's receiver).
+    ///
+    /// Any error yielded by the stream is returned as an [`ZipError::UpstreamReadError`] once reached.
+    pub fn from_stream(stream: S) -> Self {
+        Self::new(ByteStreamReader::new(stream))
+    }
+}
+
+/// Constructs a ZIP writer and a stream reader which are connected to one another in memory.
+///
+/// Data written by the writer can be read by the reader as soon as it's been written. The connection is backed by
+/// [`tokio::io::duplex()`], so at most `max_buf_size` bytes are buffered between the two. As such, the writer & reader
+/// must be driven concurrently (eg. via [`tokio::join!`] or separate tasks) if more data than that will be written.
+///
+/// As the reader stops at the central directory, it should then be drained via [`ZipFileReader::drain()`] so that the
+/// writer isn't blocked (or, if the reader is dropped, doesn't fail with a broken pipe) whilst writing it. The reader
+/// observes EOF once the writer has been closed or dropped.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder};
+/// # use async_zip::error::Result;
+/// #
+/// # async fn run() -> Result<()> {
+/// let (mut writer, mut reader) = async_zip::read::stream::duplex(1024);
+///
+/// let write = async move {
+///     let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+///     writer.write_entry_whole(entry, b"foo").await?;
+///     // The inner stream is dropped once closed, so that the reader observes EOF.
+///     writer.close().await.map(drop)
+/// };
+///
+/// let read = async move {
+///     while let Some((entry, mut entry_reader)) = reader.next_entry().await? {
+///         let entry = entry.clone();
+///         let mut data = String::new();
+///         entry_reader.read_to_string_checked(&mut data, &entry).await?;
+///     }
+///     reader.drain().await
+/// };
+///
+/// let (written, read) = tokio::join!(write, read);
+/// written.and(read.map(|_| ()))
+/// # }
+/// ```
+pub fn duplex(max_buf_size: usize) -> (ZipFileWriter<DuplexStream>, ZipFileReader<DuplexStream>) {
+    let (writer, reader) = tokio::io::duplex(max_buf_size);
+    (ZipFileWriter::new(writer), ZipFileReader::new(reader))
+}
@@ -0,0 +1,89 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md (Appendix E)
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// The length of the password verification value which follows the salt of an AES-encrypted entry.
+pub(crate) const AES_VERIFIER_LENGTH: usize = 2;
+
+/// The length of the authentication code which follows the data of an AES-encrypted entry.
+pub(crate) const AES_AUTH_CODE_LENGTH: usize = 10;
+
+/// The number of PBKDF2 iterations used to derive the keys of an AES-encrypted entry.
+const AES_KEY_ITERATIONS: u32 = 1000;
+
+/// The encryption strength used when writing AES-encrypted entries (AES-256).
+pub(crate) const AES_WRITE_STRENGTH: u8 = 3;
+
+/// The vendor version used when writing AES-encrypted entries (AE-2, which doesn't store a CRC32 value).
+pub(crate) const AES_WRITE_VERSION: u16 = 2;
+
+/// The version needed to extract an AES-encrypted entry.
+pub(crate) const AES_VERSION_NEEDED: u16 = 51;
+
+/// The keys derived from a password & salt for the encryption & authentication of an AES-encrypted entry.
+///
+/// The data is encrypted using AES in CTR mode (with a little-endian counter starting at one), and authenticated via an
+/// HMAC-SHA1 of the encrypted data, truncated to ten bytes and stored directly after it.
+pub(crate) struct AesKeys {
+    cipher: Box<dyn StreamCipher + Send + Sync>,
+    hmac: Hmac<Sha1>,
+    verifier: [u8; AES_VERIFIER_LENGTH],
+}
+
+impl AesKeys {
+    /// Derives the keys for the provided strength (1 for AES-128, 2 for AES-192, 3 for AES-256) via PBKDF2.
+    pub(crate) fn derive(strength: u8, password: &[u8], salt: &[u8]) -> Self {
+        let key_length = 8 + 8 * strength as usize;
+        let mut derived = vec![0; key_length * 2 + AES_VERIFIER_LENGTH];
+        pbkdf2::pbkdf2::<Hmac<Sha1>>(password, salt, AES_KEY_ITERATIONS, &mut derived);
+
+        let (key, hmac_key) = (&derived[..key_length], &derived[key_length..key_length * 2]);
+        let mut iv = [0; 16];
+        iv[0] = 1;
+
+        let cipher: Box<dyn StreamCipher + Send + Sync> = match strength {
+            1 => Box::new(ctr::Ctr128LE::<aes::Aes128>::new_from_slices(key, &iv).unwrap()),
+            2 => Box::new(ctr::Ctr128LE::<aes::Aes192>::new_from_slices(key, &iv).unwrap()),
+            _ => Box::new(ctr::Ctr128LE::<aes::Aes256>::new_from_slices(key, &iv).unwrap()),
+        };
+        let hmac = Hmac::<Sha1>::new_from_slice(hmac_key).unwrap();
+        let verifier = [derived[key_length * 2], derived[key_length * 2 + 1]];
+
+        Self { cipher, hmac, verifier }
+    }
+
+    /// Returns the password verification value which is stored after the salt.
+    pub(crate) fn verifier(&self) -> [u8; AES_VERIFIER_LENGTH] {
+        self.verifier
+    }
+
+    /// Encrypts a buffer of bytes in place, updating the authentication code.
+    pub(crate) fn encrypt(&mut self, buffer: &mut [u8]) {
+        self.cipher.apply_keystream(buffer);
+        self.hmac.update(buffer);
+    }
+
+    /// Decrypts a buffer of bytes in place, updating the authentication code.
+    pub(crate) fn decrypt(&mut self, buffer: &mut [u8]) {
+        self.hmac.update(buffer);
+        self.cipher.apply_keystream(buffer);
+    }
+
+    /// Returns the authentication code of all data encrypted or decrypted so far.
+    pub(crate) fn auth_code(&self) -> [u8; AES_AUTH_CODE_LENGTH] {
+        let code = self.hmac.clone().finalize().into_bytes();
+        code[..AES_AUTH_CODE_LENGTH].try_into().unwrap()
+    }
+}
+
+/// Generates a random salt of the provided length.
+pub(crate) fn generate_salt(length: usize) -> std::io::Result<Vec<u8>> {
+    let mut salt = vec![0; length];
+    getrandom::getrandom(&mut salt)?;
+    Ok(salt)
+}
@@ -0,0 +1,129 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// A description of the functionality compiled into this build of the crate.
+///
+/// Much of this crate's functionality sits behind Cargo features, so whether (eg.) an entry can be decompressed
+/// depends upon how the crate was built. This allows applications to check ahead of time, or to explain why an
+/// operation failed.
+///
+/// ### Example
+/// ```
+/// # use async_zip::error::ZipError;
+/// #
+/// # fn explain(error: &ZipError) {
+/// if let ZipError::CompressionNotSupported(method) = error {
+///     match async_zip::capabilities().compression(*method).and_then(|support| support.feature) {
+///         Some(feature) => println!("compression method {} requires the '{}' feature", method, feature),
+///         None => println!("compression method {} isn't supported", method),
+///     }
+/// }
+/// # }
+/// ```
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    /// The version of this crate.
+    pub version: &'static str,
+    /// Every compression method known to this crate, whether or not it has been compiled in.
+    pub compression: &'static [CompressionSupport],
+    /// Whether traditional PKWARE (ZipCrypto) encryption is supported when reading & writing.
+    pub zipcrypto: bool,
+    /// Whether WinZip AES encryption is supported when reading & writing (via the `aes` feature).
+    pub aes: bool,
+    /// Whether ZIP64 archives (and entries over 4 GiB) are supported when writing, and when reading via the stream
+    /// reader.
+    pub zip64: bool,
+    /// The backend used to represent entry modification dates, if any (via the `date` feature).
+    pub date_backend: Option<&'static str>,
+    /// Whether filesystem-backed reading, extraction, & archiving is supported (via the `fs` feature).
+    pub fs: bool,
+    /// Whether filenames can be NFC normalized when reading (via the `unicode` feature).
+    pub unicode: bool,
+    /// Whether operations can be cancelled via a token (via the `cancel` feature).
+    pub cancel: bool,
+    /// Whether the `futures` IO traits are supported (via the `futures` feature).
+    pub futures: bool,
+    /// Whether ZIP files can be streamed as HTTP response bodies (via the `http-body` feature).
+    pub http_body: bool,
+    /// Whether entries can be filtered by regular expression (via the `regex` feature).
+    pub regex: bool,
+    /// Whether ZIP files can be read from an object store (via the `object-store` feature).
+    pub object_store: bool,
+    /// Whether ZIP files can be read via a memory mapping (via the `mmap` feature).
+    pub mmap: bool,
+}
+
+impl Capabilities {
+    /// Returns the support for the compression method with the provided identifier, if it's known to this crate.
+    pub fn compression(&self, method: u16) -> Option<&'static CompressionSupport> {
+        self.compression.iter().find(|support| support.method == method)
+    }
+
+    /// Returns whether entries compressed with the provided method can be read.
+    pub fn can_read(&self, method: u16) -> bool {
+        self.compression(method).is_some_and(|support| support.read)
+    }
+
+    /// Returns whether entries can be written with the provided compression method.
+    pub fn can_write(&self, method: u16) -> bool {
+        self.compression(method).is_some_and(|support| support.write)
+    }
+}
+
+/// A description of whether a single compression method is supported.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionSupport {
+    /// The method's identifier, as stored within an entry's headers.
+    pub method: u16,
+    /// The method's name.
+    pub name: &'static str,
+    /// The Cargo feature which enables this method, or `None` if it's always available.
+    pub feature: Option<&'static str>,
+    /// Whether entries compressed with this method can be read.
+    pub read: bool,
+    /// Whether entries can be written with this method.
+    pub write: bool,
+}
+
+impl CompressionSupport {
+    const fn new(method: u16, name: &'static str, feature: Option<&'static str>, read: bool, write: bool) -> Self {
+        Self { method, name, feature, read, write }
+    }
+}
+
+const COMPRESSION: &[CompressionSupport] = &[
+    CompressionSupport::new(0, "stored", None, true, true),
+    CompressionSupport::new(1, "shrink", Some("legacy"), cfg!(feature = "legacy"), false),
+    CompressionSupport::new(2, "reduce (factor 1)", Some("legacy"), cfg!(feature = "legacy"), false),
+    CompressionSupport::new(3, "reduce (factor 2)", Some("legacy"), cfg!(feature = "legacy"), false),
+    CompressionSupport::new(4, "reduce (factor 3)", Some("legacy"), cfg!(feature = "legacy"), false),
+    CompressionSupport::new(5, "reduce (factor 4)", Some("legacy"), cfg!(feature = "legacy"), false),
+    CompressionSupport::new(6, "implode", Some("legacy"), cfg!(feature = "legacy"), false),
+    CompressionSupport::new(8, "deflate", Some("deflate"), cfg!(feature = "deflate"), cfg!(feature = "deflate")),
+    CompressionSupport::new(12, "bzip2", Some("bzip2"), cfg!(feature = "bzip2"), cfg!(feature = "bzip2")),
+    CompressionSupport::new(14, "lzma", Some("lzma"), cfg!(feature = "lzma"), cfg!(feature = "lzma")),
+    CompressionSupport::new(93, "zstd", Some("zstd"), cfg!(feature = "zstd"), cfg!(feature = "zstd")),
+    CompressionSupport::new(95, "xz", Some("xz"), cfg!(feature = "xz"), cfg!(feature = "xz")),
+];
+
+/// Returns a description of the functionality compiled into this build of the crate.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        compression: COMPRESSION,
+        zipcrypto: true,
+        aes: cfg!(feature = "aes"),
+        zip64: true,
+        date_backend: if cfg!(feature = "date") { Some("chrono") } else { None },
+        fs: cfg!(feature = "fs"),
+        unicode: cfg!(feature = "unicode"),
+        cancel: cfg!(feature = "cancel"),
+        futures: cfg!(feature = "futures"),
+        http_body: cfg!(feature = "http-body"),
+        regex: cfg!(feature = "regex"),
+        object_store: cfg!(feature = "object-store"),
+        mmap: cfg!(feature = "mmap"),
+    }
+}
@@ -2,6 +2,7 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::error::{Result, ZipError};
+#[cfg(feature = "tokio")]
 use async_compression::Level;
 
 /// A compression method supported by this crate.
@@ -19,6 +20,47 @@ pub enum Compression {
     Zstd,
     #[cfg(feature = "xz")]
     Xz,
+    /// Shrink (dynamic LZW) compression, as written by PKZIP 1.x. Only supported when reading.
+    #[cfg(feature = "legacy")]
+    Shrink,
+    /// Reduce compression with the contained compression factor (1-4), as written by PKZIP 0.9x. Only supported when
+    /// reading.
+    #[cfg(feature = "legacy")]
+    Reduce(u8),
+    /// Implode compression, as written by PKZIP 1.x. Only supported when reading.
+    ///
+    /// The options are stored within the entry's general purpose flag rather than its compression method.
+    #[cfg(feature = "legacy")]
+    Implode {
+        /// Whether an 8K (rather than 4K) sliding dictionary was used.
+        large_window: bool,
+        /// Whether literals were encoded using a Shannon-Fano tree (rather than stored as-is).
+        literal_tree: bool,
+    },
+}
+
+impl Compression {
+    /// Returns whether this is a legacy compression method which is only supported when reading.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn is_legacy(&self) -> bool {
+        #[cfg(feature = "legacy")]
+        if matches!(self, Compression::Shrink | Compression::Reduce(_) | Compression::Implode { .. }) {
+            return true;
+        }
+
+        false
+    }
+
+    /// Returns the method-specific options stored within bits 1 & 2 of an entry's general purpose flag.
+    #[cfg(feature = "tokio")]
+    pub(crate) fn flag_options(&self) -> u8 {
+        #[cfg(feature = "legacy")]
+        if let Compression::Implode { large_window, literal_tree } = self {
+            return u8::from(*large_window) | u8::from(*literal_tree) << 1;
+        }
+
+        0
+    }
 }
 
 impl TryFrom<u16> for Compression {
@@ -29,6 +71,12 @@ impl TryFrom<u16> for Compression {
     fn try_from(value: u16) -> Result<Self> {
         match value {
             0 => Ok(Compression::Stored),
+            #[cfg(feature = "legacy")]
+            1 => Ok(Compression::Shrink),
+            #[cfg(feature = "legacy")]
+            2..=5 => Ok(Compression::Reduce(value as u8 - 1)),
+            #[cfg(feature = "legacy")]
+            6 => Ok(Compression::Implode { large_window: false, literal_tree: false }),
             #[cfg(feature = "deflate")]
             8 => Ok(Compression::Deflate),
             #[cfg(feature = "bzip2")]
@@ -50,6 +98,12 @@ impl From<&Compression> for u16 {
     fn from(compression: &Compression) -> u16 {
         match compression {
             Compression::Stored => 0,
+            #[cfg(feature = "legacy")]
+            Compression::Shrink => 1,
+            #[cfg(feature = "legacy")]
+            Compression::Reduce(factor) => 1 + *factor as u16,
+            #[cfg(feature = "legacy")]
+            Compression::Implode { .. } => 6,
             #[cfg(feature = "deflate")]
             Compression::Deflate => 8,
             #[cfg(feature = "bzip2")]
@@ -86,6 +140,7 @@ pub enum DeflateOption {
     Super,
 }
 
+#[cfg(feature = "tokio")]
 impl DeflateOption {
     pub(crate) fn into_level(self) -> Level {
         // FIXME: There's no clear documentation on what these specific levels defined in the ZIP specification relate
@@ -21,5 +21,16 @@ pub const CDH_LENGTH: usize = 42;
 pub const EOCDR_SIGNATURE: u32 = 0x6054b50;
 pub const EOCDR_LENGTH: usize = 18;
 
+// ZIP64 end of central directory record & locator constants
+//
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4314
+#[cfg(feature = "tokio")]
+pub const ZIP64_EOCDR_SIGNATURE: u32 = 0x6064b50;
+#[cfg(feature = "tokio")]
+pub const ZIP64_EOCDR_LENGTH: usize = 52;
+#[cfg(feature = "tokio")]
+pub const ZIP64_EOCDL_SIGNATURE: u32 = 0x7064b50;
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#439
 pub const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x8074b50;
+pub const DATA_DESCRIPTOR_LENGTH: usize = 12;
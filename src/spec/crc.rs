@@ -0,0 +1,37 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! The CRC32 hashing used for entry data & Info-ZIP Unicode Path extra fields.
+//!
+//! This is the CRC-32/ISO-HDLC variant (as used by ZIP, gzip, and PNG), with the `0xEDB88320` reflected polynomial
+//! and both the initial value & final XOR being `0xFFFFFFFF`.
+
+use crc32fast::Hasher;
+
+/// Computes the CRC32 hash of the provided data in one shot.
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32fast::hash(data)
+}
+
+/// A streaming CRC32 hasher, which computes the same hash as [`crc32()`] over data provided in any number of chunks.
+#[derive(Debug, Clone, Default)]
+pub struct Crc32 {
+    hasher: Hasher,
+}
+
+impl Crc32 {
+    /// Constructs a new hasher.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the hash with the provided data.
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// Consumes this hasher and returns the computed CRC32 hash.
+    pub fn finalize(self) -> u32 {
+        self.hasher.finalize()
+    }
+}
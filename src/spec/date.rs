@@ -36,3 +36,24 @@ pub fn chrono_to_zip_time(dt: &DateTime<Utc>) -> (u16, u16) {
 
     (hour | min | second, year | month | day)
 }
+
+// Converts a number of seconds since the Unix epoch into a `chrono` structure.
+pub fn unix_to_chrono(secs: i64) -> Option<DateTime<Utc>> {
+    Utc.timestamp_opt(secs, 0).single()
+}
+
+// The number of seconds between the Windows epoch (1601-01-01) and the Unix epoch (1970-01-01).
+const WINDOWS_EPOCH_OFFSET: i64 = 11_644_473_600;
+
+// Converts a Windows FILETIME (the number of 100-nanosecond intervals since 1601-01-01) into a `chrono` structure.
+// Returns None for a zero value (which denotes an unset timestamp) or one which is out of range.
+pub fn ntfs_to_chrono(filetime: u64) -> Option<DateTime<Utc>> {
+    if filetime == 0 {
+        return None;
+    }
+
+    let secs = (filetime / 10_000_000) as i64 - WINDOWS_EPOCH_OFFSET;
+    let nanos = (filetime % 10_000_000) as u32 * 100;
+
+    Utc.timestamp_opt(secs, nanos).single()
+}
@@ -0,0 +1,114 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#444 (bit 11)
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#469
+
+/// The source from which an entry's filename was decoded.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilenameSource {
+    /// The header's filename field, encoded as UTF-8 (either flagged via the language encoding flag, or unflagged but
+    /// valid UTF-8).
+    Utf8,
+    /// The header's filename field, encoded as IBM Code Page 437.
+    Cp437,
+    /// The Info-ZIP Unicode Path extra field (0x7075), whose CRC32 matched the header's filename field.
+    UnicodePathExtraField,
+    /// The header's filename field, encoded as specified (or detected) via [`ReaderOptions::filename_encoding()`].
+    ///
+    /// [`ReaderOptions::filename_encoding()`]: crate::read::diagnostics::ReaderOptions::filename_encoding
+    #[cfg(feature = "encoding")]
+    Encoding(&'static encoding_rs::Encoding),
+}
+
+/// The encoding with which filename & comment fields lacking the language encoding flag are decoded.
+#[cfg(feature = "encoding")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FilenameEncoding {
+    /// Decoded as UTF-8 where valid, and otherwise as IBM Code Page 437.
+    #[default]
+    Default,
+    /// Decoded using the provided encoding (eg. [`encoding_rs::SHIFT_JIS`]).
+    Fixed(&'static encoding_rs::Encoding),
+    /// Decoded using the encoding detected from all of the fields, unless they're all valid UTF-8.
+    ///
+    /// Detection is heuristic, and so is only reliable over a reasonable amount of text. It's performed over the whole
+    /// central directory, except when reading lazily, where it's performed over each entry's fields individually.
+    Detect,
+}
+
+/// Resolves the encoding with which fields lacking the language encoding flag are decoded, given those fields.
+///
+/// [`None`] is returned if the fields should be decoded as per usual (see [`decode_header_string()`]).
+#[cfg(all(feature = "encoding", feature = "tokio"))]
+pub(crate) fn resolve_encoding<'a, I>(encoding: FilenameEncoding, fields: I) -> Option<&'static encoding_rs::Encoding>
+where
+    I: IntoIterator<Item = &'a [u8]>,
+{
+    match encoding {
+        FilenameEncoding::Default => None,
+        FilenameEncoding::Fixed(encoding) => Some(encoding),
+        FilenameEncoding::Detect => {
+            let mut detector = chardetng::EncodingDetector::new();
+            let mut utf8 = true;
+
+            for field in fields {
+                utf8 &= std::str::from_utf8(field).is_ok();
+                detector.feed(field, false);
+                detector.feed(b"\n", false);
+            }
+
+            detector.feed(&[], true);
+            (!utf8).then(|| detector.guess(None, false))
+        }
+    }
+}
+
+/// The Unicode characters of IBM Code Page 437 for bytes 0x80 to 0xFF (lower bytes map directly to ASCII).
+#[cfg(feature = "tokio")]
+const CP437_HIGH: [char; 128] = [
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{A0}',
+];
+
+/// Decodes bytes encoded as IBM Code Page 437.
+#[cfg(feature = "tokio")]
+pub(crate) fn decode_cp437(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| if byte.is_ascii() { *byte as char } else { CP437_HIGH[(*byte - 0x80) as usize] }).collect()
+}
+
+/// Encodes a string as IBM Code Page 437, replacing any characters which cannot be represented with an underscore.
+#[cfg(feature = "tokio")]
+pub(crate) fn encode_cp437(value: &str) -> Vec<u8> {
+    value
+        .chars()
+        .map(|c| match c.is_ascii() {
+            true => c as u8,
+            false => CP437_HIGH.iter().position(|high| *high == c).map(|index| index as u8 + 0x80).unwrap_or(b'_'),
+        })
+        .collect()
+}
+
+/// Decodes a filename or comment field from a header.
+///
+/// If the language encoding flag is set, the bytes are decoded as UTF-8 (lossily). Otherwise, the specification states
+/// that the bytes are encoded as IBM Code Page 437, but as many tools write unflagged UTF-8, bytes which are valid UTF-8
+/// are decoded as such.
+#[cfg(feature = "tokio")]
+pub(crate) fn decode_header_string(bytes: Vec<u8>, flagged_unicode: bool) -> (String, FilenameSource) {
+    if flagged_unicode {
+        return (String::from_utf8_lossy(&bytes).into_owned(), FilenameSource::Utf8);
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(value) => (value, FilenameSource::Utf8),
+        Err(err) => (decode_cp437(err.as_bytes()), FilenameSource::Cp437),
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#[cfg(feature = "tokio")]
+use crate::spec::extra_field::{AES_COMPRESSION_METHOD, AES_HEADER_ID};
+#[cfg(feature = "tokio")]
+use crate::spec::header::GeneralPurposeFlag;
+
+use std::fmt::{Display, Formatter};
+
+/// The method with which an entry's data is encrypted.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionMethod {
+    /// Traditional PKWARE encryption.
+    ZipCrypto,
+    /// WinZip AES encryption using a 128-bit key.
+    Aes128,
+    /// WinZip AES encryption using a 192-bit key.
+    Aes192,
+    /// WinZip AES encryption using a 256-bit key.
+    Aes256,
+    /// PKWARE strong encryption, which isn't supported by this crate.
+    Strong,
+}
+
+impl Display for EncryptionMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionMethod::ZipCrypto => write!(f, "ZipCrypto"),
+            EncryptionMethod::Aes128 => write!(f, "AES-128"),
+            EncryptionMethod::Aes192 => write!(f, "AES-192"),
+            EncryptionMethod::Aes256 => write!(f, "AES-256"),
+            EncryptionMethod::Strong => write!(f, "strong encryption"),
+        }
+    }
+}
+
+/// Determines the method with which an entry's data is encrypted from its header's flags, compression method, and
+/// extra field, returning [`None`] if it isn't encrypted.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#444
+#[cfg(feature = "tokio")]
+pub(crate) fn method(flags: GeneralPurposeFlag, compression: u16, extra_field: &[u8]) -> Option<EncryptionMethod> {
+    if !flags.encrypted {
+        return None;
+    }
+    if flags.strong_encryption {
+        return Some(EncryptionMethod::Strong);
+    }
+
+    // The strength is held within the WinZip AES extra field, which is parsed in full only with the `aes` feature.
+    let aes_strength = match compression {
+        AES_COMPRESSION_METHOD => {
+            crate::spec::extra_field::find_extra_field(extra_field, AES_HEADER_ID).and_then(|data| data.get(4).copied())
+        }
+        _ => None,
+    };
+
+    match aes_strength {
+        Some(1) => Some(EncryptionMethod::Aes128),
+        Some(2) => Some(EncryptionMethod::Aes192),
+        Some(3) => Some(EncryptionMethod::Aes256),
+        _ => Some(EncryptionMethod::ZipCrypto),
+    }
+}
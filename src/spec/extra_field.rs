@@ -0,0 +1,435 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#451
+
+#[cfg(feature = "tokio")]
+use std::collections::BTreeMap;
+
+/// The header ID of the Info-ZIP Unicode Path extra field.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#469
+#[cfg(feature = "tokio")]
+pub(crate) const UNICODE_PATH_HEADER_ID: u16 = 0x7075;
+
+/// The header ID of the ZIP64 extended information extra field.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#452
+#[cfg(feature = "tokio")]
+pub(crate) const ZIP64_HEADER_ID: u16 = 0x0001;
+
+/// The header ID of the Android alignment extra field, as written by `zipalign` & `apksigner`.
+pub(crate) const ALIGNMENT_HEADER_ID: u16 = 0xD935;
+
+/// The header ID of the NTFS extra field.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#455
+#[cfg(feature = "tokio")]
+pub(crate) const NTFS_HEADER_ID: u16 = 0x000A;
+
+/// The NTFS attribute tag of the modification, access, and creation timestamps.
+#[cfg(feature = "tokio")]
+const NTFS_TIMESTAMPS_TAG: u16 = 0x0001;
+
+/// The header ID of the extended timestamp extra field.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#461
+pub(crate) const EXTENDED_TIMESTAMP_HEADER_ID: u16 = 0x5455;
+
+/// The flag within an extended timestamp extra field which denotes that the modification time is present.
+#[cfg(feature = "tokio")]
+const EXTENDED_TIMESTAMP_MODIFIED: u8 = 0x01;
+
+/// The number of timestamps which may be present within an extended timestamp extra field (modification, access, and
+/// creation, in that order).
+#[cfg(feature = "tokio")]
+const EXTENDED_TIMESTAMP_COUNT: usize = 3;
+
+/// The header ID of the Info-ZIP Unix extra field (type 3), which stores an entry's owner UID & GID.
+pub(crate) const UNIX_OWNER_HEADER_ID: u16 = 0x7875;
+
+/// The version of the Info-ZIP Unix extra field (type 3) written.
+const UNIX_OWNER_VERSION: u8 = 1;
+
+/// The header ID of the WinZip AES extra field.
+///
+/// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md (Appendix E)
+#[cfg(feature = "tokio")]
+pub(crate) const AES_HEADER_ID: u16 = 0x9901;
+
+/// The compression method stored within the headers of AES-encrypted entries in place of their actual method.
+#[cfg(feature = "tokio")]
+pub(crate) const AES_COMPRESSION_METHOD: u16 = 99;
+
+/// The vendor ID stored within all WinZip AES extra fields.
+#[cfg(all(feature = "aes", feature = "tokio"))]
+const AES_VENDOR_ID: [u8; 2] = *b"AE";
+
+/// The header ID of the extra field in which an entry's custom metadata map is stored.
+///
+/// This lies within the range of IDs which the specification leaves to third-party mappings & isn't used by any known
+/// implementation (the ID's bytes spell "az" when written).
+#[cfg(feature = "tokio")]
+pub(crate) const METADATA_HEADER_ID: u16 = 0x7A61;
+
+/// The only version of the Info-ZIP Unicode Path extra field currently defined.
+#[cfg(feature = "tokio")]
+const UNICODE_PATH_VERSION: u8 = 1;
+
+/// A typed extra field which can be attached to an entry when writing (see [`ZipEntryBuilder::add_extra_field()`]).
+///
+/// [`ZipEntryBuilder::add_extra_field()`]: crate::ZipEntryBuilder::add_extra_field
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtraField {
+    /// An extended timestamp extra field, holding the modification, access, and creation times (as seconds since the
+    /// Unix epoch, clamped to the range of a signed 32-bit integer).
+    ///
+    /// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#461
+    ExtendedTimestamp { modified: Option<i64>, accessed: Option<i64>, created: Option<i64> },
+    /// An Info-ZIP Unix extra field (type 3), holding the UID & GID of the entry's owner.
+    UnixOwner { uid: u32, gid: u32 },
+    /// An Android alignment extra field, holding the alignment and the number of zeroed padding bytes which follow it.
+    ///
+    /// To pad an entry's data to an alignment automatically, see [`ZipEntryBuilder::alignment()`] instead.
+    ///
+    /// [`ZipEntryBuilder::alignment()`]: crate::ZipEntryBuilder::alignment
+    AlignmentPadding { alignment: u16, padding: u16 },
+    /// An extra field of any other type, holding its header ID and raw data block.
+    Custom { header_id: u16, data: Vec<u8> },
+}
+
+impl ExtraField {
+    /// Returns the header ID of this extra field.
+    pub fn header_id(&self) -> u16 {
+        match self {
+            ExtraField::ExtendedTimestamp { .. } => EXTENDED_TIMESTAMP_HEADER_ID,
+            ExtraField::UnixOwner { .. } => UNIX_OWNER_HEADER_ID,
+            ExtraField::AlignmentPadding { .. } => ALIGNMENT_HEADER_ID,
+            ExtraField::Custom { header_id, .. } => *header_id,
+        }
+    }
+
+    /// Encodes this extra field's data block (ie. excluding its header ID & size).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            ExtraField::ExtendedTimestamp { modified, accessed, created } => {
+                let timestamps = [*modified, *accessed, *created];
+                let mut data = vec![0];
+
+                for (index, timestamp) in timestamps.into_iter().enumerate() {
+                    if let Some(timestamp) = timestamp {
+                        let timestamp = timestamp.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+                        data[0] |= 1 << index;
+                        data.extend_from_slice(&timestamp.to_le_bytes());
+                    }
+                }
+
+                data
+            }
+            ExtraField::UnixOwner { uid, gid } => {
+                let mut data = vec![UNIX_OWNER_VERSION];
+
+                for id in [uid, gid] {
+                    data.push(4);
+                    data.extend_from_slice(&id.to_le_bytes());
+                }
+
+                data
+            }
+            ExtraField::AlignmentPadding { alignment, padding } => build_alignment(*alignment, *padding as usize),
+            ExtraField::Custom { data, .. } => data.clone(),
+        }
+    }
+}
+
+/// An iterator over the header IDs & data blocks within a raw extra field.
+///
+/// Iteration stops early if a block's declared size exceeds the remaining data.
+#[cfg(feature = "tokio")]
+pub(crate) struct ExtraFieldIter<'a> {
+    data: &'a [u8],
+}
+
+#[cfg(feature = "tokio")]
+impl<'a> Iterator for ExtraFieldIter<'a> {
+    type Item = (u16, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < 4 {
+            return None;
+        }
+
+        let header_id = u16::from_le_bytes([self.data[0], self.data[1]]);
+        let size = u16::from_le_bytes([self.data[2], self.data[3]]) as usize;
+        let block = self.data.get(4..4 + size)?;
+
+        self.data = &self.data[4 + size..];
+        Some((header_id, block))
+    }
+}
+
+/// Returns an iterator over the header IDs & data blocks within a raw extra field.
+#[cfg(feature = "tokio")]
+pub(crate) fn extra_fields(data: &[u8]) -> ExtraFieldIter<'_> {
+    ExtraFieldIter { data }
+}
+
+/// Returns the data block of the first extra field with the provided header ID, if one exists.
+#[cfg(feature = "tokio")]
+pub(crate) fn find_extra_field(data: &[u8], header_id: u16) -> Option<&[u8]> {
+    extra_fields(data).find(|(id, _)| *id == header_id).map(|(_, block)| block)
+}
+
+/// Appends an extra field block with the provided header ID & data onto a raw extra field.
+#[cfg(feature = "tokio")]
+pub(crate) fn push_extra_field(buffer: &mut Vec<u8>, header_id: u16, data: &[u8]) {
+    buffer.extend_from_slice(&header_id.to_le_bytes());
+    buffer.extend_from_slice(&(data.len() as u16).to_le_bytes());
+    buffer.extend_from_slice(data);
+}
+
+/// Returns a copy of a raw extra field with all blocks of the provided header ID removed.
+#[cfg(feature = "tokio")]
+pub(crate) fn remove_extra_field(data: &[u8], header_id: u16) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity(data.len());
+
+    for (id, block) in extra_fields(data).filter(|(id, _)| *id != header_id) {
+        push_extra_field(&mut buffer, id, block);
+    }
+
+    buffer
+}
+
+/// Parses an NTFS extra field's data block, returning the raw modification, access, and creation timestamps (in that
+/// order) if the timestamps attribute is present.
+#[cfg(feature = "tokio")]
+pub(crate) fn parse_ntfs_timestamps(data: &[u8]) -> Option<[u64; 3]> {
+    // The attributes follow four reserved bytes and share the same tag & size layout as the extra field blocks.
+    let (_, attribute) = extra_fields(data.get(4..)?).find(|(tag, _)| *tag == NTFS_TIMESTAMPS_TAG)?;
+    let attribute = attribute.get(..24)?;
+    let timestamp = |index: usize| u64::from_le_bytes(attribute[index * 8..index * 8 + 8].try_into().unwrap());
+
+    Some([timestamp(0), timestamp(1), timestamp(2)])
+}
+
+/// Parses an Info-ZIP Unix extra field (type 3)'s data block, returning the UID & GID (in that order).
+///
+/// Each ID is prefixed by its size in bytes, which may be up to eight. IDs which don't fit within 32 bits are treated
+/// as absent.
+#[cfg(feature = "tokio")]
+pub(crate) fn parse_unix_owner(data: &[u8]) -> Option<(u32, u32)> {
+    fn take_id(data: &mut &[u8]) -> Option<u32> {
+        let size = *data.first()? as usize;
+        let bytes = data.get(1..1 + size).filter(|bytes| bytes.len() <= 8)?;
+        let mut buffer = [0; 8];
+
+        buffer[..size].copy_from_slice(bytes);
+        *data = &data[1 + size..];
+        u64::from_le_bytes(buffer).try_into().ok()
+    }
+
+    let (version, mut data) = data.split_first()?;
+    if *version != UNIX_OWNER_VERSION {
+        return None;
+    }
+
+    let uid = take_id(&mut data)?;
+    let gid = take_id(&mut data)?;
+
+    Some((uid, gid))
+}
+
+/// Parses an Info-ZIP Unicode Path extra field's data block, returning the UTF-8 filename if the field's CRC32 matches
+/// the raw filename stored within the header.
+#[cfg(feature = "tokio")]
+pub(crate) fn parse_unicode_path(data: &[u8], raw_filename: &[u8]) -> Option<String> {
+    if data.len() < 5 || data[0] != UNICODE_PATH_VERSION {
+        return None;
+    }
+
+    let crc = u32::from_le_bytes(data[1..5].try_into().unwrap());
+    if crc != crate::spec::crc::crc32(raw_filename) {
+        return None;
+    }
+
+    String::from_utf8(data[5..].to_vec()).ok()
+}
+
+/// Builds an Info-ZIP Unicode Path extra field's data block for the provided filename & raw header filename.
+#[cfg(feature = "tokio")]
+pub(crate) fn build_unicode_path(filename: &str, raw_filename: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(5 + filename.len());
+
+    data.push(UNICODE_PATH_VERSION);
+    data.extend_from_slice(&crate::spec::crc::crc32(raw_filename).to_le_bytes());
+    data.extend_from_slice(filename.as_bytes());
+
+    data
+}
+
+/// Parses an extended timestamp extra field's data block, returning the modification, access, and creation times (in
+/// that order) as seconds since the Unix epoch.
+///
+/// The flags denote which times are present in the local header variant of the field, but the central directory
+/// variant only ever stores the modification time. As such, any flagged time without data is treated as absent.
+#[cfg(feature = "tokio")]
+pub(crate) fn parse_extended_timestamp(data: &[u8]) -> [Option<i64>; EXTENDED_TIMESTAMP_COUNT] {
+    let mut timestamps = [None; EXTENDED_TIMESTAMP_COUNT];
+    let (flags, mut data) = match data.split_first() {
+        Some((flags, data)) => (*flags, data),
+        None => return timestamps,
+    };
+
+    for (index, timestamp) in timestamps.iter_mut().enumerate() {
+        if flags & (1 << index) == 0 {
+            continue;
+        }
+        if data.len() < 4 {
+            break;
+        }
+
+        *timestamp = Some(i32::from_le_bytes(data[..4].try_into().unwrap()).into());
+        data = &data[4..];
+    }
+
+    timestamps
+}
+
+/// Builds an Android alignment extra field's data block, consisting of the alignment followed by zeroed padding.
+pub(crate) fn build_alignment(alignment: u16, padding: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(2 + padding);
+
+    data.extend_from_slice(&alignment.to_le_bytes());
+    data.resize(2 + padding, 0);
+
+    data
+}
+
+/// Builds a ZIP64 extended information extra field's data block from the values which overflow their header fields.
+///
+/// A value must only be present if its header field has been set to `0xFFFFFFFF`, with those present appearing in the
+/// order of the uncompressed size, compressed size, and then local file header offset.
+#[cfg(feature = "tokio")]
+pub(crate) fn build_zip64(
+    uncompressed_size: Option<u64>,
+    compressed_size: Option<u64>,
+    offset: Option<u64>,
+) -> Vec<u8> {
+    [uncompressed_size, compressed_size, offset].into_iter().flatten().flat_map(u64::to_le_bytes).collect()
+}
+
+/// Builds an extended timestamp extra field's data block containing only a modification time (as seconds since the
+/// Unix epoch, clamped to the range of a signed 32-bit integer).
+///
+/// As only the modification time is stored, the same data block is valid in both the local & central headers.
+#[cfg(feature = "tokio")]
+pub(crate) fn build_extended_timestamp(modified: i64) -> Vec<u8> {
+    let modified = modified.clamp(i32::MIN.into(), i32::MAX.into()) as i32;
+    let mut data = Vec::with_capacity(5);
+
+    data.push(EXTENDED_TIMESTAMP_MODIFIED);
+    data.extend_from_slice(&modified.to_le_bytes());
+
+    data
+}
+
+/// Parses a metadata extra field's data block, returning [`None`] if it's truncated or any key or value isn't valid
+/// UTF-8.
+///
+/// The block consists of a two-byte entry count followed by each key & value in turn, each prefixed by its two-byte
+/// length.
+#[cfg(feature = "tokio")]
+pub(crate) fn parse_metadata(data: &[u8]) -> Option<BTreeMap<String, String>> {
+    fn take_string(data: &mut &[u8]) -> Option<String> {
+        let length = u16::from_le_bytes([*data.first()?, *data.get(1)?]) as usize;
+        let string = std::str::from_utf8(data.get(2..2 + length)?).ok()?.to_owned();
+
+        *data = &data[2 + length..];
+        Some(string)
+    }
+
+    let count = u16::from_le_bytes([*data.first()?, *data.get(1)?]);
+    let mut data = &data[2..];
+    let mut metadata = BTreeMap::new();
+
+    for _ in 0..count {
+        let key = take_string(&mut data)?;
+        let value = take_string(&mut data)?;
+        metadata.insert(key, value);
+    }
+
+    Some(metadata)
+}
+
+/// Builds a metadata extra field's data block, returning [`None`] if it wouldn't fit within a single extra field block.
+#[cfg(feature = "tokio")]
+pub(crate) fn build_metadata(metadata: &BTreeMap<String, String>) -> Option<Vec<u8>> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&u16::try_from(metadata.len()).ok()?.to_le_bytes());
+
+    for string in metadata.iter().flat_map(|(key, value)| [key, value]) {
+        data.extend_from_slice(&u16::try_from(string.len()).ok()?.to_le_bytes());
+        data.extend_from_slice(string.as_bytes());
+    }
+
+    u16::try_from(data.len()).ok()?;
+    Some(data)
+}
+
+/// The parameters stored within a WinZip AES extra field.
+#[cfg(all(feature = "aes", feature = "tokio"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AesExtraField {
+    /// The vendor version (1 for AE-1, 2 for AE-2).
+    pub(crate) version: u16,
+    /// The encryption strength (1 for AES-128, 2 for AES-192, 3 for AES-256).
+    pub(crate) strength: u8,
+    /// The compression method used prior to encryption.
+    pub(crate) compression: u16,
+}
+
+#[cfg(all(feature = "aes", feature = "tokio"))]
+impl AesExtraField {
+    /// Returns the length of the encryption key in bytes.
+    pub(crate) fn key_length(&self) -> usize {
+        8 + 8 * self.strength as usize
+    }
+
+    /// Returns the length of the salt which precedes the encrypted data in bytes.
+    pub(crate) fn salt_length(&self) -> usize {
+        self.key_length() / 2
+    }
+
+    /// Returns whether the entry's CRC32 value is stored (AE-1) or zeroed (AE-2).
+    pub(crate) fn crc_stored(&self) -> bool {
+        self.version == 1
+    }
+}
+
+/// Parses a WinZip AES extra field's data block, returning [`None`] if its vendor ID or strength is unrecognised.
+#[cfg(all(feature = "aes", feature = "tokio"))]
+pub(crate) fn parse_aes(data: &[u8]) -> Option<AesExtraField> {
+    if data.len() < 7 || data[2..4] != AES_VENDOR_ID || !(1..=3).contains(&data[4]) {
+        return None;
+    }
+
+    Some(AesExtraField {
+        version: u16::from_le_bytes([data[0], data[1]]),
+        strength: data[4],
+        compression: u16::from_le_bytes([data[5], data[6]]),
+    })
+}
+
+/// Builds a WinZip AES extra field's data block.
+#[cfg(all(feature = "aes", feature = "tokio"))]
+pub(crate) fn build_aes(field: &AesExtraField) -> Vec<u8> {
+    let mut data = Vec::with_capacity(7);
+
+    data.extend_from_slice(&field.version.to_le_bytes());
+    data.extend_from_slice(&AES_VENDOR_ID);
+    data.push(field.strength);
+    data.extend_from_slice(&field.compression.to_le_bytes());
+
+    data
+}
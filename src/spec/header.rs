@@ -2,6 +2,7 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#437
+#[derive(Debug, Copy, Clone)]
 pub struct LocalFileHeader {
     pub version: u16,
     pub flags: GeneralPurposeFlag,
@@ -16,14 +17,19 @@ pub struct LocalFileHeader {
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#444
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub struct GeneralPurposeFlag {
     pub encrypted: bool,
+    /// The method-specific compression options held within bits 1 & 2 (eg. Implode's dictionary size & tree count).
+    pub compression_options: u8,
     pub data_descriptor: bool,
+    /// Whether the entry is encrypted via PKWARE strong encryption (bit 6), rather than traditional encryption.
+    pub strong_encryption: bool,
     pub filename_unicode: bool,
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4312
+#[derive(Debug, Copy, Clone)]
 pub struct CentralDirectoryRecord {
     pub v_made_by: u16,
     pub v_needed: u16,
@@ -44,12 +50,37 @@ pub struct CentralDirectoryRecord {
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4316
+#[derive(Debug, Copy, Clone)]
 pub struct EndOfCentralDirectoryHeader {
-    pub(crate) disk_num: u16,
-    pub(crate) start_cent_dir_disk: u16,
-    pub(crate) num_of_entries_disk: u16,
-    pub(crate) num_of_entries: u16,
-    pub(crate) size_cent_dir: u32,
-    pub(crate) cent_dir_offset: u32,
-    pub(crate) file_comm_length: u16,
+    pub disk_num: u16,
+    pub start_cent_dir_disk: u16,
+    pub num_of_entries_disk: u16,
+    pub num_of_entries: u16,
+    pub size_cent_dir: u32,
+    pub cent_dir_offset: u32,
+    pub file_comm_length: u16,
+}
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4314
+#[derive(Debug, Copy, Clone)]
+#[cfg(feature = "tokio")]
+pub struct Zip64EndOfCentralDirectoryRecord {
+    pub size_of_record: u64,
+    pub version_made_by: u16,
+    pub version_needed: u16,
+    pub disk_num: u32,
+    pub start_cent_dir_disk: u32,
+    pub num_of_entries_disk: u64,
+    pub num_of_entries: u64,
+    pub size_cent_dir: u64,
+    pub cent_dir_offset: u64,
+}
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4315
+#[derive(Debug, Copy, Clone)]
+#[cfg(feature = "tokio")]
+pub struct Zip64EndOfCentralDirectoryLocator {
+    pub start_zip64_eocdr_disk: u32,
+    pub zip64_eocdr_offset: u64,
+    pub num_of_disks: u32,
 }
@@ -0,0 +1,204 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! <https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#4316>
+//!
+//! As with other ZIP libraries, we face the predicament that the end of central directory record may contain a
+//! variable-length file comment. As a result, we cannot just make the assumption that the start of this record is
+//! 18 bytes (the length of the EOCDR) offset from the end of the data - we must locate it ourselves.
+//!
+//! The `zip-rs` crate handles this by reading in reverse from the end of the data. This involes seeking backwards
+//! by a single byte each iteration and reading 4 bytes into a u32. Whether this is performant/acceptable within a
+//! a non-async context, I'm unsure, but it isn't desirable within an async context.
+//!
+//! Reading in reverse is still desirable as the use of file comments is limited and they're unlikely to be large.
+//!
+//! The below method is one that compromises on these two contention points. Please submit an issue or PR if you know
+//! of a better algorithm for this (and have tested/verified its performance).
+//!
+//! The search itself performs no IO. Instead, [`EocdrLocator`] describes each window of data it needs and is then
+//! handed that window to search, allowing it to be driven by any runtime (or none at all, via [`locate_eocdr()`]).
+
+use crate::error::{Result, ZipError};
+use crate::spec::consts::{EOCDR_LENGTH, EOCDR_SIGNATURE, SIGNATURE_LENGTH};
+
+/// The default length of the window searched on each iteration, equal to 2KiB.
+const BUFFER_SIZE: usize = 2048;
+
+/// The length of the window searched on each iteration by [`SearchStrategy::Memchr`], equal to 16KiB.
+#[cfg(feature = "memchr")]
+const MEMCHR_BUFFER_SIZE: usize = 16384;
+
+/// The upper bound of where the EOCDR signature cannot be located.
+const EOCDR_UPPER_BOUND: u64 = EOCDR_LENGTH as u64;
+
+/// The lower bound of where the EOCDR signature cannot be located.
+const EOCDR_LOWER_BOUND: u64 = EOCDR_UPPER_BOUND + SIGNATURE_LENGTH as u64 + u16::MAX as u64;
+
+/// The algorithm used to search each window of data for the EOCDR signature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchStrategy {
+    /// A naive reverse linear scan over 2KiB windows.
+    #[default]
+    ReverseScan,
+    /// A reverse search for the signature's first byte via `memchr::memrchr()` over 16KiB windows, with the remaining
+    /// signature bytes then compared at each candidate.
+    ///
+    /// Over larger windows (eg. those searched within ZIP files with long comments), the vectorised search can
+    /// outweigh its per-candidate overhead. Use the `locator` benchmark to compare the two on the target device.
+    #[cfg(feature = "memchr")]
+    Memchr,
+}
+
+impl SearchStrategy {
+    /// Returns the length of the window searched on each iteration.
+    fn window(self) -> usize {
+        match self {
+            SearchStrategy::ReverseScan => BUFFER_SIZE,
+            #[cfg(feature = "memchr")]
+            SearchStrategy::Memchr => MEMCHR_BUFFER_SIZE,
+        }
+    }
+
+    /// Searches the buffer for the signature, returning the index of its last byte.
+    fn search(self, buffer: &[u8], signature: &[u8]) -> Option<usize> {
+        match self {
+            SearchStrategy::ReverseScan => reverse_search_buffer(buffer, signature),
+            #[cfg(feature = "memchr")]
+            SearchStrategy::Memchr => memchr_search_buffer(buffer, signature),
+        }
+    }
+}
+
+/// A runtime-independent search for the `end of central directory record` offset.
+///
+/// This method involves buffered reading in reverse and reverse linear searching along those buffers for the EOCDR
+/// signature. As a result of this buffered approach, we reduce seeks when compared to `zip-rs`'s method by a factor
+/// of the buffer size. We also then don't have to do individual u32 reads against the upstream reader.
+///
+/// Whilst I haven't done any in-depth benchmarks, when reading a ZIP file with the maximum length comment, this method
+/// saw a reduction in location time by a factor of 500 when compared with the `zip-rs` method.
+///
+/// ### Usage
+/// Construct the locator with the length of the data, then repeatedly read the range described by
+/// [`EocdrLocator::window()`] and pass it to [`EocdrLocator::search()`] until an offset (or an error) is returned.
+#[derive(Debug, Clone)]
+pub struct EocdrLocator {
+    length: u64,
+    window: usize,
+    end: u64,
+    strategy: SearchStrategy,
+}
+
+impl EocdrLocator {
+    /// Constructs a new locator for data of the provided length.
+    pub fn new(length: u64) -> Self {
+        Self::with_strategy(length, SearchStrategy::default())
+    }
+
+    /// Constructs a new locator for data of the provided length, which searches using the provided strategy.
+    pub fn with_strategy(length: u64, strategy: SearchStrategy) -> Self {
+        Self { length, window: strategy.window(), end: length.saturating_sub(EOCDR_UPPER_BOUND), strategy }
+    }
+
+    /// Returns the offset & length of the data which should next be passed to [`EocdrLocator::search()`].
+    pub fn window(&self) -> (u64, usize) {
+        let position = self.end.saturating_sub(self.window as u64);
+        (position, (self.end - position) as usize)
+    }
+
+    /// Shrinks the windows searched to the provided length.
+    ///
+    /// This allows sources whose buffer is smaller than a window to be searched without refilling their buffer.
+    /// Returns an error if the length is too small to ever contain the signature.
+    pub fn shrink(&mut self, length: usize) -> Result<()> {
+        if length <= SIGNATURE_LENGTH {
+            return Err(ZipError::UnableToLocateEOCDR);
+        }
+
+        self.window = std::cmp::min(self.window, length);
+        Ok(())
+    }
+
+    /// Searches the data of the current window, returning the EOCDR offset if it was found.
+    ///
+    /// If it wasn't, the locator moves onto the next window, or returns an error if there's nowhere left to search.
+    pub fn search(&mut self, buffer: &[u8]) -> Result<Option<u64>> {
+        let (position, needed) = self.window();
+        let buffer = buffer.get(..needed).ok_or(ZipError::UnableToLocateEOCDR)?;
+
+        if let Some(match_index) = self.strategy.search(buffer, &EOCDR_SIGNATURE.to_le_bytes()) {
+            return Ok(Some(position + ((match_index + 1) - SIGNATURE_LENGTH) as u64));
+        }
+
+        // If we hit the start of the data or the lower bound, we're unable to locate the EOCDR.
+        if position == 0 || position <= self.length.saturating_sub(EOCDR_LOWER_BOUND) {
+            return Err(ZipError::UnableToLocateEOCDR);
+        }
+
+        // To handle the case where the EOCDR signature crosses window boundaries, we simply overlap windows by the
+        // signature length. This significantly reduces the complexity of handling partial matches with very little
+        // overhead.
+        self.end = position + SIGNATURE_LENGTH as u64;
+        Ok(None)
+    }
+}
+
+/// Locates the `end of central directory record` offset within in-memory data.
+pub fn locate_eocdr(data: &[u8]) -> Result<u64> {
+    locate_eocdr_with_strategy(data, SearchStrategy::default())
+}
+
+/// Locates the `end of central directory record` offset within in-memory data, using the provided strategy.
+pub fn locate_eocdr_with_strategy(data: &[u8], strategy: SearchStrategy) -> Result<u64> {
+    let mut locator = EocdrLocator::with_strategy(data.len() as u64, strategy);
+
+    loop {
+        let (position, needed) = locator.window();
+        let start = position as usize;
+
+        if let Some(offset) = locator.search(&data[start..start + needed])? {
+            return Ok(offset);
+        }
+    }
+}
+
+/// A naive reverse linear search along the buffer for the specified signature bytes.
+///
+/// This is already surprisingly performant. For instance, using memchr::memchr() to match for the first byte of the
+/// signature, and then manual byte comparisons for the remaining signature bytes was actually slower by a factor of
+/// 2.25. This method was explored as tokio's `read_until()` implementation uses memchr::memchr().
+pub(crate) fn reverse_search_buffer(buffer: &[u8], signature: &[u8]) -> Option<usize> {
+    'outer: for index in (0..buffer.len()).rev() {
+        for (signature_index, signature_byte) in signature.iter().rev().enumerate() {
+            if let Some(next_index) = index.checked_sub(signature_index) {
+                if buffer[next_index] != *signature_byte {
+                    continue 'outer;
+                }
+            } else {
+                break 'outer;
+            }
+        }
+        return Some(index);
+    }
+    None
+}
+
+/// A reverse search along the buffer for the specified signature bytes, using `memchr::memrchr()` to locate candidates.
+///
+/// Matches are reported in the same manner as [`reverse_search_buffer()`] (ie. the index of the signature's last byte).
+#[cfg(feature = "memchr")]
+pub(crate) fn memchr_search_buffer(buffer: &[u8], signature: &[u8]) -> Option<usize> {
+    let (first, _) = signature.split_first()?;
+    let mut end = buffer.len();
+
+    while let Some(index) = memchr::memrchr(*first, &buffer[..end]) {
+        if buffer[index..].starts_with(signature) {
+            return Some(index + signature.len() - 1);
+        }
+
+        end = index;
+    }
+
+    None
+}
@@ -1,10 +1,21 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(all(feature = "aes", feature = "tokio"))]
+pub(crate) mod aes;
 pub(crate) mod attribute;
+pub(crate) mod capabilities;
 pub mod compression;
 pub(crate) mod consts;
+pub(crate) mod crc;
+#[cfg(all(feature = "date", feature = "tokio"))]
 pub(crate) mod date;
+pub(crate) mod encoding;
+pub(crate) mod encryption;
+pub(crate) mod extra_field;
 pub(crate) mod header;
+pub(crate) mod locator;
 pub(crate) mod parse;
 pub(crate) mod version;
+#[cfg(feature = "tokio")]
+pub(crate) mod zipcrypto;
@@ -1,10 +1,12 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
-use crate::error::Result;
+use crate::error::{Result, ZipError};
+use crate::spec::consts::{CDH_LENGTH, CDH_SIGNATURE, EOCDR_LENGTH, EOCDR_SIGNATURE};
+use crate::spec::consts::{LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH};
 use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader, GeneralPurposeFlag, LocalFileHeader};
-
-use tokio::io::{AsyncRead, AsyncReadExt};
+#[cfg(feature = "tokio")]
+use crate::spec::header::{Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord};
 
 impl LocalFileHeader {
     pub fn as_slice(&self) -> [u8; 26] {
@@ -36,12 +38,17 @@ impl GeneralPurposeFlag {
             false => 0x0,
             true => 0x8,
         };
+        let strong_encryption: u16 = match self.strong_encryption {
+            false => 0x0,
+            true => 0x40,
+        };
         let filename_unicode: u16 = match self.filename_unicode {
             false => 0x0,
             true => 0x800,
         };
+        let compression_options = u16::from(self.compression_options & 0b11) << 1;
 
-        (encrypted | data_descriptor | filename_unicode).to_le_bytes()
+        (encrypted | compression_options | data_descriptor | strong_encryption | filename_unicode).to_le_bytes()
     }
 }
 
@@ -88,6 +95,40 @@ impl EndOfCentralDirectoryHeader {
     }
 }
 
+#[cfg(feature = "tokio")]
+impl Zip64EndOfCentralDirectoryRecord {
+    pub fn as_slice(&self) -> [u8; 52] {
+        let mut array = [0; 52];
+        let mut cursor = 0;
+
+        array_push!(array, cursor, self.size_of_record.to_le_bytes());
+        array_push!(array, cursor, self.version_made_by.to_le_bytes());
+        array_push!(array, cursor, self.version_needed.to_le_bytes());
+        array_push!(array, cursor, self.disk_num.to_le_bytes());
+        array_push!(array, cursor, self.start_cent_dir_disk.to_le_bytes());
+        array_push!(array, cursor, self.num_of_entries_disk.to_le_bytes());
+        array_push!(array, cursor, self.num_of_entries.to_le_bytes());
+        array_push!(array, cursor, self.size_cent_dir.to_le_bytes());
+        array_push!(array, cursor, self.cent_dir_offset.to_le_bytes());
+
+        array
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl Zip64EndOfCentralDirectoryLocator {
+    pub fn as_slice(&self) -> [u8; 16] {
+        let mut array = [0; 16];
+        let mut cursor = 0;
+
+        array_push!(array, cursor, self.start_zip64_eocdr_disk.to_le_bytes());
+        array_push!(array, cursor, self.zip64_eocdr_offset.to_le_bytes());
+        array_push!(array, cursor, self.num_of_disks.to_le_bytes());
+
+        array
+    }
+}
+
 impl From<[u8; 26]> for LocalFileHeader {
     fn from(value: [u8; 26]) -> LocalFileHeader {
         LocalFileHeader {
@@ -108,10 +149,12 @@ impl From<[u8; 26]> for LocalFileHeader {
 impl From<u16> for GeneralPurposeFlag {
     fn from(value: u16) -> GeneralPurposeFlag {
         let encrypted = !matches!(value & 0x1, 0);
+        let compression_options = ((value & 0x6) >> 1) as u8;
         let data_descriptor = !matches!((value & 0x8) >> 3, 0);
+        let strong_encryption = !matches!((value & 0x40) >> 6, 0);
         let filename_unicode = !matches!((value & 0x800) >> 11, 0);
 
-        GeneralPurposeFlag { encrypted, data_descriptor, filename_unicode }
+        GeneralPurposeFlag { encrypted, compression_options, data_descriptor, strong_encryption, filename_unicode }
     }
 }
 
@@ -153,26 +196,37 @@ impl From<[u8; 18]> for EndOfCentralDirectoryHeader {
 }
 
 impl LocalFileHeader {
-    pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<LocalFileHeader> {
-        let mut buffer: [u8; 26] = [0; 26];
-        reader.read_exact(&mut buffer).await?;
-        Ok(LocalFileHeader::from(buffer))
+    /// Parses the header's signature alongside its fixed-size fields, returning an error if the signature doesn't
+    /// match.
+    pub fn parse_with_signature(buffer: &[u8; SIGNATURE_LENGTH + LFH_LENGTH]) -> Result<LocalFileHeader> {
+        check_signature(buffer, LFH_SIGNATURE)?;
+        Ok(LocalFileHeader::from(<[u8; LFH_LENGTH]>::try_from(&buffer[SIGNATURE_LENGTH..]).unwrap()))
+    }
+}
+
+impl CentralDirectoryRecord {
+    /// Parses the record's signature alongside its fixed-size fields, returning an error if the signature doesn't
+    /// match.
+    pub fn parse_with_signature(buffer: &[u8; SIGNATURE_LENGTH + CDH_LENGTH]) -> Result<CentralDirectoryRecord> {
+        check_signature(buffer, CDH_SIGNATURE)?;
+        Ok(CentralDirectoryRecord::from(<[u8; CDH_LENGTH]>::try_from(&buffer[SIGNATURE_LENGTH..]).unwrap()))
     }
 }
 
 impl EndOfCentralDirectoryHeader {
-    pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<EndOfCentralDirectoryHeader> {
-        let mut buffer: [u8; 18] = [0; 18];
-        reader.read_exact(&mut buffer).await?;
-        Ok(EndOfCentralDirectoryHeader::from(buffer))
+    /// Parses the record's signature alongside its fixed-size fields, returning an error if the signature doesn't
+    /// match.
+    pub fn parse_with_signature(buffer: &[u8; SIGNATURE_LENGTH + EOCDR_LENGTH]) -> Result<EndOfCentralDirectoryHeader> {
+        check_signature(buffer, EOCDR_SIGNATURE)?;
+        Ok(EndOfCentralDirectoryHeader::from(<[u8; EOCDR_LENGTH]>::try_from(&buffer[SIGNATURE_LENGTH..]).unwrap()))
     }
 }
 
-impl CentralDirectoryRecord {
-    pub async fn from_reader<R: AsyncRead + Unpin>(reader: &mut R) -> Result<CentralDirectoryRecord> {
-        let mut buffer: [u8; 42] = [0; 42];
-        reader.read_exact(&mut buffer).await?;
-        Ok(CentralDirectoryRecord::from(buffer))
+/// Returns an error if the signature at the start of a buffer doesn't match the expected signature.
+pub(crate) fn check_signature(buffer: &[u8], expected: u32) -> Result<()> {
+    match u32::from_le_bytes(buffer[..SIGNATURE_LENGTH].try_into().unwrap()) {
+        actual if actual == expected => Ok(()),
+        actual => Err(ZipError::UnexpectedHeaderError(actual, expected)),
     }
 }
 
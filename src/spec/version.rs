@@ -1,13 +1,23 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+#[cfg(feature = "tokio")]
 use crate::entry::ZipEntry;
-#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+#[cfg(all(
+    feature = "tokio",
+    any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz")
+))]
 use crate::spec::compression::Compression;
 
+#[cfg(feature = "tokio")]
 pub(crate) const SPEC_VERSION_MADE_BY: u16 = 63;
 
+/// The version needed to extract entries which use ZIP64 extensions (and of the ZIP64 end of central directory record).
+#[cfg(feature = "tokio")]
+pub(crate) const ZIP64_VERSION_NEEDED: u16 = 45;
+
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#443
+#[cfg(feature = "tokio")]
 pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
     let mut version = match entry.compression() {
         #[cfg(feature = "deflate")]
@@ -27,7 +37,7 @@ pub fn as_needed_to_extract(entry: &ZipEntry) -> u16 {
 }
 
 // https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#442
-pub fn as_made_by() -> u16 {
-    // Default to UNIX mapping for the moment.
-    3 << 8 | SPEC_VERSION_MADE_BY
+#[cfg(feature = "tokio")]
+pub fn as_made_by(entry: &ZipEntry) -> u16 {
+    u16::from(entry.attribute_compatibility()) << 8 | SPEC_VERSION_MADE_BY
 }
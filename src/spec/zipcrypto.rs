@@ -0,0 +1,97 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+// https://github.com/Majored/rs-async-zip/blob/main/SPECIFICATION.md#61
+
+/// The length of the encryption header which precedes the data of a ZipCrypto-encrypted entry.
+pub(crate) const ZIPCRYPTO_HEADER_LENGTH: usize = 12;
+
+/// The version needed to extract an entry encrypted using ZipCrypto.
+pub(crate) const ZIPCRYPTO_VERSION_NEEDED: u16 = 20;
+
+/// The CRC32 lookup table used by the ZipCrypto key update function.
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut index = 0;
+
+    while index < 256 {
+        let mut value = index as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            value = if value & 1 == 1 { 0xEDB88320 ^ (value >> 1) } else { value >> 1 };
+            bit += 1;
+        }
+
+        table[index] = value;
+        index += 1;
+    }
+
+    table
+}
+
+fn crc32_update(crc: u32, byte: u8) -> u32 {
+    CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+}
+
+/// The three keys which make up the state of the traditional PKWARE (ZipCrypto) stream cipher.
+#[derive(Clone)]
+pub(crate) struct ZipCryptoKeys {
+    key0: u32,
+    key1: u32,
+    key2: u32,
+}
+
+impl ZipCryptoKeys {
+    /// Constructs the initial key state for the provided password.
+    pub(crate) fn new(password: &[u8]) -> Self {
+        let mut keys = Self { key0: 0x12345678, key1: 0x23456789, key2: 0x34567890 };
+        password.iter().for_each(|byte| keys.update(*byte));
+        keys
+    }
+
+    /// Updates the keys with a plaintext byte.
+    pub(crate) fn update(&mut self, byte: u8) {
+        self.key0 = crc32_update(self.key0, byte);
+        self.key1 = self.key1.wrapping_add(self.key0 & 0xFF).wrapping_mul(134775813).wrapping_add(1);
+        self.key2 = crc32_update(self.key2, (self.key1 >> 24) as u8);
+    }
+
+    /// Returns the next byte of the keystream.
+    pub(crate) fn stream_byte(&self) -> u8 {
+        let temp = (self.key2 | 2) as u16;
+        (temp.wrapping_mul(temp ^ 1) >> 8) as u8
+    }
+
+    /// Decrypts a buffer of bytes in place.
+    pub(crate) fn decrypt(&mut self, buffer: &mut [u8]) {
+        for byte in buffer.iter_mut() {
+            *byte ^= self.stream_byte();
+            self.update(*byte);
+        }
+    }
+
+    /// Encrypts a buffer of bytes in place.
+    pub(crate) fn encrypt(&mut self, buffer: &mut [u8]) {
+        for byte in buffer.iter_mut() {
+            let plain = *byte;
+            *byte ^= self.stream_byte();
+            self.update(plain);
+        }
+    }
+}
+
+/// Generates an encrypted header for an entry, whose last byte is the provided check byte.
+///
+/// The keys are advanced past the header, ready to encrypt the entry's data.
+pub(crate) fn generate_header(keys: &mut ZipCryptoKeys, check: u8) -> std::io::Result<[u8; ZIPCRYPTO_HEADER_LENGTH]> {
+    let mut header = [0; ZIPCRYPTO_HEADER_LENGTH];
+    getrandom::getrandom(&mut header[..ZIPCRYPTO_HEADER_LENGTH - 1])?;
+
+    header[ZIPCRYPTO_HEADER_LENGTH - 1] = check;
+    keys.encrypt(&mut header);
+
+    Ok(header)
+}
@@ -5,3 +5,23 @@ pub(crate) mod combined;
 pub(crate) mod read;
 pub(crate) mod spec;
 pub(crate) mod write;
+
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+/// Writes a ZIP file holding the provided entries (as filename & data pairs) whole, each using the provided compression.
+pub(crate) async fn archive<N, D>(compression: Compression, entries: impl IntoIterator<Item = (N, D)>) -> Vec<u8>
+where
+    N: Into<String>,
+    D: AsRef<[u8]>,
+{
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in entries {
+        let entry = ZipEntryBuilder::new(filename.into(), compression);
+        writer.write_entry_whole(entry, data.as_ref()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
@@ -0,0 +1,86 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::limits::{DecompressionLimits, LimitKind};
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn entry_size_limit_test() {
+    let reader = ZipFileReader::new(archive(Compression::Stored, [("0.txt", [0; 64])]).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    let limits = DecompressionLimits::new().max_entry_size(64);
+    let mut entry_reader = reader.entry(0).await.unwrap().with_limits(limits);
+    assert_eq!(entry_reader.read_to_end_checked(&mut Vec::new(), entry).await.unwrap(), 64);
+
+    let limits = DecompressionLimits::new().max_entry_size(16);
+    let mut entry_reader = reader.entry(0).await.unwrap().with_limits(limits);
+    let result = entry_reader.read_to_end_checked(&mut Vec::new(), entry).await;
+    assert!(matches!(result, Err(ZipError::LimitExceeded(LimitKind::EntrySize, 16))));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn ratio_limit_test() {
+    let reader =
+        ZipFileReader::new(archive(Compression::Deflate, [("0.txt", vec![0; 1024 * 1024])]).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    let limits = DecompressionLimits::new().max_ratio(10_000);
+    let mut entry_reader = reader.entry(0).await.unwrap().with_limits(limits);
+    entry_reader.read_to_end_checked(&mut Vec::new(), entry).await.unwrap();
+
+    let limits = DecompressionLimits::new().max_ratio(100);
+    let mut entry_reader = reader.entry(0).await.unwrap().with_limits(limits);
+    let result = entry_reader.read_to_end_checked(&mut Vec::new(), entry).await;
+    assert!(matches!(result, Err(ZipError::LimitExceeded(LimitKind::ExpansionRatio, 100))));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_ratio_limit_test() {
+    use crate::read::stream::ZipFileReader;
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let mut entry_writer =
+        writer.write_entry_stream(ZipEntryBuilder::new(String::from("0.txt"), Compression::Deflate)).await.unwrap();
+    entry_writer.write_all(&vec![0; 1024 * 1024]).await.unwrap();
+    entry_writer.close().await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(&data[..]);
+    let (entry, entry_reader) = reader.next_entry().await.unwrap().unwrap();
+    let entry = entry.clone();
+
+    let limits = DecompressionLimits::new().max_ratio(100);
+    let result = entry_reader.with_limits(limits).read_to_end_checked(&mut Vec::new(), &entry).await;
+    assert!(matches!(result, Err(ZipError::LimitExceeded(LimitKind::ExpansionRatio, 100))));
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn extract_total_size_limit_test() {
+    use crate::read::extract::ExtractOptions;
+
+    let reader =
+        ZipFileReader::new(archive(Compression::Stored, (0..3).map(|index| (format!("{index}.txt"), [0; 100]))).await)
+            .await
+            .unwrap();
+    let dest = std::env::temp_dir().join(format!("async_zip_bomb_{}", std::process::id()));
+
+    let options = ExtractOptions::new().limits(DecompressionLimits::new().max_total_size(300));
+    reader.extract_with_options(dest.join("within"), options).await.unwrap();
+
+    let options = ExtractOptions::new().limits(DecompressionLimits::new().max_total_size(250));
+    let result = reader.extract_with_options(dest.join("exceeded"), options).await;
+
+    assert!(matches!(result, Err(ZipError::LimitExceeded(LimitKind::TotalSize, 250))));
+    assert!(dest.join("exceeded/1.txt").exists());
+    std::fs::remove_dir_all(dest).unwrap();
+}
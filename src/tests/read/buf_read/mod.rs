@@ -0,0 +1,65 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use std::io::SeekFrom;
+
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt};
+
+fn lines() -> Vec<String> {
+    (0..5000).map(|index| format!("line {}", index)).collect()
+}
+
+async fn read_lines(compression: Compression) {
+    let reader = ZipFileReader::new(archive(compression, [("lines.txt", lines().join("\n"))]).await).await.unwrap();
+    let mut entry_lines = reader.entry(0).await.unwrap().lines();
+    let mut read = Vec::new();
+
+    while let Some(line) = entry_lines.next_line().await.unwrap() {
+        read.push(line);
+    }
+
+    assert_eq!(read, lines());
+}
+
+#[tokio::test]
+async fn buf_read_lines_stored_test() {
+    read_lines(Compression::Stored).await;
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn buf_read_lines_deflate_test() {
+    read_lines(Compression::Deflate).await;
+}
+
+#[tokio::test]
+async fn buf_read_mixed_test() {
+    let reader =
+        ZipFileReader::new(archive(Compression::Stored, [("lines.txt", lines().join("\n"))]).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    let mut entry_reader = reader.entry(0).await.unwrap();
+
+    // Data buffered but not consumed is returned by subsequent reads, so the CRC32 value still verifies.
+    let mut line = String::new();
+    entry_reader.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "line 0\n");
+    assert_eq!(entry_reader.stream_position().await.unwrap(), 7);
+
+    let mut rest = String::new();
+    entry_reader.read_to_string_checked(&mut rest, entry).await.unwrap();
+    assert_eq!(line + &rest, lines().join("\n"));
+
+    // Seeking discards any buffered data.
+    entry_reader.seek(SeekFrom::Start(0)).await.unwrap();
+    assert!(entry_reader.fill_buf().await.unwrap().starts_with(b"line 0\n"));
+    entry_reader.consume(5);
+
+    assert_eq!(entry_reader.seek(SeekFrom::Current(2)).await.unwrap(), 7);
+    let mut buffer = [0; 6];
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"line 1");
+}
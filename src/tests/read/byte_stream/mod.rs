@@ -0,0 +1,51 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::read::stream::ZipFileReader as StreamZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use bytes::Bytes;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+
+fn entries() -> [(&'static str, String); 2] {
+    [("foo.txt", "foo ".repeat(100)), ("bar.txt", String::from("bar"))]
+}
+
+#[tokio::test]
+async fn entry_into_stream_test() {
+    let reader = ZipFileReader::new(archive(Compression::Stored, entries()).await).await.unwrap();
+    let chunks: Vec<Bytes> = reader.entry(0).await.unwrap().into_stream(64).try_collect().await.unwrap();
+
+    assert_eq!(chunks.len(), 7);
+    assert!(chunks.iter().all(|chunk| chunk.len() <= 64));
+    assert_eq!(chunks.concat(), "foo ".repeat(100).into_bytes());
+}
+
+#[tokio::test]
+async fn reader_from_stream_test() {
+    let data = archive(Compression::Stored, entries()).await;
+    let chunks = data.chunks(13).map(|chunk| Ok::<_, std::io::Error>(Bytes::copy_from_slice(chunk)));
+    let mut reader = StreamZipFileReader::from_stream(stream::iter(chunks));
+    let mut entries = Vec::new();
+
+    while let Some((entry, entry_reader)) = reader.next_entry().await.unwrap() {
+        let filename = entry.filename().to_owned();
+        let data: Vec<Bytes> = entry_reader.into_stream(8).try_collect().await.unwrap();
+        entries.push((filename, data.concat()));
+    }
+
+    assert_eq!(entries, [("foo.txt".into(), "foo ".repeat(100).into_bytes()), ("bar.txt".into(), b"bar".to_vec())]);
+}
+
+#[tokio::test]
+async fn reader_from_stream_error_test() {
+    let data = archive(Compression::Stored, entries()).await;
+    let chunks = stream::iter([Ok(Bytes::copy_from_slice(&data[..20]))])
+        .chain(stream::iter([Err(std::io::Error::other("connection reset"))]));
+    let mut reader = StreamZipFileReader::from_stream(chunks);
+
+    assert!(matches!(reader.next_entry().await, Err(ZipError::UpstreamReadError(_))));
+}
@@ -21,6 +21,14 @@ compressed_test_helper!(zstd_test, Compression::Zstd, "foo bar", include_bytes!(
 #[cfg(feature = "xz")]
 compressed_test_helper!(xz_test, Compression::Xz, "foo bar", include_bytes!("xz.data"));
 
+#[cfg(feature = "legacy")]
+compressed_test_helper!(
+    shrink_test,
+    Compression::Shrink,
+    "ababcbababaaaaaaa",
+    [0x61u8, 0xC4, 0x04, 0x1C, 0x23, 0xB0, 0x60, 0x98, 0x83, 0x08, 0xC3, 0x00]
+);
+
 /// A helper macro for generating a CompressedReader test using a specific compression method.
 macro_rules! compressed_test_helper {
     ($name:ident, $typ:expr, $data_raw:expr, $data:expr) => {
@@ -34,7 +42,7 @@ macro_rules! compressed_test_helper {
             let data_raw = $data_raw;
 
             let cursor = Cursor::new(data);
-            let mut reader = CompressedReader::new(cursor, $typ);
+            let mut reader = CompressedReader::new(cursor, $typ, data_raw.len() as u64);
 
             let mut read_data = String::new();
             reader.read_to_string(&mut read_data).await.expect("read into CompressedReader failed");
@@ -0,0 +1,42 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::spec::consts::{CDH_SIGNATURE, LFH_SIGNATURE};
+use crate::tests::archive;
+
+fn signature_offsets(data: &[u8], signature: u32) -> Vec<usize> {
+    data.windows(4).enumerate().filter(|(_, window)| *window == signature.to_le_bytes()).map(|(i, _)| i).collect()
+}
+
+#[tokio::test]
+async fn cd_record_context_test() {
+    let mut data = archive(Compression::Stored, [("foo.txt", "foo.txt"), ("bar.txt", "bar.txt")]).await;
+    let offset = signature_offsets(&data, CDH_SIGNATURE)[1];
+    data[offset] = 0;
+
+    let error = ZipFileReader::new(data).await.err().unwrap();
+    assert!(matches!(error.root(), ZipError::UnexpectedHeaderError(_, CDH_SIGNATURE)));
+
+    let context = error.context().unwrap();
+    assert_eq!((context.entry_index(), context.offset()), (Some(1), Some(offset as u64)));
+    assert!(error.to_string().ends_with(&format!("(entry 1 at offset {:#x})", offset)));
+}
+
+#[tokio::test]
+async fn local_header_context_test() {
+    let mut data = archive(Compression::Stored, [("foo.txt", "foo.txt"), ("bar.txt", "bar.txt")]).await;
+    let offset = signature_offsets(&data, LFH_SIGNATURE)[1];
+    data[offset] = 0;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert!(reader.entry(0).await.is_ok());
+
+    let error = reader.entry(1).await.err().unwrap();
+    assert!(matches!(error.root(), ZipError::UnexpectedHeaderError(_, LFH_SIGNATURE)));
+
+    let context = error.context().unwrap();
+    assert_eq!((context.entry_filename(), context.offset()), (Some("bar.txt"), Some(offset as u64)));
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+const DATA: &[u8] = b"Lorem ipsum dolor sit amet, lorem ipsum dolor sit amet, lorem ipsum dolor sit amet.";
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn copy_to_checked_test() {
+    let reader = ZipFileReader::new(archive(Compression::Deflate, [("foo.txt", DATA)]).await).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    // The in-memory read limit doesn't apply, as nothing is read into memory.
+    let mut entry_reader = reader.entry(0).await.unwrap().with_max_read_size(1);
+    let mut copied = Vec::new();
+    assert_eq!(entry_reader.copy_to_checked(&mut copied, entry).await.unwrap(), DATA.len() as u64);
+    assert_eq!(copied, DATA);
+}
+
+#[tokio::test]
+async fn copy_to_checked_crc_test() {
+    let mut data = archive(Compression::Stored, [("foo.txt", DATA)]).await;
+    let offset = data.windows(DATA.len()).position(|window| window == DATA).unwrap();
+    data[offset] ^= 0xFF;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    let error = reader.entry(0).await.unwrap().copy_to_checked(&mut tokio::io::sink(), entry).await.unwrap_err();
+    assert!(matches!(error.root(), ZipError::CRC32CheckError));
+    assert_eq!(error.context().and_then(|context| context.offset()), Some(offset as u64));
+}
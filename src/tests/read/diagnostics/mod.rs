@@ -0,0 +1,85 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::diagnostics::{OpenWarning, ParseMode, ReaderOptions};
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::tests::archive;
+
+#[tokio::test]
+async fn open_report_test() {
+    let data = archive(Compression::Stored, ["foo.txt", "bar.txt"].map(|filename| (filename, "foo"))).await;
+    let length = data.len() as u64;
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    assert!(reader.file().open_report().is_none());
+
+    let reader = ZipFileReader::new_with_options(data, ReaderOptions::new().diagnostics(true)).await.unwrap();
+    let report = reader.file().open_report().unwrap();
+
+    assert_eq!(report.entries(), 2);
+    assert_eq!(report.eocdr_offset(), length - 22);
+    assert_eq!(report.central_directory_offset() + report.central_directory_size(), report.eocdr_offset());
+    assert!(report.warnings().is_empty());
+}
+
+#[tokio::test]
+async fn open_report_warnings_test() {
+    let data = archive(Compression::Stored, ["foo.txt", "foo.txt"].map(|filename| (filename, "foo"))).await;
+    let reader = ZipFileReader::new_with_options(data, ReaderOptions::new().diagnostics(true)).await.unwrap();
+    let report = reader.file().open_report().unwrap();
+
+    assert_eq!(report.warnings(), &[OpenWarning::DuplicateFilename(String::from("foo.txt"))]);
+}
+
+#[tokio::test]
+async fn empty_filename_warning_test() {
+    let data =
+        archive(Compression::Stored, ["foo.txt", "", " /./", "bar/ /baz.txt"].map(|filename| (filename, "foo"))).await;
+    let reader = ZipFileReader::new_with_options(data, ReaderOptions::new().diagnostics(true)).await.unwrap();
+    let file = reader.file();
+
+    let empty: Vec<_> = file.entries().iter().map(|entry| entry.has_empty_filename()).collect();
+    assert_eq!(empty, [false, true, true, false]);
+    assert_eq!(file.meta(2).unwrap().raw_filename(), b" /./");
+
+    let warnings = file.open_report().unwrap().warnings();
+    assert_eq!(warnings, [OpenWarning::EmptyFilename(1), OpenWarning::EmptyFilename(2)]);
+}
+
+#[tokio::test]
+async fn parse_mode_trailing_data_test() {
+    let data = archive(Compression::Stored, ["foo.txt"].map(|filename| (filename, "foo"))).await;
+    let strict = ReaderOptions::new().parse_mode(ParseMode::Strict);
+    assert!(ZipFileReader::new_with_options(data.clone(), strict.clone()).await.is_ok());
+
+    let mut data = data;
+    data.extend_from_slice(b"bar");
+
+    let options = ReaderOptions::new().diagnostics(true);
+    let reader = ZipFileReader::new_with_options(data.clone(), options).await.unwrap();
+    assert_eq!(reader.file().open_report().unwrap().warnings(), &[OpenWarning::TrailingData(3)]);
+
+    let result = ZipFileReader::new_with_options(data, strict).await;
+    assert!(matches!(result, Err(ZipError::SpecViolation(OpenWarning::TrailingData(3)))));
+}
+
+#[tokio::test]
+async fn parse_mode_version_needed_test() {
+    let mut data = archive(Compression::Stored, ["foo.txt", "bar.txt"].map(|filename| (filename, "foo"))).await;
+    let offset = data.windows(4).rposition(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    data[offset + 6..offset + 8].copy_from_slice(&80u16.to_le_bytes());
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().meta(1).unwrap().version_needed(), 80);
+
+    let strict = ReaderOptions::new().parse_mode(ParseMode::Strict);
+    let error = ZipFileReader::new_with_options(data, strict).await.err().unwrap();
+    assert!(matches!(error, ZipError::SpecViolation(OpenWarning::UnknownVersionNeeded { index: 1, version: 80 })));
+    assert_eq!(
+        error.to_string(),
+        "ZIP file deviates from the specification: entry 1 needs unknown version 80 to extract"
+    );
+}
@@ -0,0 +1,79 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::remote::RangeSource;
+use crate::write::{ZipFileWriter, EMPTY_ARCHIVE};
+
+use std::future::Future;
+use std::io::Cursor;
+use std::ops::Range;
+
+async fn empty() -> Vec<u8> {
+    let mut buffer = Vec::new();
+    ZipFileWriter::new(&mut buffer).close().await.unwrap();
+    buffer
+}
+
+struct MemorySource(Vec<u8>);
+
+impl RangeSource for MemorySource {
+    fn length(&self) -> impl Future<Output = std::io::Result<u64>> + Send {
+        std::future::ready(Ok(self.0.len() as u64))
+    }
+
+    fn read_range(&self, range: Range<u64>) -> impl Future<Output = std::io::Result<Vec<u8>>> + Send {
+        std::future::ready(Ok(self.0[range.start as usize..range.end as usize].to_vec()))
+    }
+}
+
+#[tokio::test]
+async fn empty_write_test() {
+    assert_eq!(empty().await, EMPTY_ARCHIVE);
+}
+
+#[tokio::test]
+async fn empty_read_test() {
+    let data = empty().await;
+
+    let reader = crate::read::mem::ZipFileReader::new(data.clone()).await.unwrap();
+    assert!(reader.file().is_empty());
+
+    let reader = crate::read::seek::ZipFileReader::new(Cursor::new(data.clone())).await.unwrap();
+    assert!(reader.file().is_empty());
+
+    let reader = crate::read::positional::ZipFileReader::new(data.clone()).await.unwrap();
+    assert!(reader.file().is_empty());
+
+    let reader = crate::read::remote::ZipFileReader::new(MemorySource(data.clone())).await.unwrap();
+    assert!(reader.file().is_empty());
+
+    let mut reader = crate::read::lazy::ZipFileReader::new(Cursor::new(data.clone())).await.unwrap();
+    assert!(reader.is_empty());
+    assert!(reader.entries().next().await.is_none());
+
+    let mut reader = crate::read::stream::ZipFileReader::new(Cursor::new(data));
+    assert!(reader.next_entry().await.unwrap().is_none());
+    assert!(reader.finished());
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn empty_fs_test() {
+    let path = std::env::temp_dir().join(format!("async_zip_empty_{}.zip", std::process::id()));
+    let dest = std::env::temp_dir().join(format!("async_zip_empty_{}", std::process::id()));
+    tokio::fs::write(&path, EMPTY_ARCHIVE).await.unwrap();
+
+    let reader = crate::read::fs::ZipFileReader::new(&path).await;
+    let extracted = match &reader {
+        Ok(reader) => reader.extract(&dest).await.map(|_| ()),
+        Err(_) => Ok(()),
+    };
+
+    tokio::fs::remove_file(&path).await.unwrap();
+    let reader = reader.unwrap();
+    extracted.unwrap();
+
+    assert!(reader.file().is_empty());
+    assert!(std::fs::read_dir(&dest).unwrap().next().is_none());
+    tokio::fs::remove_dir(&dest).await.unwrap();
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::{FilenameEncoding, FilenameSource, ZipEntryBuilder};
+
+const ENTRIES: [(&str, &str); 2] = [("日本語のファイル.txt", "説明"), ("ドキュメント/報告書.txt", "")];
+
+/// Writes an archive whose filenames & comments are encoded as Shift-JIS (without the language encoding flag set).
+///
+/// As the writer always encodes as UTF-8, distinct placeholders of the same lengths are written and then replaced.
+async fn shift_jis_archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let mut replacements = Vec::new();
+
+    for (index, (filename, comment)) in ENTRIES.into_iter().enumerate() {
+        let filename = encoding_rs::SHIFT_JIS.encode(filename).0.into_owned();
+        let comment = encoding_rs::SHIFT_JIS.encode(comment).0.into_owned();
+        let filename_placeholder = vec![b'a' + index as u8; filename.len()];
+        let comment_placeholder = vec![b'A' + index as u8; comment.len()];
+
+        let entry = ZipEntryBuilder::new(String::from_utf8(filename_placeholder.clone()).unwrap(), Compression::Stored)
+            .comment(String::from_utf8(comment_placeholder.clone()).unwrap());
+        writer.write_entry_whole(entry, b"data").await.unwrap();
+
+        replacements.push((filename_placeholder, filename));
+        replacements.push((comment_placeholder, comment));
+    }
+
+    let mut data = writer.close().await.unwrap();
+
+    for (placeholder, encoded) in replacements.iter().filter(|(placeholder, _)| !placeholder.is_empty()) {
+        while let Some(position) = data.windows(placeholder.len()).position(|window| window == placeholder) {
+            data[position..position + placeholder.len()].copy_from_slice(encoded);
+        }
+    }
+
+    data
+}
+
+async fn filenames(options: ReaderOptions) -> Vec<(String, String, FilenameSource)> {
+    let reader = ZipFileReader::new_with_options(shift_jis_archive().await, options).await.unwrap();
+    let entries = reader.file().entries().iter();
+
+    entries.map(|entry| (entry.filename().to_owned(), entry.comment().to_owned(), entry.filename_source())).collect()
+}
+
+#[tokio::test]
+async fn fixed_encoding_test() {
+    let options = ReaderOptions::new().filename_encoding(FilenameEncoding::Fixed(encoding_rs::SHIFT_JIS));
+    let source = FilenameSource::Encoding(encoding_rs::SHIFT_JIS);
+
+    let expected: Vec<_> =
+        ENTRIES.iter().map(|(filename, comment)| (filename.to_string(), comment.to_string(), source)).collect();
+
+    assert_eq!(filenames(options).await, expected);
+}
+
+#[tokio::test]
+async fn detected_encoding_test() {
+    let options = ReaderOptions::new().filename_encoding(FilenameEncoding::Detect);
+    let names: Vec<_> = filenames(options).await.into_iter().map(|(filename, _, _)| filename).collect();
+
+    assert_eq!(names, ENTRIES.map(|(filename, _)| filename.to_string()));
+}
+
+#[tokio::test]
+async fn default_encoding_test() {
+    let sources: Vec<_> = filenames(ReaderOptions::new()).await.into_iter().map(|(_, _, source)| source).collect();
+    assert_eq!(sources, vec![FilenameSource::Cp437, FilenameSource::Cp437]);
+}
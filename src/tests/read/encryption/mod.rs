@@ -0,0 +1,169 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::EncryptionMethod;
+
+/// An archive written by Info-ZIP with the password "password", containing a stored entry followed by a deflated one.
+const ZIPCRYPTO_ZIP: &[u8] = include_bytes!("zipcrypto.zip");
+
+#[tokio::test]
+async fn zipcrypto_stored_test() {
+    let reader = ZipFileReader::new(ZIPCRYPTO_ZIP.to_vec()).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+    let mut entry_reader = reader.entry_with_password(0, b"password").await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+    assert_eq!(data, "Hello, encrypted world!\n");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn zipcrypto_deflate_test() {
+    let reader = ZipFileReader::new(ZIPCRYPTO_ZIP.to_vec()).await.unwrap();
+    let entry = reader.file().entries()[1].clone();
+    let mut entry_reader = reader.entry_with_password(1, b"password").await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+    assert_eq!(data, "Some compressible text.\n".repeat(50));
+}
+
+#[tokio::test]
+async fn zipcrypto_password_test() {
+    let reader = ZipFileReader::new(ZIPCRYPTO_ZIP.to_vec()).await.unwrap();
+
+    assert!(reader.file().entries().iter().all(|entry| entry.encryption() == Some(EncryptionMethod::ZipCrypto)));
+    assert!(matches!(reader.entry(0).await, Err(ZipError::EntryEncrypted(EncryptionMethod::ZipCrypto))));
+    assert!(matches!(reader.entry_with_password(0, b"incorrect").await, Err(ZipError::IncorrectPassword)));
+}
+
+#[tokio::test]
+async fn strong_encryption_test() {
+    // Set the strong encryption bit within the first entry's central directory record.
+    let mut data = ZIPCRYPTO_ZIP.to_vec();
+    let offset = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    data[offset + 8] |= 0x40;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].encryption(), Some(EncryptionMethod::Strong));
+    assert!(matches!(reader.entry(0).await, Err(ZipError::EntryEncrypted(EncryptionMethod::Strong))));
+    assert!(matches!(reader.entry_with_password(0, b"password").await, Err(ZipError::FeatureNotSupported(_))));
+}
+
+#[tokio::test]
+async fn stream_encrypted_test() {
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).zipcrypto_password(b"password");
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    // The encrypted entry is rejected, but can then be skipped over.
+    let mut reader = crate::read::stream::ZipFileReader::new(data.as_slice());
+    assert!(matches!(reader.next_entry().await, Err(ZipError::EntryEncrypted(EncryptionMethod::ZipCrypto))));
+
+    let (entry, _) = reader.next_entry().await.unwrap().unwrap();
+    assert_eq!(entry.filename(), "bar.txt");
+}
+
+/// An archive containing an AE-1 AES-256 stored entry followed by an AE-2 AES-128 deflated one, both encrypted with the
+/// password "password".
+#[cfg(feature = "aes")]
+const AES_ZIP: &[u8] = include_bytes!("aes.zip");
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_stored_test() {
+    let reader = ZipFileReader::new(AES_ZIP.to_vec()).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+    let mut entry_reader = reader.entry_with_password(0, b"password").await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+    assert_eq!(data, "Hello, encrypted world!\n");
+}
+
+#[cfg(all(feature = "aes", feature = "deflate"))]
+#[tokio::test]
+async fn aes_deflate_test() {
+    let reader = ZipFileReader::new(AES_ZIP.to_vec()).await.unwrap();
+    let entry = reader.file().entries()[1].clone();
+    let mut entry_reader = reader.entry_with_password(1, b"password").await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+    assert_eq!(data, "Some compressible text.\n".repeat(50));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_password_test() {
+    let reader = ZipFileReader::new(AES_ZIP.to_vec()).await.unwrap();
+    let methods: Vec<_> = reader.file().entries().iter().map(|entry| entry.encryption()).collect();
+
+    assert_eq!(methods, [Some(EncryptionMethod::Aes256), Some(EncryptionMethod::Aes128)]);
+    assert!(matches!(reader.entry(0).await, Err(ZipError::EntryEncrypted(EncryptionMethod::Aes256))));
+    assert!(matches!(reader.entry_with_password(0, b"incorrect").await, Err(ZipError::IncorrectPassword)));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_authentication_test() {
+    // Flip a bit within the first entry's encrypted data (following its header, filename, extra field, salt & password
+    // verification value).
+    let mut data = AES_ZIP.to_vec();
+    data[30 + 10 + 11 + 16 + 2] ^= 1;
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+    let mut entry_reader = reader.entry_with_password(0, b"password").await.unwrap();
+
+    let err = match entry_reader.read_to_end_checked(&mut Vec::new(), &entry).await {
+        Err(ZipError::UpstreamReadError(err)) => err,
+        result => panic!("expected an upstream read error, got {:?}", result),
+    };
+    assert!(matches!(
+        err.into_inner().unwrap().downcast::<ZipError>().as_deref(),
+        Ok(ZipError::AuthenticationCodeMismatch)
+    ));
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_read_buf_test() {
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+    use tokio::io::AsyncReadExt;
+
+    let data: Vec<u8> = (0..100_000u32).map(|value| value as u8).collect();
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+
+    let entry = ZipEntryBuilder::new(String::from("empty.bin"), Compression::Stored);
+    writer.write_entry_whole_aes(entry, &[], b"password").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("data.bin"), Compression::Stored);
+    writer.write_entry_whole_aes(entry, &data, b"password").await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+
+    for (index, expected) in [(0, &[][..]), (1, &data[..])] {
+        let mut entry_reader = reader.entry_with_password(index, b"password").await.unwrap();
+
+        // Reads into the spare capacity of a large buffer, whose unfilled part is uninitialised.
+        let mut read = Vec::with_capacity(1 << 20);
+        while entry_reader.read_buf(&mut read).await.unwrap() != 0 {}
+
+        assert_eq!(read, expected);
+    }
+}
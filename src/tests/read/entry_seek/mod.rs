@@ -0,0 +1,85 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::read::seek::ZipFileReader as SeekZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::{Cursor, ErrorKind, SeekFrom};
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const DATA: &[u8] = b"0123456789abcdefghij";
+
+async fn archive(compression: Compression) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    writer
+        .write_entry_whole(ZipEntryBuilder::new(String::from("padding.txt"), Compression::Stored), b"foo")
+        .await
+        .unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new(String::from("foo.txt"), compression), DATA).await.unwrap();
+    writer.close().await.unwrap();
+
+    data
+}
+
+#[tokio::test]
+async fn entry_seek_stored_test() {
+    let reader = ZipFileReader::new(archive(Compression::Stored).await).await.unwrap();
+    let mut entry_reader = reader.entry(1).await.unwrap();
+    let mut buffer = [0; 4];
+
+    assert_eq!(entry_reader.seek(SeekFrom::Start(10)).await.unwrap(), 10);
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"abcd");
+
+    assert_eq!(entry_reader.seek(SeekFrom::Current(-8)).await.unwrap(), 6);
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"6789");
+
+    assert_eq!(entry_reader.seek(SeekFrom::End(-4)).await.unwrap(), 16);
+    let mut rest = Vec::new();
+    entry_reader.read_to_end(&mut rest).await.unwrap();
+    assert_eq!(rest, b"ghij");
+
+    assert_eq!(entry_reader.seek(SeekFrom::Start(100)).await.unwrap(), DATA.len() as u64);
+    assert_eq!(entry_reader.read(&mut buffer).await.unwrap(), 0);
+
+    let error = entry_reader.seek(SeekFrom::Current(-100)).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+    let entry = reader.file().entries()[1].clone();
+    let mut data = Vec::new();
+    entry_reader.seek(SeekFrom::Start(0)).await.unwrap();
+    entry_reader.read_to_end_checked(&mut data, &entry).await.unwrap();
+    assert_eq!(data, DATA);
+}
+
+#[tokio::test]
+async fn entry_seek_borrowed_test() {
+    let mut reader = SeekZipFileReader::new(Cursor::new(archive(Compression::Stored).await)).await.unwrap();
+    let mut entry_reader = reader.entry(1).await.unwrap();
+    let mut buffer = [0; 4];
+
+    entry_reader.seek(SeekFrom::End(-6)).await.unwrap();
+    entry_reader.read_exact(&mut buffer).await.unwrap();
+    assert_eq!(&buffer, b"efgh");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn entry_seek_compressed_test() {
+    let reader = ZipFileReader::new(archive(Compression::Deflate).await).await.unwrap();
+
+    let error = reader.entry(1).await.unwrap().seek(SeekFrom::Start(4)).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Unsupported);
+
+    let mut raw_reader = reader.entry_raw(1).await.unwrap();
+    assert_eq!(
+        raw_reader.seek(SeekFrom::End(0)).await.unwrap(),
+        u64::from(reader.file().entries()[1].compressed_size())
+    );
+}
@@ -0,0 +1,312 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::extract::sanitize_filename;
+
+use std::path::PathBuf;
+
+#[test]
+fn sanitize_accepts_relative_test() {
+    assert_eq!(sanitize_filename("foo/bar.txt"), Some(PathBuf::from("foo").join("bar.txt")));
+    assert_eq!(sanitize_filename("./foo//bar.txt"), Some(PathBuf::from("foo").join("bar.txt")));
+    assert_eq!(sanitize_filename("foo\\bar.txt"), Some(PathBuf::from("foo").join("bar.txt")));
+}
+
+#[test]
+fn sanitize_rejects_traversal_test() {
+    assert!(sanitize_filename("../foo.txt").is_none());
+    assert!(sanitize_filename("foo/../../bar.txt").is_none());
+    assert!(sanitize_filename("foo\\..\\..\\bar.txt").is_none());
+    assert!(sanitize_filename("/etc/passwd").is_none());
+    assert!(sanitize_filename("\\\\server\\share\\foo.txt").is_none());
+    assert!(sanitize_filename("C:\\Windows\\foo.txt").is_none());
+    assert!(sanitize_filename("C:foo.txt").is_none());
+}
+
+#[tokio::test]
+async fn extract_round_trip_test() {
+    use crate::error::ZipError;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo/"), Compression::Stored);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("foo/bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("../escape.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"escaped").await.unwrap();
+    writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_extract_{}", std::process::id()));
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let result = reader.extract(dest.join("out")).await;
+
+    assert!(matches!(result, Err(ZipError::UnsafeFilename(_))));
+    assert_eq!(std::fs::read_to_string(dest.join("out/foo/bar.txt")).unwrap(), "foo bar");
+    assert!(!dest.join("escape.txt").exists());
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn extract_symlink_test() {
+    use crate::error::ZipError;
+    use crate::read::extract::ExtractOptions;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo/bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("foo/link"), Compression::Stored).symlink();
+    writer.write_entry_whole(entry, b"bar.txt").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("escape"), Compression::Stored).symlink();
+    writer.write_entry_whole(entry, b"../outside").await.unwrap();
+    writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_symlink_{}", std::process::id()));
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    assert!(reader.file().entries()[1].is_symlink());
+
+    let result = reader.extract(dest.join("links")).await;
+    assert!(matches!(result, Err(ZipError::UnsafeSymlinkTarget(_))));
+    assert_eq!(std::fs::read_link(dest.join("links/foo/link")).unwrap(), PathBuf::from("bar.txt"));
+    assert_eq!(std::fs::read_to_string(dest.join("links/foo/link")).unwrap(), "foo bar");
+
+    reader.extract_with_options(dest.join("files"), ExtractOptions::new().symlinks(false)).await.unwrap();
+    assert!(!std::fs::symlink_metadata(dest.join("files/escape")).unwrap().file_type().is_symlink());
+    assert_eq!(std::fs::read_to_string(dest.join("files/escape")).unwrap(), "../outside");
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn extract_symlink_chain_test() {
+    use crate::error::ZipError;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("x"), Compression::Stored).symlink();
+    writer.write_entry_whole(entry, b".").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("x/y"), Compression::Stored).symlink();
+    writer.write_entry_whole(entry, b"..").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("x/y/pwned.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"pwned").await.unwrap();
+    writer.close().await.unwrap();
+
+    let root = std::env::temp_dir().join(format!("async_zip_symlink_chain_{}", std::process::id()));
+    let dest = root.join("dest");
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+
+    let result = reader.extract(&dest).await;
+    assert!(matches!(result, Err(ZipError::UnsafeFilename(_))));
+    assert!(!root.join("pwned.txt").exists());
+    assert!(!dest.join("pwned.txt").exists());
+    assert!(std::fs::symlink_metadata(dest.join("y")).is_err());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn extract_permissions_test() {
+    use crate::read::extract::ExtractOptions;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("run.sh"), Compression::Stored).unix_permissions(0o104755);
+    writer.write_entry_whole(entry, b"#!/bin/sh").await.unwrap();
+    writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_permissions_{}", std::process::id()));
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    assert_eq!(reader.file().entries()[0].unix_mode(), Some(0o4755));
+
+    reader.extract_with_options(&dest, ExtractOptions::new().permissions(true)).await.unwrap();
+    let mode = std::fs::metadata(dest.join("run.sh")).unwrap().permissions().mode();
+    assert_eq!(mode & 0o7777, 0o755);
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
+
+#[tokio::test]
+async fn extract_mapping_test() {
+    use crate::error::ZipError;
+    use crate::read::extract::ExtractOptions;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("project-1.0/src/foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("project-1.0/README"), Compression::Stored);
+    writer.write_entry_whole(entry, b"readme").await.unwrap();
+    writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_mapping_{}", std::process::id()));
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let strip = |entry: &crate::ZipEntry| match entry.filename() {
+        "project-1.0/README" => None,
+        filename => filename.strip_prefix("project-1.0/").map(PathBuf::from),
+    };
+
+    reader.extract_with_mapping(&dest, ExtractOptions::new(), strip).await.unwrap();
+    assert_eq!(std::fs::read_to_string(dest.join("src/foo.txt")).unwrap(), "foo");
+    assert!(!dest.join("README").exists());
+    assert!(!dest.join("project-1.0").exists());
+
+    let escape = |_: &crate::ZipEntry| Some(PathBuf::from("../escape.txt"));
+    let result = reader.extract_with_mapping(&dest, ExtractOptions::new(), escape).await;
+    assert!(matches!(result, Err(ZipError::UnsafeFilename(_))));
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn extract_sequential_test() {
+    use crate::error::ZipError;
+    use crate::read::extract::ExtractOptions;
+    use crate::read::stream::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+    use std::io::Cursor;
+    use tokio::io::AsyncWriteExt;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo/bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("baz.txt"), Compression::Deflate);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"baz baz baz").await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_sequential_{}", std::process::id()));
+    let reader = ZipFileReader::new(Cursor::new(buffer.clone()));
+    reader.extract_strict(&dest, ExtractOptions::new()).await.unwrap();
+    assert_eq!(std::fs::read_to_string(dest.join("foo/bar.txt")).unwrap(), "foo bar");
+    assert_eq!(std::fs::read_to_string(dest.join("baz.txt")).unwrap(), "baz baz baz");
+
+    // Rename the entry within the central directory only, leaving its local file header untouched.
+    let position = buffer.windows(7).rposition(|window| window == b"baz.txt").unwrap();
+    buffer[position..position + 7].copy_from_slice(b"qux.txt");
+
+    let mut reader = ZipFileReader::new(Cursor::new(buffer.clone()));
+    reader.extract(dest.join("lenient")).await.unwrap();
+    assert_eq!(std::fs::read_to_string(dest.join("lenient/baz.txt")).unwrap(), "baz baz baz");
+
+    let reader = ZipFileReader::new(Cursor::new(buffer));
+    let result = reader.extract_strict(dest.join("strict"), ExtractOptions::new()).await;
+    assert!(matches!(result, Err(ZipError::CentralDirectoryMismatch(_))));
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
+
+#[tokio::test]
+async fn extract_empty_filename_test() {
+    use crate::error::ZipError;
+    use crate::read::extract::ExtractOptions;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("./"), Compression::Stored);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("  "), Compression::Stored);
+    writer.write_entry_whole(entry, b"blank").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_empty_filename_{}", std::process::id()));
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let result = reader.extract(&dest).await;
+
+    assert!(matches!(result, Err(ZipError::EmptyFilename(filename)) if filename == "  "));
+    assert!(dest.join("foo.txt").exists());
+    assert!(!dest.join("bar.txt").exists());
+    std::fs::remove_dir_all(&dest).unwrap();
+
+    let options = ExtractOptions::new().skip_empty_filenames(true);
+    let mut reader = crate::read::stream::ZipFileReader::new(std::io::Cursor::new(data));
+    reader.extract_with_options(&dest, options).await.unwrap();
+
+    assert_eq!(std::fs::read_dir(&dest).unwrap().count(), 2);
+    assert_eq!(std::fs::read_to_string(dest.join("bar.txt")).unwrap(), "bar");
+    std::fs::remove_dir_all(&dest).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn extract_ownership_test() {
+    use crate::error::ZipError;
+    use crate::read::extract::ExtractOptions;
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+    use std::os::unix::fs::MetadataExt;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("dir/"), Compression::Stored).unix_owner(1000, 100);
+    writer.write_entry_whole(entry, b"").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("dir/foo.txt"), Compression::Stored).unix_owner(1000, 100);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored).unix_owner(1001, 101);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    writer.close().await.unwrap();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_ownership_{}", std::process::id()));
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let options =
+        ExtractOptions::new().ownership(true).owner_mapping(|uid, gid| (uid == 1000).then_some((uid, gid + 1)));
+    let result = reader.extract_with_options(&dest, options).await;
+
+    // Ownership can only be restored when running as root, which is checked before anything is extracted.
+    if unsafe { libc::geteuid() } != 0 {
+        assert!(matches!(result, Err(ZipError::OwnershipRequiresRoot)));
+        assert!(!dest.exists());
+        return;
+    }
+
+    result.unwrap();
+    let owner = |path: &str| {
+        let metadata = std::fs::symlink_metadata(dest.join(path)).unwrap();
+        (metadata.uid(), metadata.gid())
+    };
+
+    assert_eq!(owner("dir"), (1000, 101));
+    assert_eq!(owner("dir/foo.txt"), (1000, 101));
+    assert_eq!(owner("bar.txt").0, 0);
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::io::{read_bytes, read_fields, read_string};
+
+#[tokio::test]
+async fn read_fields_scratch_test() {
+    let mut scratch = Vec::new();
+
+    let [filename, extra_field, comment] = read_fields(&b"foo.txtabc"[..], [7, 0, 3], &mut scratch).await.unwrap();
+    assert_eq!(
+        (filename.as_slice(), extra_field.as_slice(), comment.as_slice()),
+        (&b"foo.txt"[..], &b""[..], &b"abc"[..])
+    );
+
+    // The scratch buffer's allocation is reused by subsequent reads.
+    let capacity = scratch.capacity();
+    let [filename] = read_fields(&b"bar"[..], [3], &mut scratch).await.unwrap();
+    assert_eq!(filename, b"bar");
+    assert_eq!(scratch.capacity(), capacity);
+
+    assert!(read_fields(&b"ba"[..], [3], &mut scratch).await.is_err());
+}
+
+#[tokio::test]
+async fn read_string_test() {
+    let mut scratch = Vec::new();
+
+    assert_eq!(read_string(&b"comment"[..], 4, &mut scratch).await.unwrap(), "comm");
+    assert_eq!(read_string(&b"short"[..], 64, &mut scratch).await.unwrap(), "short");
+    assert_eq!(read_bytes(&b"\xFF\xFE"[..], 2, &mut scratch).await.unwrap(), b"\xFF\xFE");
+
+    let err = read_string(&b"\xFF\xFE"[..], 2, &mut scratch).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
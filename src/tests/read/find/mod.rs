@@ -0,0 +1,60 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::read::seek;
+use crate::spec::compression::Compression;
+use crate::spec::crc::crc32;
+use crate::tests::archive;
+
+use std::io::Cursor;
+
+const ENTRIES: [(&str, &[u8]); 3] =
+    [("README.md", b"readme"), ("config/app.toml", b"app"), ("config/app.toml", b"dup")];
+
+#[tokio::test]
+async fn find_by_crc_test() {
+    let data = archive(Compression::Stored, [("foo.txt", "foo"), ("bar.txt", "bar"), ("renamed/foo.txt", "foo")]).await;
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let file = reader.file();
+
+    assert_eq!(file.find_by_crc(crc32(b"foo"), 3), [0, 2]);
+    assert_eq!(file.find_by_crc(crc32(b"bar"), 3), [1]);
+    assert!(file.find_by_crc(crc32(b"foo"), 4).is_empty());
+    assert!(file.find_by_crc(crc32(b"baz"), 3).is_empty());
+}
+
+#[tokio::test]
+async fn entry_by_name_test() {
+    let reader = ZipFileReader::new(archive(Compression::Stored, ENTRIES).await).await.unwrap();
+    assert_eq!(reader.file().entry_index("config/app.toml"), Some(1));
+    assert_eq!(reader.file().entry_index("config/"), None);
+
+    let mut buffer = String::new();
+    let entry = &reader.file().entries()[1];
+    reader.entry_by_name("config/app.toml").await.unwrap().read_to_string_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!(buffer, "app");
+
+    let (entry, mut entry_reader) = reader.entry_and_reader_by_name("README.md").await.unwrap();
+    assert_eq!(entry.filename(), "README.md");
+    buffer.clear();
+    entry_reader.read_to_string_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!(buffer, "readme");
+
+    let result = reader.entry_by_name("missing.txt").await;
+    assert!(matches!(result, Err(ZipError::EntryNotFound(name)) if name == "missing.txt"));
+}
+
+#[tokio::test]
+async fn seek_entry_by_name_test() {
+    let mut reader = seek::ZipFileReader::new(Cursor::new(archive(Compression::Stored, ENTRIES).await)).await.unwrap();
+
+    let (entry, mut entry_reader) = reader.entry_and_reader_by_name("config/app.toml").await.unwrap();
+    let mut buffer = String::new();
+    entry_reader.read_to_string_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!((entry.filename(), buffer.as_str()), ("config/app.toml", "app"));
+
+    let result = reader.entry_and_reader_by_name("missing.txt").await;
+    assert!(matches!(result, Err(ZipError::EntryNotFound(_))));
+}
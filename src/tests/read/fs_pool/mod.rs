@@ -0,0 +1,78 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::fs::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn fs_handle_reuse_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", &b"foo data"[..]), ("bar.txt", b"bar data")] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("async_zip_fs_pool_{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    assert_eq!(reader.idle_handles(), 1);
+
+    // A handle left part way through an entry is repositioned when reused.
+    let mut partial = [0; 3];
+    reader.entry(1).await.unwrap().read_exact(&mut partial).await.unwrap();
+    assert_eq!(reader.idle_handles(), 1);
+
+    for _ in 0..4 {
+        let mut data = String::new();
+        reader.entry(0).await.unwrap().read_to_string_checked(&mut data, &reader.file().entries()[0]).await.unwrap();
+        assert_eq!(data, "foo data");
+        assert_eq!(reader.idle_handles(), 1);
+    }
+
+    let (foo, bar) = (reader.entry(0).await.unwrap(), reader.entry(1).await.unwrap());
+    assert_eq!(reader.idle_handles(), 0);
+    drop((foo, bar));
+    assert_eq!(reader.idle_handles(), 2);
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn fs_handle_dropped_mid_read_test() {
+    use std::time::Duration;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let foo = "foo ".repeat(64 * 1024);
+
+    for (filename, data) in [("foo.txt", foo.as_str()), ("bar.txt", "bar data")] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Deflate);
+        writer.write_entry_whole(entry, data.as_bytes()).await.unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("async_zip_fs_pool_dropped_{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+    let reader = ZipFileReader::new(&path).await.unwrap();
+
+    // Dropped part way through an entry, and then with a read still in flight.
+    let mut entry_reader = reader.entry(0).await.unwrap();
+    entry_reader.read_exact(&mut [0; 1024]).await.unwrap();
+    drop(entry_reader);
+
+    let mut entry_reader = reader.entry(0).await.unwrap();
+    let _ = tokio::time::timeout(Duration::ZERO, entry_reader.read_to_end(&mut Vec::new())).await;
+    drop(entry_reader);
+    assert_eq!(reader.idle_handles(), 1);
+
+    let mut data = String::new();
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut data, &reader.file().entries()[1]).await.unwrap();
+    assert_eq!(data, "bar data");
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -0,0 +1,34 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+#[tokio::test]
+async fn group_entries_test() {
+    let data = archive(Compression::Stored, [("a.txt", "aaa"), ("b.txt", "bbbb"), ("c.txt", "ccccc")]).await;
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    let file = reader.file();
+
+    let groups = file.group_entries(&[2, 0, 0], 0).unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].indices(), &[0]);
+    assert_eq!(groups[1].indices(), &[2]);
+
+    let groups = file.group_entries(&[2, 0], 64).unwrap();
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].indices(), &[0, 2]);
+
+    let group = &groups[0];
+    let block = &data[group.range().start as usize..group.range().end as usize];
+
+    let entry = file.entries()[2].clone();
+    let mut entry_reader = file.group_entry(group, 2, block).await.unwrap();
+    let mut buffer = String::new();
+    entry_reader.read_to_string_checked(&mut buffer, &entry).await.unwrap();
+    assert_eq!(buffer, "ccccc");
+
+    assert!(file.group_entry(group, 1, block).await.is_err());
+    assert!(file.group_entries(&[3], 0).is_err());
+}
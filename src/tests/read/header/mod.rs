@@ -0,0 +1,65 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// A reader which counts the number of reads issued against it.
+struct ReadCounter<R> {
+    inner: R,
+    reads: usize,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for ReadCounter<R> {
+    fn poll_read(mut self: Pin<&mut Self>, c: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        self.reads += 1;
+        Pin::new(&mut self.inner).poll_read(c, b)
+    }
+}
+
+#[tokio::test]
+async fn cd_record_read_calls_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored)
+        .extended_timestamp(true)
+        .comment(String::from("bar"));
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap();
+
+    let offset = data.windows(4).position(|window| window == b"PK\x01\x02").unwrap();
+
+    // The signature & fixed-size fields are read in one call, followed by the filename, extra field, and comment.
+    let mut reader = ReadCounter { inner: Cursor::new(&data[offset..]), reads: 0 };
+    let (entry, _) = crate::read::cd_record(&mut reader, &mut Vec::new()).await.unwrap();
+
+    assert_eq!(entry.filename(), "foo.txt");
+    assert_eq!(entry.comment(), "bar");
+    assert_eq!(reader.reads, 2);
+}
+
+#[test]
+fn parse_with_signature_test() {
+    use crate::error::ZipError;
+    use crate::spec::consts::{EOCDR_SIGNATURE, LFH_SIGNATURE};
+    use crate::spec::header::{EndOfCentralDirectoryHeader, LocalFileHeader};
+
+    let mut buffer = [0; 22];
+    buffer[..4].copy_from_slice(&EOCDR_SIGNATURE.to_le_bytes());
+    buffer[10..12].copy_from_slice(&3u16.to_le_bytes());
+
+    let eocdr = EndOfCentralDirectoryHeader::parse_with_signature(&buffer).unwrap();
+    assert_eq!(eocdr.num_of_entries, 3);
+    assert_eq!(eocdr.as_slice(), buffer[4..]);
+
+    let result = LocalFileHeader::parse_with_signature(&[0; 30]);
+    assert!(matches!(result, Err(ZipError::UnexpectedHeaderError(0, LFH_SIGNATURE))));
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::lazy::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+use tokio::io::AsyncReadExt;
+
+async fn archive(entries: usize) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+
+    for index in 0..entries {
+        let entry = ZipEntryBuilder::new(format!("{index}.txt"), Compression::Stored).comment(format!("#{index}"));
+        writer.write_entry_whole(entry, index.to_string().as_bytes()).await.unwrap();
+    }
+
+    writer.comment(String::from("lazy")).unwrap();
+    writer.close().await.unwrap();
+    buffer
+}
+
+#[tokio::test]
+async fn lazy_entry_test() {
+    let mut reader = ZipFileReader::new(Cursor::new(archive(64).await)).await.unwrap();
+
+    assert_eq!(reader.len(), 64);
+    assert_eq!(reader.comment(), "lazy");
+
+    let entry = reader.entry_info(42).await.unwrap();
+    assert_eq!(entry.filename(), "42.txt");
+    assert_eq!(entry.comment(), "#42");
+
+    let mut data = String::new();
+    reader.entry(7).await.unwrap().read_to_string(&mut data).await.unwrap();
+    assert_eq!(data, "7");
+
+    assert!(matches!(reader.entry_info(64).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
+
+#[tokio::test]
+async fn lazy_iterator_test() {
+    let mut reader = ZipFileReader::new(Cursor::new(archive(300).await)).await.unwrap();
+    let mut entries = reader.entries();
+    let mut filenames = Vec::new();
+
+    while let Some(entry) = entries.next().await {
+        filenames.push(entry.unwrap().filename().to_owned());
+    }
+
+    assert_eq!(filenames, (0..300).map(|index| format!("{index}.txt")).collect::<Vec<_>>());
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn lazy_stream_test() {
+    use futures_util::TryStreamExt;
+
+    let mut reader = ZipFileReader::new(Cursor::new(archive(16).await)).await.unwrap();
+    let stream = reader.entries().into_stream();
+    let filenames: Vec<String> = stream.map_ok(|entry| entry.filename().to_owned()).try_collect().await.unwrap();
+
+    assert_eq!(filenames, (0..16).map(|index| format!("{index}.txt")).collect::<Vec<_>>());
+}
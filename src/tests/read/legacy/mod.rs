@@ -0,0 +1,71 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::read::stream::ZipFileReader as StreamZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+const ARCHIVE: &[u8] = include_bytes!("legacy.zip");
+const DATA: &[u8] = include_bytes!("legacy.data");
+
+#[tokio::test]
+async fn legacy_entries_test() {
+    let reader = ZipFileReader::new(ARCHIVE.to_vec()).await.unwrap();
+    let compressions: Vec<Compression> = reader.file().entries().iter().map(|entry| entry.compression()).collect();
+
+    assert_eq!(
+        compressions,
+        [
+            Compression::Shrink,
+            Compression::Shrink,
+            Compression::Implode { large_window: false, literal_tree: false },
+            Compression::Implode { large_window: false, literal_tree: true },
+            Compression::Implode { large_window: true, literal_tree: false },
+            Compression::Implode { large_window: true, literal_tree: true },
+            Compression::Reduce(1),
+            Compression::Reduce(2),
+            Compression::Reduce(3),
+            Compression::Reduce(4),
+        ]
+    );
+
+    for index in 0..compressions.len() {
+        let entry = reader.file().entries()[index].clone();
+        let mut entry_reader = reader.entry(index).await.unwrap();
+        let mut data = Vec::new();
+
+        entry_reader.read_to_end_checked(&mut data, &entry).await.unwrap();
+        assert_eq!(data, DATA, "{}", entry.filename());
+    }
+}
+
+#[tokio::test]
+async fn legacy_stream_test() {
+    let mut reader = StreamZipFileReader::new(Cursor::new(ARCHIVE));
+    let mut count = 0;
+
+    while let Some((entry, mut entry_reader)) = reader.next_entry().await.unwrap() {
+        let entry = entry.clone();
+        let mut data = Vec::new();
+
+        entry_reader.read_to_end_checked(&mut data, &entry).await.unwrap();
+        assert_eq!(data, DATA, "{}", entry.filename());
+        count += 1;
+    }
+
+    assert_eq!(count, 10);
+}
+
+#[tokio::test]
+async fn legacy_write_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Shrink);
+
+    let result = writer.write_entry_whole(entry, b"foo").await;
+    assert!(matches!(result, Err(ZipError::CompressionNotSupported(1))));
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+async fn archive(compression: Compression) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new(String::from("small.txt"), compression);
+    writer.write_entry_whole(entry, b"small").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("large.txt"), compression);
+    writer.write_entry_copy(entry, &[b'a'; 64][..]).await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn max_read_size_test() {
+    let options = ReaderOptions::new().max_read_size(16);
+    let reader = ZipFileReader::new_with_options(archive(Compression::Stored).await, options).await.unwrap();
+    let entries = reader.file().entries();
+
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut buffer, &entries[0]).await.unwrap();
+    assert_eq!(buffer, b"small");
+
+    let mut buffer = String::new();
+    let result = reader.entry(1).await.unwrap().read_to_string_checked(&mut buffer, &entries[1]).await;
+    assert!(matches!(result, Err(ZipError::EntryTooLarge(16))));
+    assert!(buffer.is_empty());
+
+    // The archive-level limit can be overridden for a single entry.
+    let mut buffer = String::new();
+    let mut entry_reader = reader.entry(1).await.unwrap().with_max_read_size(64);
+    entry_reader.read_to_string_checked(&mut buffer, &entries[1]).await.unwrap();
+    assert_eq!(buffer.len(), 64);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_max_read_size_test() {
+    let data = archive(Compression::Deflate).await;
+    let mut reader = crate::read::stream::ZipFileReader::new(data.as_slice());
+    reader.max_read_size(16);
+
+    let (entry, mut entry_reader) = reader.next_entry().await.unwrap().unwrap();
+    let entry = entry.clone();
+    let mut buffer = Vec::new();
+    entry_reader.read_to_end_checked(&mut buffer, &entry).await.unwrap();
+    assert_eq!(buffer, b"small");
+
+    // The data descriptor's sizes aren't known upfront, so the limit is only hit once it's been reached.
+    let (entry, mut entry_reader) = reader.next_entry().await.unwrap().unwrap();
+    let entry = entry.clone();
+    let mut buffer = Vec::new();
+    let result = entry_reader.read_to_end_checked(&mut buffer, &entry).await;
+    assert!(matches!(result, Err(ZipError::EntryTooLarge(16))));
+    assert_eq!(buffer.len(), 17);
+}
@@ -6,13 +6,13 @@ fn search_one_byte_test() {
     let buffer: &[u8] = &[0x0, 0x0, 0x0, 0x0, 0x0, 0x0];
     let signature: &[u8] = &[0x1];
 
-    let matched = crate::read::io::locator::reverse_search_buffer(buffer, signature);
+    let matched = crate::spec::locator::reverse_search_buffer(buffer, signature);
     assert!(matched.is_none());
 
     let buffer: &[u8] = &[0x2, 0x1, 0x0, 0x0, 0x0, 0x0];
     let signature: &[u8] = &[0x1];
 
-    let matched = crate::read::io::locator::reverse_search_buffer(buffer, signature);
+    let matched = crate::spec::locator::reverse_search_buffer(buffer, signature);
     assert!(matched.is_some());
     assert_eq!(1, matched.unwrap());
 }
@@ -22,7 +22,7 @@ fn search_two_byte_test() {
     let buffer: &[u8] = &[0x2, 0x1, 0x0, 0x0, 0x0, 0x0];
     let signature: &[u8] = &[0x2, 0x1];
 
-    let matched = crate::read::io::locator::reverse_search_buffer(buffer, signature);
+    let matched = crate::spec::locator::reverse_search_buffer(buffer, signature);
     assert!(matched.is_some());
     assert_eq!(1, matched.unwrap());
 }
@@ -62,3 +62,44 @@ async fn locator_buffer_boundary_test() {
     assert!(eocdr.is_ok());
     assert_eq!(eocdr.unwrap(), 0);
 }
+
+#[tokio::test]
+async fn locator_small_source_buffer_test() {
+    use std::io::Cursor;
+    use tokio::io::BufReader;
+
+    let data = &include_bytes!("empty-with-max-comment.zip");
+    let mut reader = BufReader::with_capacity(64, Cursor::new(data));
+    let eocdr = crate::read::io::locator::eocdr(&mut reader).await;
+
+    assert!(eocdr.is_ok());
+    assert_eq!(eocdr.unwrap(), 0);
+}
+
+#[test]
+fn locate_in_memory_test() {
+    use crate::error::ZipError;
+
+    assert_eq!(crate::spec::locator::locate_eocdr(include_bytes!("empty.zip")).unwrap(), 0);
+    assert_eq!(crate::spec::locator::locate_eocdr(include_bytes!("empty-with-max-comment.zip")).unwrap(), 0);
+    assert_eq!(crate::spec::locator::locate_eocdr(include_bytes!("empty-buffer-boundary.zip")).unwrap(), 0);
+    assert!(matches!(crate::spec::locator::locate_eocdr(&[0; 64]), Err(ZipError::UnableToLocateEOCDR)));
+}
+
+#[cfg(feature = "memchr")]
+#[test]
+fn memchr_search_test() {
+    use crate::spec::locator::{locate_eocdr_with_strategy, SearchStrategy};
+
+    let buffer: &[u8] = &[0x2, 0x1, 0x0, 0x2, 0x1, 0x2];
+    let signature: &[u8] = &[0x2, 0x1];
+
+    let matched = crate::spec::locator::memchr_search_buffer(buffer, signature);
+    assert_eq!(matched, crate::spec::locator::reverse_search_buffer(buffer, signature));
+    assert_eq!(matched, Some(4));
+
+    let data = include_bytes!("empty-with-max-comment.zip");
+    assert_eq!(locate_eocdr_with_strategy(data, SearchStrategy::Memchr).unwrap(), 0);
+    let data = include_bytes!("empty-buffer-boundary.zip");
+    assert_eq!(locate_eocdr_with_strategy(data, SearchStrategy::Memchr).unwrap(), 0);
+}
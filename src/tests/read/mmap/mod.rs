@@ -0,0 +1,36 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mmap::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn mmap_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", &b"foo data"[..]), ("bar.txt", b"bar data")] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let path = std::env::temp_dir().join(format!("async_zip_mmap_{}.zip", std::process::id()));
+    tokio::fs::write(&path, writer.close().await.unwrap()).await.unwrap();
+
+    // SAFETY: The file isn't modified until the reader has been dropped.
+    let reader = unsafe { ZipFileReader::map(&path).await.unwrap() };
+    let (mut foo, mut bar) = (reader.entry(0).await.unwrap(), reader.entry(1).await.unwrap());
+    let (entries, mut foo_data, mut bar_data) = (reader.file().entries(), String::new(), String::new());
+
+    let (foo_read, bar_read) = tokio::join!(
+        foo.read_to_string_checked(&mut foo_data, &entries[0]),
+        bar.read_to_string_checked(&mut bar_data, &entries[1])
+    );
+    foo_read.unwrap();
+    bar_read.unwrap();
+    assert_eq!((foo_data.as_str(), bar_data.as_str()), ("foo data", "bar data"));
+
+    drop((foo, bar, reader));
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -1,5 +1,51 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod bomb;
+pub(crate) mod buf_read;
+#[cfg(feature = "futures")]
+pub(crate) mod byte_stream;
 pub(crate) mod compression;
+pub(crate) mod context;
+pub(crate) mod copy;
+pub(crate) mod diagnostics;
+pub(crate) mod empty;
+#[cfg(feature = "encoding")]
+pub(crate) mod encoding;
+pub(crate) mod encryption;
+pub(crate) mod entry_seek;
+#[cfg(feature = "fs")]
+pub(crate) mod extract;
+pub(crate) mod fields;
+pub(crate) mod find;
+#[cfg(feature = "fs")]
+pub(crate) mod fs_pool;
+pub(crate) mod group;
+pub(crate) mod header;
+pub(crate) mod lazy;
+#[cfg(feature = "legacy")]
+pub(crate) mod legacy;
+pub(crate) mod limit;
 pub(crate) mod locator;
+#[cfg(feature = "mmap")]
+pub(crate) mod mmap;
+pub(crate) mod nested;
+#[cfg(feature = "unicode")]
+pub(crate) mod normalize;
+#[cfg(feature = "object-store")]
+pub(crate) mod object_store;
+pub(crate) mod offset;
+pub(crate) mod overhead;
+pub(crate) mod parse_cd;
+pub(crate) mod pattern;
+pub(crate) mod positional;
+pub(crate) mod progress;
+#[cfg(feature = "deflate")]
+pub(crate) mod ratio;
+pub(crate) mod recover;
+pub(crate) mod remote;
+#[cfg(feature = "deflate")]
+pub(crate) mod rewrite;
+pub(crate) mod spawn;
+pub(crate) mod stream;
+pub(crate) mod yielding;
@@ -0,0 +1,69 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::read::nested::NestedZipFileReader;
+use crate::read::seek;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+async fn outer_archive(compression: Compression) -> Vec<u8> {
+    let inner = archive(Compression::Stored, [("inner.txt", "nested data")]).await;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("prefix.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"prefix").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("inner.zip"), compression);
+    writer.write_entry_whole(entry, &inner).await.unwrap();
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn nested_stored_test() {
+    let mut reader = seek::ZipFileReader::new(Cursor::new(outer_archive(Compression::Stored).await)).await.unwrap();
+    let entry = reader.file().entries()[1].clone();
+    let nested = NestedZipFileReader::new(reader.entry(1).await.unwrap(), &entry).await.unwrap();
+    assert!(!nested.is_buffered());
+    assert_eq!(nested.file().entries()[0].filename(), "inner.txt");
+
+    let mut inner = match nested {
+        NestedZipFileReader::Seek(inner) => inner,
+        NestedZipFileReader::Mem(_) => unreachable!(),
+    };
+    let entry = inner.file().entries()[0].clone();
+    let mut buffer = String::new();
+    inner.entry(0).await.unwrap().read_to_string_checked(&mut buffer, &entry).await.unwrap();
+    assert_eq!(buffer, "nested data");
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn nested_compressed_test() {
+    let reader = ZipFileReader::new(outer_archive(Compression::Deflate).await).await.unwrap();
+    let entry = reader.file().entries()[1].clone();
+    let nested = NestedZipFileReader::new(reader.entry(1).await.unwrap(), &entry).await.unwrap();
+    assert!(nested.is_buffered());
+
+    let inner = match nested {
+        NestedZipFileReader::Mem(inner) => inner,
+        NestedZipFileReader::Seek(_) => unreachable!(),
+    };
+    let mut buffer = String::new();
+    let entry = &inner.file().entries()[0];
+    inner.entry(0).await.unwrap().read_to_string_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!(buffer, "nested data");
+}
+
+#[tokio::test]
+async fn nested_not_archive_test() {
+    use crate::error::ZipError;
+
+    let reader = ZipFileReader::new(outer_archive(Compression::Stored).await).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+    let result = NestedZipFileReader::new(reader.entry(0).await.unwrap(), &entry).await;
+    assert!(matches!(result, Err(ZipError::UnableToLocateEOCDR)));
+}
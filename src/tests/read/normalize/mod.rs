@@ -0,0 +1,44 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+/// "café.txt" with a decomposed (NFD) "é".
+const DECOMPOSED: &str = "cafe\u{301}.txt";
+const COMPOSED: &str = "caf\u{e9}.txt";
+
+#[tokio::test]
+async fn normalize_filenames_test() {
+    let data = archive(Compression::Stored, [DECOMPOSED, "foo.txt"].map(|filename| (filename, "foo"))).await;
+
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].filename(), DECOMPOSED);
+
+    let options = ReaderOptions::new().normalize_filenames(true);
+    let reader = ZipFileReader::new_with_options(data, options).await.unwrap();
+    let filenames: Vec<&str> = reader.file().entries().iter().map(|entry| entry.filename()).collect();
+    assert_eq!(filenames, [COMPOSED, "foo.txt"]);
+}
+
+#[tokio::test]
+async fn normalize_collision_test() {
+    let options = ReaderOptions::new().normalize_filenames(true);
+    let result = ZipFileReader::new_with_options(
+        archive(Compression::Stored, [COMPOSED, DECOMPOSED].map(|filename| (filename, "foo"))).await,
+        options,
+    )
+    .await;
+    assert!(matches!(result, Err(ZipError::NormalizedFilenameCollision(filename)) if filename == COMPOSED));
+
+    let options = ReaderOptions::new().normalize_filenames(true);
+    ZipFileReader::new_with_options(
+        archive(Compression::Stored, [COMPOSED, COMPOSED].map(|filename| (filename, "foo"))).await,
+        options,
+    )
+    .await
+    .unwrap();
+}
@@ -0,0 +1,45 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::object_store::ObjectStoreSource;
+use crate::read::remote::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::sync::Arc;
+
+use object_store::memory::InMemory;
+use object_store::path::Path;
+use object_store::ObjectStore;
+
+#[tokio::test]
+async fn object_store_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", &b"foo"[..]), ("bar/baz.txt", b"baz")] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    let location = Path::from("archives/foo.zip");
+    store.put(&location, writer.close().await.unwrap().into()).await.unwrap();
+
+    let reader = ZipFileReader::new(ObjectStoreSource::new(store, location)).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 2);
+
+    let mut buffer = String::new();
+    let entry = &reader.file().entries()[1];
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!((entry.filename(), buffer.as_str()), ("bar/baz.txt", "baz"));
+}
+
+#[tokio::test]
+async fn object_store_missing_test() {
+    let source = ObjectStoreSource::new(InMemory::new(), Path::from("missing.zip"));
+    let result = ZipFileReader::new(source).await;
+    assert!(
+        matches!(result, Err(crate::error::ZipError::UpstreamReadError(error)) if error.kind() == std::io::ErrorKind::NotFound)
+    );
+}
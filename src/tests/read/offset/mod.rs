@@ -0,0 +1,36 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn data_offset_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", "foo"), ("bar/baz.txt", "baz")] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        writer.write_entry_whole(entry, data.as_bytes()).await.unwrap();
+    }
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+
+    for (index, expected) in [(0, "foo"), (1, "baz")] {
+        let header_offset = reader.file().meta(index).unwrap().header_offset();
+        let data_offset = reader.data_offset(index).await.unwrap();
+        let filename_length = reader.file().entries()[index].filename().len() as u64;
+
+        assert_eq!(data_offset, header_offset + 30 + filename_length);
+        assert_eq!(&data[data_offset as usize..data_offset as usize + 3], expected.as_bytes());
+    }
+
+    let mut seek_reader = crate::read::seek::ZipFileReader::new(Cursor::new(data)).await.unwrap();
+    assert_eq!(seek_reader.data_offset(1).await.unwrap(), reader.data_offset(1).await.unwrap());
+    assert!(matches!(seek_reader.data_offset(2).await, Err(ZipError::EntryIndexOutOfBounds)));
+}
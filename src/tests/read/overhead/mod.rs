@@ -0,0 +1,46 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn entry_overhead_test() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored).comment(String::from("bar"));
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"bar bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.comment(String::from("comment")).unwrap();
+    writer.close().await.unwrap();
+
+    let length = buffer.len() as u64;
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let file = reader.file();
+
+    let foo = file.entry_overhead(0).unwrap();
+    assert_eq!((foo.local(), foo.central_directory_record()), (30 + 7, 46 + 7));
+
+    let bar = file.entry_overhead(1).unwrap();
+    // Streamed entries reserve a zeroed ZIP64 extra field locally, so their data descriptor holds eight-byte sizes.
+    assert_eq!((bar.local(), bar.central_directory_record()), (30 + 7 + 20 + 24, 46 + 7 + 3));
+
+    assert!(file.entry_overhead(2).is_none());
+    assert_eq!(file.total_overhead(), length - 3 - 7);
+
+    let (foo, bar) = (file.meta(0).unwrap(), file.meta(1).unwrap());
+    assert_eq!((foo.header_offset(), foo.data_descriptor(), foo.encrypted()), (0, false, false));
+    assert_eq!((bar.header_offset(), bar.data_descriptor(), bar.encrypted()), (30 + 7 + 3, true, false));
+    assert_eq!(bar.overhead(), file.entry_overhead(1).unwrap());
+    assert!(file.meta(2).is_none());
+}
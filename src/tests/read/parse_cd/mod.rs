@@ -0,0 +1,50 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::file::ZipFile;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+/// Returns an archive and the byte range of its central directory, as recorded by its end of central directory record.
+async fn archive() -> (Vec<u8>, std::ops::Range<usize>) {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (filename, data) in [("foo.txt", &b"foo"[..]), ("bar/", &[]), ("bar/baz.txt", &[b'z'; 100])] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored).comment(filename.to_uppercase());
+        writer.write_entry_whole(entry, data).await.unwrap();
+    }
+
+    let buffer = writer.close().await.unwrap();
+    let eocdr = &buffer[buffer.len() - 22..];
+    let size = u32::from_le_bytes(eocdr[12..16].try_into().unwrap()) as usize;
+    let offset = u32::from_le_bytes(eocdr[16..20].try_into().unwrap()) as usize;
+
+    (buffer, offset..offset + size)
+}
+
+#[tokio::test]
+async fn parse_cd_test() {
+    let (buffer, range) = archive().await;
+    let file = ZipFile::parse_cd(&buffer[range], 3).unwrap();
+    let reader = ZipFileReader::new(buffer.clone()).await.unwrap();
+
+    assert_eq!(file.entries().len(), 3);
+    for (parsed, read) in file.entries().iter().zip(reader.file().entries()) {
+        assert_eq!(parsed.filename(), read.filename());
+        assert_eq!(parsed.comment(), read.comment());
+        assert_eq!(parsed.crc32(), read.crc32());
+        assert_eq!(parsed.uncompressed_size(), read.uncompressed_size());
+    }
+    assert_eq!(file.meta(2).unwrap().file_offset, reader.file().meta(2).unwrap().file_offset);
+}
+
+#[tokio::test]
+async fn parse_cd_truncated_test() {
+    let (buffer, range) = archive().await;
+
+    assert!(ZipFile::parse_cd(&buffer[range.start..range.end - 1], 3).is_err());
+    assert!(ZipFile::parse_cd(&buffer[range.clone()], 4).is_err());
+    assert!(ZipFile::parse_cd(&[], 0).unwrap().is_empty());
+}
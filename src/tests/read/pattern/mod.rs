@@ -0,0 +1,100 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+use crate::EntryPattern;
+
+const FILENAMES: &[&str] =
+    &["README.md", "assets/logo.png", "assets/icons/large/app.png", "assets/logo.jpg", "src/lib.rs"];
+
+fn glob(pattern: &str) -> EntryPattern {
+    EntryPattern::glob(pattern).unwrap()
+}
+
+#[test]
+fn glob_syntax_test() {
+    assert!(glob("*.md").matches("README.md"));
+    assert!(!glob("*.md").matches("docs/README.md"));
+    assert!(glob("src/?ib.rs").matches("src/lib.rs"));
+    assert!(!glob("src?lib.rs").matches("src/lib.rs"));
+    assert!(glob("[a-c]at").matches("bat"));
+    assert!(!glob("[!a-c]at").matches("bat"));
+    assert!(glob("[^a-c]at").matches("hat"));
+    assert!(glob("[]]").matches("]"));
+    assert!(glob("\\*.txt").matches("*.txt"));
+    assert!(!glob("\\*.txt").matches("a.txt"));
+    assert!(glob("a**b").matches("axxb"));
+    assert!(!glob("a**b").matches("a/b"));
+}
+
+#[test]
+fn glob_recursive_test() {
+    let pattern = glob("assets/**/*.png");
+    assert!(pattern.matches("assets/logo.png"));
+    assert!(pattern.matches("assets/icons/large/app.png"));
+    assert!(!pattern.matches("assets/logo.jpg"));
+    assert!(!pattern.matches("other/assets/logo.png"));
+
+    assert!(glob("**/*.rs").matches("lib.rs"));
+    assert!(glob("**/*.rs").matches("src/read/lib.rs"));
+    assert!(glob("docs/**").matches("docs/a/b.md"));
+    assert!(glob("docs/**").matches("docs/"));
+    assert!(!glob("docs/**").matches("docs"));
+}
+
+#[test]
+fn glob_invalid_test() {
+    assert!(matches!(EntryPattern::glob("[abc"), Err(ZipError::InvalidPattern(_))));
+    assert!(matches!(EntryPattern::glob("foo\\"), Err(ZipError::InvalidPattern(_))));
+}
+
+#[test]
+fn glob_pathological_test() {
+    let filename = "a".repeat(256);
+    assert!(!glob("*a*a*a*a*a*a*a*a*a*a*b").matches(&filename));
+    assert!(!glob("**/**/**/**/**/b").matches(&"a/".repeat(128)));
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn regex_test() {
+    let pattern = EntryPattern::regex(r"^assets/.*\.png$").unwrap();
+    assert!(pattern.matches("assets/icons/large/app.png"));
+    assert!(!pattern.matches("assets/logo.jpg"));
+    assert!(matches!(EntryPattern::regex("("), Err(ZipError::InvalidPattern(_))));
+}
+
+#[tokio::test]
+async fn entries_matching_test() {
+    let reader =
+        ZipFileReader::new(archive(Compression::Stored, FILENAMES.iter().map(|filename| (*filename, filename))).await)
+            .await
+            .unwrap();
+    assert_eq!(reader.file().entries_matching(&glob("assets/**/*.png")), [1, 2]);
+    assert_eq!(reader.file().entries_matching(&glob("*")), [0]);
+    assert!(reader.file().entries_matching(&glob("*.toml")).is_empty());
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn extract_filter_test() {
+    use crate::read::extract::ExtractOptions;
+
+    let dest = std::env::temp_dir().join(format!("async_zip_pattern_{}", std::process::id()));
+    let reader =
+        ZipFileReader::new(archive(Compression::Stored, FILENAMES.iter().map(|filename| (*filename, filename))).await)
+            .await
+            .unwrap();
+    let options = ExtractOptions::new().filter(glob("assets/**/*.png"));
+    reader.extract_with_options(&dest, options).await.unwrap();
+
+    assert!(dest.join("assets/logo.png").exists());
+    assert!(dest.join("assets/icons/large/app.png").exists());
+    assert!(!dest.join("assets/logo.jpg").exists());
+    assert!(!dest.join("README.md").exists());
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
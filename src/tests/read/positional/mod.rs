@@ -0,0 +1,62 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::positional::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use std::sync::Arc;
+
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn positional_concurrent_test() {
+    let source = Arc::new(archive(Compression::Stored, [("foo.txt", [b'f'; 4096]), ("bar.txt", [b'b'; 4096])]).await);
+    let reader = ZipFileReader::from_arc(source.clone(), Default::default()).await.unwrap();
+
+    assert_eq!(reader.file().entries().len(), 2);
+    assert!(Arc::ptr_eq(reader.source(), &source));
+
+    let (mut foo, mut bar) = (reader.entry(0).await.unwrap(), reader.entry(1).await.unwrap());
+    let (mut foo_data, mut bar_data) = (Vec::new(), Vec::new());
+
+    // Interleave small reads from both entries over the one shared source.
+    let (mut foo_chunk, mut bar_chunk) = ([0; 512], [0; 512]);
+    loop {
+        let foo_read = foo.read(&mut foo_chunk).await.unwrap();
+        let bar_read = bar.read(&mut bar_chunk).await.unwrap();
+
+        if foo_read == 0 && bar_read == 0 {
+            break;
+        }
+
+        foo_data.extend_from_slice(&foo_chunk[..foo_read]);
+        bar_data.extend_from_slice(&bar_chunk[..bar_read]);
+    }
+
+    assert_eq!(foo_data, vec![b'f'; 4096]);
+    assert_eq!(bar_data, vec![b'b'; 4096]);
+
+    let entries = reader.file().entries();
+    assert_eq!(foo.compute_hash(), entries[0].crc32());
+    assert_eq!(bar.compute_hash(), entries[1].crc32());
+}
+
+#[cfg(all(feature = "fs", unix))]
+#[tokio::test]
+async fn positional_file_test() {
+    use std::io::Write;
+
+    let path = std::env::temp_dir().join(format!("async_zip_positional_{}.zip", std::process::id()));
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(&archive(Compression::Stored, [("foo.txt", [b'f'; 4096]), ("bar.txt", [b'b'; 4096])]).await)
+        .unwrap();
+
+    let reader = ZipFileReader::new(std::fs::File::open(&path).unwrap()).await.unwrap();
+    let mut data = Vec::new();
+    reader.entry(1).await.unwrap().read_to_end(&mut data).await.unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(data, vec![b'b'; 4096]);
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use std::sync::{Arc, Mutex};
+
+use tokio::io::AsyncReadExt;
+
+const ENTRIES: [(&str, &[u8]); 3] = [("foo/", &[]), ("foo/bar.txt", &[b'b'; 1000]), ("baz.txt", b"baz")];
+
+#[tokio::test]
+async fn entry_progress_test() {
+    let reader = ZipFileReader::new(archive(Compression::Stored, ENTRIES).await).await.unwrap();
+    let reported = Arc::new(Mutex::new(Vec::new()));
+
+    let sink = reported.clone();
+    let mut entry_reader = reader.entry(1).await.unwrap().with_progress(move |progress| {
+        sink.lock().unwrap().push((progress.read(), progress.total()));
+    });
+
+    let mut buffer = [0; 400];
+    while entry_reader.read(&mut buffer).await.unwrap() != 0 {}
+
+    assert_eq!(*reported.lock().unwrap(), [(400, 1000), (800, 1000), (1000, 1000)]);
+    assert_eq!(entry_reader.progress().read(), 1000);
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn extract_progress_test() {
+    use crate::read::extract::ExtractOptions;
+    use std::path::PathBuf;
+
+    let dest = std::env::temp_dir().join(format!("async_zip_progress_{}", std::process::id()));
+    let reader = ZipFileReader::new(archive(Compression::Stored, ENTRIES).await).await.unwrap();
+    let reported = Arc::new(Mutex::new(Vec::new()));
+
+    let sink = reported.clone();
+    let options = ExtractOptions::new().progress(move |progress| {
+        sink.lock().unwrap().push(progress);
+    });
+
+    // Skip the directory entry, so that only two entries are to be extracted.
+    let map = |entry: &crate::ZipEntry| Some(PathBuf::from(entry.filename())).filter(|_| !entry.dir());
+    let result = reader.extract_with_mapping(&dest, options, map).await;
+    tokio::fs::remove_dir_all(&dest).await.unwrap();
+    result.unwrap();
+
+    let reported = reported.lock().unwrap();
+    let last = reported.last().unwrap();
+
+    assert!(reported.iter().all(|progress| progress.entries_total() == Some(2)));
+    assert!(reported.iter().any(|progress| progress.entries_completed() == 0 && progress.entry().read() == 1000));
+    assert_eq!((last.entries_completed(), last.entry().read(), last.entry().total()), (2, 3, 3));
+}
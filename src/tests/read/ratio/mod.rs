@@ -0,0 +1,31 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn suspicious_entries_test() {
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("zeros.bin"), Compression::Deflate);
+    writer.write_entry_whole(entry, &vec![0; 1 << 20]).await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("empty.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, &[]).await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(entries[0].compression_ratio(), Some(1.0));
+    assert!(entries[1].compression_ratio().unwrap() > 100.0);
+    assert_eq!(entries[2].compression_ratio(), None);
+
+    assert_eq!(reader.file().suspicious_entries(100.0), [1]);
+    assert!(reader.file().suspicious_entries(f64::INFINITY).is_empty());
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::diagnostics::{OpenWarning, ReaderOptions};
+use crate::spec::compression::Compression;
+use crate::spec::consts::CDH_SIGNATURE;
+use crate::tests::archive;
+
+use std::io::Cursor;
+
+use tokio::io::AsyncReadExt;
+
+/// Writes an archive of three entries, corrupting the signature of the second's central directory record.
+async fn corrupt_archive() -> (Vec<u8>, usize) {
+    let mut data =
+        archive(Compression::Stored, ["foo.txt", "bar.txt", "baz.txt"].map(|filename| (filename, filename))).await;
+    let signature = CDH_SIGNATURE.to_le_bytes();
+    let offset = data.windows(4).enumerate().filter(|(_, window)| *window == signature).nth(1).unwrap().0;
+    data[offset] = 0;
+
+    (data, offset)
+}
+
+#[tokio::test]
+async fn skip_corrupt_entries_test() {
+    let (data, offset) = corrupt_archive().await;
+    assert!(crate::read::mem::ZipFileReader::new(data.clone()).await.is_err());
+
+    let options = ReaderOptions::new().skip_corrupt_entries(true).diagnostics(true);
+    let reader = crate::read::mem::ZipFileReader::new_with_options(data, options).await.unwrap();
+    let filenames: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename()).collect();
+    assert_eq!(filenames, ["foo.txt", "baz.txt"]);
+
+    let warnings = reader.file().open_report().unwrap().warnings();
+    assert!(matches!(warnings[0], OpenWarning::CorruptRecord { index: 1, offset: o, .. } if o == offset as u64));
+
+    let mut data = String::new();
+    reader.entry(1).await.unwrap().read_to_string(&mut data).await.unwrap();
+    assert_eq!(data, "baz.txt");
+}
+
+#[tokio::test]
+async fn lazy_skip_corrupt_entries_test() {
+    let (data, _) = corrupt_archive().await;
+    assert!(crate::read::lazy::ZipFileReader::new(Cursor::new(data.clone())).await.is_err());
+
+    let options = ReaderOptions::new().skip_corrupt_entries(true);
+    let mut reader = crate::read::lazy::ZipFileReader::new_with_options(Cursor::new(data), options).await.unwrap();
+    assert_eq!(reader.len(), 3);
+
+    let mut entries = reader.entries();
+    let mut results = Vec::new();
+
+    while let Some(entry) = entries.next().await {
+        results.push(entry.map(|entry| entry.filename().to_owned()));
+    }
+
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_deref().unwrap(), "foo.txt");
+    assert!(matches!(results[1], Err(ZipError::UnexpectedHeaderError(_, CDH_SIGNATURE))));
+    assert_eq!(results[2].as_deref().unwrap(), "baz.txt");
+
+    let mut data = String::new();
+    reader.entry(2).await.unwrap().read_to_string(&mut data).await.unwrap();
+    assert_eq!(data, "baz.txt");
+}
@@ -0,0 +1,69 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::remote::{RangeSource, ZipFileReader};
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::future::Future;
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// An in-memory source which counts the number of ranged reads issued against it.
+struct CountingSource {
+    data: Vec<u8>,
+    reads: AtomicUsize,
+}
+
+impl RangeSource for CountingSource {
+    fn length(&self) -> impl Future<Output = std::io::Result<u64>> + Send {
+        std::future::ready(Ok(self.data.len() as u64))
+    }
+
+    fn read_range(&self, range: Range<u64>) -> impl Future<Output = std::io::Result<Vec<u8>>> + Send {
+        self.reads.fetch_add(1, Ordering::SeqCst);
+        std::future::ready(Ok(self.data[range.start as usize..range.end as usize].to_vec()))
+    }
+}
+
+async fn archive(comment: String) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar bar").await.unwrap();
+    writer.comment(comment).unwrap();
+    writer.close().await.unwrap();
+    buffer
+}
+
+#[tokio::test]
+async fn remote_read_test() {
+    let source = CountingSource { data: archive(String::from("comment")).await, reads: AtomicUsize::new(0) };
+    let reader = ZipFileReader::new(source).await.unwrap();
+
+    assert_eq!(reader.source().reads.load(Ordering::SeqCst), 1);
+    assert_eq!(reader.file().entries().len(), 2);
+    assert_eq!(reader.file().comment(), "comment");
+
+    let mut data = String::new();
+    let entry = &reader.file().entries()[1];
+    reader.entry(1).await.unwrap().read_to_string_checked(&mut data, entry).await.unwrap();
+
+    assert_eq!(data, "bar bar");
+    assert_eq!(reader.source().reads.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn remote_max_comment_test() {
+    let comment = "a".repeat(u16::MAX as usize);
+    let source = CountingSource { data: archive(comment).await, reads: AtomicUsize::new(0) };
+    let reader = ZipFileReader::new(source).await.unwrap();
+
+    // The central directory lies beyond the trailing bytes searched for the EOCDR, so must be fetched separately.
+    assert_eq!(reader.source().reads.load(Ordering::SeqCst), 2);
+    assert_eq!(reader.file().entries()[0].filename(), "foo.txt");
+    assert_eq!(reader.file().comment().len(), u16::MAX as usize);
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::read::rewrite::RewriteAction;
+use crate::read::seek::ZipFileReader as SeekZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+const FILENAMES: [&str; 3] = ["foo.txt", "bar.txt", "baz.txt"];
+
+fn decide(filename: &str) -> RewriteAction {
+    match filename {
+        "foo.txt" => RewriteAction::Drop,
+        "bar.txt" => RewriteAction::Rename(String::from("qux.txt")),
+        _ => RewriteAction::Comment(String::from("baz comment")),
+    }
+}
+
+async fn assert_rewritten(data: Vec<u8>) {
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let filenames: Vec<&str> = reader.file().entries().iter().map(|entry| entry.filename()).collect();
+    assert_eq!(filenames, ["qux.txt", "baz.txt"]);
+    assert_eq!(reader.file().entries()[1].comment(), "baz comment");
+
+    for (index, expected) in ["bar.txt", "baz.txt"].into_iter().enumerate() {
+        let entry = reader.file().entries()[index].clone();
+        let mut buffer = Vec::new();
+
+        assert_eq!(entry.compression(), Compression::Deflate);
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+        assert_eq!(buffer, expected.repeat(8).as_bytes());
+    }
+}
+
+#[tokio::test]
+async fn rewrite_mem_test() {
+    let reader = ZipFileReader::new(
+        archive(Compression::Deflate, FILENAMES.map(|filename| (filename, filename.repeat(8)))).await,
+    )
+    .await
+    .unwrap();
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    reader.rewrite(&mut writer, |entry| decide(entry.filename())).await.unwrap();
+    writer.close().await.unwrap();
+
+    assert_rewritten(data).await;
+}
+
+#[tokio::test]
+async fn rewrite_seek_test() {
+    let mut reader = SeekZipFileReader::new(Cursor::new(
+        archive(Compression::Deflate, FILENAMES.map(|filename| (filename, filename.repeat(8)))).await,
+    ))
+    .await
+    .unwrap();
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    reader.rewrite(&mut writer, |entry| decide(entry.filename())).await.unwrap();
+    writer.close().await.unwrap();
+
+    assert_rewritten(data).await;
+}
+
+#[tokio::test]
+async fn rewrite_encrypted_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).zipcrypto_password(b"password");
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored), b"bar").await.unwrap();
+    writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let result = reader.rewrite(&mut writer, |_| RewriteAction::Keep).await;
+    assert!(matches!(result, Err(ZipError::FeatureNotSupported(_))));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    reader.rewrite(&mut writer, |entry| decide(entry.filename())).await.unwrap();
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn rewrite_in_place_test() {
+    use crate::read::fs::ZipFileReader;
+
+    let dir = std::env::temp_dir().join(format!("async_zip_rewrite_{}", std::process::id()));
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    let path = dir.join("foo.zip");
+    tokio::fs::write(
+        &path,
+        archive(Compression::Deflate, FILENAMES.map(|filename| (filename, filename.repeat(8)))).await,
+    )
+    .await
+    .unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    reader.rewrite_to_path(dir.join("copy.zip"), |entry| decide(entry.filename())).await.unwrap();
+    reader.rewrite_to_path(dir.join(".").join("foo.zip"), |entry| decide(entry.filename())).await.unwrap();
+
+    assert_rewritten(tokio::fs::read(dir.join("copy.zip")).await.unwrap()).await;
+    assert_rewritten(tokio::fs::read(&path).await.unwrap()).await;
+
+    let mut remaining: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    remaining.sort();
+    assert_eq!(remaining, ["copy.zip", "foo.zip"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(all(feature = "fs", unix))]
+#[tokio::test]
+async fn rewrite_in_place_concurrent_test() {
+    use crate::read::fs::ZipFileReader;
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = std::env::temp_dir().join(format!("async_zip_rewrite_concurrent_{}", std::process::id()));
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    let path = dir.join("foo.zip");
+    tokio::fs::write(
+        &path,
+        archive(Compression::Deflate, FILENAMES.map(|filename| (filename, filename.repeat(8)))).await,
+    )
+    .await
+    .unwrap();
+    tokio::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).await.unwrap();
+
+    // Both rewrites use their own temporary file, so neither clobbers the other's before it's renamed.
+    let (first, second) = (ZipFileReader::new(&path).await.unwrap(), ZipFileReader::new(&path).await.unwrap());
+    let (first, second) = tokio::join!(
+        first.rewrite_to_path(&path, |entry| decide(entry.filename())),
+        second.rewrite_to_path(&path, |entry| decide(entry.filename())),
+    );
+    first.unwrap();
+    second.unwrap();
+
+    assert_rewritten(tokio::fs::read(&path).await.unwrap()).await;
+    assert_eq!(tokio::fs::metadata(&path).await.unwrap().permissions().mode() & 0o777, 0o640);
+
+    let remaining: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(remaining, ["foo.zip"]);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use tokio::io::AsyncReadExt;
+
+#[tokio::test]
+async fn spawn_mem_test() {
+    let reader =
+        ZipFileReader::new(archive(Compression::Stored, [("foo.txt", "foo.txt"), ("bar.txt", "bar.txt")]).await)
+            .await
+            .unwrap();
+    let mut handles = Vec::new();
+
+    for index in 0..2 {
+        let mut entry_reader = reader.entry(index).await.unwrap();
+
+        handles.push(tokio::spawn(async move {
+            let mut data = Vec::new();
+            entry_reader.read_to_end(&mut data).await.unwrap();
+            data
+        }));
+    }
+
+    drop(reader);
+    assert_eq!(handles.remove(0).await.unwrap(), b"foo.txt");
+    assert_eq!(handles.remove(0).await.unwrap(), b"bar.txt");
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn spawn_fs_test() {
+    use crate::read::fs::ZipFileReader;
+
+    let path = std::env::temp_dir().join(format!("async_zip_spawn_{}.zip", std::process::id()));
+    tokio::fs::write(&path, archive(Compression::Stored, [("foo.txt", "foo.txt"), ("bar.txt", "bar.txt")]).await)
+        .await
+        .unwrap();
+
+    let reader = ZipFileReader::new(&path).await.unwrap();
+    let mut entry_reader = reader.entry(1).await.unwrap();
+    drop(reader);
+
+    let data = tokio::spawn(async move {
+        let mut data = Vec::new();
+        entry_reader.read_to_end(&mut data).await.unwrap();
+        data
+    });
+
+    assert_eq!(data.await.unwrap(), b"bar.txt");
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,291 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::stream::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+const ENTRIES: [(&str, &str); 2] = [("foo.txt", "foo"), ("bar.txt", "bar bar")];
+
+async fn read_archive<R: tokio::io::AsyncRead + Unpin>(mut reader: ZipFileReader<R>) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+
+    while let Some((entry, mut entry_reader)) = reader.next_entry().await.unwrap() {
+        let entry = entry.clone();
+        let mut data = String::new();
+        entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+        entries.push((entry.filename().to_string(), data));
+    }
+
+    assert!(reader.finished());
+    entries
+}
+
+#[tokio::test]
+async fn stream_read_test() {
+    let data = archive(Compression::Stored, ENTRIES).await;
+    let entries = read_archive(ZipFileReader::new(Cursor::new(data))).await;
+
+    assert_eq!(entries, vec![("foo.txt".into(), "foo".into()), ("bar.txt".into(), "bar bar".into())]);
+}
+
+#[tokio::test]
+async fn stream_skip_unread_test() {
+    let data = archive(Compression::Stored, ENTRIES).await;
+    let mut reader = ZipFileReader::new(Cursor::new(data));
+
+    assert!(reader.next_entry().await.unwrap().is_some());
+    let (entry, _) = reader.next_entry().await.unwrap().expect("second entry missing");
+    assert_eq!(entry.filename(), "bar.txt");
+    assert!(reader.next_entry().await.unwrap().is_none());
+}
+
+#[cfg(feature = "gzip")]
+#[tokio::test]
+async fn stream_read_outer_gzip_test() {
+    use crate::read::stream::OuterCompression;
+    use async_compression::tokio::write::GzipEncoder;
+    use tokio::io::AsyncWriteExt;
+
+    let data = archive(Compression::Stored, ENTRIES).await;
+    let mut encoder = GzipEncoder::new(Vec::new());
+    encoder.write_all(&data).await.unwrap();
+    encoder.shutdown().await.unwrap();
+
+    let reader = ZipFileReader::with_outer(Cursor::new(encoder.into_inner()), OuterCompression::Gzip);
+    let entries = read_archive(reader).await;
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1], ("bar.txt".into(), "bar bar".into()));
+}
+
+#[tokio::test]
+async fn stream_list_test() {
+    let data = archive(Compression::Stored, ENTRIES).await;
+    let mut listing = ZipFileReader::new(Cursor::new(data)).list();
+    let mut filenames = Vec::new();
+
+    while let Some(entry) = listing.next().await.unwrap() {
+        filenames.push((entry.filename().to_string(), entry.uncompressed_size()));
+    }
+
+    assert_eq!(filenames, vec![("foo.txt".into(), 3), ("bar.txt".into(), 7)]);
+    assert!(listing.into_inner().finished());
+}
+
+#[cfg(feature = "deflate")]
+async fn write_descriptor_archive(signature: bool) -> Vec<u8> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    writer.data_descriptor_signature(signature);
+
+    for (filename, data) in [("foo.txt", "foo ".repeat(2048)), ("bar.txt", String::from("bar bar"))] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Deflate);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(data.as_bytes()).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    writer.close().await.unwrap();
+    buffer
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_read_data_descriptor_test() {
+    let data = write_descriptor_archive(true).await;
+    let entries = read_archive(ZipFileReader::new(Cursor::new(data))).await;
+
+    assert_eq!(entries, vec![("foo.txt".into(), "foo ".repeat(2048)), ("bar.txt".into(), "bar bar".into())]);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_read_unsigned_data_descriptor_test() {
+    let data = write_descriptor_archive(false).await;
+    assert_eq!(data.len() + 2 * 4, write_descriptor_archive(true).await.len());
+
+    let entries = read_archive(ZipFileReader::new(Cursor::new(data))).await;
+    assert_eq!(entries, vec![("foo.txt".into(), "foo ".repeat(2048)), ("bar.txt".into(), "bar bar".into())]);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_skip_data_descriptor_test() {
+    let data = write_descriptor_archive(true).await;
+    let mut listing = ZipFileReader::new(Cursor::new(data)).list();
+    let mut filenames = Vec::new();
+
+    while let Some(entry) = listing.next().await.unwrap() {
+        filenames.push(entry.filename().to_string());
+    }
+
+    assert_eq!(filenames, vec![String::from("foo.txt"), String::from("bar.txt")]);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_partial_data_descriptor_test() {
+    use crate::error::ZipError;
+    use tokio::io::AsyncReadExt;
+
+    let data = write_descriptor_archive(true).await;
+    let mut reader = ZipFileReader::new(Cursor::new(data));
+
+    let (_, mut entry_reader) = reader.next_entry().await.unwrap().unwrap();
+    entry_reader.read_exact(&mut [0; 4]).await.unwrap();
+
+    assert!(matches!(reader.next_entry().await, Err(ZipError::EntryNotFullyRead)));
+}
+
+#[tokio::test]
+async fn stream_read_stored_data_descriptor_test() {
+    use tokio::io::AsyncWriteExt;
+
+    // The data contains the data descriptor's signature (followed by sizes which don't match) part way through.
+    let mut contents = b"foo".to_vec();
+    contents.extend_from_slice(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes());
+    contents.extend_from_slice(&[0; 12]);
+    contents.extend_from_slice(b"bar");
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+
+    for (filename, data) in [("foo.txt", &contents[..]), ("bar.txt", b"bar bar")] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(data).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(buffer.clone()));
+    let mut entries = Vec::new();
+
+    while let Some((entry, mut entry_reader)) = reader.next_entry().await.unwrap() {
+        let entry = entry.clone();
+        let mut data = Vec::new();
+        entry_reader.read_to_end_checked(&mut data, &entry).await.unwrap();
+        entries.push((entry.filename().to_string(), data));
+    }
+
+    assert_eq!(entries, vec![("foo.txt".into(), contents), ("bar.txt".into(), b"bar bar".to_vec())]);
+
+    let mut listing = ZipFileReader::new(Cursor::new(buffer)).list();
+    let mut filenames = Vec::new();
+
+    while let Some(entry) = listing.next().await.unwrap() {
+        filenames.push(entry.filename().to_string());
+    }
+
+    assert_eq!(filenames, vec![String::from("foo.txt"), String::from("bar.txt")]);
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn stream_data_descriptor_size_mismatch_test() {
+    use crate::error::ZipError;
+    use crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE;
+
+    let mut data = write_descriptor_archive(true).await;
+    let signature = DATA_DESCRIPTOR_SIGNATURE.to_le_bytes();
+    let descriptor = data.windows(4).position(|window| window == signature).unwrap();
+    data[descriptor + 8] ^= 1;
+
+    let mut reader = ZipFileReader::new(Cursor::new(data));
+    reader.next_entry().await.unwrap();
+
+    let error = reader.next_entry().await.err().unwrap();
+    assert!(matches!(error.root(), ZipError::DataDescriptorSizeMismatch));
+}
+
+#[tokio::test]
+async fn data_descriptor_parse_test() {
+    let mut data = Vec::new();
+    data.extend_from_slice(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes());
+    data.extend_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0]);
+    assert_eq!(crate::read::data_descriptor(Cursor::new(data), false, 1).await.unwrap(), (1, 2, 3));
+
+    let mut data = vec![1, 0, 0, 0];
+    data.extend_from_slice(&2u64.to_le_bytes());
+    data.extend_from_slice(&3u64.to_le_bytes());
+    assert_eq!(crate::read::data_descriptor(Cursor::new(data), true, 1).await.unwrap(), (1, 2, 3));
+}
+
+#[tokio::test]
+async fn data_descriptor_signature_crc_test() {
+    use crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE;
+
+    // Both forms are told apart when the CRC32 value matches the signature.
+    let signature = DATA_DESCRIPTOR_SIGNATURE.to_le_bytes();
+    let signed = [signature, signature, [2, 0, 0, 0], [3, 0, 0, 0]].concat();
+    let unsigned = [signature, [2, 0, 0, 0], [3, 0, 0, 0]].concat();
+    let expected = (DATA_DESCRIPTOR_SIGNATURE, 2, 3);
+
+    for data in [signed, unsigned] {
+        let descriptor = crate::read::data_descriptor(Cursor::new(data), false, DATA_DESCRIPTOR_SIGNATURE);
+        assert_eq!(descriptor.await.unwrap(), expected);
+    }
+}
+
+#[tokio::test]
+async fn stream_duplex_test() {
+    let (mut writer, mut reader) = crate::read::stream::duplex(64);
+
+    let write = async move {
+        for index in 0..16 {
+            let entry = ZipEntryBuilder::new(format!("{}.txt", index), Compression::Stored);
+            writer.write_entry_whole(entry, index.to_string().repeat(32).as_bytes()).await.unwrap();
+        }
+
+        writer.close().await.unwrap();
+    };
+
+    let read = async move {
+        let mut entries = Vec::new();
+
+        while let Some((entry, mut entry_reader)) = reader.next_entry().await.unwrap() {
+            let entry = entry.clone();
+            let mut data = String::new();
+            entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+            entries.push((entry.filename().to_string(), data));
+        }
+
+        assert!(reader.drain().await.unwrap() > 64);
+        entries
+    };
+
+    let (_, entries) = tokio::join!(write, read);
+    let expected: Vec<_> = (0..16).map(|index| (format!("{}.txt", index), index.to_string().repeat(32))).collect();
+    assert_eq!(entries, expected);
+}
+
+#[cfg(feature = "futures")]
+#[tokio::test]
+async fn stream_into_stream_test() {
+    use futures_util::{StreamExt, TryStreamExt};
+    use tokio::io::AsyncReadExt;
+
+    let data = archive(Compression::Stored, ENTRIES).await;
+    let entries: Vec<_> = ZipFileReader::new(Cursor::new(data.clone())).into_stream().try_collect().await.unwrap();
+    let mut read = Vec::new();
+
+    for (entry, mut reader) in entries {
+        let mut data = String::new();
+        reader.read_to_string(&mut data).await.unwrap();
+        read.push((entry.filename().to_string(), data));
+    }
+
+    assert_eq!(read, vec![("foo.txt".into(), "foo".into()), ("bar.txt".into(), "bar bar".into())]);
+
+    let listing = ZipFileReader::new(Cursor::new(data)).list().into_stream();
+    let filenames: Vec<_> = listing.map(|entry| entry.unwrap().filename().to_string()).collect().await;
+    assert_eq!(filenames, ["foo.txt", "bar.txt"]);
+}
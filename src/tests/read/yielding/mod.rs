@@ -0,0 +1,52 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::diagnostics::ReaderOptions;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use std::future::Future;
+use std::task::{Context, Poll, Waker};
+
+/// Drives a future to completion, returning its output and the number of times it returned pending.
+fn poll_counting<F: Future>(future: F) -> (F::Output, usize) {
+    let mut future = std::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    let mut pending = 0;
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return (output, pending),
+            Poll::Pending => pending += 1,
+        }
+    }
+}
+
+// These are driven outside of a runtime, as tokio's cooperative budgeting would otherwise also cause pending polls.
+#[test]
+fn yield_interval_test() {
+    let (data, _) = poll_counting(archive(Compression::Stored, (0..100).map(|index| (format!("{index}.txt"), "foo"))));
+
+    // In-memory sources never wait on IO, so any pending polls are yields.
+    let options = ReaderOptions::new().yield_interval(10);
+    let (reader, pending) = poll_counting(ZipFileReader::new_with_options(data.clone(), options));
+    assert_eq!(reader.unwrap().file().entries().len(), 100);
+    assert_eq!(pending, 10);
+
+    let options = ReaderOptions::new().yield_interval(0);
+    let (reader, pending) = poll_counting(ZipFileReader::new_with_options(data, options));
+    assert_eq!(reader.unwrap().file().entries().len(), 100);
+    assert_eq!(pending, 0);
+}
+
+#[test]
+fn yield_lazy_test() {
+    let (data, _) = poll_counting(archive(Compression::Stored, (0..100).map(|index| (format!("{index}.txt"), "foo"))));
+
+    let options = ReaderOptions::new().yield_interval(25);
+    let reader = crate::read::lazy::ZipFileReader::new_with_options(std::io::Cursor::new(data), options);
+    let (reader, pending) = poll_counting(reader);
+    assert_eq!(reader.unwrap().len(), 100);
+    assert_eq!(pending, 4);
+}
@@ -0,0 +1,27 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+
+#[test]
+fn compression_matches_enum_test() {
+    let capabilities = crate::capabilities();
+
+    for method in 0..=u16::MAX {
+        let supported = Compression::try_from(method).is_ok();
+        assert_eq!(capabilities.can_read(method), supported, "method {}", method);
+
+        let writable = Compression::try_from(method).is_ok_and(|compression| !compression.is_legacy());
+        assert_eq!(capabilities.can_write(method), writable, "method {}", method);
+    }
+}
+
+#[test]
+fn compression_features_test() {
+    let capabilities = crate::capabilities();
+
+    assert_eq!(capabilities.compression(0).unwrap().feature, None);
+    assert_eq!(capabilities.compression(12).unwrap().feature, Some("bzip2"));
+    assert_eq!(capabilities.compression(5).unwrap().feature, Some("legacy"));
+    assert!(capabilities.compression(9).is_none());
+}
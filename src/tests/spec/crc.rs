@@ -0,0 +1,35 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::{crc32, Crc32, ZipEntryBuilder};
+
+#[test]
+fn crc32_check_value_test() {
+    assert_eq!(crc32(b"123456789"), 0xCBF43926);
+    assert_eq!(crc32(b""), 0);
+}
+
+#[test]
+fn crc32_streaming_test() {
+    let mut hasher = Crc32::new();
+    hasher.update(b"1234");
+    hasher.update(b"");
+    hasher.update(b"56789");
+
+    assert_eq!(hasher.finalize(), crc32(b"123456789"));
+}
+
+#[tokio::test]
+async fn crc32_entry_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo bar").await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].crc32(), crc32(b"foo bar"));
+}
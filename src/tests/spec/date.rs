@@ -10,3 +10,71 @@ fn date_conversion_test() {
     let result_dt = crate::spec::date::zip_date_to_chrono(date, time);
     assert_eq!(result_dt, original_dt);
 }
+
+#[test]
+fn ntfs_conversion_test() {
+    // 2022-10-23T16:55:02.1234567Z as a Windows FILETIME.
+    let result_dt = crate::spec::date::ntfs_to_chrono(133_110_177_021_234_567).unwrap();
+    assert_eq!(result_dt, Utc.timestamp_opt(1666544102, 123_456_700).unwrap());
+    assert!(crate::spec::date::ntfs_to_chrono(0).is_none());
+}
+
+#[tokio::test]
+async fn ntfs_extra_field_test() {
+    use crate::read::mem::ZipFileReader;
+    use crate::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let mut attribute = Vec::new();
+    attribute.extend_from_slice(&133_110_177_021_234_567u64.to_le_bytes());
+    attribute.extend_from_slice(&0u64.to_le_bytes());
+    attribute.extend_from_slice(&116_444_736_000_000_000u64.to_le_bytes());
+
+    let mut data = vec![0; 4];
+    crate::spec::extra_field::push_extra_field(&mut data, 0x0001, &attribute);
+    let mut extra_field = Vec::new();
+    crate::spec::extra_field::push_extra_field(&mut extra_field, crate::spec::extra_field::NTFS_HEADER_ID, &data);
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).extra_field(extra_field);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.ntfs_last_modification_date(), Some(&Utc.timestamp_opt(1666544102, 123_456_700).unwrap()));
+    assert_eq!(entry.ntfs_last_access_date(), None);
+    assert_eq!(entry.ntfs_creation_date(), Some(&Utc.timestamp_opt(0, 0).unwrap()));
+}
+
+#[tokio::test]
+async fn extended_timestamp_test() {
+    use crate::read::mem::ZipFileReader;
+    use crate::write::ZipFileWriter;
+    use crate::{Compression, ZipEntryBuilder};
+
+    let original_dt = Utc.timestamp_opt(1666544103, 0).unwrap();
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).last_modification_date(original_dt);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(entries[0].extended_last_modification_date(), Some(&original_dt));
+    assert_eq!(entries[0].last_modification_date(), &original_dt);
+    assert!(entries[1].extended_last_modification_date().is_none());
+}
+
+#[test]
+fn extended_timestamp_parse_test() {
+    let data = [0x07, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00];
+    assert_eq!(crate::spec::extra_field::parse_extended_timestamp(&data), [Some(1), Some(2), None]);
+}
@@ -0,0 +1,42 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::{FilenameSource, ZipEntryBuilder};
+
+#[test]
+fn cp437_round_trip_test() {
+    let encoded = crate::spec::encoding::encode_cp437("Ünïcödé ✓.txt");
+    assert_eq!(crate::spec::encoding::decode_cp437(&encoded), "Ünïcödé _.txt");
+}
+
+#[test]
+fn cp437_header_fallback_test() {
+    let (value, source) = crate::spec::encoding::decode_header_string(vec![0x80, b'a'], false);
+    assert_eq!((value.as_str(), source), ("Ça", FilenameSource::Cp437));
+
+    let (value, source) = crate::spec::encoding::decode_header_string("ü".as_bytes().to_vec(), false);
+    assert_eq!((value.as_str(), source), ("ü", FilenameSource::Utf8));
+}
+
+#[tokio::test]
+async fn unicode_path_extra_field_test() {
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+
+    let entry = ZipEntryBuilder::new(String::from("日本語.txt"), Compression::Stored).unicode_path_extra_field(true);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("日本語.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(entries[0].filename(), "日本語.txt");
+    assert_eq!(entries[0].filename_source(), FilenameSource::UnicodePathExtraField);
+    assert_eq!(entries[1].filename(), "日本語.txt");
+    assert_eq!(entries[1].filename_source(), FilenameSource::Utf8);
+}
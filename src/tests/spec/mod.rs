@@ -1,4 +1,7 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod capabilities;
+pub(crate) mod crc;
 pub(crate) mod date;
+pub(crate) mod encoding;
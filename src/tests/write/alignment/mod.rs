@@ -0,0 +1,55 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::spec::extra_field::ALIGNMENT_HEADER_ID;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use tokio::io::AsyncWriteExt;
+
+const ENTRIES: [(&str, u16); 4] = [("a.txt", 4), ("lib/arm64/libfoo.so", 4096), ("bc.txt", 0), ("res/raw/d.bin", 4)];
+
+/// Returns the offset at which an entry's data begins, as given by its local file header.
+fn data_offset(data: &[u8], header_offset: u64) -> u64 {
+    let header = &data[header_offset as usize..];
+    let filename_length = u16::from_le_bytes([header[26], header[27]]) as u64;
+    let extra_field_length = u16::from_le_bytes([header[28], header[29]]) as u64;
+
+    header_offset + 30 + filename_length + extra_field_length
+}
+
+#[tokio::test]
+async fn alignment_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for (index, (filename, alignment)) in ENTRIES.into_iter().enumerate() {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored).alignment(alignment);
+
+        // Each of the writers which write a local file header upfront is exercised.
+        if index % 2 == 0 {
+            writer.write_entry_whole(entry, filename.as_bytes()).await.unwrap();
+        } else {
+            let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+            entry_writer.write_all(filename.as_bytes()).await.unwrap();
+            entry_writer.close().await.unwrap();
+        }
+    }
+
+    let data = writer.close().await.unwrap();
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+
+    for (index, (filename, alignment)) in ENTRIES.into_iter().enumerate() {
+        let offset = data_offset(&data, reader.file().meta(index).unwrap().header_offset());
+        assert_eq!(offset % u64::from(alignment.max(1)), 0, "{} isn't aligned", filename);
+
+        // The alignment extra field is only written within the local file header.
+        let entry = &reader.file().entries()[index];
+        assert!(crate::spec::extra_field::find_extra_field(entry.extra_field(), ALIGNMENT_HEADER_ID).is_none());
+
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+        assert_eq!(buffer, filename.as_bytes());
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn append_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    writer.comment(String::from("foo bar")).unwrap();
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).comment(String::from("first"));
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap();
+    let original_length = data.len();
+
+    let mut cursor = Cursor::new(data);
+    let mut writer = ZipFileWriter::append(&mut cursor).await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("baz.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"baz").await.unwrap();
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+
+    let data = cursor.into_inner();
+    assert!(data.len() > original_length);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().comment(), "foo bar");
+    assert_eq!(reader.file().entries()[0].comment(), "first");
+
+    let expected = [("foo.txt", b"foo"), ("bar.txt", b"bar"), ("baz.txt", b"baz")];
+    for (index, (filename, expected)) in expected.iter().enumerate() {
+        let entry = reader.file().entries()[index].clone();
+        let mut buffer = Vec::new();
+
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+        assert_eq!(entry.filename(), *filename);
+        assert_eq!(buffer, *expected);
+    }
+}
+
+#[tokio::test]
+async fn append_invalid_test() {
+    let mut cursor = Cursor::new(b"This isn't a ZIP file.".to_vec());
+    let result = ZipFileWriter::append(&mut cursor).await;
+
+    assert!(matches!(result, Err(ZipError::UnableToLocateEOCDR)));
+}
@@ -0,0 +1,60 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::{archive_dir, ArchiveOptions, ZipFileWriter};
+
+#[tokio::test]
+async fn archive_dir_test() {
+    let root = std::env::temp_dir().join(format!("async_zip_archive_{}", std::process::id()));
+    std::fs::create_dir_all(root.join("src/nested")).unwrap();
+    std::fs::create_dir_all(root.join("target/debug")).unwrap();
+    std::fs::create_dir_all(root.join("empty")).unwrap();
+    std::fs::write(root.join("README"), b"readme").unwrap();
+    std::fs::write(root.join("src/main.rs"), b"fn main() {}").unwrap();
+    std::fs::write(root.join("src/main.rs.orig"), b"fn main() { todo!() }").unwrap();
+    std::fs::write(root.join("src/nested/lib.rs"), b"pub mod nested;").unwrap();
+    std::fs::write(root.join("target/debug/build.log"), b"log").unwrap();
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("src/main.rs", root.join("link")).unwrap();
+
+    let options = ArchiveOptions::new()
+        .compression(Compression::Stored)
+        .include(|path| path.extension().is_none_or(|extension| extension != "orig"))
+        .exclude(|path| path.starts_with("target"));
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    archive_dir(&mut writer, &root, &options).await.unwrap();
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+
+    let filenames: Vec<&str> = reader.file().entries().iter().map(|entry| entry.filename()).collect();
+    #[cfg(unix)]
+    assert_eq!(filenames, ["README", "empty/", "link", "src/", "src/main.rs", "src/nested/", "src/nested/lib.rs"]);
+    #[cfg(not(unix))]
+    assert_eq!(filenames, ["README", "empty/", "src/", "src/main.rs", "src/nested/", "src/nested/lib.rs"]);
+
+    let index = filenames.iter().position(|filename| *filename == "src/nested/lib.rs").unwrap();
+    let entry = &reader.file().entries()[index];
+    let mut buffer = Vec::new();
+    reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!(buffer, b"pub mod nested;");
+
+    let modified = std::fs::metadata(root.join("src/nested/lib.rs")).unwrap().modified().unwrap();
+    let modified = chrono::DateTime::<chrono::Utc>::from(modified).timestamp();
+    assert_eq!(entry.last_modification_date().timestamp(), modified);
+    assert!(reader.file().entries()[1].dir());
+
+    #[cfg(unix)]
+    {
+        let index = filenames.iter().position(|filename| *filename == "link").unwrap();
+        let entry = &reader.file().entries()[index];
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+
+        assert!(entry.is_symlink());
+        assert_eq!(buffer, b"src/main.rs");
+    }
+
+    std::fs::remove_dir_all(root).unwrap();
+}
@@ -0,0 +1,35 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn attribute_round_trip_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new(String::from("run.sh"), Compression::Stored).unix_permissions(0o100755);
+    writer.write_entry_whole(entry, b"#!/bin/sh").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("README.TXT"), Compression::Stored)
+        .attribute_compatibility(AttributeCompatibility::Dos)
+        .internal_file_attribute(1)
+        .external_file_attribute(0x01);
+    writer.write_entry_whole(entry, b"readme").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!(entries[0].attribute_compatibility(), AttributeCompatibility::Unix);
+    assert_eq!(entries[0].unix_permissions(), Some(0o100755));
+    assert_eq!(entries[0].unix_mode(), Some(0o755));
+
+    // The host is read back from the upper byte of "version made by", so MS-DOS attributes aren't treated as Unix ones.
+    assert_eq!(entries[1].attribute_compatibility(), AttributeCompatibility::Dos);
+    assert_eq!(entries[1].internal_file_attribute(), 1);
+    assert_eq!(entries[1].external_file_attribute(), 0x01);
+    assert_eq!(entries[1].unix_permissions(), None);
+}
@@ -0,0 +1,57 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test]
+async fn writer_cancel_test() {
+    let token = CancellationToken::new();
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    writer.cancellation_token(token.clone());
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    token.cancel();
+
+    let offset = writer.offset();
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    let result = writer.write_entry_whole(entry, b"bar").await;
+
+    assert!(matches!(result, Err(ZipError::Cancelled(1))));
+    assert_eq!(writer.offset(), offset);
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    assert_eq!(reader.file().entries().len(), 1);
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn extract_cancel_test() {
+    use crate::read::extract::ExtractOptions;
+
+    let mut buffer = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close().await.unwrap();
+
+    let token = CancellationToken::new();
+    token.cancel();
+
+    let dest = std::env::temp_dir().join(format!("async_zip_cancel_{}", std::process::id()));
+    let reader = ZipFileReader::new(buffer).await.unwrap();
+    let result = reader.extract_with_options(&dest, ExtractOptions::new().cancellation_token(token)).await;
+
+    assert!(matches!(result, Err(ZipError::Cancelled(0))));
+    assert!(!dest.join("foo.txt").exists());
+
+    std::fs::remove_dir_all(dest).unwrap();
+}
@@ -0,0 +1,71 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// A writer which stores all data written to it, counting the number of writes.
+#[derive(Default)]
+struct WriteCounter {
+    data: Vec<u8>,
+    writes: usize,
+}
+
+impl AsyncWrite for WriteCounter {
+    fn poll_write(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        self.writes += 1;
+        self.data.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Streams 100 short lines into a stored entry, returning the number of writes which reached the inner writer.
+async fn write_lines(chunk_size: usize) -> usize {
+    let mut counter = WriteCounter::default();
+    let mut writer = ZipFileWriter::new(&mut counter);
+    writer.stream_chunk_size(chunk_size);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+
+    for index in 0..100 {
+        entry_writer.write_all(format!("line {}\n", index).as_bytes()).await.unwrap();
+    }
+
+    entry_writer.close().await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(counter.data).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+    let mut data = String::new();
+    reader.entry(0).await.unwrap().read_to_string_checked(&mut data, &entry).await.unwrap();
+
+    let expected: String = (0..100).map(|index| format!("line {}\n", index)).collect();
+    assert_eq!(data, expected);
+
+    counter.writes
+}
+
+#[tokio::test]
+async fn stream_chunk_size_test() {
+    let unbuffered = write_lines(0).await;
+    let buffered = write_lines(1024).await;
+
+    assert!(unbuffered >= 100, "{}", unbuffered);
+    assert!(buffered < 20, "{}", buffered);
+}
@@ -0,0 +1,38 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn comment_roundtrip_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(String::from("Ünïcode comment")).unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().comment(), "Ünïcode comment");
+}
+
+#[tokio::test]
+async fn comment_length_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment(String::from("kept")).unwrap();
+
+    let result = writer.comment("a".repeat(u16::MAX as usize + 1));
+    assert!(matches!(result, Err(ZipError::CommentTooLong(65536))));
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().comment(), "kept");
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.comment("a".repeat(u16::MAX as usize)).unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().comment().len(), u16::MAX as usize);
+}
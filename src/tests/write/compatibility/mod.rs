@@ -0,0 +1,50 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::stream::ZipFileReader;
+use crate::spec::extra_field::{EXTENDED_TIMESTAMP_HEADER_ID, UNICODE_PATH_HEADER_ID};
+use crate::write::ZipFileWriter;
+use crate::{AttributeCompatibility, FilenameSource, ZipEntryBuilder};
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn max_compatibility_test() {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = ZipFileWriter::new(&mut cursor);
+
+    let entry = ZipEntryBuilder::max_compatibility(String::from("café.txt"));
+    writer.write_entry_reader(entry, &mut Cursor::new(b"foo foo foo")).await.unwrap();
+    let entry = ZipEntryBuilder::max_compatibility(String::from("bar.txt"));
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+    writer.close().await.unwrap();
+
+    let mut reader = ZipFileReader::new(Cursor::new(cursor.into_inner()));
+    let mut entries = Vec::new();
+
+    while let Some((entry, mut entry_reader)) = reader.next_entry().await.unwrap() {
+        let entry = entry.clone();
+        let mut data = String::new();
+        entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+        entries.push((entry, data));
+    }
+
+    let (entry, data) = &entries[0];
+    assert_eq!((entry.filename(), data.as_str()), ("café.txt", "foo foo foo"));
+    assert_eq!(entry.filename_source(), FilenameSource::UnicodePathExtraField);
+    assert!(crate::spec::extra_field::find_extra_field(entry.extra_field(), EXTENDED_TIMESTAMP_HEADER_ID).is_some());
+
+    let (entry, data) = &entries[1];
+    assert_eq!((entry.filename(), data.as_str()), ("bar.txt", "bar"));
+    assert!(crate::spec::extra_field::find_extra_field(entry.extra_field(), UNICODE_PATH_HEADER_ID).is_none());
+}
+
+#[test]
+fn max_compatibility_versions_test() {
+    let entry = ZipEntryBuilder::max_compatibility(String::from("foo/")).build();
+
+    assert_eq!(entry.attribute_compatibility(), AttributeCompatibility::Dos);
+    assert_eq!(entry.external_file_attribute(), 0x10);
+    assert_eq!(crate::spec::version::as_needed_to_extract(&entry), 20);
+    assert_eq!(crate::spec::version::as_made_by(&entry) >> 8, 0);
+}
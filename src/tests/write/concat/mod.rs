@@ -0,0 +1,38 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::tests::archive;
+
+use std::io::Cursor;
+
+#[tokio::test]
+async fn concat_test() {
+    let foo = archive(Compression::Deflate, [("foo/a.txt", "foo a"), ("foo/b.txt", "foo b")]).await;
+    let bar = archive(Compression::Deflate, [("bar/a.txt", "bar a")]).await;
+
+    let mut output = Vec::new();
+    crate::write::concat([Cursor::new(foo), Cursor::new(bar)], &mut output).await.unwrap();
+
+    let reader = ZipFileReader::new(output).await.unwrap();
+    let filenames: Vec<_> = reader.file().entries().iter().map(|entry| entry.filename()).collect();
+    assert_eq!(filenames, ["foo/a.txt", "foo/b.txt", "bar/a.txt"]);
+
+    for (index, expected) in ["foo a", "foo b", "bar a"].iter().enumerate() {
+        let mut data = String::new();
+        let entry = &reader.file().entries()[index];
+        reader.entry(index).await.unwrap().read_to_string_checked(&mut data, entry).await.unwrap();
+        assert_eq!(&data, expected);
+    }
+}
+
+#[tokio::test]
+async fn concat_duplicate_test() {
+    let foo = archive(Compression::Deflate, [("a.txt", "foo")]).await;
+    let bar = archive(Compression::Deflate, [("a.txt", "bar")]).await;
+
+    let result = crate::write::concat([Cursor::new(foo), Cursor::new(bar)], Vec::new()).await;
+    assert!(matches!(result, Err(ZipError::DuplicateFilename(filename)) if filename == "a.txt"));
+}
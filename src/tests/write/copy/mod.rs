@@ -0,0 +1,38 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn write_entry_copy_test() {
+    let data: Vec<u8> = (0..100_000u32).map(|value| (value % 251) as u8).collect();
+    let (mut source, sink) = tokio::io::duplex(4096);
+
+    let producer = {
+        let data = data.clone();
+        tokio::spawn(async move {
+            source.write_all(&data).await.unwrap();
+        })
+    };
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("foo.bin"), Compression::Stored);
+    let copied = writer.write_entry_copy(entry, sink).await.unwrap();
+    producer.await.unwrap();
+
+    assert_eq!(copied, data.len() as u64);
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.uncompressed_size(), data.len() as u32);
+    assert!(reader.file().meta(0).unwrap().general_purpose_flag().data_descriptor);
+
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+    assert_eq!(buffer, data);
+}
@@ -0,0 +1,59 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+async fn archive() -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    writer.write_dir_entry(ZipEntryBuilder::new(String::from("foo"), Compression::Stored)).await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("bar/"), Compression::Stored).unix_permissions(0o700);
+    writer.write_dir_entry(entry).await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("baz/"), Compression::Stored)
+        .attribute_compatibility(AttributeCompatibility::Dos);
+    writer.write_dir_entry(entry).await.unwrap();
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn dir_entry_test() {
+    let reader = ZipFileReader::new(archive().await).await.unwrap();
+    let file = reader.file();
+
+    let filenames: Vec<_> = file.entries().iter().map(|entry| entry.filename()).collect();
+    assert_eq!(filenames, ["foo/", "bar/", "baz/"]);
+
+    for (index, entry) in file.entries().iter().enumerate() {
+        assert!(entry.dir());
+        assert_eq!(entry.compression(), Compression::Stored);
+        assert_eq!(entry.uncompressed_size(), 0);
+        assert_eq!(entry.external_file_attribute() & 0x10, 0x10);
+        assert!(!file.meta(index).unwrap().general_purpose_flag().data_descriptor);
+    }
+
+    assert_eq!(file.entries()[0].unix_permissions(), Some(0o040755));
+    assert_eq!(file.entries()[1].unix_permissions(), Some(0o040700));
+    assert_eq!(file.entries()[2].unix_permissions(), None);
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn dir_entry_extract_test() {
+    let reader = ZipFileReader::new(archive().await).await.unwrap();
+    let dest = std::env::temp_dir().join(format!("async_zip_dir_{}", std::process::id()));
+
+    reader.extract(&dest).await.unwrap();
+
+    for name in ["foo", "bar", "baz"] {
+        assert!(dest.join(name).is_dir());
+    }
+
+    tokio::fs::remove_dir_all(&dest).await.unwrap();
+}
@@ -0,0 +1,78 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+/// A writer which discards all data written to it, counting the number of times it's been flushed.
+#[derive(Default)]
+struct FlushCounter {
+    flushes: usize,
+}
+
+impl AsyncWrite for FlushCounter {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.flushes += 1;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+async fn write_entries(writer: &mut ZipFileWriter<&mut FlushCounter>) {
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"bar").await.unwrap();
+    entry_writer.close().await.unwrap();
+}
+
+#[tokio::test]
+async fn flush_entries_test() {
+    let mut counter = FlushCounter::default();
+    let mut writer = ZipFileWriter::new(&mut counter);
+    writer.flush_entries(true);
+
+    write_entries(&mut writer).await;
+    assert_eq!(writer.writer.get_ref().flushes, 2);
+}
+
+#[tokio::test]
+async fn flush_entries_disabled_test() {
+    let mut counter = FlushCounter::default();
+    let mut writer = ZipFileWriter::new(&mut counter);
+
+    write_entries(&mut writer).await;
+    assert_eq!(writer.writer.get_ref().flushes, 0);
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn close_synced_test() {
+    let path = std::env::temp_dir().join(format!("async_zip_durability_{}.zip", std::process::id()));
+    let mut file = tokio::fs::File::create(&path).await.unwrap();
+    let mut writer = ZipFileWriter::new(&mut file);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.close_synced().await.unwrap();
+
+    let reader = crate::read::fs::ZipFileReader::new(&path).await.unwrap();
+    assert_eq!(reader.file().entries()[0].filename(), "foo.txt");
+
+    tokio::fs::remove_file(&path).await.unwrap();
+}
@@ -0,0 +1,116 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::{EncryptionMethod, ZipEntryBuilder};
+
+use tokio::io::AsyncWriteExt;
+
+const PASSWORD: &[u8] = b"password";
+
+#[cfg(feature = "aes")]
+async fn write_archive(compression: Compression) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    let entry = ZipEntryBuilder::new(String::from("whole.txt"), compression);
+    writer.write_entry_whole_aes(entry, b"Hello, encrypted world!", PASSWORD).await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("stream.txt"), compression);
+    let mut entry_writer = writer.write_entry_stream_aes(entry, PASSWORD).await.unwrap();
+    entry_writer.write_all(b"Hello, encrypted ").await.unwrap();
+    entry_writer.write_all(b"stream!").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("plain.txt"), compression);
+    writer.write_entry_whole(entry, b"Hello, world!").await.unwrap();
+    writer.close().await.unwrap();
+
+    data
+}
+
+async fn read_entry(reader: &ZipFileReader, index: usize) -> String {
+    let entry = reader.file().entries()[index].clone();
+    let mut entry_reader = reader.entry_with_password(index, PASSWORD).await.unwrap();
+
+    let mut data = String::new();
+    entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+    data
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_stored_round_trip_test() {
+    let reader = ZipFileReader::new(write_archive(Compression::Stored).await).await.unwrap();
+
+    assert_eq!(read_entry(&reader, 0).await, "Hello, encrypted world!");
+    assert_eq!(read_entry(&reader, 1).await, "Hello, encrypted stream!");
+    assert_eq!(read_entry(&reader, 2).await, "Hello, world!");
+
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.compression(), Compression::Stored);
+    assert_eq!(entry.crc32(), 0);
+    assert_eq!(entry.compressed_size(), 16 + 2 + 23 + 10);
+}
+
+#[cfg(all(feature = "aes", feature = "deflate"))]
+#[tokio::test]
+async fn aes_deflate_round_trip_test() {
+    let reader = ZipFileReader::new(write_archive(Compression::Deflate).await).await.unwrap();
+
+    assert_eq!(read_entry(&reader, 0).await, "Hello, encrypted world!");
+    assert_eq!(read_entry(&reader, 1).await, "Hello, encrypted stream!");
+    assert_eq!(reader.file().entries()[1].compression(), Compression::Deflate);
+}
+
+#[cfg(feature = "aes")]
+#[tokio::test]
+async fn aes_write_password_test() {
+    let reader = ZipFileReader::new(write_archive(Compression::Stored).await).await.unwrap();
+
+    assert!(matches!(reader.entry(0).await, Err(ZipError::EntryEncrypted(EncryptionMethod::Aes256))));
+    assert!(matches!(reader.entry_with_password(1, b"incorrect").await, Err(ZipError::IncorrectPassword)));
+}
+
+async fn write_zipcrypto_archive(compression: Compression) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    let entry = ZipEntryBuilder::new(String::from("whole.txt"), compression).zipcrypto_password(PASSWORD);
+    writer.write_entry_whole(entry, b"Hello, encrypted world!").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("stream.txt"), compression).zipcrypto_password(PASSWORD);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"Hello, encrypted ").await.unwrap();
+    entry_writer.write_all(b"stream!").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    writer.close().await.unwrap();
+    data
+}
+
+#[tokio::test]
+async fn zipcrypto_stored_round_trip_test() {
+    let reader = ZipFileReader::new(write_zipcrypto_archive(Compression::Stored).await).await.unwrap();
+
+    assert_eq!(read_entry(&reader, 0).await, "Hello, encrypted world!");
+    assert_eq!(read_entry(&reader, 1).await, "Hello, encrypted stream!");
+
+    let entry = &reader.file().entries()[0];
+    assert_eq!(entry.crc32(), crc32fast::hash(b"Hello, encrypted world!"));
+    assert_eq!(entry.compressed_size(), 12 + 23);
+
+    assert!(matches!(reader.entry(0).await, Err(ZipError::EntryEncrypted(EncryptionMethod::ZipCrypto))));
+}
+
+#[cfg(feature = "deflate")]
+#[tokio::test]
+async fn zipcrypto_deflate_round_trip_test() {
+    let reader = ZipFileReader::new(write_zipcrypto_archive(Compression::Deflate).await).await.unwrap();
+
+    assert_eq!(read_entry(&reader, 0).await, "Hello, encrypted world!");
+    assert_eq!(read_entry(&reader, 1).await, "Hello, encrypted stream!");
+}
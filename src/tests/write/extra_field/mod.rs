@@ -0,0 +1,73 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::spec::extra_field::find_extra_field;
+use crate::write::ZipFileWriter;
+use crate::{ExtraField, ZipEntryBuilder};
+
+#[tokio::test]
+async fn typed_extra_fields_test() {
+    let fields = [
+        ExtraField::ExtendedTimestamp { modified: Some(1_000_000_000), accessed: Some(1_500_000_000), created: None },
+        ExtraField::UnixOwner { uid: 1000, gid: 100 },
+        ExtraField::Custom { header_id: 0x6666, data: vec![1, 2, 3] },
+    ];
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).extra_fields(fields);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+
+    assert_eq!(entry.extended_last_modification_date().unwrap().timestamp(), 1_000_000_000);
+    assert_eq!(entry.extended_last_access_date().unwrap().timestamp(), 1_500_000_000);
+    assert!(entry.extended_creation_date().is_none());
+    assert_eq!(find_extra_field(entry.extra_field(), 0x7875), Some(&[1, 4, 0xE8, 3, 0, 0, 4, 100, 0, 0, 0][..]));
+    assert_eq!(find_extra_field(entry.extra_field(), 0x6666), Some(&[1, 2, 3][..]));
+}
+
+#[test]
+fn typed_extra_field_replaced_test() {
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored)
+        .add_extra_field(ExtraField::Custom { header_id: 0x6666, data: vec![1] })
+        .add_extra_field(ExtraField::AlignmentPadding { alignment: 4, padding: 2 })
+        .add_extra_field(ExtraField::Custom { header_id: 0x6666, data: vec![2] })
+        .build();
+
+    assert_eq!(entry.extra_field(), [0x35, 0xD9, 4, 0, 4, 0, 0, 0, 0x66, 0x66, 1, 0, 2]);
+}
+
+#[tokio::test]
+async fn unix_owner_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).unix_owner(1000, 100);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+    writer.write_entry_whole(ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored), b"bar").await.unwrap();
+    let data = writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entries = reader.file().entries();
+
+    assert_eq!((entries[0].uid(), entries[0].gid()), (Some(1000), Some(100)));
+    assert_eq!((entries[1].uid(), entries[1].gid()), (None, None));
+}
+
+#[test]
+fn unix_owner_variable_size_test() {
+    let parse = |data: &[u8]| {
+        let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored)
+            .add_extra_field(ExtraField::Custom { header_id: 0x7875, data: data.to_vec() })
+            .build();
+        (entry.uid(), entry.gid())
+    };
+
+    assert_eq!(parse(&[1, 2, 0xE8, 3, 1, 100]), (Some(1000), Some(100)));
+    assert_eq!(parse(&[1, 8, 1, 0, 0, 0, 0, 0, 0, 0, 0]), (Some(1), Some(0)));
+    assert_eq!(parse(&[1, 8, 0, 0, 0, 0, 1, 0, 0, 0, 1, 0]), (None, None));
+    assert_eq!(parse(&[1, 4, 0xE8, 3]), (None, None));
+    assert_eq!(parse(&[2, 1, 0, 1, 0]), (None, None));
+}
@@ -0,0 +1,49 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::ZipEntryBuilder;
+
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn file_metadata_test() {
+    let dir = std::env::temp_dir().join(format!("async_zip_file_metadata_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("run.sh");
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+    let file = std::fs::File::create(&path).unwrap();
+    file.set_modified(modified).unwrap();
+    #[cfg(unix)]
+    std::fs::set_permissions(&path, std::os::unix::fs::PermissionsExt::from_mode(0o750)).unwrap();
+
+    let metadata = std::fs::metadata(&path).unwrap();
+    let entry = ZipEntryBuilder::new(String::from("run.sh"), Compression::Stored).file_metadata(&metadata).build();
+    assert_eq!(entry.last_modification_date().timestamp(), 1_600_000_000);
+    assert!(!entry.dir());
+    #[cfg(unix)]
+    assert_eq!(entry.unix_mode(), Some(0o750));
+
+    let metadata = std::fs::metadata(&dir).unwrap();
+    let entry = ZipEntryBuilder::new(String::from("dir"), Compression::Stored).file_metadata(&metadata).build();
+    assert_eq!(entry.filename(), "dir/");
+    assert!(entry.dir());
+    assert_eq!(entry.external_file_attribute() & 0x10, 0x10);
+    assert_eq!(entry.unix_permissions().map(|mode| mode & 0o170000), Some(0o040000));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(feature = "fs")]
+#[tokio::test]
+async fn file_test() {
+    let path = std::env::temp_dir().join(format!("async_zip_file_{}.txt", std::process::id()));
+    std::fs::write(&path, b"foo").unwrap();
+
+    let file = tokio::fs::File::open(&path).await.unwrap();
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).file(&file).await.unwrap().build();
+    assert_eq!(entry.unix_permissions().map(|mode| mode & 0o170000), Some(0o100000));
+
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,31 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn finish_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"bar bar bar").await.unwrap();
+    let finished = entry_writer.finish().await.unwrap();
+
+    assert_eq!(finished.entry().filename(), "bar.txt");
+    assert_eq!((finished.compressed_size(), finished.uncompressed_size()), (11, 11));
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = &reader.file().entries()[1];
+
+    assert_eq!(finished.crc32(), entry.crc32());
+    assert_eq!(finished.entry().compressed_size(), entry.compressed_size());
+    assert_eq!(finished.header_offset(), reader.file().meta(1).unwrap().header_offset());
+}
@@ -0,0 +1,62 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+async fn write(writer: &mut ZipFileWriter<&mut Cursor<Vec<u8>>>, filename: &str, data: &[u8]) {
+    let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+    writer.write_entry_whole(entry, data).await.unwrap();
+}
+
+async fn filenames(data: Vec<u8>) -> Vec<String> {
+    let reader = ZipFileReader::new(data).await.unwrap();
+    reader.file().entries().iter().map(|entry| entry.filename().to_owned()).collect()
+}
+
+#[tokio::test]
+async fn group_commit_rollback_test() {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = ZipFileWriter::new(&mut cursor);
+
+    assert!(matches!(writer.commit(), Err(ZipError::NoGroupOpen)));
+    write(&mut writer, "foo.txt", b"foo").await;
+
+    writer.begin_group().unwrap();
+    assert!(matches!(writer.begin_group(), Err(ZipError::GroupAlreadyOpen)));
+    write(&mut writer, "bar.txt", b"bar").await;
+    write(&mut writer, "baz.txt", b"baz").await;
+    writer.rollback().await.unwrap();
+
+    writer.begin_group().unwrap();
+    write(&mut writer, "qux.txt", b"qux").await;
+    writer.commit().unwrap();
+
+    writer.begin_group().unwrap();
+    write(&mut writer, "quux.txt", b"quux").await;
+    writer.close().await.unwrap();
+
+    assert_eq!(filenames(cursor.into_inner()).await, ["foo.txt", "qux.txt"]);
+}
+
+#[tokio::test]
+async fn group_stale_data_test() {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = ZipFileWriter::new(&mut cursor);
+
+    write(&mut writer, "foo.txt", b"foo").await;
+    writer.begin_group().unwrap();
+    write(&mut writer, "bar.txt", &[b'b'; 100_000]).await;
+    writer.rollback().await.unwrap();
+    writer.close().await.unwrap();
+
+    // The discarded entry is longer than the maximum EOCDR search distance, so it must not be left trailing it.
+    let data = cursor.into_inner();
+    assert!(data.len() > 100_000);
+    assert_eq!(filenames(data).await, ["foo.txt"]);
+}
@@ -0,0 +1,66 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipBody;
+use crate::ZipEntryBuilder;
+
+use std::future::poll_fn;
+use std::pin::Pin;
+
+use http_body::Body;
+
+const ENTRIES: [(&str, &[u8]); 2] = [("foo.txt", b"This is the first file."), ("bar/baz.txt", &[7; 100_000])];
+
+async fn collect(mut body: ZipBody) -> (Vec<u8>, usize) {
+    let (mut data, mut frames) = (Vec::new(), 0);
+
+    while let Some(frame) = poll_fn(|c| Pin::new(&mut body).poll_frame(c)).await {
+        let frame = frame.unwrap().into_data().unwrap();
+        assert!(frame.len() <= 1024);
+
+        data.extend_from_slice(&frame);
+        frames += 1;
+    }
+
+    assert!(body.is_end_stream());
+    (data, frames)
+}
+
+#[tokio::test]
+async fn http_body_test() {
+    let (mut writer, body) = crate::write::body(1024);
+
+    let write = async move {
+        for (filename, data) in ENTRIES {
+            let entry = ZipEntryBuilder::new(filename.to_string(), Compression::Stored);
+            writer.write_entry_whole(entry, data).await.unwrap();
+        }
+
+        drop(writer.close().await.unwrap());
+    };
+
+    let ((), (data, frames)) = tokio::join!(write, collect(body));
+    assert!(frames > 1);
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries().len(), ENTRIES.len());
+
+    for (index, (entry, (filename, expected))) in reader.file().entries().iter().zip(ENTRIES).enumerate() {
+        assert_eq!(entry.filename(), filename);
+
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
+
+#[tokio::test]
+async fn http_body_dropped_test() {
+    let (mut writer, body) = crate::write::body(1024);
+    drop(body);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    assert!(writer.write_entry_whole(entry, &[0; 4096]).await.is_err());
+}
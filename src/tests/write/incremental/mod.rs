@@ -0,0 +1,63 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use tokio::io::AsyncWriteExt;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+const CHUNKS: usize = 32;
+
+/// Generates an incompressible chunk of data, so that the compressed size tracks the uncompressed size.
+fn chunk(seed: &mut u64) -> Vec<u8> {
+    (0..CHUNK_SIZE)
+        .map(|_| {
+            *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (*seed >> 56) as u8
+        })
+        .collect()
+}
+
+/// Streamed data must be compressed & passed to the inner writer as it's written, rather than accumulated until the
+/// entry is closed.
+#[tokio::test]
+async fn incremental_compression_test() {
+    let mut methods = vec![Compression::Stored];
+    #[cfg(feature = "deflate")]
+    methods.push(Compression::Deflate);
+    #[cfg(feature = "bzip2")]
+    methods.push(Compression::Bz);
+    #[cfg(feature = "lzma")]
+    methods.push(Compression::Lzma);
+    #[cfg(feature = "zstd")]
+    methods.push(Compression::Zstd);
+    #[cfg(feature = "xz")]
+    methods.push(Compression::Xz);
+
+    for compression in methods {
+        let mut writer = ZipFileWriter::new(tokio::io::sink());
+        let compressed = Arc::new(AtomicU64::new(0));
+
+        let reported = compressed.clone();
+        writer.progress(move |progress| reported.store(progress.entry().compressed(), Ordering::SeqCst));
+
+        let entry = ZipEntryBuilder::new(String::from("foo.bin"), compression);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        let mut seed = 0;
+
+        for _ in 0..CHUNKS {
+            entry_writer.write_all(&chunk(&mut seed)).await.unwrap();
+        }
+
+        // At most a block's worth of data (eg. 900 KiB for bzip2) may be held by the encoder before the entry is closed.
+        let written = compressed.load(Ordering::SeqCst);
+        assert!(written >= ((CHUNKS - 16) * CHUNK_SIZE) as u64, "{:?} only wrote {} bytes", compression, written);
+
+        entry_writer.close().await.unwrap();
+    }
+}
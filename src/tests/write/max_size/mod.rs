@@ -0,0 +1,53 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn whole_entry_rejected_before_write() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.max_size(100);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    let result = writer.write_entry_whole(entry, &[0; 64]).await;
+
+    assert!(matches!(result, Err(ZipError::ArchiveSizeExceeded(100))));
+    assert_eq!(writer.writer.offset(), 0);
+}
+
+#[tokio::test]
+async fn stream_entry_fails_once_exceeded() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.max_size(200);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.expect("failed to start stream entry");
+
+    entry_writer.write_all(&[0; 32]).await.expect("failed to write within the limit");
+    assert!(entry_writer.write_all(&[0; 128]).await.is_err());
+}
+
+#[tokio::test]
+async fn stream_entry_rejected_before_write() {
+    use tokio::io::AsyncWriteExt;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.max_size(200);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.expect("failed to start stream entry");
+
+    entry_writer.write_all(&[0; 32]).await.expect("failed to write within the limit");
+    let error = entry_writer.write_all(&[0; 128]).await.unwrap_err();
+    let error = error.get_ref().and_then(|inner| inner.downcast_ref::<ZipError>());
+    assert!(matches!(error, Some(ZipError::ArchiveSizeExceeded(200))));
+    drop(entry_writer);
+
+    // The data over budget is never passed on, leaving room for the central directory.
+    assert!(writer.offset() <= 200);
+}
@@ -0,0 +1,40 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::collections::BTreeMap;
+
+#[tokio::test]
+async fn metadata_round_trip_test() {
+    let mut metadata = BTreeMap::new();
+    metadata.insert(String::from("content-type"), String::from("text/plain"));
+    metadata.insert(String::from("owner"), String::from("ünïcödé"));
+
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).metadata(&metadata);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"bar").await.unwrap();
+
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert_eq!(reader.file().entries()[0].metadata(), Some(metadata));
+    assert_eq!(reader.file().entries()[1].metadata(), None);
+}
+
+#[test]
+fn metadata_replaced_test() {
+    let first = BTreeMap::from([(String::from("foo"), String::from("bar"))]);
+    let second = BTreeMap::from([(String::from("bar"), String::new())]);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).metadata(&first).metadata(&second);
+    assert_eq!(entry.build().metadata(), Some(second));
+}
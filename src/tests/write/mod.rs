@@ -1,4 +1,44 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod alignment;
+pub(crate) mod append;
+#[cfg(feature = "fs")]
+pub(crate) mod archive;
+pub(crate) mod attribute;
+#[cfg(feature = "cancel")]
+pub(crate) mod cancel;
+pub(crate) mod chunk;
+pub(crate) mod comment;
+#[cfg(feature = "deflate")]
+pub(crate) mod compatibility;
+#[cfg(feature = "deflate")]
+pub(crate) mod concat;
+pub(crate) mod copy;
+pub(crate) mod dir;
+pub(crate) mod durability;
+pub(crate) mod encryption;
+pub(crate) mod extra_field;
+pub(crate) mod file_metadata;
+pub(crate) mod finish;
+pub(crate) mod group;
+#[cfg(feature = "http-body")]
+pub(crate) mod http_body;
+pub(crate) mod incremental;
+pub(crate) mod max_size;
+pub(crate) mod metadata;
+#[cfg(feature = "deflate")]
+pub(crate) mod offload;
 pub(crate) mod offset;
+pub(crate) mod order;
+pub(crate) mod owned;
+#[cfg(feature = "deflate")]
+pub(crate) mod parallel;
+pub(crate) mod patch;
+pub(crate) mod policy;
+pub(crate) mod progress;
+#[cfg(feature = "deflate")]
+pub(crate) mod raw;
+pub(crate) mod summary;
+pub(crate) mod tee;
+pub(crate) mod zip64;
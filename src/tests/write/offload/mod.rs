@@ -0,0 +1,38 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use chrono::{TimeZone, Utc};
+
+async fn write(offload: bool) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.offload_compression(offload);
+
+    for (index, compression) in
+        [Compression::Deflate, Compression::Stored, Compression::Deflate].into_iter().enumerate()
+    {
+        // The modification date is fixed so that archives written at different times are byte-for-byte identical.
+        let entry = ZipEntryBuilder::new(format!("{}.txt", index), compression)
+            .last_modification_date(Utc.timestamp_opt(1_666_544_102, 0).unwrap());
+        writer.write_entry_whole(entry, "offloaded ".repeat(4096).as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+#[tokio::test]
+async fn offload_matches_inline_test() {
+    let offloaded = write(true).await;
+    assert_eq!(offloaded, write(false).await);
+
+    let reader = ZipFileReader::new(offloaded).await.unwrap();
+    for (index, entry) in reader.file().entries().iter().enumerate() {
+        let mut buffer = Vec::new();
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+        assert_eq!(buffer, "offloaded ".repeat(4096).into_bytes());
+    }
+}
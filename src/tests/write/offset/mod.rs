@@ -20,3 +20,47 @@ async fn basic() {
     writer.write_all(b"Bar. Bar.").await.expect("failed to write data");
     assert_eq!(writer.offset(), 37);
 }
+
+#[tokio::test]
+async fn padding_entry_test() {
+    use crate::error::ZipError;
+    use crate::spec::compression::Compression;
+    use crate::write::ZipFileWriter;
+    use crate::ZipEntryBuilder;
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from(".pad"), Compression::Stored);
+    let result = writer.write_padding_entry(entry, 40).await;
+    assert!(matches!(result, Err(ZipError::PaddingOffsetUnreachable(40))));
+
+    let entry = ZipEntryBuilder::new(String::from(".pad"), Compression::Stored);
+    writer.write_padding_entry(entry, 4096).await.unwrap();
+    assert_eq!(writer.offset(), 4096);
+}
+
+#[tokio::test]
+async fn padding_entry_policy_test() {
+    use crate::read::mem::ZipFileReader;
+    use crate::spec::compression::Compression;
+    use crate::write::{CompressionPolicy, ZipFileWriter};
+    use crate::ZipEntryBuilder;
+
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    writer.compression_policy(CompressionPolicy::new().compress_threshold(0));
+
+    // Neither the policy nor the password may change the padding entry's length.
+    let entry = ZipEntryBuilder::new(String::from(".pad"), Compression::Deflate).zipcrypto_password(b"foo");
+    writer.write_padding_entry(entry, 4096).await.unwrap();
+    assert_eq!(writer.offset(), 4096);
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let entry = &reader.file().entries()[0];
+    assert_eq!((entry.compression(), entry.compressed_size()), (Compression::Stored, entry.uncompressed_size()));
+    assert!(!entry.is_encrypted());
+}
@@ -0,0 +1,56 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::read::stream::ZipFileReader as StreamReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+async fn archive<F: FnOnce(&mut ZipFileWriter<Vec<u8>>)>(configure: F) -> Vec<u8> {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    configure(&mut writer);
+
+    for filename in ["mimetype", "c.txt", "a.txt", "b.txt"] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        writer.write_entry_whole(entry, filename.as_bytes()).await.unwrap();
+    }
+
+    writer.close().await.unwrap()
+}
+
+fn filenames(reader: &ZipFileReader) -> Vec<&str> {
+    reader.file().entries().iter().map(|entry| entry.filename()).collect()
+}
+
+#[tokio::test]
+async fn sorted_order_test() {
+    let data = archive(|writer| writer.central_directory_order(|filename| filename.to_string())).await;
+    let reader = ZipFileReader::new(data.clone()).await.unwrap();
+
+    assert_eq!(filenames(&reader), ["a.txt", "b.txt", "c.txt", "mimetype"]);
+    assert_eq!(reader.file().meta(3).unwrap().file_offset, 0);
+
+    for (index, filename) in filenames(&reader).into_iter().enumerate() {
+        let mut entry_reader = reader.entry(index).await.unwrap();
+        let mut buffer = String::new();
+
+        entry_reader.read_to_string_checked(&mut buffer, &reader.file().entries()[index]).await.unwrap();
+        assert_eq!(buffer, filename);
+    }
+
+    // The data remains in the order it was written.
+    let mut stream = StreamReader::new(Cursor::new(data));
+    let (entry, _) = stream.next_entry().await.unwrap().unwrap();
+    assert_eq!(entry.filename(), "mimetype");
+}
+
+#[tokio::test]
+async fn stable_order_test() {
+    let data = archive(|writer| writer.central_directory_order(|filename| filename != "b.txt")).await;
+    let reader = ZipFileReader::new(data).await.unwrap();
+
+    assert_eq!(filenames(&reader), ["b.txt", "mimetype", "c.txt", "a.txt"]);
+}
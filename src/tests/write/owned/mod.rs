@@ -0,0 +1,46 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn owned_writer_task_test() {
+    let task = tokio::spawn(async move {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+
+        writer.write_entry_whole(entry, b"foo").await.unwrap();
+        writer.close().await.unwrap()
+    });
+
+    let reader = ZipFileReader::new(task.await.unwrap()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].filename(), "foo.txt");
+}
+
+#[tokio::test]
+async fn owned_writer_reuse_test() {
+    let (stream, mut peer) = tokio::io::duplex(1 << 16);
+
+    let mut writer = ZipFileWriter::new(stream);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    // The stream remains open once the archive is complete, and so can be used for further messages.
+    let mut stream = writer.close().await.unwrap();
+    stream.write_all(b"done").await.unwrap();
+    drop(stream);
+
+    let mut received = Vec::new();
+    peer.read_to_end(&mut received).await.unwrap();
+
+    let (archive, trailer) = received.split_at(received.len() - 4);
+    assert_eq!(trailer, b"done");
+
+    let reader = ZipFileReader::new(archive.to_vec()).await.unwrap();
+    assert_eq!(reader.file().entries()[0].filename(), "foo.txt");
+}
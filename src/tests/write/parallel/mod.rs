@@ -0,0 +1,42 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use chrono::{TimeZone, Utc};
+
+fn entries() -> impl Iterator<Item = (ZipEntryBuilder, Vec<u8>)> {
+    (0..24).map(|index| {
+        let compression = if index % 5 == 0 { Compression::Stored } else { Compression::Deflate };
+        // The modification date is fixed so that archives written at different times are byte-for-byte identical.
+        let entry = ZipEntryBuilder::new(format!("{}.txt", index), compression)
+            .last_modification_date(Utc.timestamp_opt(1_666_544_102, 0).unwrap());
+        (entry, format!("entry {} ", index).repeat(index * 512).into_bytes())
+    })
+}
+
+#[tokio::test]
+async fn parallel_matches_sequential_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    for (entry, data) in entries() {
+        writer.write_entry_whole(entry, &data).await.unwrap();
+    }
+    let sequential = writer.close().await.unwrap();
+
+    for workers in [0, 1, 4] {
+        let mut writer = ZipFileWriter::new(Vec::new());
+        writer.write_entries_whole_parallel(entries(), workers).await.unwrap();
+        assert_eq!(writer.close().await.unwrap(), sequential);
+    }
+
+    let reader = ZipFileReader::new(sequential).await.unwrap();
+    for (index, (_, data)) in entries().enumerate() {
+        let mut buffer = Vec::new();
+        let entry = &reader.file().entries()[index];
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, entry).await.unwrap();
+        assert_eq!(buffer, data);
+    }
+}
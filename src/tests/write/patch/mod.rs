@@ -0,0 +1,52 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader as MemZipFileReader;
+use crate::read::stream::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+
+use tokio::io::AsyncWriteExt;
+
+const ENTRIES: [(&str, &str); 2] = [("foo.txt", "foo foo foo"), ("bar.txt", "bar bar")];
+
+async fn write(patch: bool) -> Vec<u8> {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = ZipFileWriter::new(&mut cursor);
+    writer.patch_local_headers(patch);
+
+    for (filename, data) in ENTRIES {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+        entry_writer.write_all(data.as_bytes()).await.unwrap();
+        entry_writer.close().await.unwrap();
+    }
+
+    writer.close().await.unwrap();
+    cursor.into_inner()
+}
+
+#[tokio::test]
+async fn patch_local_headers_test() {
+    let data = write(true).await;
+    assert_eq!(data.len() + 2 * (20 + 24), write(false).await.len());
+
+    let file = MemZipFileReader::new(data.clone()).await.unwrap().file().clone();
+    assert!((0..ENTRIES.len()).all(|index| !file.meta(index).unwrap().data_descriptor()));
+
+    // The stream reader relies upon the sizes within local file headers for entries without a data descriptor.
+    let mut reader = ZipFileReader::new(Cursor::new(data));
+    let mut entries = Vec::new();
+
+    while let Some((entry, mut entry_reader)) = reader.next_entry().await.unwrap() {
+        let entry = entry.clone();
+        let mut data = String::new();
+        entry_reader.read_to_string_checked(&mut data, &entry).await.unwrap();
+        entries.push((entry.filename().to_owned(), data));
+    }
+
+    assert_eq!(entries, ENTRIES.map(|(filename, data)| (filename.to_owned(), data.to_owned())));
+}
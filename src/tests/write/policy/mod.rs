@@ -0,0 +1,51 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::{CompressionPolicy, ZipFileWriter};
+use crate::ZipEntryBuilder;
+
+use async_compression::Level;
+
+#[tokio::test]
+async fn policy_stored_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    writer.compression_policy(CompressionPolicy::new().compress_threshold(64));
+
+    let small = ZipEntryBuilder::new(String::from("small.txt"), Compression::Deflate);
+    writer.write_entry_whole(small, b"foo bar").await.unwrap();
+    let large = ZipEntryBuilder::new(String::from("large.txt"), Compression::Deflate);
+    writer.write_entry_whole(large, &[b'a'; 1024]).await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    let compressions: Vec<Compression> = reader.file().entries().iter().map(|entry| entry.compression()).collect();
+    assert_eq!(compressions, [Compression::Stored, Compression::Deflate]);
+
+    for (index, expected) in [&b"foo bar"[..], &[b'a'; 1024][..]].into_iter().enumerate() {
+        let entry = reader.file().entries()[index].clone();
+        let mut buffer = Vec::new();
+
+        reader.entry(index).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
+
+#[test]
+fn policy_level_test() {
+    let policy = CompressionPolicy::new().compress_threshold(10).default_threshold(100).best_threshold(1000);
+    let apply = |compression, size| {
+        let mut entry: ZipEntry = ZipEntryBuilder::new(String::from("foo.txt"), compression).into();
+        policy.apply(&mut entry, size);
+        entry
+    };
+
+    assert_eq!(apply(Compression::Deflate, 9).compression(), Compression::Stored);
+    assert!(matches!(apply(Compression::Deflate, 10).compression_level, Level::Fastest));
+    assert!(matches!(apply(Compression::Deflate, 100).compression_level, Level::Default));
+    assert!(matches!(apply(Compression::Deflate, 1000).compression_level, Level::Best));
+    assert_eq!(apply(Compression::Stored, 1000).compression(), Compression::Stored);
+}
@@ -0,0 +1,52 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::write::progress::WriteProgress;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use tokio::io::AsyncWriteExt;
+
+#[tokio::test]
+async fn write_progress_test() {
+    let mut buffer = Cursor::new(Vec::new());
+    let mut writer = ZipFileWriter::new(&mut buffer);
+    let reported: Arc<Mutex<Vec<WriteProgress>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let sink = reported.clone();
+    writer.progress(move |progress| sink.lock().unwrap().push(progress));
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    writer.write_entry_whole(entry, b"foo").await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(&[b'b'; 100]).await.unwrap();
+    entry_writer.write_all(&[b'b'; 100]).await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let entry = ZipEntryBuilder::new(String::from("baz.txt"), Compression::Stored);
+    writer.write_entry_reader(entry, &mut &[b'z'; 20000][..]).await.unwrap();
+
+    writer.close().await.unwrap();
+    let reported = reported.lock().unwrap();
+
+    assert_eq!(reported.first().unwrap().entries_completed(), 1);
+    assert_eq!(reported.first().unwrap().entry().consumed(), 3);
+
+    // Streamed data is reported as it's written, before the entry is completed.
+    assert!(reported.iter().any(|progress| progress.entries_completed() == 1 && progress.entry().consumed() == 100));
+    assert!(reported.windows(2).all(|pair| pair[0].archive_size() <= pair[1].archive_size()));
+    assert!(reported.windows(2).all(|pair| pair[0].entries_completed() <= pair[1].entries_completed()));
+
+    // Once the last entry has been completed, the estimated size is exactly that of the closed archive.
+    let last = reported.last().unwrap();
+    assert_eq!(last.entries_completed(), 3);
+    assert_eq!(last.entry().consumed(), 20000);
+    assert_eq!(last.entry().compressed(), 20000);
+    assert_eq!(last.estimated_size(), buffer.get_ref().len() as u64);
+}
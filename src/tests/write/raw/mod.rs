@@ -0,0 +1,100 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::ZipError;
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+const DATA: &[u8] = b"Lorem ipsum dolor sit amet, lorem ipsum dolor sit amet, lorem ipsum dolor sit amet.";
+
+#[tokio::test]
+async fn raw_copy_test() {
+    let mut source = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut source);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Deflate);
+    writer.write_entry_whole(entry, DATA).await.unwrap();
+    writer.close().await.unwrap();
+
+    let source = ZipFileReader::new(source).await.unwrap();
+    let entry = source.file().entries()[0].clone();
+
+    let mut raw = Vec::new();
+    source.entry_raw(0).await.unwrap().read_to_end_checked(&mut raw, &entry).await.unwrap();
+    assert_eq!(raw.len(), entry.compressed_size() as usize);
+    assert_ne!(raw, DATA);
+
+    let mut copied = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut copied);
+    writer.copy_entry_raw(&entry, source.entry_raw(0).await.unwrap()).await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(copied).await.unwrap();
+    let copied_entry = reader.file().entries()[0].clone();
+    assert_eq!(copied_entry.compression(), Compression::Deflate);
+    assert_eq!(copied_entry.crc32(), entry.crc32());
+    assert_eq!(copied_entry.compressed_size(), entry.compressed_size());
+
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut buffer, &copied_entry).await.unwrap();
+    assert_eq!(buffer, DATA);
+}
+
+#[tokio::test]
+async fn raw_copy_truncated_test() {
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).build();
+    let mut source = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut source);
+    writer.write_entry_whole(entry, DATA).await.unwrap();
+    writer.close().await.unwrap();
+
+    let source = ZipFileReader::new(source).await.unwrap();
+    let entry = source.file().entries()[0].clone();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let result = writer.copy_entry_raw(&entry, &DATA[..10]).await;
+    assert!(matches!(result, Err(ZipError::UpstreamReadError(_))));
+}
+
+#[tokio::test]
+async fn raw_encrypted_test() {
+    let mut data = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut data);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored).zipcrypto_password(b"password");
+    writer.write_entry_whole(entry, DATA).await.unwrap();
+    writer.close().await.unwrap();
+
+    let reader = ZipFileReader::new(data).await.unwrap();
+    assert!(matches!(reader.entry_raw(0).await, Err(ZipError::FeatureNotSupported(_))));
+}
+
+#[tokio::test]
+async fn precompressed_test() {
+    let mut source = Vec::new();
+    let mut writer = ZipFileWriter::new(&mut source);
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Deflate);
+    writer.write_entry_whole(entry, DATA).await.unwrap();
+    writer.close().await.unwrap();
+
+    let source = ZipFileReader::new(source).await.unwrap();
+    let mut compressed = Vec::new();
+    let entry = source.file().entries()[0].clone();
+    source.entry_raw(0).await.unwrap().read_to_end_checked(&mut compressed, &entry).await.unwrap();
+
+    let mut hasher = crate::spec::crc::Crc32::new();
+    hasher.update(DATA);
+    let crc = hasher.finalize();
+
+    let mut writer = ZipFileWriter::new(Vec::new());
+    let entry = ZipEntryBuilder::new(String::from("bar.txt"), Compression::Deflate);
+    writer.write_entry_precompressed(entry, crc, DATA.len() as u64, &compressed).await.unwrap();
+
+    let reader = ZipFileReader::new(writer.close().await.unwrap()).await.unwrap();
+    let entry = reader.file().entries()[0].clone();
+    assert_eq!((entry.crc32(), entry.compressed_size()), (crc, compressed.len() as u32));
+
+    let mut buffer = Vec::new();
+    reader.entry(0).await.unwrap().read_to_end_checked(&mut buffer, &entry).await.unwrap();
+    assert_eq!(buffer, DATA);
+}
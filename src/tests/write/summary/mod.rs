@@ -0,0 +1,26 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::spec::consts::{CDH_SIGNATURE, EOCDR_SIGNATURE};
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+#[tokio::test]
+async fn close_with_summary_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+
+    for filename in ["foo.txt", "bar.txt", "baz.txt"] {
+        let entry = ZipEntryBuilder::new(String::from(filename), Compression::Stored);
+        writer.write_entry_whole(entry, filename.as_bytes()).await.unwrap();
+    }
+
+    let (data, summary) = writer.close_with_summary().await.unwrap();
+    let cd_offset = data.windows(4).position(|window| window == CDH_SIGNATURE.to_le_bytes()).unwrap();
+    let eocdr_offset = data.windows(4).rposition(|window| window == EOCDR_SIGNATURE.to_le_bytes()).unwrap();
+
+    assert_eq!(summary.entries(), 3);
+    assert_eq!(summary.total_size(), data.len() as u64);
+    assert_eq!(summary.central_directory_offset(), cd_offset as u64);
+    assert_eq!(summary.central_directory_size(), (eocdr_offset - cd_offset) as u64);
+}
@@ -0,0 +1,34 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::read::mem::ZipFileReader;
+use crate::spec::compression::Compression;
+use crate::write::{TeeWriter, ZipFileWriter};
+use crate::ZipEntryBuilder;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[tokio::test]
+async fn tee_writer_test() {
+    // The second sink has a small buffer, so the writer must wait on the reader draining it.
+    let (sink, mut peer) = tokio::io::duplex(64);
+    let reader = tokio::spawn(async move {
+        let mut received = Vec::new();
+        peer.read_to_end(&mut received).await.unwrap();
+        received
+    });
+
+    let mut writer = ZipFileWriter::new(TeeWriter::new(Vec::new(), sink));
+    for index in 0..8 {
+        let entry = ZipEntryBuilder::new(format!("{}.txt", index), Compression::Stored);
+        writer.write_entry_whole(entry, &vec![index; 1024]).await.unwrap();
+    }
+
+    let mut tee = writer.close().await.unwrap();
+    tee.shutdown().await.unwrap();
+    let (first, _) = tee.into_inner();
+    let second = reader.await.unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(ZipFileReader::new(second).await.unwrap().file().entries().len(), 8);
+}
@@ -0,0 +1,176 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::spec::compression::Compression;
+use crate::spec::consts::{CDH_SIGNATURE, EOCDR_SIGNATURE, ZIP64_EOCDL_SIGNATURE, ZIP64_EOCDR_SIGNATURE};
+use crate::spec::extra_field::ZIP64_HEADER_ID;
+use crate::write::io::offset::AsyncOffsetWriter;
+use crate::write::ZipFileWriter;
+use crate::ZipEntryBuilder;
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+
+const START_OFFSET: usize = 5 * 1024 * 1024 * 1024;
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn find_signature(data: &[u8], signature: u32) -> usize {
+    data.windows(4).position(|window| window == signature.to_le_bytes()).unwrap()
+}
+
+/// Writes a streamed entry as if it were preceded by more than 4 GiB of data, without that data being written.
+#[tokio::test]
+async fn zip64_offset_test() {
+    let mut writer = ZipFileWriter::new(Vec::new());
+    writer.writer = AsyncOffsetWriter::with_offset(Vec::new(), START_OFFSET);
+
+    let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    entry_writer.write_all(b"foo").await.unwrap();
+    entry_writer.close().await.unwrap();
+
+    let data = writer.close().await.unwrap();
+    let cdh = find_signature(&data, CDH_SIGNATURE);
+    let cd_offset = START_OFFSET as u64 + cdh as u64;
+
+    // The central directory record's offset is held within a ZIP64 extra field, following the filename.
+    assert_eq!(read_u32(&data, cdh + 42), u32::MAX);
+    let extra_field = &data[cdh + 46 + "foo.txt".len()..];
+    assert_eq!(&extra_field[0..4], [ZIP64_HEADER_ID.to_le_bytes(), 8u16.to_le_bytes()].concat());
+    assert_eq!(read_u64(extra_field, 4), START_OFFSET as u64);
+
+    let eocdr = find_signature(&data, ZIP64_EOCDR_SIGNATURE);
+    assert_eq!(read_u64(&data, eocdr + 24), 1);
+    assert_eq!(read_u64(&data, eocdr + 48), cd_offset);
+
+    let locator = find_signature(&data, ZIP64_EOCDL_SIGNATURE);
+    assert_eq!(read_u64(&data, locator + 8), START_OFFSET as u64 + eocdr as u64);
+
+    let eocdr = find_signature(&data, EOCDR_SIGNATURE);
+    assert_eq!(read_u32(&data, eocdr + 16), u32::MAX);
+}
+
+static ZEROS: [u8; 64 * 1024] = [0; 64 * 1024];
+
+/// A run of data written to a [`SparseBuffer`].
+enum Segment {
+    Data(Vec<u8>),
+    Zeros(u64),
+}
+
+/// An in-memory buffer which only records the length of runs of zeros, allowing archives over 4 GiB to be written and
+/// read back without holding their data.
+#[derive(Default)]
+struct SparseBuffer {
+    segments: Vec<Segment>,
+    position: usize,
+    offset: u64,
+}
+
+impl AsyncWrite for SparseBuffer {
+    fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let length = buf.len().min(ZEROS.len());
+
+        match (this.segments.last_mut(), buf[..length] == ZEROS[..length]) {
+            (Some(Segment::Zeros(zeros)), true) => *zeros += length as u64,
+            (_, true) => this.segments.push(Segment::Zeros(length as u64)),
+            (Some(Segment::Data(data)), false) => data.extend_from_slice(&buf[..length]),
+            (_, false) => this.segments.push(Segment::Data(buf[..length].to_vec())),
+        }
+
+        Poll::Ready(Ok(length))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncRead for SparseBuffer {
+    fn poll_read(self: Pin<&mut Self>, _: &mut Context<'_>, b: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        while let Some(segment) = this.segments.get(this.position) {
+            let (length, remaining) = match segment {
+                Segment::Data(data) => (data.len() as u64, &data[this.offset as usize..]),
+                Segment::Zeros(zeros) => (*zeros, &ZEROS[..(*zeros - this.offset).min(ZEROS.len() as u64) as usize]),
+            };
+
+            if this.offset == length {
+                this.position += 1;
+                this.offset = 0;
+                continue;
+            }
+
+            let read = remaining.len().min(b.remaining());
+            b.put_slice(&remaining[..read]);
+            this.offset += read as u64;
+            break;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Writes a streamed entry whose sizes exceed 4 GiB (without the data being held) and reads it back via its data
+/// descriptor.
+#[tokio::test]
+async fn zip64_sizes_test() {
+    let size = u64::from(u32::MAX) + 1024 * 1024;
+    let mut writer = ZipFileWriter::new(SparseBuffer::default());
+
+    let entry = ZipEntryBuilder::new(String::from("foo.bin"), Compression::Stored);
+    let mut entry_writer = writer.write_entry_stream(entry).await.unwrap();
+    let mut written = 0;
+
+    while written < size {
+        let length = (size - written).min(1024 * 1024);
+        entry_writer.write_all(&vec![0; length as usize]).await.unwrap();
+        written += length;
+    }
+
+    entry_writer.close().await.unwrap();
+    let buffer = writer.close().await.unwrap();
+
+    // The local file header reserves a zeroed ZIP64 extra field, signalling an eight-byte size data descriptor.
+    let Some(Segment::Data(header)) = buffer.segments.first() else { panic!("expected a local file header") };
+    let extra_field = &header[30 + "foo.bin".len()..];
+    assert_eq!(&extra_field[..4], [ZIP64_HEADER_ID.to_le_bytes(), 16u16.to_le_bytes()].concat());
+    assert_eq!(&extra_field[4..20], [0; 16]);
+
+    // The central directory record holds both sizes within its ZIP64 extra field.
+    let Some(Segment::Data(trailer)) = buffer.segments.last() else { panic!("expected a central directory") };
+    let cdh = find_signature(trailer, CDH_SIGNATURE);
+    assert_eq!((read_u32(trailer, cdh + 20), read_u32(trailer, cdh + 24)), (u32::MAX, u32::MAX));
+    let extra_field = &trailer[cdh + 46 + "foo.bin".len()..];
+    assert_eq!((read_u64(extra_field, 4), read_u64(extra_field, 12)), (size, size));
+
+    let mut reader = crate::read::stream::ZipFileReader::new(buffer);
+    let (_, mut entry_reader) = reader.next_entry().await.unwrap().unwrap();
+    let mut data = vec![0; 1024 * 1024];
+    let mut read = 0;
+
+    loop {
+        match entry_reader.read(&mut data).await.unwrap() {
+            0 => break,
+            length => read += length as u64,
+        }
+    }
+
+    assert_eq!(read, size);
+    assert!(reader.next_entry().await.unwrap().is_none());
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+use crate::read::io::checkpoint::{Checkpoint, DEFAULT_YIELD_INTERVAL};
+use crate::spec::consts::SIGNATURE_LENGTH;
+use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader};
+use crate::write::{CentralDirectoryEntry, HeaderFields};
+
+use tokio::io::{AsyncBufRead, AsyncSeek, AsyncSeekExt, SeekFrom};
+
+/// The central directory of an existing ZIP file which is being appended to.
+pub(crate) struct ExistingDirectory {
+    pub offset: u64,
+    pub entries: Vec<CentralDirectoryEntry>,
+    pub comment: String,
+}
+
+/// Reads the central directory records of an existing ZIP file as-is, so that they can be rewritten unchanged.
+pub(crate) async fn existing_directory<R>(mut reader: R) -> Result<ExistingDirectory>
+where
+    R: AsyncBufRead + AsyncSeek + Unpin,
+{
+    let eocdr_offset = crate::read::io::locator::eocdr(&mut reader).await?;
+
+    reader.seek(SeekFrom::Start(eocdr_offset + SIGNATURE_LENGTH as u64)).await?;
+    let eocdr = EndOfCentralDirectoryHeader::from_reader(&mut reader).await?;
+    let comment =
+        crate::read::io::read_string(&mut reader, eocdr.file_comm_length.into(), &mut Vec::new()).await?.to_owned();
+
+    if eocdr.disk_num != eocdr.start_cent_dir_disk || eocdr.num_of_entries != eocdr.num_of_entries_disk {
+        return Err(ZipError::FeatureNotSupported("Spanned/split files"));
+    }
+
+    reader.seek(SeekFrom::Start(eocdr.cent_dir_offset.into())).await?;
+    let mut entries = Vec::with_capacity(eocdr.num_of_entries.into());
+    let mut checkpoint = Checkpoint::new(DEFAULT_YIELD_INTERVAL);
+    let mut scratch = Vec::new();
+
+    for _ in 0..eocdr.num_of_entries {
+        checkpoint.tick().await;
+        let header = CentralDirectoryRecord::from_reader_with_signature(&mut reader).await?;
+        let lengths = [header.file_name_length, header.extra_field_length, header.file_comment_length].map(usize::from);
+        let [filename, extra_field, comment] = crate::read::io::read_fields(&mut reader, lengths, &mut scratch).await?;
+        let fields = HeaderFields { filename, extra_field, comment, unicode: header.flags.filename_unicode };
+
+        entries.push(CentralDirectoryEntry { header, fields });
+    }
+
+    Ok(ExistingDirectory { offset: eocdr.cent_dir_offset.into(), entries, comment })
+}
@@ -0,0 +1,180 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::builder::ZipEntryBuilder;
+use crate::error::{Result, ZipError};
+use crate::spec::compression::Compression;
+use crate::write::ZipFileWriter;
+
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::fs::File;
+use tokio::io::AsyncWrite;
+
+/// A set of options which control how a directory tree is archived.
+#[derive(Debug, Clone)]
+pub struct ArchiveOptions {
+    pub(crate) compression: Compression,
+    pub(crate) include: Option<PathFilter>,
+    pub(crate) exclude: Option<PathFilter>,
+}
+
+impl Default for ArchiveOptions {
+    fn default() -> Self {
+        ArchiveOptions {
+            #[cfg(feature = "deflate")]
+            compression: Compression::Deflate,
+            #[cfg(not(feature = "deflate"))]
+            compression: Compression::Stored,
+            include: None,
+            exclude: None,
+        }
+    }
+}
+
+impl ArchiveOptions {
+    /// Constructs a new set of options with their default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the compression method used for file entries (defaults to Deflate if enabled, otherwise Stored).
+    ///
+    /// Directory entries are always stored, as they contain no data.
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets a filter which a file's path (relative to the root) must match for it to be archived.
+    ///
+    /// This filter isn't applied to directories, so that the files within them can still be matched. As such, a
+    /// directory's entry is written even if none of the files within it are included.
+    pub fn include<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.include = Some(PathFilter(Arc::new(filter)));
+        self
+    }
+
+    /// Sets a filter which excludes any file or directory whose path (relative to the root) it matches.
+    ///
+    /// Excluding a directory also excludes everything within it, without the directory ever being walked.
+    pub fn exclude<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&Path) -> bool + Send + Sync + 'static,
+    {
+        self.exclude = Some(PathFilter(Arc::new(filter)));
+        self
+    }
+}
+
+/// A cloneable wrapper around a path filter.
+#[derive(Clone)]
+pub(crate) struct PathFilter(Arc<dyn Fn(&Path) -> bool + Send + Sync>);
+
+impl PathFilter {
+    fn matches(&self, path: &Path) -> bool {
+        (self.0)(path)
+    }
+}
+
+impl Debug for PathFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PathFilter")
+    }
+}
+
+/// Writes every file & directory beneath the provided root directory as entries of the provided ZIP writer.
+///
+/// The tree is walked asynchronously, with the entries of each directory written in the order of their names (and so
+/// the resulting archive is deterministic for a given tree). Each entry's filename is its path relative to the root
+/// (using `/` as the separator), and its last modification date & Unix permissions are taken from its metadata (see
+/// [`ZipEntryBuilder::file_metadata()`]). Directories are written via [`ZipFileWriter::write_dir_entry()`], and
+/// symbolic links are written as symlink entries (rather than being followed). The root itself isn't written as an
+/// entry, and the writer isn't closed so that further entries may still be written.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use async_zip::write::{ArchiveOptions, ZipFileWriter};
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// let mut writer = ZipFileWriter::new(File::create("./project.zip").await?);
+/// let options = ArchiveOptions::new().exclude(|path| path.starts_with("target"));
+///
+/// async_zip::write::archive_dir(&mut writer, "./project", &options).await?;
+/// writer.close().await?;
+/// #   Ok(())
+/// # }
+/// ```
+pub async fn archive_dir<W, P>(writer: &mut ZipFileWriter<W>, root: P, options: &ArchiveOptions) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    P: AsRef<Path>,
+{
+    let root = root.as_ref();
+    let mut pending = vec![PathBuf::new()];
+
+    while let Some(directory) = pending.pop() {
+        let mut children = Vec::new();
+        let mut read_dir = tokio::fs::read_dir(root.join(&directory)).await?;
+
+        while let Some(child) = read_dir.next_entry().await? {
+            children.push(child.file_name());
+        }
+        children.sort();
+
+        let mut subdirectories = Vec::new();
+
+        for name in children {
+            let relative = directory.join(name);
+            if options.exclude.as_ref().is_some_and(|filter| filter.matches(&relative)) {
+                continue;
+            }
+
+            let path = root.join(&relative);
+            let metadata = tokio::fs::symlink_metadata(&path).await?;
+            let builder =
+                ZipEntryBuilder::new(entry_filename(&relative)?, options.compression).file_metadata(&metadata);
+
+            if metadata.is_dir() {
+                writer.write_dir_entry(builder).await?;
+                subdirectories.push(relative);
+                continue;
+            }
+            if options.include.as_ref().is_some_and(|filter| !filter.matches(&relative)) {
+                continue;
+            }
+
+            if metadata.is_symlink() {
+                let target = tokio::fs::read_link(&path).await?;
+                let target = target.to_str().ok_or_else(|| non_utf8_path(&target))?;
+                writer.write_entry_whole(builder, target.as_bytes()).await?;
+            } else {
+                writer.write_entry_copy(builder, File::open(&path).await?).await?;
+            }
+        }
+
+        // Subdirectories are pushed in reverse so that they're walked in the order of their names.
+        pending.extend(subdirectories.into_iter().rev());
+    }
+
+    Ok(())
+}
+
+/// Converts a relative path into an entry filename, using `/` as the separator.
+fn entry_filename(path: &Path) -> Result<String> {
+    let components: Option<Vec<&str>> = path.iter().map(|component| component.to_str()).collect();
+    let components = components.ok_or_else(|| non_utf8_path(path))?;
+
+    Ok(components.join("/"))
+}
+
+fn non_utf8_path(path: &Path) -> ZipError {
+    ZipError::NonUtf8Path(path.to_string_lossy().into_owned())
+}
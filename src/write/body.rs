@@ -0,0 +1,104 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::write::ZipFileWriter;
+
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use bytes::{Bytes, BytesMut};
+use http_body::{Body, Frame};
+use tokio::io::{AsyncRead, DuplexStream, ReadBuf};
+
+/// Constructs a ZIP writer whose output is served as an HTTP response body, as it's written.
+///
+/// This allows a dynamically generated archive (eg. a "download all" response) to be streamed to a client without it
+/// being written to a temporary file or held in memory. The connection is backed by [`tokio::io::duplex()`], so at most
+/// `max_buf_size` bytes are buffered between the two, and the body yields chunks of at most that size. As such, the
+/// writer must be driven concurrently with the body (eg. within a separate task).
+///
+/// The body ends once the writer has been closed and the inner stream dropped. If the writer is instead dropped before
+/// being closed (eg. after an error), the body ends early and the client receives a truncated ZIP file. Likewise, if
+/// the body is dropped (eg. as the client disconnected), writing fails with a broken pipe error.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder};
+/// # use async_zip::error::Result;
+/// # use async_zip::write::ZipBody;
+/// #
+/// # async fn run() -> ZipBody {
+/// let (mut writer, body) = async_zip::write::body(64 * 1024);
+///
+/// tokio::spawn(async move {
+///     let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Deflate);
+///     writer.write_entry_whole(entry, b"This is an example file.").await?;
+///     // The inner stream is dropped once closed, so that the body ends.
+///     writer.close().await.map(drop)
+/// });
+///
+/// // eg. Response::new(body) within a hyper or axum handler.
+/// body
+/// # }
+/// ```
+pub fn body(max_buf_size: usize) -> (ZipFileWriter<DuplexStream>, ZipBody) {
+    let (writer, reader) = tokio::io::duplex(max_buf_size);
+    let body = ZipBody { reader, chunk_size: max_buf_size.max(1), buffer: BytesMut::new(), finished: false };
+
+    (ZipFileWriter::new(writer), body)
+}
+
+/// An HTTP response body which serves the output of a ZIP writer as it's written.
+///
+/// This type cannot be directly constructed so instead, [`body()`] must be used. When the `futures` feature is also
+/// enabled, this implements [`Stream`](futures_util::stream::Stream) too.
+pub struct ZipBody {
+    reader: DuplexStream,
+    chunk_size: usize,
+    buffer: BytesMut,
+    finished: bool,
+}
+
+impl ZipBody {
+    /// Polls for the next chunk of data written by the writer, or [`None`] if the writer has finished.
+    fn poll_chunk(&mut self, c: &mut Context<'_>) -> Poll<Option<std::io::Result<Bytes>>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        // The buffer is kept across polls so that it isn't reallocated each time the writer hasn't written anything.
+        self.buffer.resize(self.chunk_size, 0);
+        let mut read_buf = ReadBuf::new(&mut self.buffer);
+        ready!(Pin::new(&mut self.reader).poll_read(c, &mut read_buf))?;
+
+        match read_buf.filled().len() {
+            0 => {
+                self.finished = true;
+                Poll::Ready(None)
+            }
+            read => Poll::Ready(Some(Ok(self.buffer.split_to(read).freeze()))),
+        }
+    }
+}
+
+impl Body for ZipBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, std::io::Error>>> {
+        self.get_mut().poll_chunk(c).map(|chunk| chunk.map(|chunk| chunk.map(Frame::data)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.finished
+    }
+}
+
+#[cfg(feature = "futures")]
+impl futures_util::stream::Stream for ZipBody {
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, c: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_chunk(c)
+    }
+}
@@ -1,7 +1,9 @@
 // Copyright (c) 2021 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+use crate::error::ZipError;
 use crate::spec::compression::Compression;
+use crate::write::io::cipher::CipherWriter;
 use crate::write::io::offset::AsyncOffsetWriter;
 
 use std::io::Error;
@@ -12,52 +14,78 @@ use std::task::{Context, Poll};
 use async_compression::tokio::write;
 use tokio::io::AsyncWrite;
 
+/// The writer which compressed data is written to (encrypting it if needed).
+pub(crate) type EntryDataWriter<'b, W> = CipherWriter<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>;
+
 pub enum CompressedAsyncWriter<'b, W: AsyncWrite + Unpin> {
-    Stored(ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>),
+    Stored(EntryDataWriter<'b, W>),
     #[cfg(feature = "deflate")]
-    Deflate(write::DeflateEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Deflate(write::DeflateEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "bzip2")]
-    Bz(write::BzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Bz(write::BzEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "lzma")]
-    Lzma(write::LzmaEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Lzma(write::LzmaEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "zstd")]
-    Zstd(write::ZstdEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Zstd(write::ZstdEncoder<EntryDataWriter<'b, W>>),
     #[cfg(feature = "xz")]
-    Xz(write::XzEncoder<ShutdownIgnoredWriter<&'b mut AsyncOffsetWriter<W>>>),
+    Xz(write::XzEncoder<EntryDataWriter<'b, W>>),
 }
 
 impl<'b, W: AsyncWrite + Unpin> CompressedAsyncWriter<'b, W> {
     pub fn from_raw(writer: &'b mut AsyncOffsetWriter<W>, compression: Compression) -> Self {
+        Self::from_data_writer(CipherWriter::Plain(ShutdownIgnoredWriter::new(writer)), compression)
+    }
+
+    /// Constructs a writer which compresses data before writing it to the provided writer.
+    pub(crate) fn from_data_writer(writer: EntryDataWriter<'b, W>, compression: Compression) -> Self {
         match compression {
-            Compression::Stored => CompressedAsyncWriter::Stored(ShutdownIgnoredWriter(writer)),
+            Compression::Stored => CompressedAsyncWriter::Stored(writer),
+            #[cfg(feature = "deflate")]
+            Compression::Deflate => CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(writer)),
+            #[cfg(feature = "bzip2")]
+            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(writer)),
+            #[cfg(feature = "lzma")]
+            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(writer)),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(writer)),
+            #[cfg(feature = "xz")]
+            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(writer)),
+            // Legacy compression methods are rejected before any writer is constructed.
+            #[cfg(feature = "legacy")]
+            Compression::Shrink | Compression::Reduce(_) | Compression::Implode { .. } => unreachable!(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &AsyncOffsetWriter<W> {
+        match self {
+            CompressedAsyncWriter::Stored(inner) => inner.get_ref().get_ref(),
             #[cfg(feature = "deflate")]
-            Compression::Deflate => {
-                CompressedAsyncWriter::Deflate(write::DeflateEncoder::new(ShutdownIgnoredWriter(writer)))
-            }
+            CompressedAsyncWriter::Deflate(inner) => inner.get_ref().get_ref().get_ref(),
             #[cfg(feature = "bzip2")]
-            Compression::Bz => CompressedAsyncWriter::Bz(write::BzEncoder::new(ShutdownIgnoredWriter(writer))),
+            CompressedAsyncWriter::Bz(inner) => inner.get_ref().get_ref().get_ref(),
             #[cfg(feature = "lzma")]
-            Compression::Lzma => CompressedAsyncWriter::Lzma(write::LzmaEncoder::new(ShutdownIgnoredWriter(writer))),
+            CompressedAsyncWriter::Lzma(inner) => inner.get_ref().get_ref().get_ref(),
             #[cfg(feature = "zstd")]
-            Compression::Zstd => CompressedAsyncWriter::Zstd(write::ZstdEncoder::new(ShutdownIgnoredWriter(writer))),
+            CompressedAsyncWriter::Zstd(inner) => inner.get_ref().get_ref().get_ref(),
             #[cfg(feature = "xz")]
-            Compression::Xz => CompressedAsyncWriter::Xz(write::XzEncoder::new(ShutdownIgnoredWriter(writer))),
+            CompressedAsyncWriter::Xz(inner) => inner.get_ref().get_ref().get_ref(),
         }
     }
 
-    pub fn into_inner(self) -> &'b mut AsyncOffsetWriter<W> {
+    /// Consumes this writer and returns the writer which compressed data was written to.
+    pub(crate) fn into_data_writer(self) -> EntryDataWriter<'b, W> {
         match self {
-            CompressedAsyncWriter::Stored(inner) => inner.into_inner(),
+            CompressedAsyncWriter::Stored(inner) => inner,
             #[cfg(feature = "deflate")]
-            CompressedAsyncWriter::Deflate(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Deflate(inner) => inner.into_inner(),
             #[cfg(feature = "bzip2")]
-            CompressedAsyncWriter::Bz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Bz(inner) => inner.into_inner(),
             #[cfg(feature = "lzma")]
-            CompressedAsyncWriter::Lzma(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Lzma(inner) => inner.into_inner(),
             #[cfg(feature = "zstd")]
-            CompressedAsyncWriter::Zstd(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Zstd(inner) => inner.into_inner(),
             #[cfg(feature = "xz")]
-            CompressedAsyncWriter::Xz(inner) => inner.into_inner().into_inner(),
+            CompressedAsyncWriter::Xz(inner) => inner.into_inner(),
         }
     }
 }
@@ -112,21 +140,55 @@ impl<'b, W: AsyncWrite + Unpin> AsyncWrite for CompressedAsyncWriter<'b, W> {
     }
 }
 
-pub struct ShutdownIgnoredWriter<W: AsyncWrite + Unpin>(W);
+/// A writer which ignores shutdowns, as the ZIP file's writer continues to be used once an entry's data is written.
+///
+/// If a limit has been set, writes which would pass more than the remaining number of bytes to the inner writer are
+/// rejected before any of their data is written, so that the ZIP file's maximum size is never exceeded.
+pub struct ShutdownIgnoredWriter<W: AsyncWrite + Unpin> {
+    inner: W,
+    remaining: Option<u64>,
+    max_size: u64,
+}
 
 impl<W: AsyncWrite + Unpin> ShutdownIgnoredWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self { inner, remaining: None, max_size: 0 }
+    }
+
+    /// Limits the number of bytes which may be written, with further writes failing with
+    /// [`ZipError::ArchiveSizeExceeded`] for the provided maximum size.
+    pub(crate) fn limit(mut self, remaining: u64, max_size: u64) -> Self {
+        self.remaining = Some(remaining);
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
     pub fn into_inner(self) -> W {
-        self.0
+        self.inner
     }
 }
 
 impl<W: AsyncWrite + Unpin> AsyncWrite for ShutdownIgnoredWriter<W> {
     fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
-        Pin::new(&mut self.0).poll_write(cx, buf)
+        if self.remaining.is_some_and(|remaining| buf.len() as u64 > remaining) {
+            return Poll::Ready(Err(Error::other(ZipError::ArchiveSizeExceeded(self.max_size))));
+        }
+
+        let poll = Pin::new(&mut self.inner).poll_write(cx, buf);
+
+        if let (Some(remaining), Poll::Ready(Ok(written))) = (self.remaining.as_mut(), &poll) {
+            *remaining -= *written as u64;
+        }
+
+        poll
     }
 
     fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
-        Pin::new(&mut self.0).poll_flush(cx)
+        Pin::new(&mut self.inner).poll_flush(cx)
     }
 
     fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context) -> Poll<std::result::Result<(), Error>> {
@@ -0,0 +1,59 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::error::{Result, ZipError};
+use crate::read::rewrite::RewriteAction;
+use crate::read::seek::ZipFileReader;
+use crate::write::ZipFileWriter;
+
+use std::collections::HashSet;
+
+use tokio::io::{AsyncRead, AsyncSeek, AsyncWrite};
+
+/// Concatenates the entries of several ZIP files into a single ZIP file, streamed to the provided writer.
+///
+/// Each source is opened in turn and its entries are raw-copied (ie. without being decompressed and recompressed) in
+/// the order they appear, before a fresh central directory covering every entry is written. Only the central directory
+/// of the source currently being copied is held in memory, so no source is ever buffered in full and the writer
+/// needn't be seekable (eg. an HTTP response body).
+///
+/// Entries must have distinct filenames across all sources, otherwise [`ZipError::DuplicateFilename`] is returned.
+/// Encrypted entries aren't supported, and the comments of the sources aren't retained.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::error::Result;
+/// # use tokio::fs::File;
+/// #
+/// # async fn run() -> Result<()> {
+/// let sources = vec![File::open("./foo.zip").await?, File::open("./bar.zip").await?];
+/// let output = File::create("./combined.zip").await?;
+///
+/// async_zip::write::concat(sources, output).await?;
+/// #   Ok(())
+/// # }
+/// ```
+pub async fn concat<I, R, W>(sources: I, writer: W) -> Result<()>
+where
+    I: IntoIterator<Item = R>,
+    R: AsyncRead + AsyncSeek + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let mut writer = ZipFileWriter::new(writer);
+    let mut filenames = HashSet::new();
+
+    for source in sources {
+        let mut reader = ZipFileReader::new(source).await?;
+
+        for entry in reader.file().entries() {
+            if !filenames.insert(entry.filename().to_string()) {
+                return Err(ZipError::DuplicateFilename(entry.filename().to_string()));
+            }
+        }
+
+        reader.rewrite(&mut writer, |_| RewriteAction::Keep).await?;
+    }
+
+    writer.close().await?;
+    Ok(())
+}
@@ -0,0 +1,192 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::Result;
+#[cfg(feature = "aes")]
+use crate::spec::aes::{AesKeys, AES_AUTH_CODE_LENGTH, AES_VERIFIER_LENGTH, AES_VERSION_NEEDED};
+#[cfg(feature = "aes")]
+use crate::spec::aes::{AES_WRITE_STRENGTH, AES_WRITE_VERSION};
+#[cfg(feature = "aes")]
+use crate::spec::extra_field::{AesExtraField, AES_COMPRESSION_METHOD, AES_HEADER_ID};
+use crate::spec::zipcrypto::{ZipCryptoKeys, ZIPCRYPTO_HEADER_LENGTH, ZIPCRYPTO_VERSION_NEEDED};
+use crate::write::io::cipher::{CipherWriterState, WriteCipher};
+use crate::write::HeaderFields;
+
+use std::borrow::Cow;
+
+/// The encryption applied to an entry's data as it's written.
+///
+/// Entries are either encrypted via traditional PKWARE (ZipCrypto) encryption, whose data is preceded by a 12-byte
+/// encryption header, or via WinZip AE-2 (using AES-256), which stores a zeroed CRC32 value and instead authenticates
+/// the encrypted data via an HMAC-SHA1 code appended to it.
+#[derive(Default)]
+pub(crate) struct EntryEncryption {
+    zipcrypto: Option<ZipCryptoKeys>,
+    #[cfg(feature = "aes")]
+    aes: Option<AesEncryption>,
+}
+
+#[cfg(feature = "aes")]
+struct AesEncryption {
+    field: AesExtraField,
+    salt: Vec<u8>,
+    keys: AesKeys,
+}
+
+impl EntryEncryption {
+    /// Constructs the encryption requested by the entry itself (ie. ZipCrypto, if a password has been set).
+    pub(crate) fn new(entry: &ZipEntry) -> Self {
+        let zipcrypto = entry.zipcrypto_password.as_deref().map(ZipCryptoKeys::new);
+        Self {
+            zipcrypto,
+            #[cfg(feature = "aes")]
+            aes: None,
+        }
+    }
+
+    /// Generates a salt and derives the keys to encrypt an entry via AES with the provided password.
+    #[cfg(feature = "aes")]
+    pub(crate) fn aes(entry: &ZipEntry, password: &[u8]) -> Result<Self> {
+        let field = AesExtraField {
+            version: AES_WRITE_VERSION,
+            strength: AES_WRITE_STRENGTH,
+            compression: entry.compression().into(),
+        };
+        let salt = crate::spec::aes::generate_salt(field.salt_length())?;
+        let keys = AesKeys::derive(field.strength, password, &salt);
+
+        Ok(Self { zipcrypto: None, aes: Some(AesEncryption { field, salt, keys }) })
+    }
+
+    /// Returns whether the entry's data is encrypted.
+    pub(crate) fn encrypted(&self) -> bool {
+        self.zipcrypto.is_some() || !self.crc_stored()
+    }
+
+    /// Returns whether the CRC32 value of the entry's uncompressed data is stored within its headers.
+    pub(crate) fn crc_stored(&self) -> bool {
+        #[cfg(feature = "aes")]
+        if self.aes.is_some() {
+            return false;
+        }
+
+        true
+    }
+
+    /// Returns whether the check byte of the encryption header must be taken from the CRC32 value, unless the entry
+    /// uses a data descriptor (see [`EntryEncryption::header()`]).
+    pub(crate) fn check_from_crc(&self) -> bool {
+        self.zipcrypto.is_some()
+    }
+
+    /// Returns the compression method to store within the entry's headers.
+    pub(crate) fn compression(&self, entry: &ZipEntry) -> u16 {
+        #[cfg(feature = "aes")]
+        if self.aes.is_some() {
+            return AES_COMPRESSION_METHOD;
+        }
+
+        entry.compression().into()
+    }
+
+    /// Returns the CRC32 value to store within the entry's headers given that of its uncompressed data.
+    pub(crate) fn crc(&self, crc: u32) -> u32 {
+        match self.crc_stored() {
+            true => crc,
+            false => 0,
+        }
+    }
+
+    /// Returns the version needed to extract the entry.
+    pub(crate) fn version_needed(&self, entry: &ZipEntry) -> u16 {
+        #[cfg(feature = "aes")]
+        if self.aes.is_some() {
+            return std::cmp::max(crate::spec::version::as_needed_to_extract(entry), AES_VERSION_NEEDED);
+        }
+        if self.zipcrypto.is_some() {
+            return std::cmp::max(crate::spec::version::as_needed_to_extract(entry), ZIPCRYPTO_VERSION_NEEDED);
+        }
+
+        crate::spec::version::as_needed_to_extract(entry)
+    }
+
+    /// Appends any extra field required by the encryption method onto the entry's header fields.
+    pub(crate) fn push_extra_field(&self, fields: &mut HeaderFields) {
+        #[cfg(feature = "aes")]
+        if let Some(aes) = &self.aes {
+            let data = crate::spec::extra_field::build_aes(&aes.field);
+            crate::spec::extra_field::push_extra_field(&mut fields.extra_field, AES_HEADER_ID, &data);
+        }
+
+        #[cfg(not(feature = "aes"))]
+        let _ = fields;
+    }
+
+    /// Returns the number of bytes the encryption adds to the entry's compressed data.
+    pub(crate) fn overhead(&self) -> u64 {
+        #[cfg(feature = "aes")]
+        if let Some(aes) = &self.aes {
+            return (aes.salt.len() + AES_VERIFIER_LENGTH + AES_AUTH_CODE_LENGTH) as u64;
+        }
+        if self.zipcrypto.is_some() {
+            return ZIPCRYPTO_HEADER_LENGTH as u64;
+        }
+
+        0
+    }
+
+    /// Returns the bytes which precede the entry's encrypted data (ie. the AES salt & password verification value, or
+    /// the ZipCrypto encryption header).
+    ///
+    /// The last byte of a ZipCrypto encryption header is the provided check byte, which extractors compare against to
+    /// verify the password. This is the high byte of the CRC32 value or, if a data descriptor is used, of the MS-DOS
+    /// modification time.
+    pub(crate) fn header(&mut self, check: u8) -> Result<Vec<u8>> {
+        #[cfg(feature = "aes")]
+        if let Some(aes) = &self.aes {
+            return Ok([aes.salt.as_slice(), &aes.keys.verifier()].concat());
+        }
+
+        match &mut self.zipcrypto {
+            Some(keys) => Ok(crate::spec::zipcrypto::generate_header(keys, check)?.to_vec()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Encrypts the entry's compressed data in whole, returning it alongside the header & authentication code (if
+    /// any).
+    pub(crate) fn seal(mut self, data: &[u8], check: u8) -> Result<Cow<'_, [u8]>> {
+        if !self.encrypted() {
+            return Ok(Cow::Borrowed(data));
+        }
+
+        let mut sealed = self.header(check)?;
+        let data_offset = sealed.len();
+        sealed.extend_from_slice(data);
+
+        #[cfg(feature = "aes")]
+        if let Some(mut aes) = self.aes {
+            aes.keys.encrypt(&mut sealed[data_offset..]);
+            sealed.extend_from_slice(&aes.keys.auth_code());
+
+            return Ok(Cow::Owned(sealed));
+        }
+
+        if let Some(keys) = &mut self.zipcrypto {
+            keys.encrypt(&mut sealed[data_offset..]);
+        }
+
+        Ok(Cow::Owned(sealed))
+    }
+
+    /// Consumes the encryption, returning the state needed to encrypt a stream of data (if the entry is encrypted).
+    pub(crate) fn into_writer_state(self) -> Option<CipherWriterState> {
+        #[cfg(feature = "aes")]
+        if let Some(aes) = self.aes {
+            return Some(CipherWriterState::new(WriteCipher::Aes(aes.keys)));
+        }
+
+        self.zipcrypto.map(|keys| CipherWriterState::new(WriteCipher::ZipCrypto(keys)))
+    }
+}
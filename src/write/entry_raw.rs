@@ -0,0 +1,97 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag, LocalFileHeader};
+use crate::write::progress::EntryProgress;
+use crate::write::{CentralDirectoryEntry, HeaderFields, ZipFileWriter};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+/// An entry writer which copies already-compressed data as-is, using the entry's existing CRC32 value & sizes.
+pub struct EntryRawWriter<'b, 'c, W: AsyncWrite + Unpin, R: AsyncRead + Unpin> {
+    writer: &'b mut ZipFileWriter<W>,
+    entry: &'c ZipEntry,
+    reader: R,
+}
+
+impl<'b, 'c, W: AsyncWrite + Unpin, R: AsyncRead + Unpin> EntryRawWriter<'b, 'c, W, R> {
+    pub fn from_raw(writer: &'b mut ZipFileWriter<W>, entry: &'c ZipEntry, reader: R) -> Self {
+        Self { writer, entry, reader }
+    }
+
+    pub async fn write(self) -> Result<()> {
+        if self.entry.zipcrypto_password.is_some() {
+            return Err(ZipError::FeatureNotSupported("ZipCrypto encryption of raw copied entries"));
+        }
+
+        let fields = HeaderFields::new(self.entry);
+        let alignment = crate::write::alignment_field(self.entry, &fields, self.writer.writer.offset());
+        let data_length =
+            crate::write::lfh_length(&fields) + alignment.len() as u64 + u64::from(self.entry.compressed_size());
+        self.writer.remaining_size(data_length, crate::write::cdh_length(&fields))?;
+        let progress = self.writer.progress_reporter(&fields);
+
+        let (mod_time, mod_date) = crate::spec::date::chrono_to_zip_time(self.entry.last_modification_date());
+        let lf_header = LocalFileHeader {
+            compressed_size: self.entry.compressed_size(),
+            uncompressed_size: self.entry.uncompressed_size(),
+            compression: self.entry.compression().into(),
+            crc: self.entry.crc32(),
+            extra_field_length: (fields.extra_field.len() + alignment.len()) as u16,
+            file_name_length: fields.filename.len() as u16,
+            mod_time,
+            mod_date,
+            version: crate::spec::version::as_needed_to_extract(self.entry),
+            flags: GeneralPurposeFlag {
+                data_descriptor: false,
+                strong_encryption: false,
+                encrypted: false,
+                compression_options: self.entry.compression().flag_options(),
+                filename_unicode: fields.unicode,
+            },
+        };
+
+        let header = CentralDirectoryRecord {
+            v_made_by: crate::spec::version::as_made_by(self.entry),
+            v_needed: lf_header.version,
+            compressed_size: lf_header.compressed_size,
+            uncompressed_size: lf_header.uncompressed_size,
+            compression: lf_header.compression,
+            crc: lf_header.crc,
+            extra_field_length: fields.extra_field.len() as u16,
+            file_name_length: lf_header.file_name_length,
+            file_comment_length: fields.comment.len() as u16,
+            mod_time: lf_header.mod_time,
+            mod_date: lf_header.mod_date,
+            flags: lf_header.flags,
+            disk_start: 0,
+            inter_attr: self.entry.internal_file_attribute(),
+            exter_attr: self.entry.external_file_attribute(),
+            lh_offset: self.writer.writer.offset() as u32,
+        };
+
+        self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
+        self.writer.writer.write_all(&lf_header.as_slice()).await?;
+        self.writer.writer.write_all(&fields.filename).await?;
+        self.writer.writer.write_all(&fields.extra_field).await?;
+        self.writer.writer.write_all(&alignment).await?;
+
+        let mut reader = self.reader.take(self.entry.compressed_size().into());
+        let copied = tokio::io::copy(&mut reader, &mut self.writer.writer).await?;
+
+        if copied != u64::from(self.entry.compressed_size()) {
+            return Err(ZipError::UpstreamReadError(std::io::ErrorKind::UnexpectedEof.into()));
+        }
+
+        self.writer.cd_entries.push(CentralDirectoryEntry { header, fields });
+
+        if let Some(progress) = progress {
+            let entry = EntryProgress { consumed: copied, compressed: copied };
+            progress.report(entry, self.writer.writer.offset() as u64, true);
+        }
+
+        self.writer.entry_written().await
+    }
+}
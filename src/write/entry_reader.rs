@@ -0,0 +1,153 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::error::{Result, ZipError};
+use crate::spec::consts::{LFH_LENGTH, LFH_SIGNATURE, SIGNATURE_LENGTH};
+use crate::spec::crc::Crc32;
+use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag, LocalFileHeader};
+use crate::write::compressed_writer::CompressedAsyncWriter;
+use crate::write::io::offset::AsyncOffsetWriter;
+use crate::write::progress::EntryProgress;
+use crate::write::{CentralDirectoryEntry, HeaderFields, ZipFileWriter};
+
+use std::io::SeekFrom;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt};
+
+/// The size of the buffer used when copying data from the source reader.
+const BUFFER_SIZE: usize = 8192;
+
+/// An entry writer which copies data from a reader and backpatches the local file header once its sizes are known.
+pub struct EntryReaderWriter<'b, 'c, W: AsyncWrite + AsyncSeek + Unpin, R: AsyncRead + Unpin> {
+    writer: &'b mut ZipFileWriter<W>,
+    entry: ZipEntry,
+    reader: &'c mut R,
+}
+
+impl<'b, 'c, W: AsyncWrite + AsyncSeek + Unpin, R: AsyncRead + Unpin> EntryReaderWriter<'b, 'c, W, R> {
+    pub fn from_raw(writer: &'b mut ZipFileWriter<W>, entry: ZipEntry, reader: &'c mut R) -> Self {
+        Self { writer, entry, reader }
+    }
+
+    pub async fn write(self) -> Result<()> {
+        crate::write::check_compression(&self.entry)?;
+
+        // The check byte of a ZipCrypto encryption header is derived from the CRC32 value, which isn't known upfront.
+        if self.entry.zipcrypto_password.is_some() {
+            return Err(ZipError::FeatureNotSupported("ZipCrypto encryption of entries written from a reader"));
+        }
+
+        let fields = HeaderFields::new(&self.entry);
+        let alignment = crate::write::alignment_field(&self.entry, &fields, self.writer.writer.offset());
+        let lfh_length = crate::write::lfh_length(&fields) + alignment.len() as u64;
+        let remaining_size = self.writer.remaining_size(lfh_length, crate::write::cdh_length(&fields))?;
+        let progress = self.writer.progress_reporter(&fields);
+
+        let (mod_time, mod_date) = crate::spec::date::chrono_to_zip_time(self.entry.last_modification_date());
+        let lfh_offset = self.writer.writer.offset();
+
+        let mut lf_header = LocalFileHeader {
+            compressed_size: 0,
+            uncompressed_size: 0,
+            compression: self.entry.compression().into(),
+            crc: 0,
+            extra_field_length: (fields.extra_field.len() + alignment.len()) as u16,
+            file_name_length: fields.filename.len() as u16,
+            mod_time,
+            mod_date,
+            version: crate::spec::version::as_needed_to_extract(&self.entry),
+            flags: GeneralPurposeFlag {
+                data_descriptor: false,
+                strong_encryption: false,
+                encrypted: false,
+                compression_options: 0,
+                filename_unicode: fields.unicode,
+            },
+        };
+
+        self.writer.writer.write_all(&LFH_SIGNATURE.to_le_bytes()).await?;
+        self.writer.writer.write_all(&lf_header.as_slice()).await?;
+        self.writer.writer.write_all(&fields.filename).await?;
+        self.writer.writer.write_all(&fields.extra_field).await?;
+        self.writer.writer.write_all(&alignment).await?;
+
+        let data_offset = self.writer.writer.offset();
+        let mut hasher = Crc32::new();
+        let mut writer =
+            AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut self.writer.writer, self.entry.compression()));
+        let mut buffer = vec![0; BUFFER_SIZE];
+
+        loop {
+            let read = self.reader.read(&mut buffer).await?;
+            if read == 0 {
+                break;
+            }
+
+            hasher.update(&buffer[..read]);
+            writer.write_all(&buffer[..read]).await?;
+
+            if let Some(progress) = &progress {
+                let archive_size = writer.get_ref().get_ref().offset();
+                let entry =
+                    EntryProgress { consumed: writer.offset() as u64, compressed: (archive_size - data_offset) as u64 };
+                progress.report(entry, archive_size as u64, false);
+            }
+        }
+
+        writer.shutdown().await?;
+        let uncompressed_size = writer.offset() as u32;
+        let compressed_size = self.writer.writer.offset() - data_offset;
+
+        if let (Some(max_size), Some(remaining_size)) = (self.writer.max_size, remaining_size) {
+            if compressed_size as u64 > remaining_size {
+                return Err(ZipError::ArchiveSizeExceeded(max_size));
+            }
+        }
+
+        lf_header.crc = hasher.finalize();
+        lf_header.compressed_size = compressed_size as u32;
+        lf_header.uncompressed_size = uncompressed_size;
+
+        // Seek back to the start of the local file header and overwrite it before returning to the end of the data.
+        // Relative seeks are used as the writer may not have been at position zero when the ZIP writer was constructed.
+        let end_offset = self.writer.writer.offset();
+        let header_length = SIGNATURE_LENGTH + LFH_LENGTH;
+        let inner = self.writer.writer.get_mut();
+
+        inner.flush().await?;
+        inner.seek(SeekFrom::Current(-((end_offset - lfh_offset) as i64))).await?;
+        inner.write_all(&LFH_SIGNATURE.to_le_bytes()).await?;
+        inner.write_all(&lf_header.as_slice()).await?;
+        inner.flush().await?;
+        inner.seek(SeekFrom::Current((end_offset - lfh_offset - header_length) as i64)).await?;
+
+        let header = CentralDirectoryRecord {
+            v_made_by: crate::spec::version::as_made_by(&self.entry),
+            v_needed: lf_header.version,
+            compressed_size: lf_header.compressed_size,
+            uncompressed_size: lf_header.uncompressed_size,
+            compression: lf_header.compression,
+            crc: lf_header.crc,
+            extra_field_length: fields.extra_field.len() as u16,
+            file_name_length: lf_header.file_name_length,
+            file_comment_length: fields.comment.len() as u16,
+            mod_time: lf_header.mod_time,
+            mod_date: lf_header.mod_date,
+            flags: lf_header.flags,
+            disk_start: 0,
+            inter_attr: self.entry.internal_file_attribute(),
+            exter_attr: self.entry.external_file_attribute(),
+            lh_offset: lfh_offset as u32,
+        };
+
+        self.writer.cd_entries.push(CentralDirectoryEntry { header, fields });
+
+        if let Some(progress) = progress {
+            let entry = EntryProgress { consumed: uncompressed_size.into(), compressed: compressed_size as u64 };
+            progress.report(entry, self.writer.writer.offset() as u64, true);
+        }
+
+        self.writer.entry_written().await
+    }
+}
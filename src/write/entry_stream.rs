@@ -2,19 +2,57 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
-use crate::error::Result;
+use crate::error::{Result, ZipError};
+use crate::spec::consts::{DATA_DESCRIPTOR_LENGTH, SIGNATURE_LENGTH};
+use crate::spec::crc::Crc32;
+use crate::spec::extra_field::{push_extra_field, remove_extra_field, ZIP64_HEADER_ID};
 use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag, LocalFileHeader};
-use crate::write::compressed_writer::CompressedAsyncWriter;
+use crate::spec::version::ZIP64_VERSION_NEEDED;
+use crate::write::compressed_writer::{CompressedAsyncWriter, ShutdownIgnoredWriter};
+use crate::write::encryption::EntryEncryption;
+use crate::write::io::cipher::CipherWriter;
 use crate::write::io::offset::AsyncOffsetWriter;
-use crate::write::CentralDirectoryEntry;
+use crate::write::progress::{EntryProgress, EntryProgressReporter};
 use crate::write::ZipFileWriter;
+use crate::write::{CentralDirectoryEntry, HeaderFields};
 
-use std::io::Error;
+use std::future::Future;
+use std::io::{Error, SeekFrom};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
-use crc32fast::Hasher;
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::io::{AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufWriter};
+
+/// The additional length of a data descriptor whose sizes are eight bytes each.
+const ZIP64_DESCRIPTOR_EXTRA: usize = 8;
+
+/// The offset of the CRC32 value (followed by the compressed & uncompressed sizes) within a local file header.
+const LFH_CRC_OFFSET: usize = SIGNATURE_LENGTH + 10;
+
+/// A function which overwrites the CRC32 value & sizes of a local file header, given how far back it was written.
+///
+/// This allows seeking to be supported by the stream writer without it requiring that all writers are seekable.
+pub(crate) type LfhPatcher<W> = for<'a> fn(&'a mut W, u64, [u8; 12]) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>>;
+
+/// Seeks back to overwrite a local file header's CRC32 value & sizes, and then restores the writer's position.
+///
+/// Relative seeks are used as the writer may not have been at position zero when the ZIP writer was constructed.
+pub(crate) fn patch_lfh<W>(
+    writer: &mut W,
+    distance: u64,
+    fields: [u8; 12],
+) -> Pin<Box<dyn Future<Output = Result<()>> + '_>>
+where
+    W: AsyncWrite + AsyncSeek + Unpin,
+{
+    Box::pin(async move {
+        writer.seek(SeekFrom::Current(-(distance as i64))).await?;
+        writer.write_all(&fields).await?;
+        writer.seek(SeekFrom::Current(distance as i64 - fields.len() as i64)).await?;
+
+        Ok(())
+    })
+}
 
 /// An entry writer which supports the streaming of data (ie. the writing of unknown size or data at runtime).
 ///
@@ -22,14 +60,24 @@ use tokio::io::{AsyncWrite, AsyncWriteExt};
 /// - This writer cannot be manually constructed; instead, use [`ZipFileWriter::write_entry_stream()`].
 /// - [`EntryStreamWriter::close()`] must be called before a stream writer goes out of scope.
 /// - Utilities for working with [`AsyncWrite`] values are provided by [`AsyncWriteExt`].
+/// - If a minimum chunk size has been set via [`ZipFileWriter::stream_chunk_size()`], written data is accumulated until
+///   it reaches that size before being passed to the encoder. Flushing this writer passes on any accumulated data.
+/// - Data is compressed (and encrypted) as it's written rather than once the entry is closed, so memory usage is bounded
+///   by the encoder's internal buffers regardless of the entry's size.
 pub struct EntryStreamWriter<'b, W: AsyncWrite + Unpin> {
-    writer: AsyncOffsetWriter<CompressedAsyncWriter<'b, W>>,
+    writer: AsyncOffsetWriter<BufWriter<CompressedAsyncWriter<'b, W>>>,
     cd_entries: &'b mut Vec<CentralDirectoryEntry>,
     entry: ZipEntry,
-    hasher: Hasher,
+    fields: HeaderFields,
+    hasher: Crc32,
     lfh: LocalFileHeader,
     lfh_offset: usize,
     data_offset: usize,
+    flush_entries: bool,
+    descriptor_signature: bool,
+    crc_stored: bool,
+    progress: Option<EntryProgressReporter>,
+    patcher: Option<LfhPatcher<W>>,
 }
 
 impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
@@ -37,40 +85,143 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
         writer: &'b mut ZipFileWriter<W>,
         entry: ZipEntry,
     ) -> Result<EntryStreamWriter<'b, W>> {
+        let encryption = EntryEncryption::new(&entry);
+        Self::from_raw_with_encryption(writer, entry, encryption).await
+    }
+
+    pub(crate) async fn from_raw_with_encryption(
+        writer: &'b mut ZipFileWriter<W>,
+        entry: ZipEntry,
+        mut encryption: EntryEncryption,
+    ) -> Result<EntryStreamWriter<'b, W>> {
+        crate::write::check_compression(&entry)?;
+
+        let patcher = writer.lfh_patcher.filter(|_| !encryption.check_from_crc());
+        let descriptor_signature = writer.descriptor_signature;
+        let descriptor_length = match (patcher, descriptor_signature) {
+            (Some(_), _) => 0,
+            (None, true) => (SIGNATURE_LENGTH + DATA_DESCRIPTOR_LENGTH + ZIP64_DESCRIPTOR_EXTRA) as u64,
+            (None, false) => (DATA_DESCRIPTOR_LENGTH + ZIP64_DESCRIPTOR_EXTRA) as u64,
+        };
+        let mut fields = HeaderFields::new(&entry);
+        encryption.push_extra_field(&mut fields);
+
+        let record_length = crate::write::cdh_length(&fields);
+        let progress = writer.progress_reporter(&fields);
+
+        // As the sizes aren't known upfront, entries using a data descriptor may grow past 4 GiB, so a zeroed ZIP64 extra
+        // field is reserved within their local file header (APPNOTE 4.3.9.2). This tells readers that the data
+        // descriptor holds eight-byte sizes. It's replaced within the central directory record once the sizes are known.
+        if patcher.is_none() {
+            fields.extra_field = remove_extra_field(&fields.extra_field, ZIP64_HEADER_ID);
+            push_extra_field(
+                &mut fields.extra_field,
+                ZIP64_HEADER_ID,
+                &crate::spec::extra_field::build_zip64(Some(0), Some(0), None),
+            );
+        }
+
+        let alignment = crate::write::alignment_field(&entry, &fields, writer.writer.offset());
+        let data_length =
+            crate::write::lfh_length(&fields) + alignment.len() as u64 + descriptor_length + encryption.overhead();
+        let remaining_size = writer.remaining_size(data_length, record_length)?;
+        let max_size = writer.max_size;
+        let flush_entries = writer.flush_entries;
+        let chunk_size = writer.stream_chunk_size;
+
         let lfh_offset = writer.writer.offset();
-        let lfh = EntryStreamWriter::write_lfh(writer, &entry).await?;
+        let lfh =
+            EntryStreamWriter::write_lfh(writer, &entry, &fields, &alignment, &encryption, patcher.is_none()).await?;
         let data_offset = writer.writer.offset();
+        let crc_stored = encryption.crc_stored();
+
+        // As the CRC32 value isn't known upfront, a ZipCrypto header's check byte is taken from the modification time.
+        writer.writer.write_all(&encryption.header((lfh.mod_time >> 8) as u8)?).await?;
 
+        // The compressed data's length isn't known upfront, so it's checked against the remaining size before each write
+        // (with the encryption overhead having already been accounted for).
         let cd_entries = &mut writer.cd_entries;
-        let writer = AsyncOffsetWriter::new(CompressedAsyncWriter::from_raw(&mut writer.writer, entry.compression()));
+        let mut data_writer = ShutdownIgnoredWriter::new(&mut writer.writer);
+        if let (Some(max_size), Some(remaining)) = (max_size, remaining_size) {
+            data_writer = data_writer.limit(remaining, max_size);
+        }
+
+        let data_writer = match encryption.into_writer_state() {
+            Some(state) => CipherWriter::Encrypted(data_writer, Box::new(state)),
+            None => CipherWriter::Plain(data_writer),
+        };
+        let writer = CompressedAsyncWriter::from_data_writer(data_writer, entry.compression());
+        let writer = AsyncOffsetWriter::new(BufWriter::with_capacity(chunk_size, writer));
+
+        Ok(EntryStreamWriter {
+            writer,
+            cd_entries,
+            entry,
+            fields,
+            lfh,
+            lfh_offset,
+            data_offset,
+            flush_entries,
+            descriptor_signature,
+            crc_stored,
+            progress,
+            patcher,
+            hasher: Crc32::new(),
+        })
+    }
+
+    /// Reports the progress made writing the entry's data so far, if a progress callback has been set.
+    fn report_progress(&self) {
+        if let Some(progress) = &self.progress {
+            let archive_size = self.writer.get_ref().get_ref().get_ref().offset();
+            let compressed = (archive_size - self.data_offset) as u64;
 
-        Ok(EntryStreamWriter { writer, cd_entries, entry, lfh, lfh_offset, data_offset, hasher: Hasher::new() })
+            progress.report(
+                EntryProgress { consumed: self.writer.offset() as u64, compressed },
+                archive_size as u64,
+                false,
+            );
+        }
     }
 
-    async fn write_lfh(writer: &'b mut ZipFileWriter<W>, entry: &ZipEntry) -> Result<LocalFileHeader> {
+    async fn write_lfh(
+        writer: &'b mut ZipFileWriter<W>,
+        entry: &ZipEntry,
+        fields: &HeaderFields,
+        alignment: &[u8],
+        encryption: &EntryEncryption,
+        data_descriptor: bool,
+    ) -> Result<LocalFileHeader> {
         let (mod_time, mod_date) = crate::spec::date::chrono_to_zip_time(entry.last_modification_date());
+        let version = match data_descriptor {
+            true => encryption.version_needed(entry).max(ZIP64_VERSION_NEEDED),
+            false => encryption.version_needed(entry),
+        };
 
         let lfh = LocalFileHeader {
             compressed_size: 0,
             uncompressed_size: 0,
-            compression: entry.compression().into(),
+            compression: encryption.compression(entry),
             crc: 0,
-            extra_field_length: entry.extra_field().len() as u16,
-            file_name_length: entry.filename().as_bytes().len() as u16,
+            extra_field_length: (fields.extra_field.len() + alignment.len()) as u16,
+            file_name_length: fields.filename.len() as u16,
             mod_time,
             mod_date,
-            version: crate::spec::version::as_needed_to_extract(entry),
+            version,
             flags: GeneralPurposeFlag {
-                data_descriptor: true,
-                encrypted: false,
-                filename_unicode: !entry.filename().is_ascii(),
+                data_descriptor,
+                strong_encryption: false,
+                encrypted: encryption.encrypted(),
+                compression_options: 0,
+                filename_unicode: fields.unicode,
             },
         };
 
         writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         writer.writer.write_all(&lfh.as_slice()).await?;
-        writer.writer.write_all(entry.filename().as_bytes()).await?;
-        writer.writer.write_all(entry.extra_field()).await?;
+        writer.writer.write_all(&fields.filename).await?;
+        writer.writer.write_all(&fields.extra_field).await?;
+        writer.writer.write_all(alignment).await?;
 
         Ok(lfh)
     }
@@ -79,45 +230,162 @@ impl<'b, W: AsyncWrite + Unpin> EntryStreamWriter<'b, W> {
     ///
     /// This includes:
     /// - Finalising the CRC32 hash value for the written data.
-    /// - Calculating the compressed and uncompressed byte sizes.
+    /// - Calculating the compressed and uncompressed byte sizes (switching to ZIP64 sizes should either exceed 4 GiB).
     /// - Constructing a central directory header.
     /// - Pushing that central directory header to the [`ZipFileWriter`]'s store.
     ///
     /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<()> {
-        self.writer.shutdown().await?;
+    pub async fn close(self) -> Result<()> {
+        self.finish().await.map(|_| ())
+    }
+
+    /// Consumes this entry writer and completes all closing tasks as per [`EntryStreamWriter::close()`], returning the
+    /// finalised entry.
+    ///
+    /// This allows the values computed whilst writing (eg. for building an external manifest or index) to be retrieved.
+    pub async fn finish(mut self) -> Result<FinishedEntry> {
+        self.writer.shutdown().await.map_err(unwrap_size_error)?;
+
+        // AE-2 encrypted entries store a zeroed CRC32 value, relying on the authentication code instead.
+        let crc = if self.crc_stored { self.hasher.finalize() } else { 0 };
+        let uncompressed_size = self.writer.offset() as u64;
+        let data_writer = self.writer.into_inner().into_inner().into_data_writer();
+
+        #[cfg(feature = "aes")]
+        let auth_code = data_writer.auth_code();
+        let inner_writer = data_writer.into_inner().into_inner();
+
+        #[cfg(feature = "aes")]
+        if let Some(auth_code) = auth_code {
+            inner_writer.write_all(&auth_code).await?;
+        }
+
+        let compressed_size = (inner_writer.offset() - self.data_offset) as u64;
+
+        // A value of `0xFFFFFFFF` indicates that the actual value is held within a ZIP64 extra field instead.
+        let zip64_sizes = compressed_size >= u32::MAX.into() || uncompressed_size >= u32::MAX.into();
+        let zip64_offset = self.lfh_offset as u64 >= u32::MAX.into();
+
+        match self.patcher {
+            Some(_) if zip64_sizes => {
+                return Err(ZipError::FeatureNotSupported("ZIP64 sizes within a patched local file header"));
+            }
+            Some(patch) => {
+                let mut sizes = [0; 12];
+                sizes[0..4].copy_from_slice(&crc.to_le_bytes());
+                sizes[4..8].copy_from_slice(&(compressed_size as u32).to_le_bytes());
+                sizes[8..12].copy_from_slice(&(uncompressed_size as u32).to_le_bytes());
+
+                // The inner writer is returned to its position once patched, so its tracked offset remains correct.
+                let distance = inner_writer.offset() - (self.lfh_offset + LFH_CRC_OFFSET);
+                patch(inner_writer.get_mut(), distance as u64, sizes).await?;
+            }
+            None => {
+                if self.descriptor_signature {
+                    inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
+                }
+                inner_writer.write_all(&crc.to_le_bytes()).await?;
 
-        let crc = self.hasher.finalize();
-        let uncompressed_size = self.writer.offset() as u32;
-        let inner_writer = self.writer.into_inner().into_inner();
-        let compressed_size = (inner_writer.offset() - self.data_offset) as u32;
+                // The local file header holds a ZIP64 extra field, so the sizes are always eight bytes each.
+                inner_writer.write_all(&compressed_size.to_le_bytes()).await?;
+                inner_writer.write_all(&uncompressed_size.to_le_bytes()).await?;
+            }
+        }
+
+        // The ZIP64 extra field reserved within the local file header is only kept if it's needed.
+        self.fields.extra_field = remove_extra_field(&self.fields.extra_field, ZIP64_HEADER_ID);
 
-        inner_writer.write_all(&crate::spec::consts::DATA_DESCRIPTOR_SIGNATURE.to_le_bytes()).await?;
-        inner_writer.write_all(&crc.to_le_bytes()).await?;
-        inner_writer.write_all(&compressed_size.to_le_bytes()).await?;
-        inner_writer.write_all(&uncompressed_size.to_le_bytes()).await?;
+        let mut v_needed = self.lfh.version;
+        if zip64_sizes || zip64_offset {
+            let data = crate::spec::extra_field::build_zip64(
+                zip64_sizes.then_some(uncompressed_size),
+                zip64_sizes.then_some(compressed_size),
+                zip64_offset.then_some(self.lfh_offset as u64),
+            );
+
+            push_extra_field(&mut self.fields.extra_field, ZIP64_HEADER_ID, &data);
+            v_needed = v_needed.max(ZIP64_VERSION_NEEDED);
+        }
 
         let cdh = CentralDirectoryRecord {
-            compressed_size,
-            uncompressed_size,
+            compressed_size: compressed_size.min(u32::MAX.into()) as u32,
+            uncompressed_size: uncompressed_size.min(u32::MAX.into()) as u32,
             crc,
-            v_made_by: crate::spec::version::as_made_by(),
-            v_needed: self.lfh.version,
+            v_made_by: crate::spec::version::as_made_by(&self.entry),
+            v_needed,
             compression: self.lfh.compression,
-            extra_field_length: self.lfh.extra_field_length,
+            extra_field_length: self.fields.extra_field.len() as u16,
             file_name_length: self.lfh.file_name_length,
-            file_comment_length: self.entry.comment().len() as u16,
+            file_comment_length: self.fields.comment.len() as u16,
             mod_time: self.lfh.mod_time,
             mod_date: self.lfh.mod_date,
             flags: self.lfh.flags,
             disk_start: 0,
             inter_attr: self.entry.internal_file_attribute(),
             exter_attr: self.entry.external_file_attribute(),
-            lh_offset: self.lfh_offset as u32,
+            lh_offset: (self.lfh_offset as u64).min(u32::MAX.into()) as u32,
         };
 
-        self.cd_entries.push(CentralDirectoryEntry { header: cdh, entry: self.entry });
-        Ok(())
+        self.cd_entries.push(CentralDirectoryEntry { header: cdh, fields: self.fields });
+
+        if let Some(progress) = self.progress {
+            let entry = EntryProgress { consumed: uncompressed_size, compressed: compressed_size };
+            progress.report(entry, inner_writer.offset() as u64, true);
+        }
+
+        if self.flush_entries {
+            inner_writer.flush().await?;
+        }
+
+        let mut entry = self.entry;
+        entry.crc32 = crc;
+        entry.compressed_size = compressed_size.min(u32::MAX.into()) as u32;
+        entry.uncompressed_size = uncompressed_size.min(u32::MAX.into()) as u32;
+
+        Ok(FinishedEntry { entry, header_offset: self.lfh_offset as u64, compressed_size, uncompressed_size })
+    }
+}
+
+/// An entry which has been fully written via an [`EntryStreamWriter`], returned by [`EntryStreamWriter::finish()`].
+#[derive(Clone)]
+pub struct FinishedEntry {
+    entry: ZipEntry,
+    header_offset: u64,
+    compressed_size: u64,
+    uncompressed_size: u64,
+}
+
+impl FinishedEntry {
+    /// Returns the entry as written, with its CRC32 value & sizes filled in.
+    ///
+    /// Sizes beyond 32 bits are saturated, as they are within the central directory record.
+    pub fn entry(&self) -> &ZipEntry {
+        &self.entry
+    }
+
+    /// Returns the CRC32 value stored for the entry (which is zero for AE-2 encrypted entries).
+    pub fn crc32(&self) -> u32 {
+        self.entry.crc32
+    }
+
+    /// Returns the offset of the entry's local file header from the start of the ZIP file.
+    pub fn header_offset(&self) -> u64 {
+        self.header_offset
+    }
+
+    /// Returns the number of bytes written for the entry's data (ie. compressed & encrypted).
+    pub fn compressed_size(&self) -> u64 {
+        self.compressed_size
+    }
+
+    /// Returns the number of bytes of uncompressed data written.
+    pub fn uncompressed_size(&self) -> u64 {
+        self.uncompressed_size
+    }
+
+    /// Consumes this value and returns the entry as written.
+    pub fn into_entry(self) -> ZipEntry {
+        self.entry
     }
 }
 
@@ -127,6 +395,7 @@ impl<'a, W: AsyncWrite + Unpin> AsyncWrite for EntryStreamWriter<'a, W> {
 
         if let Poll::Ready(Ok(written)) = poll {
             self.hasher.update(&buf[0..written]);
+            self.report_progress();
         }
 
         poll
@@ -140,3 +409,14 @@ impl<'a, W: AsyncWrite + Unpin> AsyncWrite for EntryStreamWriter<'a, W> {
         Pin::new(&mut self.writer).poll_shutdown(cx)
     }
 }
+
+/// Converts an IO error into a [`ZipError`], unwrapping it if it holds a [`ZipError::ArchiveSizeExceeded`] error.
+///
+/// The maximum size is enforced within the [`AsyncWrite`] implementation of the entry's data writer, so is raised as an
+/// IO error.
+fn unwrap_size_error(error: Error) -> ZipError {
+    match error.get_ref().and_then(|inner| inner.downcast_ref::<ZipError>()) {
+        Some(ZipError::ArchiveSizeExceeded(max_size)) => ZipError::ArchiveSizeExceeded(*max_size),
+        _ => ZipError::UpstreamReadError(error),
+    }
+}
@@ -2,71 +2,111 @@
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
 use crate::entry::ZipEntry;
-use crate::error::Result;
+use crate::error::{Result, ZipError};
 use crate::spec::compression::Compression;
 use crate::spec::header::{CentralDirectoryRecord, GeneralPurposeFlag, LocalFileHeader};
-use crate::write::{CentralDirectoryEntry, ZipFileWriter};
+use crate::write::encryption::EntryEncryption;
+use crate::write::progress::EntryProgress;
+use crate::write::{CentralDirectoryEntry, HeaderFields, ZipFileWriter};
 
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use std::io::Cursor;
 
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
 use async_compression::tokio::write;
-use crc32fast::Hasher;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 pub struct EntryWholeWriter<'b, 'c, W: AsyncWrite + Unpin> {
     writer: &'b mut ZipFileWriter<W>,
     entry: ZipEntry,
     data: &'c [u8],
+    encryption: EntryEncryption,
+    compressed: Option<Vec<u8>>,
 }
 
 impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
     pub fn from_raw(writer: &'b mut ZipFileWriter<W>, entry: ZipEntry, data: &'c [u8]) -> Self {
-        Self { writer, entry, data }
+        let encryption = EntryEncryption::new(&entry);
+        Self { writer, entry, data, encryption, compressed: None }
     }
 
-    pub async fn write(self) -> Result<()> {
-        let mut _compressed_data: Option<Vec<u8>> = None;
+    /// Sets the entry's already compressed data (if any), so that it isn't compressed again when written.
+    pub(crate) fn compressed(mut self, data: Option<Vec<u8>>) -> Self {
+        self.compressed = data;
+        self
+    }
+
+    /// Sets the encryption applied to the entry's data.
+    #[cfg(feature = "aes")]
+    pub(crate) fn encryption(mut self, encryption: EntryEncryption) -> Self {
+        self.encryption = encryption;
+        self
+    }
+
+    pub async fn write(mut self) -> Result<()> {
+        crate::write::check_compression(&self.entry)?;
+
+        let mut _compressed_data: Option<Vec<u8>> = self.compressed.take();
         let compressed_data = match self.entry.compression() {
             Compression::Stored => self.data,
             #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
-            _ => {
-                _compressed_data =
-                    Some(compress(self.entry.compression(), self.data, self.entry.compression_level).await);
+            compression if !compression.is_legacy() => {
+                if _compressed_data.is_none() {
+                    _compressed_data = Some(compress(compression, self.data, self.entry.compression_level).await);
+                }
                 _compressed_data.as_ref().unwrap()
             }
+            // Legacy methods are rejected above, but still need covering when no other codec is enabled.
+            #[allow(unreachable_patterns)]
+            compression => return Err(ZipError::CompressionNotSupported(compression.into())),
         };
 
+        let mut fields = HeaderFields::new(&self.entry);
+        self.encryption.push_extra_field(&mut fields);
+
+        let compression = self.encryption.compression(&self.entry);
+        let crc = self.encryption.crc(crate::spec::crc::crc32(self.data));
+        let version = self.encryption.version_needed(&self.entry);
+        let encrypted = self.encryption.encrypted();
+        let alignment = crate::write::alignment_field(&self.entry, &fields, self.writer.writer.offset());
+        let data_length = crate::write::lfh_length(&fields)
+            + (alignment.len() + compressed_data.len()) as u64
+            + self.encryption.overhead();
+        self.writer.remaining_size(data_length, crate::write::cdh_length(&fields))?;
+        let progress = self.writer.progress_reporter(&fields);
+
         let (mod_time, mod_date) = crate::spec::date::chrono_to_zip_time(self.entry.last_modification_date());
+        let compressed_data = self.encryption.seal(compressed_data, (crc >> 24) as u8)?;
 
         let lf_header = LocalFileHeader {
             compressed_size: compressed_data.len() as u32,
             uncompressed_size: self.data.len() as u32,
-            compression: self.entry.compression().into(),
-            crc: compute_crc(self.data),
-            extra_field_length: self.entry.extra_field().len() as u16,
-            file_name_length: self.entry.filename().as_bytes().len() as u16,
+            compression,
+            crc,
+            extra_field_length: (fields.extra_field.len() + alignment.len()) as u16,
+            file_name_length: fields.filename.len() as u16,
             mod_time,
             mod_date,
-            version: crate::spec::version::as_needed_to_extract(&self.entry),
+            version,
             flags: GeneralPurposeFlag {
                 data_descriptor: false,
-                encrypted: false,
-                filename_unicode: !self.entry.filename().is_ascii(),
+                strong_encryption: false,
+                encrypted,
+                compression_options: 0,
+                filename_unicode: fields.unicode,
             },
         };
 
         let header = CentralDirectoryRecord {
-            v_made_by: crate::spec::version::as_made_by(),
+            v_made_by: crate::spec::version::as_made_by(&self.entry),
             v_needed: lf_header.version,
             compressed_size: lf_header.compressed_size,
             uncompressed_size: lf_header.uncompressed_size,
             compression: lf_header.compression,
             crc: lf_header.crc,
-            extra_field_length: lf_header.extra_field_length,
+            extra_field_length: fields.extra_field.len() as u16,
             file_name_length: lf_header.file_name_length,
-            file_comment_length: self.entry.comment().len() as u16,
+            file_comment_length: fields.comment.len() as u16,
             mod_time: lf_header.mod_time,
             mod_date: lf_header.mod_date,
             flags: lf_header.flags,
@@ -78,18 +118,24 @@ impl<'b, 'c, W: AsyncWrite + Unpin> EntryWholeWriter<'b, 'c, W> {
 
         self.writer.writer.write_all(&crate::spec::consts::LFH_SIGNATURE.to_le_bytes()).await?;
         self.writer.writer.write_all(&lf_header.as_slice()).await?;
-        self.writer.writer.write_all(self.entry.filename().as_bytes()).await?;
-        self.writer.writer.write_all(self.entry.extra_field()).await?;
-        self.writer.writer.write_all(compressed_data).await?;
+        self.writer.writer.write_all(&fields.filename).await?;
+        self.writer.writer.write_all(&fields.extra_field).await?;
+        self.writer.writer.write_all(&alignment).await?;
+        self.writer.writer.write_all(&compressed_data).await?;
+
+        self.writer.cd_entries.push(CentralDirectoryEntry { header, fields });
 
-        self.writer.cd_entries.push(CentralDirectoryEntry { header, entry: self.entry });
+        if let Some(progress) = progress {
+            let entry = EntryProgress { consumed: self.data.len() as u64, compressed: compressed_data.len() as u64 };
+            progress.report(entry, self.writer.writer.offset() as u64, true);
+        }
 
-        Ok(())
+        self.writer.entry_written().await
     }
 }
 
 #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
-async fn compress(compression: Compression, data: &[u8], level: async_compression::Level) -> Vec<u8> {
+pub(crate) async fn compress(compression: Compression, data: &[u8], level: async_compression::Level) -> Vec<u8> {
     // TODO: Reduce reallocations of Vec by making a lower-bound estimate of the length reduction and
     // pre-initialising the Vec to that length. Then truncate() to the actual number of bytes written.
     match compression {
@@ -131,9 +177,3 @@ async fn compress(compression: Compression, data: &[u8], level: async_compressio
         _ => unreachable!(),
     }
 }
-
-fn compute_crc(data: &[u8]) -> u32 {
-    let mut hasher = Hasher::new();
-    hasher.update(data);
-    hasher.finalize()
-}
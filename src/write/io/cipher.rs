@@ -0,0 +1,138 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+#[cfg(feature = "aes")]
+use crate::spec::aes::{AesKeys, AES_AUTH_CODE_LENGTH};
+use crate::spec::zipcrypto::ZipCryptoKeys;
+
+use std::io::Error;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// A wrapping writer which encrypts data written via [`AsyncWrite`], if the entry is to be encrypted.
+///
+/// Encrypted data is buffered until it's been fully written to the inner writer, so that the cipher's state only ever
+/// advances once per byte accepted from the caller. Any buffered data is written out when flushing or shutting down.
+pub enum CipherWriter<W: AsyncWrite + Unpin> {
+    Plain(W),
+    Encrypted(W, Box<CipherWriterState>),
+}
+
+/// The cipher with which an entry's data is encrypted as it's written.
+pub(crate) enum WriteCipher {
+    ZipCrypto(ZipCryptoKeys),
+    #[cfg(feature = "aes")]
+    Aes(AesKeys),
+}
+
+impl WriteCipher {
+    fn encrypt(&mut self, buffer: &mut [u8]) {
+        match self {
+            WriteCipher::ZipCrypto(keys) => keys.encrypt(buffer),
+            #[cfg(feature = "aes")]
+            WriteCipher::Aes(keys) => keys.encrypt(buffer),
+        }
+    }
+}
+
+/// The state required to encrypt the data of an entry, including any encrypted data yet to be written.
+pub struct CipherWriterState {
+    cipher: WriteCipher,
+    pending: Vec<u8>,
+    position: usize,
+}
+
+impl CipherWriterState {
+    pub(crate) fn new(cipher: WriteCipher) -> Self {
+        Self { cipher, pending: Vec::new(), position: 0 }
+    }
+}
+
+impl<W: AsyncWrite + Unpin> CipherWriter<W> {
+    pub fn get_ref(&self) -> &W {
+        match self {
+            CipherWriter::Plain(inner) => inner,
+            CipherWriter::Encrypted(inner, _) => inner,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        match self {
+            CipherWriter::Plain(inner) => inner,
+            CipherWriter::Encrypted(inner, _) => inner,
+        }
+    }
+
+    /// Returns the authentication code of the encrypted data (if the data was encrypted using AES).
+    ///
+    /// This should only be called once the writer has been flushed or shut down.
+    #[cfg(feature = "aes")]
+    pub(crate) fn auth_code(&self) -> Option<[u8; AES_AUTH_CODE_LENGTH]> {
+        match self {
+            CipherWriter::Encrypted(_, state) => match &state.cipher {
+                WriteCipher::Aes(keys) => Some(keys.auth_code()),
+                WriteCipher::ZipCrypto(_) => None,
+            },
+            CipherWriter::Plain(_) => None,
+        }
+    }
+}
+
+/// Writes any buffered encrypted data to the inner writer.
+fn poll_write_pending<W>(inner: &mut W, state: &mut CipherWriterState, cx: &mut Context) -> Poll<Result<(), Error>>
+where
+    W: AsyncWrite + Unpin,
+{
+    while state.position < state.pending.len() {
+        let written = ready!(Pin::new(&mut *inner).poll_write(cx, &state.pending[state.position..]))?;
+
+        if written == 0 {
+            return Poll::Ready(Err(std::io::ErrorKind::WriteZero.into()));
+        }
+
+        state.position += written;
+    }
+
+    state.pending.clear();
+    state.position = 0;
+
+    Poll::Ready(Ok(()))
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for CipherWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<std::result::Result<usize, Error>> {
+        match self.get_mut() {
+            CipherWriter::Plain(inner) => Pin::new(inner).poll_write(cx, buf),
+            CipherWriter::Encrypted(inner, state) => {
+                ready!(poll_write_pending(inner, state, cx))?;
+
+                state.pending.extend_from_slice(buf);
+                state.cipher.encrypt(&mut state.pending);
+
+                Poll::Ready(Ok(buf.len()))
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        match self.get_mut() {
+            CipherWriter::Plain(inner) => Pin::new(inner).poll_flush(cx),
+            CipherWriter::Encrypted(inner, state) => {
+                ready!(poll_write_pending(inner, state, cx))?;
+                Pin::new(inner).poll_flush(cx)
+            }
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<std::result::Result<(), Error>> {
+        match self.get_mut() {
+            CipherWriter::Plain(inner) => Pin::new(inner).poll_shutdown(cx),
+            CipherWriter::Encrypted(inner, state) => {
+                ready!(poll_write_pending(inner, state, cx))?;
+                Pin::new(inner).poll_shutdown(cx)
+            }
+        }
+    }
+}
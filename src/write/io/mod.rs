@@ -1,4 +1,6 @@
 // Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
 // MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
 
+pub(crate) mod cipher;
 pub(crate) mod offset;
+pub(crate) mod tee;
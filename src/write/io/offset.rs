@@ -28,7 +28,12 @@ where
 {
     /// Constructs a new wrapper from an inner [`AsyncWrite`] writer.
     pub fn new(inner: W) -> Self {
-        Self { inner, offset: 0 }
+        Self::with_offset(inner, 0)
+    }
+
+    /// Constructs a new wrapper from an inner [`AsyncWrite`] writer, starting from the provided byte offset.
+    pub fn with_offset(inner: W, offset: usize) -> Self {
+        Self { inner, offset }
     }
 
     /// Returns the current byte offset.
@@ -36,6 +41,23 @@ where
         self.offset
     }
 
+    /// Sets the current byte offset, eg. once the inner writer has been seeked.
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+
+    /// Returns a reference to the inner [`AsyncWrite`] writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner [`AsyncWrite`] writer.
+    ///
+    /// Any data written directly to the inner writer won't be reflected in the tracked offset.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
     /// Consumes this wrapper and returns the inner [`AsyncWrite`] writer.
     pub fn into_inner(self) -> W {
         self.inner
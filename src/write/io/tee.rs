@@ -0,0 +1,135 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use std::io::{Error, ErrorKind};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+/// A writer which mirrors all data written to it into two [`AsyncWrite`] sinks.
+///
+/// This allows a ZIP file to be delivered to two destinations at once (eg. a local file and a network upload) without
+/// it being produced twice or buffered in full. Each write is accepted by the first sink before being copied into a
+/// small internal buffer, which must be drained into the second sink before any further data is accepted. As such,
+/// the writer only progresses as fast as the slower of the two sinks, and at most a single write's worth of data is
+/// held in memory. Flushing or shutting down this writer does so for both sinks once the buffer has been drained.
+///
+/// ### Example
+/// ```no_run
+/// # use async_zip::{Compression, ZipEntryBuilder, write::{TeeWriter, ZipFileWriter}};
+/// # use async_zip::error::Result;
+/// # use tokio::fs::File;
+/// # use tokio::io::AsyncWriteExt;
+/// #
+/// # async fn run(upload: tokio::io::DuplexStream) -> Result<()> {
+/// let file = File::create("./foo.zip").await?;
+/// let mut writer = ZipFileWriter::new(TeeWriter::new(file, upload));
+///
+/// let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+/// writer.write_entry_whole(entry, b"This is an example file.").await?;
+///
+/// let mut tee = writer.close().await?;
+/// tee.shutdown().await?;
+/// let (file, upload) = tee.into_inner();
+/// #   Ok(())
+/// # }
+/// ```
+pub struct TeeWriter<A, B> {
+    first: A,
+    second: B,
+    pending: Vec<u8>,
+    drained: usize,
+}
+
+impl<A, B> TeeWriter<A, B>
+where
+    A: AsyncWrite + Unpin,
+    B: AsyncWrite + Unpin,
+{
+    /// Constructs a new writer which mirrors data into the two provided sinks.
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second, pending: Vec::new(), drained: 0 }
+    }
+
+    /// Returns references to the two inner sinks.
+    pub fn get_ref(&self) -> (&A, &B) {
+        (&self.first, &self.second)
+    }
+
+    /// Returns mutable references to the two inner sinks.
+    ///
+    /// Any data written directly to either sink won't be mirrored into the other.
+    pub fn get_mut(&mut self) -> (&mut A, &mut B) {
+        (&mut self.first, &mut self.second)
+    }
+
+    /// Consumes this writer and returns the two inner sinks.
+    ///
+    /// Any data accepted by the first sink but not yet written to the second is discarded, so this writer should be
+    /// flushed or shut down beforehand.
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+
+    /// Writes any data accepted by the first sink into the second.
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        while self.drained < self.pending.len() {
+            let written = ready!(Pin::new(&mut self.second).poll_write(cx, &self.pending[self.drained..]))?;
+            if written == 0 {
+                return Poll::Ready(Err(Error::from(ErrorKind::WriteZero)));
+            }
+
+            self.drained += written;
+        }
+
+        self.pending.clear();
+        self.drained = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<A, B> AsyncWrite for TeeWriter<A, B>
+where
+    A: AsyncWrite + Unpin,
+    B: AsyncWrite + Unpin,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+
+        let written = ready!(Pin::new(&mut this.first).poll_write(cx, buf))?;
+        this.pending.extend_from_slice(&buf[..written]);
+
+        // The data has been accepted regardless, but start writing it to the second sink straight away.
+        if let Poll::Ready(Err(err)) = this.poll_drain(cx) {
+            return Poll::Ready(Err(err));
+        }
+
+        Poll::Ready(Ok(written))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+
+        let first = Pin::new(&mut this.first).poll_flush(cx)?;
+        let second = Pin::new(&mut this.second).poll_flush(cx)?;
+        ready!(first);
+        ready!(second);
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+
+        let first = Pin::new(&mut this.first).poll_shutdown(cx)?;
+        let second = Pin::new(&mut this.second).poll_shutdown(cx)?;
+        ready!(first);
+        ready!(second);
+
+        Poll::Ready(Ok(()))
+    }
+}
@@ -50,24 +50,170 @@
 //! # }
 //! ```
 
+pub(crate) mod append;
+#[cfg(feature = "fs")]
+pub(crate) mod archive;
+#[cfg(feature = "http-body")]
+pub(crate) mod body;
 pub(crate) mod compressed_writer;
+pub(crate) mod concat;
+pub(crate) mod encryption;
+pub(crate) mod entry_raw;
+pub(crate) mod entry_reader;
 pub(crate) mod entry_stream;
 pub(crate) mod entry_whole;
 pub(crate) mod io;
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+pub(crate) mod parallel;
+pub(crate) mod policy;
+pub mod progress;
+pub(crate) mod summary;
 
-pub use entry_stream::EntryStreamWriter;
+#[cfg(feature = "fs")]
+pub use archive::{archive_dir, ArchiveOptions};
+#[cfg(feature = "http-body")]
+pub use body::{body, ZipBody};
+pub use concat::concat;
+pub use entry_stream::{EntryStreamWriter, FinishedEntry};
+pub use io::tee::TeeWriter;
+pub use policy::CompressionPolicy;
+pub use summary::ArchiveSummary;
 
-use crate::entry::ZipEntry;
-use crate::error::Result;
+/// The smallest valid ZIP file: an end of central directory record for an archive containing no entries.
+///
+/// This is byte-for-byte what [`ZipFileWriter::close()`] emits when no entries were written (and no comment was set),
+/// and so can be used directly by tools which emit placeholder archives.
+pub const EMPTY_ARCHIVE: [u8; SIGNATURE_LENGTH + EOCDR_LENGTH] =
+    [0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+
+use crate::entry::{ZipEntry, DOS_DIRECTORY, S_IFDIR, S_IFMT};
+use crate::error::{Result, ZipError};
+use crate::read::progress::ProgressCallback;
+use crate::spec::attribute::AttributeCompatibility;
+use crate::spec::compression::Compression;
+use crate::spec::consts::{CDH_LENGTH, EOCDR_LENGTH, LFH_LENGTH, SIGNATURE_LENGTH, ZIP64_EOCDR_LENGTH};
+use crate::spec::encoding::encode_cp437;
+use crate::spec::extra_field::{build_alignment, ALIGNMENT_HEADER_ID};
+use crate::spec::extra_field::{build_extended_timestamp, build_unicode_path, push_extra_field, remove_extra_field};
+use crate::spec::extra_field::{EXTENDED_TIMESTAMP_HEADER_ID, UNICODE_PATH_HEADER_ID};
 use crate::spec::header::{CentralDirectoryRecord, EndOfCentralDirectoryHeader};
+use crate::spec::header::{Zip64EndOfCentralDirectoryLocator, Zip64EndOfCentralDirectoryRecord};
+use crate::spec::version::{SPEC_VERSION_MADE_BY, ZIP64_VERSION_NEEDED};
+#[cfg(feature = "aes")]
+use encryption::EntryEncryption;
+use entry_raw::EntryRawWriter;
+use entry_reader::EntryReaderWriter;
+use entry_stream::LfhPatcher;
 use entry_whole::EntryWholeWriter;
 use io::offset::AsyncOffsetWriter;
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+use parallel::CompressionJob;
+use progress::{EntryProgressReporter, WriteProgress};
+
+#[cfg(feature = "fs")]
+use std::borrow::BorrowMut;
+use std::cmp::Ordering;
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+use std::collections::VecDeque;
+#[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+use std::sync::Arc;
+
+#[cfg(feature = "fs")]
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufReader, SeekFrom};
+#[cfg(feature = "cancel")]
+use tokio_util::sync::CancellationToken;
 
-use tokio::io::{AsyncWrite, AsyncWriteExt};
+/// A comparison of two filenames, determining the order of their central directory records.
+type CentralDirectoryOrder = dyn Fn(&str, &str) -> Ordering + Send + Sync;
+
+/// The point at which a write group was opened, to which the writer is returned if the group is rolled back.
+struct WriteGroup {
+    offset: usize,
+    entries: usize,
+}
 
 pub(crate) struct CentralDirectoryEntry {
     pub header: CentralDirectoryRecord,
-    pub entry: ZipEntry,
+    pub fields: HeaderFields,
+}
+
+/// The filename, extra field, and comment of an entry, encoded as they're written to its headers.
+pub(crate) struct HeaderFields {
+    pub filename: Vec<u8>,
+    pub extra_field: Vec<u8>,
+    pub comment: Vec<u8>,
+    pub unicode: bool,
+}
+
+impl HeaderFields {
+    /// Encodes the header fields of an entry.
+    ///
+    /// Non-ASCII filenames & comments are encoded as UTF-8 with the language encoding flag set unless the entry opts
+    /// into an Info-ZIP Unicode Path extra field, in which case they're encoded as IBM Code Page 437 instead.
+    pub(crate) fn new(entry: &ZipEntry) -> Self {
+        let ascii = entry.filename().is_ascii() && entry.comment().is_ascii();
+        let mut extra_field = entry.extra_field().to_vec();
+
+        if entry.extended_timestamp {
+            let timestamp = build_extended_timestamp(entry.last_modification_date().timestamp());
+            extra_field = remove_extra_field(&extra_field, EXTENDED_TIMESTAMP_HEADER_ID);
+            push_extra_field(&mut extra_field, EXTENDED_TIMESTAMP_HEADER_ID, &timestamp);
+        }
+
+        if ascii || !entry.unicode_path_extra_field {
+            return HeaderFields {
+                filename: entry.filename().as_bytes().to_vec(),
+                extra_field,
+                comment: entry.comment().as_bytes().to_vec(),
+                unicode: !ascii,
+            };
+        }
+
+        let filename = encode_cp437(entry.filename());
+        extra_field = remove_extra_field(&extra_field, UNICODE_PATH_HEADER_ID);
+
+        if !entry.filename().is_ascii() {
+            let unicode_path = build_unicode_path(entry.filename(), &filename);
+            push_extra_field(&mut extra_field, UNICODE_PATH_HEADER_ID, &unicode_path);
+        }
+
+        HeaderFields { filename, extra_field, comment: encode_cp437(entry.comment()), unicode: false }
+    }
+}
+
+/// Returns an error if the entry's compression method is one which is only supported when reading.
+pub(crate) fn check_compression(entry: &ZipEntry) -> Result<()> {
+    match entry.compression().is_legacy() {
+        true => Err(ZipError::CompressionNotSupported(entry.compression().into())),
+        false => Ok(()),
+    }
+}
+
+/// Returns the length of an entry's local file header (excluding any data descriptor).
+pub(crate) fn lfh_length(fields: &HeaderFields) -> u64 {
+    (SIGNATURE_LENGTH + LFH_LENGTH + fields.filename.len() + fields.extra_field.len()) as u64
+}
+
+/// Returns the alignment extra field to append to an entry's local file header extra field so that its data begins at
+/// a multiple of the entry's alignment, or an empty field if no alignment has been set.
+pub(crate) fn alignment_field(entry: &ZipEntry, fields: &HeaderFields, lfh_offset: usize) -> Vec<u8> {
+    let alignment = entry.alignment as usize;
+    let mut field = Vec::new();
+
+    if alignment > 1 {
+        // The field's header & alignment value occupy six bytes, with the zeroed padding following them.
+        let data_offset = lfh_offset + lfh_length(fields) as usize + 6;
+        let padding = (alignment - data_offset % alignment) % alignment;
+        push_extra_field(&mut field, ALIGNMENT_HEADER_ID, &build_alignment(entry.alignment, padding));
+    }
+
+    field
+}
+
+/// Returns the length of an entry's central directory record.
+pub(crate) fn cdh_length(fields: &HeaderFields) -> u64 {
+    (SIGNATURE_LENGTH + CDH_LENGTH + fields.filename.len() + fields.extra_field.len() + fields.comment.len()) as u64
 }
 
 /// A ZIP file writer which acts over AsyncWrite implementers.
@@ -78,27 +224,470 @@ pub struct ZipFileWriter<W: AsyncWrite + Unpin> {
     pub(crate) writer: AsyncOffsetWriter<W>,
     pub(crate) cd_entries: Vec<CentralDirectoryEntry>,
     comment_opt: Option<String>,
+    max_size: Option<u64>,
+    flush_entries: bool,
+    descriptor_signature: bool,
+    stream_chunk_size: usize,
+    compression_policy: Option<CompressionPolicy>,
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    offload_compression: bool,
+    #[cfg(feature = "cancel")]
+    cancellation_token: Option<CancellationToken>,
+    progress: Option<ProgressCallback<WriteProgress>>,
+    cd_order: Option<Box<CentralDirectoryOrder>>,
+    group: Option<WriteGroup>,
+    stale_end: u64,
+    lfh_patcher: Option<LfhPatcher<W>>,
 }
 
 impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
-    /// Construct a new ZIP file writer from a mutable reference to a writer.
+    /// Construct a new ZIP file writer from a writer.
+    ///
+    /// The writer may be owned (allowing this ZIP writer to be moved into a task, with the writer returned by
+    /// [`ZipFileWriter::close()`]) or a mutable reference.
     pub fn new(writer: W) -> Self {
-        Self { writer: AsyncOffsetWriter::new(writer), cd_entries: Vec::new(), comment_opt: None }
+        Self {
+            writer: AsyncOffsetWriter::new(writer),
+            cd_entries: Vec::new(),
+            comment_opt: None,
+            max_size: None,
+            flush_entries: false,
+            descriptor_signature: true,
+            stream_chunk_size: 0,
+            compression_policy: None,
+            #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+            offload_compression: false,
+            #[cfg(feature = "cancel")]
+            cancellation_token: None,
+            progress: None,
+            cd_order: None,
+            group: None,
+            stale_end: 0,
+            lfh_patcher: None,
+        }
     }
 
     /// Write a new ZIP entry of known size and data.
     pub async fn write_entry_whole<E: Into<ZipEntry>>(&mut self, entry: E, data: &[u8]) -> Result<()> {
-        EntryWholeWriter::from_raw(self, entry.into(), data).write().await
+        self.check_cancelled()?;
+        let entry = self.apply_compression_policy(entry.into(), data);
+        let compressed = self.offloaded_compression(&entry, data).await?;
+
+        EntryWholeWriter::from_raw(self, entry, data).compressed(compressed).write().await
+    }
+
+    /// Write several new ZIP entries of known size and data, compressing up to `workers` of them concurrently.
+    ///
+    /// Whilst entries are written in the order provided, the data of those which follow is compressed ahead of time on
+    /// dedicated worker threads (rather than runtime tasks, so no particular runtime is required). This improves
+    /// throughput on multi-core machines when writing many entries, whilst the resulting ZIP file is identical to one
+    /// written by calling [`ZipFileWriter::write_entry_whole()`] for each entry in turn. A value of zero for `workers`
+    /// is treated as one.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// # #[cfg(feature = "deflate")]
+    /// # {
+    /// # use async_zip::{Compression, ZipEntryBuilder, write::ZipFileWriter};
+    /// # use async_zip::error::Result;
+    /// #
+    /// # async fn run() -> Result<()> {
+    /// let mut writer = ZipFileWriter::new(Vec::new());
+    /// let entries = (0..64).map(|index| {
+    ///     let entry = ZipEntryBuilder::new(format!("{}.txt", index), Compression::Deflate);
+    ///     (entry, format!("This is file number {}.", index).repeat(1024))
+    /// });
+    ///
+    /// let workers = std::thread::available_parallelism().map_or(1, usize::from);
+    /// writer.write_entries_whole_parallel(entries, workers).await?;
+    /// writer.close().await?;
+    /// #   Ok(())
+    /// # }
+    /// # }
+    /// ```
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub async fn write_entries_whole_parallel<I, E, D>(&mut self, entries: I, workers: usize) -> Result<()>
+    where
+        I: IntoIterator<Item = (E, D)>,
+        E: Into<ZipEntry>,
+        D: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let mut entries = entries.into_iter();
+        let mut pending = VecDeque::new();
+
+        loop {
+            // Keep each worker busy by queuing further entries as earlier ones are written.
+            while pending.len() < workers.max(1) {
+                let Some((entry, data)) = entries.next() else { break };
+                let data = Arc::new(data);
+                let entry = self.apply_compression_policy(entry.into(), (*data).as_ref());
+                check_compression(&entry)?;
+
+                let job = match entry.compression() {
+                    Compression::Stored => None,
+                    _ => Some(CompressionJob::spawn(&entry, data.clone())),
+                };
+                pending.push_back((entry, data, job));
+            }
+
+            let Some((entry, data, job)) = pending.pop_front() else { break };
+            self.check_cancelled()?;
+
+            let writer = EntryWholeWriter::from_raw(self, entry, (*data).as_ref());
+            match job {
+                Some(job) => writer.compressed(Some(job.await)).write().await?,
+                None => writer.write().await?,
+            }
+        }
+
+        Ok(())
     }
 
     /// Write an entry of unknown size and data via streaming (ie. using a data descriptor).
+    ///
+    /// If the writer can be seeked, the entry's local file header may instead be patched once its data has been written
+    /// (see [`ZipFileWriter::patch_local_headers()`]).
     pub async fn write_entry_stream<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<EntryStreamWriter<'_, W>> {
+        self.check_cancelled()?;
         EntryStreamWriter::from_raw(self, entry.into()).await
     }
 
-    /// Set the ZIP file comment.
-    pub fn comment(&mut self, comment: String) {
+    /// Write a new ZIP entry of known size and data, encrypted via WinZip AE-2 (using AES-256) with the provided
+    /// password.
+    ///
+    /// AE-2 entries store a zeroed CRC32 value, with the integrity of their data instead protected by an authentication
+    /// code. As with all WinZip AES entries, only the entry's data is encrypted (not its filename or other metadata).
+    #[cfg(feature = "aes")]
+    pub async fn write_entry_whole_aes<E>(&mut self, entry: E, data: &[u8], password: &[u8]) -> Result<()>
+    where
+        E: Into<ZipEntry>,
+    {
+        self.check_cancelled()?;
+        let entry = self.apply_compression_policy(entry.into(), data);
+        let encryption = EntryEncryption::aes(&entry, password)?;
+        let compressed = self.offloaded_compression(&entry, data).await?;
+
+        EntryWholeWriter::from_raw(self, entry, data).compressed(compressed).encryption(encryption).write().await
+    }
+
+    /// Write an entry of unknown size and data via streaming (ie. using a data descriptor), encrypted via WinZip AE-2
+    /// (using AES-256) with the provided password.
+    ///
+    /// See [`ZipFileWriter::write_entry_whole_aes()`] for more information.
+    #[cfg(feature = "aes")]
+    pub async fn write_entry_stream_aes<E>(&mut self, entry: E, password: &[u8]) -> Result<EntryStreamWriter<'_, W>>
+    where
+        E: Into<ZipEntry>,
+    {
+        self.check_cancelled()?;
+        let entry = entry.into();
+        let encryption = EntryEncryption::aes(&entry, password)?;
+
+        EntryStreamWriter::from_raw_with_encryption(self, entry, encryption).await
+    }
+
+    /// Write an entry whose data is read from the provided reader, without using a data descriptor.
+    ///
+    /// The local file header is first written with placeholder sizes & CRC32, and is then backpatched once all data
+    /// has been read. As a result, neither the data nor its size need to be known upfront but the writer must be
+    /// seekable.
+    pub async fn write_entry_reader<E, R>(&mut self, entry: E, reader: &mut R) -> Result<()>
+    where
+        W: AsyncSeek,
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        self.check_cancelled()?;
+        EntryReaderWriter::from_raw(self, entry.into(), reader).write().await
+    }
+
+    /// Write an entry whose data is read from the provided reader, using a data descriptor.
+    ///
+    /// Data is compressed and its CRC32 value computed as it's read, so neither the data nor its size need to be known
+    /// upfront, and the data is never buffered in full. Unlike [`ZipFileWriter::write_entry_reader()`], the writer
+    /// needn't be seekable. Returns the number of (uncompressed) bytes read.
+    pub async fn write_entry_copy<E, R>(&mut self, entry: E, mut reader: R) -> Result<u64>
+    where
+        E: Into<ZipEntry>,
+        R: AsyncRead + Unpin,
+    {
+        let mut stream = self.write_entry_stream(entry).await?;
+        let copied = tokio::io::copy(&mut reader, &mut stream).await?;
+
+        stream.close().await?;
+        Ok(copied)
+    }
+
+    /// Write an entry whose raw (ie. already compressed) data is read from the provided reader.
+    ///
+    /// The data is copied as-is, with the entry's existing compression method, CRC32 value, and sizes written to its
+    /// headers. Paired with the readers' `entry_raw()` methods, this allows entries to be copied between archives (and
+    /// renamed or otherwise modified) without being decompressed and recompressed. Exactly as many bytes as the entry's
+    /// compressed size are read.
+    pub async fn copy_entry_raw<R>(&mut self, entry: &ZipEntry, raw: R) -> Result<()>
+    where
+        R: AsyncRead + Unpin,
+    {
+        self.check_cancelled()?;
+        EntryRawWriter::from_raw(self, entry, raw).write().await
+    }
+
+    /// Write an entry whose data has already been compressed (via the entry's compression method), alongside the CRC32
+    /// value & size of its uncompressed data.
+    ///
+    /// The data is written verbatim, with the provided values written to the entry's headers as-is. This allows entries
+    /// to be proxied from other sources (eg. precomputed Deflate blobs or deduplicating stores) without being
+    /// decompressed and recompressed. The values aren't verified against the data, so must be accurate for the entry
+    /// to be extractable.
+    pub async fn write_entry_precompressed<E>(
+        &mut self,
+        entry: E,
+        crc: u32,
+        uncompressed_size: u64,
+        data: &[u8],
+    ) -> Result<()>
+    where
+        E: Into<ZipEntry>,
+    {
+        let mut entry = entry.into();
+        entry.crc32 = crc;
+        entry.compressed_size =
+            data.len().try_into().map_err(|_| ZipError::FeatureNotSupported("ZIP64 precompressed entries"))?;
+        entry.uncompressed_size =
+            uncompressed_size.try_into().map_err(|_| ZipError::FeatureNotSupported("ZIP64 precompressed entries"))?;
+
+        self.copy_entry_raw(&entry, data).await
+    }
+
+    /// Write a stored padding entry so that the next entry's local file header begins at the provided offset.
+    ///
+    /// Offsets are relative to the position of the inner writer when this ZIP writer was constructed. The padding
+    /// entry's data consists of zeroed bytes sized to fill the gap, so the offset must be at or beyond the end of the
+    /// padding entry's local file header. The entry's compression method and any encryption are ignored, and it's
+    /// always stored as-is (bypassing any compression policy).
+    pub async fn write_padding_entry<E: Into<ZipEntry>>(&mut self, entry: E, offset: u64) -> Result<()> {
+        self.check_cancelled()?;
+        let mut entry = entry.into();
+        entry.compression = Compression::Stored;
+        entry.zipcrypto_password = None;
+        entry.alignment = 0;
+
+        let header_end = self.writer.offset() as u64 + lfh_length(&HeaderFields::new(&entry));
+        let data_length = offset.checked_sub(header_end).ok_or(ZipError::PaddingOffsetUnreachable(offset))?;
+
+        EntryWholeWriter::from_raw(self, entry, &vec![0; data_length as usize]).write().await
+    }
+
+    /// Write a directory entry, so that the directory is recreated on extraction even if it contains no entries.
+    ///
+    /// A trailing `/` is appended to the entry's filename if not already present. The entry is always stored without
+    /// encryption or data (and so without a data descriptor), and its external attribute is marked as a directory: the
+    /// MS-DOS directory attribute is set and, for Unix compatible entries, the file type is set to a directory (with
+    /// `0o755` permissions if none have been set).
+    pub async fn write_dir_entry<E: Into<ZipEntry>>(&mut self, entry: E) -> Result<()> {
+        self.check_cancelled()?;
+        let mut entry = entry.into();
+
+        if !entry.filename.ends_with('/') {
+            entry.filename.push('/');
+        }
+
+        entry.compression = Compression::Stored;
+        entry.zipcrypto_password = None;
+        entry.external_file_attribute |= DOS_DIRECTORY;
+
+        if matches!(entry.attribute_compatibility, AttributeCompatibility::Unix) {
+            let mode = (entry.external_file_attribute >> 16) as u16 & !S_IFMT;
+            let mode = if mode == 0 { 0o755 } else { mode };
+            entry.external_file_attribute = (entry.external_file_attribute & 0xFFFF) | ((S_IFDIR | mode) as u32) << 16;
+        }
+
+        EntryWholeWriter::from_raw(self, entry, &[]).write().await
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn offset(&self) -> u64 {
+        self.writer.offset() as u64
+    }
+
+    /// Set the ZIP file comment, which is written after the end of central directory record.
+    ///
+    /// As its length is stored in a 16-bit field, [`ZipError::CommentTooLong`] is returned (and any existing comment is
+    /// retained) if the comment's UTF-8 encoding exceeds 65535 bytes.
+    pub fn comment(&mut self, comment: String) -> Result<()> {
+        if comment.len() > u16::MAX as usize {
+            return Err(ZipError::CommentTooLong(comment.len()));
+        }
+
         self.comment_opt = Some(comment);
+        Ok(())
+    }
+
+    /// Set the maximum size (in bytes) which the completed ZIP file is permitted to reach.
+    ///
+    /// The projected size of the archive (including all central directory records and the end of central directory
+    /// record) is checked before each entry is written, and [`ZipError::ArchiveSizeExceeded`] is returned without
+    /// writing anything if the entry wouldn't fit. As the compressed size of a streamed entry isn't known upfront, a
+    /// stream writer will instead fail as soon as the data written to it would exceed the remaining budget (with none of
+    /// the data over budget having been written).
+    pub fn max_size(&mut self, size: u64) {
+        self.max_size = Some(size);
+    }
+
+    /// Set whether the inner writer is flushed once each entry has been fully written.
+    ///
+    /// This ensures that each completed entry has been handed off to the inner writer's destination (eg. the OS for a
+    /// file), at the cost of additional flush calls. See [`ZipFileWriter::close_synced()`] for syncing a file to disk.
+    pub fn flush_entries(&mut self, flush: bool) {
+        self.flush_entries = flush;
+    }
+
+    /// Set whether data descriptors written for streamed entries begin with their optional signature.
+    ///
+    /// The signature is written by default, though some consumers expect data descriptors without it.
+    pub fn data_descriptor_signature(&mut self, value: bool) {
+        self.descriptor_signature = value;
+    }
+
+    /// Set the minimum size (in bytes) of the chunks in which data written to stream writers is passed to the encoder.
+    ///
+    /// Smaller writes are accumulated until the chunk size has been reached (or the stream writer is flushed), which
+    /// improves throughput & compression when data is produced in many small writes (eg. line-by-line). Defaults to 0,
+    /// which passes each write to the encoder as-is.
+    pub fn stream_chunk_size(&mut self, size: usize) {
+        self.stream_chunk_size = size;
+    }
+
+    /// Set the policy used to select the compression of entries written whole based on their size.
+    ///
+    /// See [`CompressionPolicy`] for more information.
+    pub fn compression_policy(&mut self, policy: CompressionPolicy) {
+        self.compression_policy = Some(policy);
+    }
+
+    /// Set whether or not the data of whole entries is compressed on a worker thread (defaults to false).
+    ///
+    /// Compressing data at high levels (particularly with Deflate, bzip2, or xz) is CPU-bound, and so otherwise occupies
+    /// the runtime's worker thread for as long as it takes. When enabled, each entry written via
+    /// [`ZipFileWriter::write_entry_whole()`] (or its AES variant) is instead compressed on a dedicated thread, which
+    /// the writer awaits without blocking other tasks. This comes at the cost of copying the entry's data for the
+    /// thread, so is best suited to large entries. Only a single entry is compressed at a time; see
+    /// [`ZipFileWriter::write_entries_whole_parallel()`] to compress several at once.
+    #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+    pub fn offload_compression(&mut self, value: bool) {
+        self.offload_compression = value;
+    }
+
+    /// Set a token which, once cancelled, causes any further entries to be rejected before anything is written.
+    ///
+    /// [`ZipError::Cancelled`] is then returned with the number of entries written so far. Those entries are left intact,
+    /// so the writer can still be closed to produce a valid (partial) ZIP file. As entries are written one at a time,
+    /// this also allows long-running operations built on this writer (eg. [`rewrite`](crate::read::rewrite)) to be
+    /// abandoned gracefully.
+    #[cfg(feature = "cancel")]
+    pub fn cancellation_token(&mut self, token: CancellationToken) {
+        self.cancellation_token = Some(token);
+    }
+
+    /// Set a callback to which the progress of writing entries is reported.
+    ///
+    /// The callback is invoked as data is written to an entry (when streamed or read from a reader) and once each entry
+    /// has been completed. See the [`progress`] module for more information.
+    pub fn progress<F>(&mut self, callback: F)
+    where
+        F: Fn(WriteProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(ProgressCallback::new(callback));
+    }
+
+    /// Set the order in which central directory records are written, independently of the order entries were written.
+    ///
+    /// When closed, the records are stably sorted by the key returned for each entry's filename (as stored within its
+    /// record), so entries with equal keys remain in the order they were written. The ZIP specification places no
+    /// requirement on the two orders matching, and so this allows (for example) frequently-accessed entries to be placed
+    /// first for readers which scan the central directory linearly, or a `mimetype` entry to be written first whilst the
+    /// central directory remains sorted by filename.
+    pub fn central_directory_order<F, K>(&mut self, key: F)
+    where
+        F: Fn(&str) -> K + Send + Sync + 'static,
+        K: Ord,
+    {
+        self.cd_order = Some(Box::new(move |a, b| key(a).cmp(&key(b))));
+    }
+
+    /// Returns an error if the cancellation token (if one has been set) has been cancelled.
+    fn check_cancelled(&self) -> Result<()> {
+        #[cfg(feature = "cancel")]
+        if self.cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+            return Err(ZipError::Cancelled(self.cd_entries.len()));
+        }
+
+        Ok(())
+    }
+
+    /// Applies the compression policy (if one has been set) to an entry which is to be written whole.
+    fn apply_compression_policy(&self, mut entry: ZipEntry, data: &[u8]) -> ZipEntry {
+        if let Some(policy) = &self.compression_policy {
+            policy.apply(&mut entry, data.len() as u64);
+        }
+
+        entry
+    }
+
+    /// Compresses an entry's data on a worker thread if compression is offloaded, returning the compressed data.
+    #[cfg_attr(
+        not(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz")),
+        allow(unused_variables)
+    )]
+    async fn offloaded_compression(&self, entry: &ZipEntry, data: &[u8]) -> Result<Option<Vec<u8>>> {
+        #[cfg(any(feature = "deflate", feature = "bzip2", feature = "zstd", feature = "lzma", feature = "xz"))]
+        if self.offload_compression && entry.compression() != Compression::Stored {
+            check_compression(entry)?;
+            return Ok(Some(CompressionJob::spawn(entry, Arc::new(data.to_vec())).await));
+        }
+
+        Ok(None)
+    }
+
+    /// Flushes the inner writer if it's been configured to be flushed after each entry.
+    pub(crate) async fn entry_written(&mut self) -> Result<()> {
+        if self.flush_entries {
+            self.writer.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of bytes remaining before the maximum size is reached, once the data & central directory
+    /// record lengths provided have been accounted for.
+    ///
+    /// Returns [`None`] if no maximum size has been set, or an error if the maximum size would already be exceeded.
+    pub(crate) fn remaining_size(&self, data_length: u64, record_length: u64) -> Result<Option<u64>> {
+        let max_size = match self.max_size {
+            Some(max_size) => max_size,
+            None => return Ok(None),
+        };
+
+        let projected = self.writer.offset() as u64 + self.trailer_length() + data_length + record_length;
+
+        max_size.checked_sub(projected).map(Some).ok_or(ZipError::ArchiveSizeExceeded(max_size))
+    }
+
+    /// Returns the combined length of the central directory records of all entries written so far and the end of
+    /// central directory record.
+    fn trailer_length(&self) -> u64 {
+        let records_length: u64 = self.cd_entries.iter().map(|entry| cdh_length(&entry.fields)).sum();
+        let comment_length = self.comment_opt.as_ref().map(|comment| comment.len()).unwrap_or_default();
+
+        records_length + (SIGNATURE_LENGTH + EOCDR_LENGTH + comment_length) as u64
+    }
+
+    /// Returns a reporter for the progress of an entry about to be written, if a progress callback has been set.
+    pub(crate) fn progress_reporter(&self, fields: &HeaderFields) -> Option<EntryProgressReporter> {
+        let trailer_length = self.trailer_length() + cdh_length(fields);
+        self.progress
+            .clone()
+            .map(|callback| EntryProgressReporter::new(callback, self.cd_entries.len(), trailer_length))
     }
 
     /// Consumes this ZIP writer and completes all closing tasks.
@@ -108,25 +697,63 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
     /// - Writing the end of central directory header.
     /// - Writing the file comment.
     ///
+    /// The inner writer is then returned, so that it can be reused once the ZIP file is complete (eg. a socket which
+    /// remains open for further use, or a file which is to be synced).
+    ///
     /// Failiure to call this function before going out of scope would result in a corrupted ZIP file.
-    pub async fn close(mut self) -> Result<()> {
+    pub async fn close(self) -> Result<W> {
+        self.close_with_summary().await.map(|(writer, _)| writer)
+    }
+
+    /// Consumes this ZIP writer and completes all closing tasks as per [`ZipFileWriter::close()`], returning the inner
+    /// writer alongside a summary of the ZIP file written (eg. for logging its statistics).
+    ///
+    /// As with [`ZipFileWriter::close()`], the inner writer isn't flushed or shut down, so that callers may do so.
+    pub async fn close_with_summary(mut self) -> Result<(W, ArchiveSummary)> {
+        if let Some(group) = self.group.take() {
+            self.cd_entries.truncate(group.entries);
+        }
+
+        if let Some(compare) = &self.cd_order {
+            self.cd_entries.sort_by(|a, b| {
+                compare(&String::from_utf8_lossy(&a.fields.filename), &String::from_utf8_lossy(&b.fields.filename))
+            });
+        }
+
+        // Data from rolled back groups which hasn't since been overwritten mustn't trail the end of central directory
+        // record (as readers search backwards for it), so it's overwritten with padding ahead of the central directory.
+        let end = self.writer.offset() as u64 + self.trailer_length();
+
+        if let Some(padding) = self.stale_end.checked_sub(end).filter(|padding| *padding > 0) {
+            tokio::io::copy(&mut tokio::io::repeat(0).take(padding), &mut self.writer).await?;
+        }
+
         let cd_offset = self.writer.offset();
 
         for entry in &self.cd_entries {
             self.writer.write_all(&crate::spec::consts::CDH_SIGNATURE.to_le_bytes()).await?;
             self.writer.write_all(&entry.header.as_slice()).await?;
-            self.writer.write_all(entry.entry.filename().as_bytes()).await?;
-            self.writer.write_all(entry.entry.extra_field()).await?;
-            self.writer.write_all(entry.entry.comment().as_bytes()).await?;
+            self.writer.write_all(&entry.fields.filename).await?;
+            self.writer.write_all(&entry.fields.extra_field).await?;
+            self.writer.write_all(&entry.fields.comment).await?;
+        }
+
+        let num_of_entries = self.cd_entries.len() as u64;
+        let size_cent_dir = (self.writer.offset() - cd_offset) as u64;
+
+        // Values which overflow their end of central directory record fields are held in a ZIP64 record instead.
+        if num_of_entries >= u16::MAX.into() || size_cent_dir >= u32::MAX.into() || cd_offset as u64 >= u32::MAX.into()
+        {
+            self.write_zip64_trailer(num_of_entries, size_cent_dir, cd_offset as u64).await?;
         }
 
         let header = EndOfCentralDirectoryHeader {
             disk_num: 0,
             start_cent_dir_disk: 0,
-            num_of_entries_disk: self.cd_entries.len() as u16,
-            num_of_entries: self.cd_entries.len() as u16,
-            size_cent_dir: (self.writer.offset() - cd_offset) as u32,
-            cent_dir_offset: cd_offset as u32,
+            num_of_entries_disk: num_of_entries.min(u16::MAX.into()) as u16,
+            num_of_entries: num_of_entries.min(u16::MAX.into()) as u16,
+            size_cent_dir: size_cent_dir.min(u32::MAX.into()) as u32,
+            cent_dir_offset: (cd_offset as u64).min(u32::MAX.into()) as u32,
             file_comm_length: self.comment_opt.as_ref().map(|v| v.len() as u16).unwrap_or_default(),
         };
 
@@ -136,6 +763,138 @@ impl<W: AsyncWrite + Unpin> ZipFileWriter<W> {
             self.writer.write_all(comment.as_bytes()).await?;
         }
 
+        let summary = ArchiveSummary {
+            total_size: self.writer.offset() as u64,
+            entries: self.cd_entries.len(),
+            central_directory_offset: cd_offset as u64,
+            central_directory_size: size_cent_dir,
+        };
+
+        Ok((self.writer.into_inner(), summary))
+    }
+
+    /// Writes the ZIP64 end of central directory record & locator which precede the end of central directory record.
+    async fn write_zip64_trailer(
+        &mut self,
+        num_of_entries: u64,
+        size_cent_dir: u64,
+        cent_dir_offset: u64,
+    ) -> Result<()> {
+        let zip64_eocdr_offset = self.writer.offset() as u64;
+        let record = Zip64EndOfCentralDirectoryRecord {
+            // The size of the record excludes the leading signature & size fields.
+            size_of_record: (ZIP64_EOCDR_LENGTH - 8) as u64,
+            version_made_by: SPEC_VERSION_MADE_BY,
+            version_needed: ZIP64_VERSION_NEEDED,
+            disk_num: 0,
+            start_cent_dir_disk: 0,
+            num_of_entries_disk: num_of_entries,
+            num_of_entries,
+            size_cent_dir,
+            cent_dir_offset,
+        };
+        let locator =
+            Zip64EndOfCentralDirectoryLocator { start_zip64_eocdr_disk: 0, zip64_eocdr_offset, num_of_disks: 1 };
+
+        self.writer.write_all(&crate::spec::consts::ZIP64_EOCDR_SIGNATURE.to_le_bytes()).await?;
+        self.writer.write_all(&record.as_slice()).await?;
+        self.writer.write_all(&crate::spec::consts::ZIP64_EOCDL_SIGNATURE.to_le_bytes()).await?;
+        self.writer.write_all(&locator.as_slice()).await?;
+
+        Ok(())
+    }
+}
+
+impl<W: AsyncRead + AsyncWrite + AsyncSeek + Unpin> ZipFileWriter<W> {
+    /// Construct a new ZIP file writer which appends entries to the existing ZIP file held by the provided writer.
+    ///
+    /// The existing central directory is read and the writer is positioned at its start, so that new entries overwrite
+    /// it. Upon [`ZipFileWriter::close()`], the existing central directory records are rewritten unchanged, followed by
+    /// those of the new entries, the end of central directory record, and the existing ZIP file comment (unless
+    /// replaced via [`ZipFileWriter::comment()`]). No existing entry data is read or rewritten.
+    ///
+    /// The ZIP file must start at the beginning of the writer. Unless the comment is replaced with a shorter one, the
+    /// rewritten central directory & end of central directory record are never shorter than the originals, and so no
+    /// stale data is left at the end of the writer.
+    pub async fn append(mut writer: W) -> Result<Self> {
+        let existing = append::existing_directory(BufReader::new(&mut writer)).await?;
+        let offset = existing.offset.try_into().map_err(|_| ZipError::TargetZip64NotSupported)?;
+        writer.seek(SeekFrom::Start(existing.offset)).await?;
+
+        let mut zip_writer = Self::new(writer);
+        zip_writer.writer = AsyncOffsetWriter::with_offset(zip_writer.writer.into_inner(), offset);
+        zip_writer.cd_entries = existing.entries;
+        zip_writer.comment_opt = Some(existing.comment).filter(|comment| !comment.is_empty());
+
+        Ok(zip_writer)
+    }
+}
+
+impl<W: AsyncWrite + AsyncSeek + Unpin> ZipFileWriter<W> {
+    /// Opens a write group, so that the entries written until it's committed or rolled back are treated as a batch.
+    ///
+    /// Either all entries within a group appear in the central directory or none do. This simplifies error handling
+    /// when a batch of related entries is written from several sources and one fails part-way. Groups can't be nested,
+    /// and a group which is still open when the writer is closed is rolled back.
+    pub fn begin_group(&mut self) -> Result<()> {
+        if self.group.is_some() {
+            return Err(ZipError::GroupAlreadyOpen);
+        }
+
+        self.group = Some(WriteGroup { offset: self.writer.offset(), entries: self.cd_entries.len() });
+        Ok(())
+    }
+
+    /// Commits the open write group, keeping all entries written since it was opened.
+    pub fn commit(&mut self) -> Result<()> {
+        self.group.take().map(|_| ()).ok_or(ZipError::NoGroupOpen)
+    }
+
+    /// Rolls back the open write group, discarding all entries written since it was opened.
+    ///
+    /// The writer is seeked back to where the group was opened, so the discarded data is overwritten by any entries
+    /// written afterwards. Should less data be written afterwards, the remainder is overwritten with padding ahead of the
+    /// central directory upon [`ZipFileWriter::close()`], as the writer can't be truncated.
+    pub async fn rollback(&mut self) -> Result<()> {
+        let group = self.group.take().ok_or(ZipError::NoGroupOpen)?;
+        let discarded = self.writer.offset() - group.offset;
+
+        // A relative seek is used as the writer may not have been at position zero when the ZIP writer was constructed.
+        self.writer.get_mut().seek(SeekFrom::Current(-(discarded as i64))).await?;
+        self.stale_end = self.stale_end.max(self.writer.offset() as u64);
+        self.writer.set_offset(group.offset);
+        self.cd_entries.truncate(group.entries);
+
+        Ok(())
+    }
+
+    /// Sets whether streamed entries have their local file header patched with their CRC32 value & sizes once written,
+    /// rather than being followed by a data descriptor.
+    ///
+    /// Some strict consumers (eg. older Java runtimes and certain embedded parsers) reject entries which use data
+    /// descriptors, so this is useful for compatibility when writing to a file. ZipCrypto-encrypted entries still use a
+    /// data descriptor, as their encryption header depends upon the CRC32 value otherwise.
+    pub fn patch_local_headers(&mut self, value: bool) {
+        self.lfh_patcher = value.then_some(entry_stream::patch_lfh::<W> as LfhPatcher<W>);
+    }
+}
+
+#[cfg(feature = "fs")]
+impl<W> ZipFileWriter<W>
+where
+    W: AsyncWrite + Unpin + BorrowMut<File>,
+{
+    /// Consumes this ZIP writer, completes all closing tasks, and then flushes & syncs the file to disk.
+    ///
+    /// See [`ZipFileWriter::close()`] for the closing tasks performed. Once this has returned, the complete ZIP file is
+    /// durable against a crash or power loss (to the extent that the OS & file system honour [`File::sync_all()`]).
+    pub async fn close_synced(self) -> Result<()> {
+        let mut writer = self.close().await?;
+        let file = writer.borrow_mut();
+
+        file.flush().await?;
+        file.sync_all().await?;
+
         Ok(())
     }
 }
@@ -0,0 +1,82 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! Compresses the data of entries on worker threads, so that several entries can be compressed concurrently whilst
+//! previously compressed entries are written.
+//!
+//! Dedicated threads are used rather than runtime tasks so that no particular runtime (or runtime feature) is required.
+//! The compressors are driven by polling them directly, as their writes into an in-memory buffer never wait.
+
+use crate::entry::ZipEntry;
+use crate::spec::compression::Compression;
+
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// The result of compressing an entry's data, or the payload of a panic (which is propagated to the awaiting task).
+type JobResult = std::thread::Result<Vec<u8>>;
+
+/// A future which resolves to an entry's compressed data once its worker thread has compressed it.
+pub(crate) struct CompressionJob {
+    state: Arc<Mutex<JobState>>,
+}
+
+#[derive(Default)]
+struct JobState {
+    result: Option<JobResult>,
+    waker: Option<Waker>,
+}
+
+impl CompressionJob {
+    /// Spawns a worker thread which compresses the provided data as per the entry's compression method & level.
+    pub(crate) fn spawn<D>(entry: &ZipEntry, data: Arc<D>) -> Self
+    where
+        D: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        let state = Arc::new(Mutex::new(JobState::default()));
+        let worker_state = state.clone();
+        let (compression, level) = (entry.compression(), entry.compression_level);
+
+        std::thread::spawn(move || {
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| compress(compression, (*data).as_ref(), level)));
+            let mut state = worker_state.lock().unwrap();
+
+            state.result = Some(result);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+
+        Self { state }
+    }
+}
+
+impl Future for CompressionJob {
+    type Output = Vec<u8>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.result.take() {
+            Some(Ok(compressed)) => Poll::Ready(compressed),
+            Some(Err(payload)) => std::panic::resume_unwind(payload),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// Compresses the provided data to completion on the current thread.
+fn compress(compression: Compression, data: &[u8], level: async_compression::Level) -> Vec<u8> {
+    // Writes to an in-memory buffer never wait, so compression always completes within a single poll.
+    let mut future = std::pin::pin!(crate::write::entry_whole::compress(compression, data, level));
+    match future.as_mut().poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(compressed) => compressed,
+        Poll::Pending => unreachable!("writes to an in-memory buffer are always ready"),
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+use crate::entry::ZipEntry;
+use crate::spec::compression::Compression;
+
+use async_compression::Level;
+
+/// A policy which selects the compression of entries based on their size.
+///
+/// Entries smaller than the compression threshold are stored, as the headers of such entries dominate and compression
+/// saves little (if anything). Larger entries keep their compression method, with the level escalating from fastest,
+/// to default, to best as their size reaches each subsequent threshold.
+///
+/// As an entry's size must be known upfront, the policy only applies to entries written whole (ie. via
+/// [`ZipFileWriter::write_entry_whole()`]). Entries requested as [`Compression::Stored`] are always stored.
+///
+/// [`ZipFileWriter::write_entry_whole()`]: crate::write::ZipFileWriter::write_entry_whole
+#[derive(Debug, Clone)]
+pub struct CompressionPolicy {
+    compress_threshold: u64,
+    default_threshold: u64,
+    best_threshold: u64,
+}
+
+impl Default for CompressionPolicy {
+    fn default() -> Self {
+        Self { compress_threshold: 256, default_threshold: 64 * 1024, best_threshold: 1024 * 1024 }
+    }
+}
+
+impl CompressionPolicy {
+    /// Constructs a new policy with the default thresholds (256 bytes, 64 KiB, and 1 MiB).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the size (in bytes) from which entries are compressed, with smaller entries being stored.
+    pub fn compress_threshold(mut self, size: u64) -> Self {
+        self.compress_threshold = size;
+        self
+    }
+
+    /// Sets the size (in bytes) from which entries are compressed using the default level, rather than the fastest.
+    pub fn default_threshold(mut self, size: u64) -> Self {
+        self.default_threshold = size;
+        self
+    }
+
+    /// Sets the size (in bytes) from which entries are compressed using the best level.
+    pub fn best_threshold(mut self, size: u64) -> Self {
+        self.best_threshold = size;
+        self
+    }
+
+    /// Applies this policy to an entry of the provided uncompressed size.
+    pub(crate) fn apply(&self, entry: &mut ZipEntry, size: u64) {
+        if entry.compression() == Compression::Stored {
+            return;
+        }
+
+        if size < self.compress_threshold {
+            entry.compression = Compression::Stored;
+        } else if size < self.default_threshold {
+            entry.compression_level = Level::Fastest;
+        } else if size < self.best_threshold {
+            entry.compression_level = Level::Default;
+        } else {
+            entry.compression_level = Level::Best;
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+//! A module which supports reporting the progress of writing entries.
+//!
+//! As with [`read::progress`], progress is reported via a callback which is invoked each time data is written, and so
+//! should be cheap so as not to hold up writing.
+//!
+//! ### Example
+//! ```no_run
+//! # use async_zip::{Compression, ZipEntryBuilder, write::ZipFileWriter};
+//! # use async_zip::error::Result;
+//! #
+//! # async fn run() -> Result<()> {
+//! let mut writer = ZipFileWriter::new(Vec::new());
+//! writer.progress(|progress| {
+//!     println!("{} bytes written, ~{} bytes expected", progress.archive_size(), progress.estimated_size());
+//! });
+//!
+//! let entry = ZipEntryBuilder::new(String::from("foo.txt"), Compression::Stored);
+//! writer.write_entry_whole(entry, b"This is an example file.").await?;
+//! writer.close().await?;
+//! #   Ok(())
+//! # }
+//! ```
+//!
+//! [`read::progress`]: crate::read::progress
+
+use crate::read::progress::ProgressCallback;
+
+/// A snapshot of the progress made writing an entry's data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EntryProgress {
+    pub(crate) consumed: u64,
+    pub(crate) compressed: u64,
+}
+
+impl EntryProgress {
+    /// Returns the number of bytes consumed so far (ie. uncompressed bytes, unless copying an entry's raw data).
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    /// Returns the number of bytes written to the archive so far for the entry's data (ie. compressed & encrypted).
+    ///
+    /// Compressors buffer data internally, so this may lag behind [`EntryProgress::consumed()`] until the entry has
+    /// been completed.
+    pub fn compressed(&self) -> u64 {
+        self.compressed
+    }
+}
+
+/// A snapshot of the progress made writing a ZIP file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteProgress {
+    pub(crate) entry: EntryProgress,
+    pub(crate) entries_completed: usize,
+    pub(crate) archive_size: u64,
+    pub(crate) estimated_size: u64,
+}
+
+impl WriteProgress {
+    /// Returns the progress made writing the entry currently being written (or the entry last written).
+    pub fn entry(&self) -> EntryProgress {
+        self.entry
+    }
+
+    /// Returns the number of entries which have been written so far.
+    pub fn entries_completed(&self) -> usize {
+        self.entries_completed
+    }
+
+    /// Returns the number of bytes written to the archive so far.
+    pub fn archive_size(&self) -> u64 {
+        self.archive_size
+    }
+
+    /// Returns the size the archive would reach if it were closed once the current entry has been completed.
+    ///
+    /// This accounts for the central directory records of all entries written so far (including the current entry)
+    /// and the end of central directory record, but not for any data of the current entry yet to be written.
+    pub fn estimated_size(&self) -> u64 {
+        self.estimated_size
+    }
+}
+
+/// Reports the progress of a single entry, capturing the writer's state when the entry was started.
+pub(crate) struct EntryProgressReporter {
+    callback: ProgressCallback<WriteProgress>,
+    entries_completed: usize,
+    trailer_length: u64,
+}
+
+impl EntryProgressReporter {
+    pub(crate) fn new(
+        callback: ProgressCallback<WriteProgress>,
+        entries_completed: usize,
+        trailer_length: u64,
+    ) -> Self {
+        Self { callback, entries_completed, trailer_length }
+    }
+
+    /// Reports the entry's progress given the current size of the archive, and whether or not it's been completed.
+    pub(crate) fn report(&self, entry: EntryProgress, archive_size: u64, completed: bool) {
+        self.callback.report(WriteProgress {
+            entry,
+            entries_completed: self.entries_completed + completed as usize,
+            archive_size,
+            estimated_size: archive_size + self.trailer_length,
+        });
+    }
+}
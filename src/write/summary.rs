@@ -0,0 +1,37 @@
+// Copyright (c) 2022 Harry [Majored] [hello@majored.pw]
+// MIT License (https://github.com/Majored/rs-async-zip/blob/main/LICENSE)
+
+/// A summary of a ZIP file's structure, returned once it has been written via [`ZipFileWriter::close_with_summary()`].
+///
+/// Offsets & sizes are relative to the start of the ZIP file.
+///
+/// [`ZipFileWriter::close_with_summary()`]: crate::write::ZipFileWriter::close_with_summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveSummary {
+    pub(crate) total_size: u64,
+    pub(crate) entries: usize,
+    pub(crate) central_directory_offset: u64,
+    pub(crate) central_directory_size: u64,
+}
+
+impl ArchiveSummary {
+    /// Returns the total number of bytes written (ie. the size of the ZIP file).
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Returns the number of entries within the central directory.
+    pub fn entries(&self) -> usize {
+        self.entries
+    }
+
+    /// Returns the offset of the central directory.
+    pub fn central_directory_offset(&self) -> u64 {
+        self.central_directory_offset
+    }
+
+    /// Returns the size of the central directory (excluding the end of central directory record).
+    pub fn central_directory_size(&self) -> u64 {
+        self.central_directory_size
+    }
+}